@@ -53,6 +53,9 @@ mod benchmarks {
     pub mod brainfuck;
     pub mod external_functions;
     pub mod fib;
+    pub mod raw_table_insert;
+    pub mod string_match;
+    pub mod vec_push;
 }
 
 criterion::criterion_main! {
@@ -63,4 +66,7 @@ criterion::criterion_main! {
     benchmarks::brainfuck::benches,
     benchmarks::fib::benches,
     benchmarks::external_functions::benches,
+    benchmarks::vec_push::benches,
+    benchmarks::raw_table_insert::benches,
+    benchmarks::string_match::benches,
 }
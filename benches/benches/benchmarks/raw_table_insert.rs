@@ -0,0 +1,55 @@
+//! Benchmark inserting elements with precomputed hashes into a raw table one
+//! at a time, compared to inserting them as a single batch.
+
+use core::convert::Infallible;
+
+use criterion::Criterion;
+
+use rune::alloc::error::CustomError;
+use rune::alloc::hashbrown::raw::RawTable;
+
+criterion::criterion_group!(benches, raw_table_insert);
+
+const COUNT: usize = 10_000;
+
+fn hash(value: &u64) -> u64 {
+    value.wrapping_mul(0x9e37_79b9_7f4a_7c15)
+}
+
+fn hasher(_: &mut (), value: &u64) -> Result<u64, Infallible> {
+    Ok(hash(value))
+}
+
+fn expect<T>(result: Result<T, CustomError<Infallible>>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(..) => panic!("failed to allocate"),
+    }
+}
+
+fn raw_table_insert(b: &mut Criterion) {
+    let mut group = b.benchmark_group("raw_table_insert");
+
+    group.bench_function("insert", |b| {
+        b.iter(|| {
+            let mut table = RawTable::<u64>::new();
+
+            for n in 0..COUNT as u64 {
+                expect(table.insert(&mut (), hash(&n), n, hasher));
+            }
+
+            table
+        });
+    });
+
+    group.bench_function("try_insert_batch", |b| {
+        b.iter(|| {
+            let mut table = RawTable::<u64>::new();
+            let items = (0..COUNT).map(|n| (hash(&(n as u64)), n as u64));
+            expect(table.try_insert_batch(&mut (), items, hasher));
+            table
+        });
+    });
+
+    group.finish();
+}
@@ -0,0 +1,62 @@
+//! Benchmark dispatching a match over many string literals, with and without
+//! the string table optimization.
+
+use std::fmt::Write;
+use std::sync::Arc;
+
+use criterion::Criterion;
+
+use rune::compile::Options;
+use rune::{Context, Vm};
+
+criterion::criterion_group!(benches, string_match_table, string_match_linear);
+
+const ARMS: usize = 50;
+
+fn vm(threshold: usize) -> Vm {
+    let mut source = String::new();
+    source.push_str("pub fn main(cmd) { match cmd { ");
+
+    for n in 0..ARMS {
+        write!(source, "\"cmd{n}\" => {n}, ").expect("failed to write");
+    }
+
+    source.push_str("_ => -1 } }");
+
+    let context = Context::with_default_modules().expect("failed to build context");
+    let mut sources = crate::sources(&source);
+
+    let mut options = Options::default();
+    options.match_string_table(threshold);
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()
+        .expect("program to compile successfully");
+
+    let context = Arc::new(context.runtime().expect("failed to build runtime"));
+    Vm::new(context, Arc::new(unit))
+}
+
+fn bench(b: &mut Criterion, name: &str, threshold: usize) {
+    let mut vm = vm(threshold);
+    let entry = rune::Hash::type_hash(["main"]);
+    let commands = (0..ARMS).map(|n| format!("cmd{n}")).collect::<Vec<_>>();
+
+    b.bench_function(name, |b| {
+        b.iter(|| {
+            for cmd in &commands {
+                vm.call(entry, (cmd.as_str(),)).expect("failed call");
+            }
+        });
+    });
+}
+
+fn string_match_table(b: &mut Criterion) {
+    bench(b, "string_match_table", 8);
+}
+
+fn string_match_linear(b: &mut Criterion) {
+    bench(b, "string_match_linear", 0);
+}
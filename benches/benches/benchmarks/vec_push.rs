@@ -0,0 +1,22 @@
+//! Benchmark growing a vector through repeated pushes, which exercises the
+//! reallocation path of the global allocator.
+
+use criterion::Criterion;
+
+use rune::alloc::Vec;
+
+criterion::criterion_group!(benches, vec_push);
+
+fn vec_push(b: &mut Criterion) {
+    b.bench_function("vec_push", |b| {
+        b.iter(|| {
+            let mut vec = Vec::new();
+
+            for n in 0..100_000u64 {
+                vec.try_push(n).expect("failed push");
+            }
+
+            vec
+        });
+    });
+}
@@ -46,6 +46,7 @@ impl Context {
 
         Tokens {
             try_clone: path(m, ["alloc", "clone", "TryClone"]),
+            heap_size: path(m, ["alloc", "heap_size", "HeapSize"]),
             alloc: path(m, ["alloc"]),
         }
     }
@@ -64,5 +65,6 @@ fn path<const N: usize>(base: &syn::Path, path: [&'static str; N]) -> syn::Path
 
 pub(crate) struct Tokens {
     pub(crate) try_clone: syn::Path,
+    pub(crate) heap_size: syn::Path,
     pub(crate) alloc: syn::Path,
 }
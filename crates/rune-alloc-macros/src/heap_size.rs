@@ -0,0 +1,211 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::parse::Parse;
+use syn::spanned::Spanned as _;
+
+use crate::context::{Context, Tokens};
+
+pub(super) fn expand(mut input: syn::DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
+    let cx = Context::new();
+
+    let attr = parse_type_attr(&cx, &input.attrs);
+
+    let tokens = cx.tokens_with_module(attr.module.as_ref());
+
+    let Tokens { heap_size, .. } = &tokens;
+
+    match attr.predicates {
+        // An explicit `bound` replaces the default bounds entirely, so
+        // `bound = {}` can be used for phantom type parameters.
+        Some(predicates) => {
+            input
+                .generics
+                .make_where_clause()
+                .predicates
+                .extend(predicates);
+        }
+        None => {
+            let bounds = input
+                .generics
+                .type_params()
+                .map(|p| -> syn::WherePredicate {
+                    let ident = &p.ident;
+                    syn::parse_quote!(#ident: #heap_size)
+                })
+                .collect::<Vec<_>>();
+
+            if !bounds.is_empty() {
+                input.generics.make_where_clause().predicates.extend(bounds);
+            }
+        }
+    }
+
+    let implementation = match input.data {
+        syn::Data::Struct(st) => {
+            let mut sizes = Vec::new();
+
+            for (index, f) in st.fields.iter().enumerate() {
+                if parse_field_attr(&cx, &f.attrs).skip {
+                    continue;
+                }
+
+                let member = match &f.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(syn::Index::from(index)),
+                };
+
+                sizes.push(quote_spanned! { f.ty.span() => #heap_size::heap_size(&self.#member) });
+            }
+
+            quote!(0 #(+ #sizes)*)
+        }
+        syn::Data::Enum(en) => {
+            let mut variants = Vec::new();
+
+            for v in en.variants {
+                let name = v.ident;
+
+                let mut assigns = Vec::new();
+                let mut sizes = Vec::new();
+
+                for (index, f) in v.fields.iter().enumerate() {
+                    let (member, var) = match &f.ident {
+                        Some(ident) => (
+                            syn::Member::Named(ident.clone()),
+                            quote::format_ident!("{}", ident),
+                        ),
+                        None => (
+                            syn::Member::Unnamed(syn::Index::from(index)),
+                            quote::format_ident!("_{}", index),
+                        ),
+                    };
+
+                    if parse_field_attr(&cx, &f.attrs).skip {
+                        continue;
+                    }
+
+                    assigns.push(match &f.ident {
+                        Some(..) => quote!(#member),
+                        None => quote!(#member: #var),
+                    });
+                    sizes.push(quote_spanned! { f.ty.span() => #heap_size::heap_size(#var) });
+                }
+
+                variants.push(quote! {
+                    Self::#name { #(#assigns,)* .. } => 0 #(+ #sizes)*
+                });
+            }
+
+            quote! {
+                match self {
+                    #(#variants,)*
+                }
+            }
+        }
+        syn::Data::Union(un) => {
+            cx.error(syn::Error::new_spanned(
+                un.union_token,
+                "HeapSize: Unions are not supported",
+            ));
+            quote!()
+        }
+    };
+
+    if cx.has_errors() {
+        return Err(cx.into_errors());
+    }
+
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #heap_size for #name #ty_generics #where_clause {
+            #[inline]
+            fn heap_size(&self) -> usize {
+                #implementation
+            }
+        }
+    })
+}
+
+#[derive(Default)]
+struct TypeAttr {
+    predicates: Option<syn::punctuated::Punctuated<syn::WherePredicate, syn::Token![,]>>,
+    module: Option<syn::Path>,
+}
+
+fn parse_type_attr(cx: &Context, input: &[syn::Attribute]) -> TypeAttr {
+    let mut attr = TypeAttr::default();
+
+    for a in input {
+        if !a.path().is_ident("heap_size") {
+            continue;
+        }
+
+        let result = a.parse_nested_meta(|parser| {
+            if parser.path.is_ident("bound") {
+                parser.input.parse::<syn::Token![=]>()?;
+                let content;
+                syn::braced!(content in parser.input);
+                attr.predicates
+                    .get_or_insert_with(Default::default)
+                    .extend(content.parse_terminated(syn::WherePredicate::parse, syn::Token![,])?);
+                return Ok(());
+            }
+
+            if parser.path.is_ident("crate") {
+                if parser.input.parse::<Option<syn::Token![=]>>()?.is_some() {
+                    attr.module = Some(parser.input.parse::<syn::Path>()?);
+                } else {
+                    attr.module = Some(syn::parse_quote!(crate));
+                }
+
+                return Ok(());
+            }
+
+            Err(syn::Error::new(
+                parser.input.span(),
+                "unsupported attribute",
+            ))
+        });
+
+        if let Err(error) = result {
+            cx.error(error);
+        }
+    }
+
+    attr
+}
+
+#[derive(Default, Clone)]
+struct FieldAttr {
+    skip: bool,
+}
+
+fn parse_field_attr(cx: &Context, input: &[syn::Attribute]) -> FieldAttr {
+    let mut attr = FieldAttr::default();
+
+    for a in input {
+        if !a.path().is_ident("heap_size") {
+            continue;
+        }
+
+        let result = a.parse_nested_meta(|parser| {
+            if parser.path.is_ident("skip") {
+                attr.skip = true;
+                return Ok(());
+            }
+
+            Err(syn::Error::new(
+                parser.input.span(),
+                "unsupported attribute",
+            ))
+        });
+
+        if let Err(error) = result {
+            cx.error(error);
+        }
+    }
+
+    attr
+}
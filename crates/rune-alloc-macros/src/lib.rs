@@ -28,6 +28,7 @@
 extern crate proc_macro;
 
 mod context;
+mod heap_size;
 mod try_clone;
 
 #[proc_macro_derive(TryClone, attributes(try_clone))]
@@ -39,6 +40,15 @@ pub fn try_clone(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .into()
 }
 
+#[proc_macro_derive(HeapSize, attributes(heap_size))]
+pub fn heap_size(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    heap_size::expand(input)
+        .unwrap_or_else(to_compile_errors)
+        .into()
+}
+
 fn to_compile_errors<I>(errors: I) -> proc_macro2::TokenStream
 where
     I: IntoIterator<Item = syn::Error>,
@@ -1,6 +1,7 @@
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::parse::Parse;
+use syn::spanned::Spanned as _;
 
 use crate::context::{Context, Tokens};
 
@@ -11,55 +12,101 @@ pub(super) fn expand(mut input: syn::DeriveInput) -> Result<TokenStream, Vec<syn
 
     let tokens = cx.tokens_with_module(attr.module.as_ref());
 
-    if !attr.predicates.is_empty() {
-        input
-            .generics
-            .make_where_clause()
-            .predicates
-            .extend(attr.predicates);
-    }
-
     let Tokens {
         try_clone, alloc, ..
     } = &tokens;
 
-    let implementation = if attr.copy {
-        quote!(*self)
+    match attr.predicates {
+        // An explicit `bound` replaces the default bounds entirely, so
+        // `bound = {}` can be used for phantom type parameters.
+        Some(predicates) => {
+            input
+                .generics
+                .make_where_clause()
+                .predicates
+                .extend(predicates);
+        }
+        None => {
+            // Without an explicit `bound`, require every type parameter to
+            // implement `TryClone` the same way `#[derive(Clone)]` does.
+            let bounds = input
+                .generics
+                .type_params()
+                .map(|p| -> syn::WherePredicate {
+                    let ident = &p.ident;
+                    syn::parse_quote!(#ident: #try_clone)
+                })
+                .collect::<Vec<_>>();
+
+            if !bounds.is_empty() {
+                input.generics.make_where_clause().predicates.extend(bounds);
+            }
+        }
+    }
+
+    let (implementation, clone_from) = if attr.copy {
+        (quote!(*self), quote!(*self = *source;))
     } else {
         match input.data {
             syn::Data::Struct(st) => {
-                let fields = st.fields.into_iter().enumerate().map(|(index, f)| {
+                let mut fields = Vec::new();
+                let mut clone_from = Vec::new();
+
+                for (index, f) in st.fields.into_iter().enumerate() {
                     let member = match &f.ident {
                         Some(ident) => syn::Member::Named(ident.clone()),
                         None => syn::Member::Unnamed(syn::Index::from(index)),
                     };
 
                     let attr = parse_field_attr(&cx, &f.attrs);
-
-                    let expr = match attr.with {
-                        With::Copy => quote! { self.#member },
-                        With::None => quote! { #try_clone::try_clone(&self.#member)? },
-                        With::With(with) => quote! { #with(&self.#member) },
-                        With::TryWith(with) => quote! { #with(&self.#member)? },
+                    let span = f.ty.span();
+                    let spanned = respan(try_clone, span);
+
+                    let (expr, from) = match attr.with {
+                        With::Copy => (
+                            quote! { self.#member },
+                            quote! { self.#member = source.#member; },
+                        ),
+                        With::None => (
+                            quote_spanned! { span => #spanned::try_clone(&self.#member)? },
+                            quote_spanned! { span => #spanned::try_clone_from(&mut self.#member, &source.#member)?; },
+                        ),
+                        With::With(with) => (
+                            quote! { #with(&self.#member) },
+                            quote! { self.#member = #with(&source.#member); },
+                        ),
+                        With::TryWith(with) => (
+                            quote! { #with(&self.#member)? },
+                            quote! { self.#member = #with(&source.#member)?; },
+                        ),
                     };
 
-                    syn::FieldValue {
+                    fields.push(syn::FieldValue {
                         attrs: Vec::new(),
                         member,
                         colon_token: Some(<syn::Token![:]>::default()),
                         expr: syn::Expr::Verbatim(expr),
-                    }
-                });
+                    });
 
-                quote! {
-                    Self { #(#fields),* }
+                    clone_from.push(from);
                 }
+
+                (quote!(Self { #(#fields),* }), quote!(#(#clone_from)*))
             }
             syn::Data::Enum(en) => {
-                let variants = en.variants.into_iter().map(|v| {
+                let mut variants = Vec::new();
+                let mut clone_from = Vec::new();
+
+                for v in en.variants {
                     let name = v.ident;
 
-                    let members = v.fields.iter().enumerate().map(|(index, f)| {
+                    let mut assigns = Vec::new();
+                    let mut this_assigns = Vec::new();
+                    let mut source_assigns = Vec::new();
+                    let mut fields = Vec::new();
+                    let mut from = Vec::new();
+
+                    for (index, f) in v.fields.iter().enumerate() {
                         let (member, var) = match &f.ident {
                             Some(ident) => (
                                 syn::Member::Named(ident.clone()),
@@ -71,73 +118,84 @@ pub(super) fn expand(mut input: syn::DeriveInput) -> Result<TokenStream, Vec<syn
                             ),
                         };
 
-                        let attr = parse_field_attr(&cx, &f.attrs);
-                        (index, f, member, var, attr)
-                    });
+                        let this = quote::format_ident!("this_{}", var);
+                        let source = quote::format_ident!("source_{}", var);
+
+                        assigns.push(match &f.ident {
+                            Some(..) => quote!(#member),
+                            None => quote!(#member: #var),
+                        });
+                        this_assigns.push(quote!(#member: #this));
+                        source_assigns.push(quote!(#member: #source));
 
-                    let assigns =
-                        members
-                            .clone()
-                            .map(|(index, f, member, var, _)| match &f.ident {
-                                Some(..) => syn::FieldValue {
-                                    attrs: Vec::new(),
-                                    member,
-                                    colon_token: None,
-                                    expr: syn::Expr::Verbatim(quote!()),
-                                },
-                                None => {
-                                    let member = syn::Member::Unnamed(syn::Index::from(index));
-
-                                    let expr = syn::Expr::Path(syn::ExprPath {
-                                        attrs: Vec::new(),
-                                        qself: None,
-                                        path: syn::Path::from(var),
-                                    });
-
-                                    syn::FieldValue {
-                                        attrs: Vec::new(),
-                                        member,
-                                        colon_token: Some(<syn::Token![:]>::default()),
-                                        expr,
-                                    }
-                                }
-                            });
-
-                    let fields = members.clone().map(|(_, _, member, var, attr)| {
-                        let expr = match attr.with {
-                            With::Copy => quote! { *#var },
-                            With::None => quote! { #try_clone::try_clone(#var)? },
-                            With::With(with) => quote! { #with(#var) },
-                            With::TryWith(with) => quote! { #with(#var)? },
+                        let attr = parse_field_attr(&cx, &f.attrs);
+                        let span = f.ty.span();
+                        let spanned = respan(try_clone, span);
+
+                        let (expr, stmt) = match attr.with {
+                            With::Copy => (quote! { *#var }, quote! { *#this = *#source; }),
+                            With::None => (
+                                quote_spanned! { span => #spanned::try_clone(#var)? },
+                                quote_spanned! { span => #spanned::try_clone_from(#this, #source)?; },
+                            ),
+                            With::With(with) => {
+                                (quote! { #with(#var) }, quote! { *#this = #with(#source); })
+                            }
+                            With::TryWith(with) => (
+                                quote! { #with(#var)? },
+                                quote! { *#this = #with(#source)?; },
+                            ),
                         };
 
-                        syn::FieldValue {
+                        fields.push(syn::FieldValue {
                             attrs: Vec::new(),
                             member,
                             colon_token: Some(<syn::Token![:]>::default()),
                             expr: syn::Expr::Verbatim(expr),
-                        }
-                    });
+                        });
+
+                        from.push(stmt);
+                    }
 
-                    quote! {
+                    variants.push(quote! {
                         Self::#name { #(#assigns),* } => {
                             Self::#name { #(#fields),* }
                         }
-                    }
-                });
+                    });
 
-                quote! {
+                    clone_from.push(quote! {
+                        (Self::#name { #(#this_assigns),* }, Self::#name { #(#source_assigns),* }) => {
+                            #(#from)*
+                        }
+                    });
+                }
+
+                let implementation = quote! {
                     match self {
                         #(#variants),*
                     }
-                }
+                };
+
+                // Matching variants are cloned field-wise, reusing the
+                // resources of `self`, everything else is replaced.
+                let clone_from = quote! {
+                    match (self, source) {
+                        #(#clone_from,)*
+                        #[allow(unreachable_patterns)]
+                        (this, source) => {
+                            *this = #try_clone::try_clone(source)?;
+                        }
+                    }
+                };
+
+                (implementation, clone_from)
             }
             syn::Data::Union(un) => {
                 cx.error(syn::Error::new_spanned(
                     un.union_token,
                     "TryClone: Unions are not supported",
                 ));
-                quote!()
+                (quote!(), quote!())
             }
         }
     };
@@ -154,13 +212,18 @@ pub(super) fn expand(mut input: syn::DeriveInput) -> Result<TokenStream, Vec<syn
             fn try_clone(&self) -> #alloc::Result<Self> {
                 Ok(#implementation)
             }
+
+            fn try_clone_from(&mut self, source: &Self) -> #alloc::Result<()> {
+                #clone_from
+                Ok(())
+            }
         }
     })
 }
 
 #[derive(Default)]
 struct TypeAttr {
-    predicates: syn::punctuated::Punctuated<syn::WherePredicate, syn::Token![,]>,
+    predicates: Option<syn::punctuated::Punctuated<syn::WherePredicate, syn::Token![,]>>,
     copy: bool,
     module: Option<syn::Path>,
 }
@@ -179,6 +242,7 @@ fn parse_type_attr(cx: &Context, input: &[syn::Attribute]) -> TypeAttr {
                 let content;
                 syn::braced!(content in parser.input);
                 attr.predicates
+                    .get_or_insert_with(Default::default)
                     .extend(content.parse_terminated(syn::WherePredicate::parse, syn::Token![,])?);
                 return Ok(());
             }
@@ -265,3 +329,15 @@ fn parse_field_attr(cx: &Context, input: &[syn::Attribute]) -> FieldAttr {
 
     attr
 }
+
+/// Re-span a path so that errors raised through it, such as a missing
+/// `TryClone` implementation, point at the field being cloned.
+fn respan(path: &syn::Path, span: Span) -> TokenStream {
+    path.to_token_stream()
+        .into_iter()
+        .map(|mut tt| {
+            tt.set_span(span);
+            tt
+        })
+        .collect()
+}
@@ -0,0 +1,244 @@
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::fmt;
+
+use crate::alloc::{AllocError, Allocator, Global};
+use crate::error::Error;
+use crate::ptr::{self, NonNull};
+
+use super::global::dangling;
+
+/// The alignment of the slab backing a [`BumpAllocator`].
+const SLAB_ALIGN: usize = 16;
+
+/// An allocator which hands out memory from a single slab by bumping a
+/// pointer.
+///
+/// Deallocating is a no-op. Instead, all memory is released at once by calling
+/// [`reset`], after which the whole slab is available again. This makes it a
+/// good fit for many short-lived allocations which all die at the same time.
+///
+/// Allocations fail once the slab has been exhausted.
+///
+/// The allocator can be sent to other threads, but can't be shared between
+/// them.
+///
+/// [`reset`]: BumpAllocator::reset
+///
+/// # Examples
+///
+/// ```
+/// use rune::alloc::Vec;
+/// use rune::alloc::alloc::BumpAllocator;
+///
+/// let mut bump = BumpAllocator::try_with_capacity(1024)?;
+///
+/// {
+///     let mut vec = Vec::try_with_capacity_in(16, &bump)?;
+///     vec.try_extend_from_slice(b"hello")?;
+///     assert_eq!(vec, b"hello");
+/// }
+///
+/// assert_eq!(bump.used(), 16);
+/// bump.reset();
+/// assert_eq!(bump.used(), 0);
+/// # Ok::<_, rune::alloc::Error>(())
+/// ```
+pub struct BumpAllocator {
+    /// The slab being allocated from.
+    slab: NonNull<u8>,
+    /// The size of the slab in bytes.
+    capacity: usize,
+    /// The offset of the first unused byte in the slab.
+    offset: Cell<usize>,
+}
+
+// SAFETY: The allocator uniquely owns its slab.
+unsafe impl Send for BumpAllocator {}
+
+impl BumpAllocator {
+    /// Construct a new bump allocator backed by a slab of `capacity` bytes,
+    /// which is allocated using the [`Global`] allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::alloc::BumpAllocator;
+    ///
+    /// let bump = BumpAllocator::try_with_capacity(1024)?;
+    /// assert_eq!(bump.capacity(), 1024);
+    /// assert_eq!(bump.used(), 0);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, Error> {
+        let layout =
+            Layout::from_size_align(capacity, SLAB_ALIGN).map_err(|_| Error::CapacityOverflow)?;
+
+        let slab = if capacity == 0 {
+            dangling(&layout)
+        } else {
+            Global.allocate(layout)?.cast()
+        };
+
+        Ok(Self {
+            slab,
+            capacity,
+            offset: Cell::new(0),
+        })
+    }
+
+    /// The size of the slab in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of bytes which have been handed out from the slab since it
+    /// was last reset, including any padding needed for alignment.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Release every allocation at once, making the whole slab available
+    /// again.
+    ///
+    /// Since this requires exclusive access, nothing borrowing the allocator
+    /// can still be alive.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.offset.set(0);
+    }
+
+    /// Test if `ptr` with the given size is the most recent allocation.
+    #[inline]
+    fn is_last(&self, ptr: NonNull<u8>, size: usize) -> bool {
+        ptr.as_ptr() as usize + size == self.slab.as_ptr() as usize + self.offset.get()
+    }
+
+    fn bump(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Ok(NonNull::slice_from_raw_parts(dangling(&layout), 0));
+        }
+
+        let offset = self.offset.get();
+
+        // SAFETY: The offset never exceeds the capacity of the slab.
+        let pad = unsafe { self.slab.as_ptr().add(offset).align_offset(layout.align()) };
+
+        let Some(start) = offset.checked_add(pad) else {
+            return Err(AllocError { layout });
+        };
+
+        let end = match start.checked_add(layout.size()) {
+            Some(end) if end <= self.capacity => end,
+            _ => return Err(AllocError { layout }),
+        };
+
+        self.offset.set(end);
+
+        // SAFETY: `start` is within the bounds of the slab.
+        let ptr = unsafe { NonNull::new_unchecked(self.slab.as_ptr().add(start)) };
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+}
+
+unsafe impl Allocator for BumpAllocator {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.bump(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert!(
+            new_layout.size() >= old_layout.size(),
+            "`new_layout.size()` must be greater than or equal to `old_layout.size()`"
+        );
+
+        // The most recent allocation can be grown in place.
+        if old_layout.size() != 0
+            && self.is_last(ptr, old_layout.size())
+            && ptr.as_ptr().align_offset(new_layout.align()) == 0
+        {
+            let start = ptr.as_ptr() as usize - self.slab.as_ptr() as usize;
+
+            if let Some(end) = start.checked_add(new_layout.size()) {
+                if end <= self.capacity {
+                    self.offset.set(end);
+                    return Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()));
+                }
+            }
+        }
+
+        let new_ptr = self.bump(new_layout)?;
+
+        // SAFETY: The old allocation is valid for `old_layout.size()` bytes,
+        // and the new allocation is handed out after it so they can't overlap.
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr() as *mut u8, old_layout.size());
+        Ok(new_ptr)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert!(
+            new_layout.size() <= old_layout.size(),
+            "`new_layout.size()` must be smaller than or equal to `old_layout.size()`"
+        );
+
+        if new_layout.size() == 0 {
+            return Ok(NonNull::slice_from_raw_parts(dangling(&new_layout), 0));
+        }
+
+        if ptr.as_ptr().align_offset(new_layout.align()) == 0 {
+            // Give back the tail of the most recent allocation.
+            if self.is_last(ptr, old_layout.size()) {
+                let delta = old_layout.size() - new_layout.size();
+                self.offset.set(self.offset.get() - delta);
+            }
+
+            return Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()));
+        }
+
+        let new_ptr = self.bump(new_layout)?;
+
+        // SAFETY: The old allocation is valid for at least `new_layout.size()`
+        // bytes, and the new allocation is handed out after it so they can't
+        // overlap.
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr() as *mut u8, new_layout.size());
+        Ok(new_ptr)
+    }
+}
+
+impl Drop for BumpAllocator {
+    fn drop(&mut self) {
+        if self.capacity != 0 {
+            // SAFETY: The slab was allocated with this layout in
+            // `try_with_capacity`.
+            unsafe {
+                let layout = Layout::from_size_align_unchecked(self.capacity, SLAB_ALIGN);
+                Global.deallocate(self.slab, layout);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BumpAllocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BumpAllocator")
+            .field("capacity", &self.capacity)
+            .field("used", &self.offset.get())
+            .finish()
+    }
+}
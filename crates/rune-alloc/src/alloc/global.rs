@@ -1,10 +1,10 @@
 use core::alloc::Layout;
 
 use crate::alloc::{AllocError, Allocator};
-use crate::ptr::{invalid_mut, NonNull};
+use crate::ptr::{self, invalid_mut, NonNull};
 
 #[cfg(feature = "alloc")]
-use ::rust_alloc::alloc::{alloc, alloc_zeroed, dealloc};
+use ::rust_alloc::alloc::{alloc, alloc_zeroed, dealloc, realloc};
 
 /// Creates a `NonNull` that is dangling, but well-aligned for this Layout.
 ///
@@ -82,4 +82,92 @@ unsafe impl Allocator for Global {
             self.release(layout);
         }
     }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert!(
+            new_layout.size() >= old_layout.size(),
+            "`new_layout.size()` must be greater than or equal to `old_layout.size()`"
+        );
+
+        match old_layout.size() {
+            0 => self.allocate(new_layout),
+            // SAFETY: `new_size` is non-zero since it's greater than or equal
+            // to `old_size`, and the alignment is unchanged. Other conditions
+            // must be upheld by the caller.
+            old_size if old_layout.align() == new_layout.align() => {
+                let new_size = new_layout.size();
+                let delta = Layout::from_size_align_unchecked(new_size - old_size, 1);
+                self.take(delta)?;
+
+                let raw_ptr = realloc(ptr.as_ptr(), old_layout, new_size);
+
+                let Some(ptr) = NonNull::new(raw_ptr) else {
+                    self.release(delta);
+                    return Err(AllocError { layout: new_layout });
+                };
+
+                Ok(NonNull::slice_from_raw_parts(ptr, new_size))
+            }
+            old_size => {
+                let new_ptr = self.allocate(new_layout)?;
+
+                // SAFETY: The new allocation is at least as large as the old
+                // one and can't overlap it since the old allocation is still
+                // live. The safety contract for `deallocate` must be upheld by
+                // the caller.
+                ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr() as *mut u8, old_size);
+                self.deallocate(ptr, old_layout);
+                Ok(new_ptr)
+            }
+        }
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert!(
+            new_layout.size() <= old_layout.size(),
+            "`new_layout.size()` must be smaller than or equal to `old_layout.size()`"
+        );
+
+        match new_layout.size() {
+            // SAFETY: conditions must be upheld by the caller
+            0 => {
+                self.deallocate(ptr, old_layout);
+                Ok(NonNull::slice_from_raw_parts(dangling(&new_layout), 0))
+            }
+            // SAFETY: `new_size` is non-zero and the alignment is unchanged.
+            // Other conditions must be upheld by the caller.
+            new_size if old_layout.align() == new_layout.align() => {
+                let raw_ptr = realloc(ptr.as_ptr(), old_layout, new_size);
+
+                let Some(ptr) = NonNull::new(raw_ptr) else {
+                    return Err(AllocError { layout: new_layout });
+                };
+
+                let delta = Layout::from_size_align_unchecked(old_layout.size() - new_size, 1);
+                self.release(delta);
+                Ok(NonNull::slice_from_raw_parts(ptr, new_size))
+            }
+            new_size => {
+                let new_ptr = self.allocate(new_layout)?;
+
+                // SAFETY: The new allocation is valid for `new_size` bytes and
+                // can't overlap the old allocation since it is still live. The
+                // safety contract for `deallocate` must be upheld by the
+                // caller.
+                ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr() as *mut u8, new_size);
+                self.deallocate(ptr, old_layout);
+                Ok(new_ptr)
+            }
+        }
+    }
 }
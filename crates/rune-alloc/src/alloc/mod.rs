@@ -6,6 +6,9 @@ mod allocator;
 pub use self::global::Global;
 mod global;
 
+pub use self::bump::BumpAllocator;
+mod bump;
+
 use core::alloc::Layout;
 use core::convert::Infallible;
 use core::fmt;
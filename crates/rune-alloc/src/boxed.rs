@@ -167,6 +167,7 @@ use core::pin::Pin;
 use crate::alloc::{AllocError, Allocator, Global};
 use crate::clone::TryClone;
 use crate::error::Error;
+use crate::heap_size::HeapSize;
 use crate::iter::TryFromIteratorIn;
 use crate::path::Path;
 use crate::ptr::{self, Unique};
@@ -217,6 +218,55 @@ impl<T> Box<T, Global> {
     pub fn try_pin(x: T) -> Result<Pin<Box<T>>, AllocError> {
         Ok(Box::try_new(x)?.into())
     }
+
+    /// Constructs a new box with uninitialized contents, returning an error if
+    /// the allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::Box;
+    ///
+    /// let mut five = Box::<u32>::try_new_uninit()?;
+    ///
+    /// let five: Box<u32> = unsafe {
+    ///     // Deferred initialization:
+    ///     five.as_mut_ptr().write(5);
+    ///
+    ///     five.assume_init()
+    /// };
+    ///
+    /// assert_eq!(*five, 5);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn try_new_uninit() -> Result<Box<mem::MaybeUninit<T>>, AllocError> {
+        Box::try_new_uninit_in(Global)
+    }
+
+    /// Constructs a new box with uninitialized contents, with the memory being
+    /// filled with `0` bytes, returning an error if the allocation fails.
+    ///
+    /// See [`MaybeUninit::zeroed`][zeroed] for examples of correct and
+    /// incorrect usage of this method.
+    ///
+    /// [zeroed]: mem::MaybeUninit::zeroed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::Box;
+    ///
+    /// let zero = Box::<u32>::try_new_zeroed()?;
+    /// let zero = unsafe { zero.assume_init() };
+    ///
+    /// assert_eq!(*zero, 0);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn try_new_zeroed() -> Result<Box<mem::MaybeUninit<T>>, AllocError> {
+        Box::try_new_zeroed_in(Global)
+    }
 }
 
 impl<T: ?Sized> Box<T> {
@@ -320,6 +370,39 @@ impl<T, A: Allocator> Box<T, A> {
         unsafe { Ok(Box::from_raw_in(ptr.as_ptr(), alloc)) }
     }
 
+    /// Constructs a new box with uninitialized contents, with the memory being
+    /// filled with `0` bytes in the provided allocator, returning an error if
+    /// the allocation fails.
+    ///
+    /// This uses [`Allocator::allocate_zeroed`], so allocators which can hand
+    /// out memory that is already zeroed avoid writing to it.
+    ///
+    /// See [`MaybeUninit::zeroed`][zeroed] for examples of correct and
+    /// incorrect usage of this method.
+    ///
+    /// [zeroed]: mem::MaybeUninit::zeroed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::Box;
+    /// use rune::alloc::alloc::Global;
+    ///
+    /// let zero = Box::<u32>::try_new_zeroed_in(Global)?;
+    /// let zero = unsafe { zero.assume_init() };
+    ///
+    /// assert_eq!(*zero, 0);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_new_zeroed_in(alloc: A) -> Result<Box<mem::MaybeUninit<T>, A>, AllocError>
+    where
+        A: Allocator,
+    {
+        let layout = Layout::new::<mem::MaybeUninit<T>>();
+        let ptr = alloc.allocate_zeroed(layout)?.cast();
+        unsafe { Ok(Box::from_raw_in(ptr.as_ptr(), alloc)) }
+    }
+
     /// Consumes the `Box`, returning the wrapped value.
     #[inline]
     pub fn into_inner(boxed: Self) -> T {
@@ -602,6 +685,59 @@ impl<T, A: Allocator> Box<mem::MaybeUninit<T>, A> {
     }
 }
 
+impl<T> Box<[T], Global> {
+    /// Constructs a new boxed slice with uninitialized contents. Returns an
+    /// error if the allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::Box;
+    ///
+    /// let mut values = Box::<[u32]>::try_new_uninit_slice(3)?;
+    ///
+    /// let values = unsafe {
+    ///     // Deferred initialization:
+    ///     values[0].as_mut_ptr().write(1);
+    ///     values[1].as_mut_ptr().write(2);
+    ///     values[2].as_mut_ptr().write(3);
+    ///     values.assume_init()
+    /// };
+    ///
+    /// assert_eq!(*values, [1, 2, 3]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn try_new_uninit_slice(len: usize) -> Result<Box<[mem::MaybeUninit<T>]>, Error> {
+        Box::try_new_uninit_slice_in(len, Global)
+    }
+
+    /// Constructs a new boxed slice with uninitialized contents, with the
+    /// memory being filled with `0` bytes. Returns an error if the allocation
+    /// fails.
+    ///
+    /// See [`MaybeUninit::zeroed`][zeroed] for examples of correct and
+    /// incorrect usage of this method.
+    ///
+    /// [zeroed]: mem::MaybeUninit::zeroed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::Box;
+    ///
+    /// let values = Box::<[u32]>::try_new_zeroed_slice(3)?;
+    /// let values = unsafe { values.assume_init() };
+    ///
+    /// assert_eq!(*values, [0, 0, 0]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn try_new_zeroed_slice(len: usize) -> Result<Box<[mem::MaybeUninit<T>]>, Error> {
+        Box::try_new_zeroed_slice_in(len, Global)
+    }
+}
+
 impl<T, A: Allocator> Box<[T], A> {
     /// Constructs a new boxed slice with uninitialized contents. Returns an error if
     /// the allocation fails
@@ -633,12 +769,53 @@ impl<T, A: Allocator> Box<[T], A> {
         unsafe {
             let layout = match Layout::array::<mem::MaybeUninit<T>>(len) {
                 Ok(l) => l,
-                Err(_) => return Err(Error::LayoutError),
+                Err(_) => return Err(Error::CapacityOverflow),
             };
             let ptr = alloc.allocate(layout)?;
             Ok(RawVec::from_raw_parts_in(ptr.as_ptr() as *mut _, len, alloc).into_box(len))
         }
     }
+
+    /// Constructs a new boxed slice with uninitialized contents in the
+    /// provided allocator, with the memory being filled with `0` bytes.
+    /// Returns an error if the allocation fails.
+    ///
+    /// This uses [`Allocator::allocate_zeroed`], so allocators which can hand
+    /// out memory that is already zeroed avoid writing to it.
+    ///
+    /// See [`MaybeUninit::zeroed`][zeroed] for examples of correct and
+    /// incorrect usage of this method.
+    ///
+    /// [zeroed]: mem::MaybeUninit::zeroed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{Box, Error};
+    /// use rune::alloc::alloc::Global;
+    ///
+    /// let values = Box::<[u8]>::try_new_zeroed_slice_in(1024, Global)?;
+    /// let values = unsafe { values.assume_init() };
+    /// assert!(values.iter().all(|&b| b == 0));
+    ///
+    /// let error = Box::<[u64]>::try_new_zeroed_slice_in(usize::MAX, Global);
+    /// assert!(matches!(error, Err(Error::CapacityOverflow)));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn try_new_zeroed_slice_in(
+        len: usize,
+        alloc: A,
+    ) -> Result<Box<[mem::MaybeUninit<T>], A>, Error> {
+        unsafe {
+            let layout = match Layout::array::<mem::MaybeUninit<T>>(len) {
+                Ok(l) => l,
+                Err(_) => return Err(Error::CapacityOverflow),
+            };
+            let ptr = alloc.allocate_zeroed(layout)?;
+            Ok(RawVec::from_raw_parts_in(ptr.as_ptr() as *mut _, len, alloc).into_box(len))
+        }
+    }
 }
 
 impl<T, A: Allocator> Box<[mem::MaybeUninit<T>], A> {
@@ -712,6 +889,16 @@ impl<A: Allocator + Clone> TryClone for Box<str, A> {
     }
 }
 
+impl<T: ?Sized, A: Allocator> HeapSize for Box<T, A>
+where
+    T: HeapSize,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        mem::size_of_val::<T>(self) + (**self).heap_size()
+    }
+}
+
 impl<T: ?Sized, A: Allocator> Borrow<T> for Box<T, A> {
     fn borrow(&self) -> &T {
         self
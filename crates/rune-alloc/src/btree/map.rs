@@ -18,6 +18,7 @@ use crate::alloc::{AllocError, Allocator, Global};
 use crate::boxed::Box;
 use crate::clone::TryClone;
 use crate::error::{CustomError, Error};
+use crate::heap_size::HeapSize;
 use crate::iter::{TryExtend, TryFromIteratorIn};
 
 use super::borrow::DormantMutRef;
@@ -337,6 +338,19 @@ impl<K: TryClone, V: TryClone, A: Allocator + Clone> TryClone for BTreeMap<K, V,
     }
 }
 
+impl<K, V, A: Allocator> HeapSize for BTreeMap<K, V, A>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.iter().fold(self.allocation_size(), |n, (k, v)| {
+            n + k.heap_size() + v.heap_size()
+        })
+    }
+}
+
 #[cfg(test)]
 impl<K: TryClone, V: TryClone, A: Allocator + Clone> Clone for BTreeMap<K, V, A> {
     #[inline]
@@ -2867,6 +2881,14 @@ impl<K, V, A: Allocator> BTreeMap<K, V, A> {
         self.len() == 0
     }
 
+    /// Calculates the size of the allocations backing the nodes of the map.
+    pub(crate) fn allocation_size(&self) -> usize {
+        match &self.root {
+            Some(root) => root.reborrow().calc_allocation_size(),
+            None => 0,
+        }
+    }
+
     /// Returns a [`Cursor`] pointing at the first element that is above the
     /// given bound.
     ///
@@ -3156,11 +3178,8 @@ impl<K: Debug, V: Debug> Debug for Cursor<'_, K, V> {
 /// methods.
 pub struct CursorMut<'a, K: 'a, V: 'a, A = Global> {
     current: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
-    #[cfg_attr(not(test), allow(unused))]
     root: DormantMutRef<'a, Option<node::Root<K, V>>>,
-    #[cfg_attr(not(test), allow(unused))]
     length: &'a mut usize,
-    #[cfg_attr(not(test), allow(unused))]
     alloc: &'a mut A,
 }
 
@@ -3176,8 +3195,7 @@ impl<'a, K, V> Cursor<'a, K, V> {
     /// If the cursor is pointing to the "ghost" non-element then this will move it to
     /// the first element of the `BTreeMap`. If it is pointing to the last
     /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
-    #[cfg(test)]
-    pub(crate) fn move_next(&mut self) {
+    pub fn move_next(&mut self) {
         match self.current.take() {
             None => {
                 self.current = self.root.and_then(|root| {
@@ -3199,8 +3217,7 @@ impl<'a, K, V> Cursor<'a, K, V> {
     /// If the cursor is pointing to the "ghost" non-element then this will move it to
     /// the last element of the `BTreeMap`. If it is pointing to the first
     /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
-    #[cfg(test)]
-    pub(crate) fn move_prev(&mut self) {
+    pub fn move_prev(&mut self) {
         match self.current.take() {
             None => {
                 self.current = self.root.and_then(|root| {
@@ -3249,8 +3266,7 @@ impl<'a, K, V> Cursor<'a, K, V> {
     /// If the cursor is pointing to the "ghost" non-element then this returns
     /// the first element of the `BTreeMap`. If it is pointing to the last
     /// element of the `BTreeMap` then this returns `None`.
-    #[cfg(test)]
-    pub(crate) fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
         let mut next = self.clone();
         next.move_next();
         next.current.as_ref().map(|current| current.into_kv())
@@ -3261,8 +3277,7 @@ impl<'a, K, V> Cursor<'a, K, V> {
     /// If the cursor is pointing to the "ghost" non-element then this returns
     /// the last element of the `BTreeMap`. If it is pointing to the first
     /// element of the `BTreeMap` then this returns `None`.
-    #[cfg(test)]
-    pub(crate) fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
         let mut prev = self.clone();
         prev.move_prev();
         prev.current.as_ref().map(|current| current.into_kv())
@@ -3275,8 +3290,7 @@ impl<'a, K, V, A> CursorMut<'a, K, V, A> {
     /// If the cursor is pointing to the "ghost" non-element then this will move it to
     /// the first element of the `BTreeMap`. If it is pointing to the last
     /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
-    #[cfg(test)]
-    pub(crate) fn move_next(&mut self) {
+    pub fn move_next(&mut self) {
         match self.current.take() {
             None => {
                 // SAFETY: The previous borrow of root has ended.
@@ -3294,6 +3308,29 @@ impl<'a, K, V, A> CursorMut<'a, K, V, A> {
         }
     }
 
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    pub fn move_prev(&mut self) {
+        match self.current.take() {
+            None => {
+                // SAFETY: The previous borrow of root has ended.
+                self.current = unsafe { self.root.reborrow() }.as_mut().and_then(|root| {
+                    root.borrow_mut()
+                        .last_leaf_edge()
+                        .forget_node_type()
+                        .left_kv()
+                        .ok()
+                });
+            }
+            Some(current) => {
+                self.current = current.next_back_leaf_edge().next_back_kv().ok();
+            }
+        }
+    }
+
     /// Returns a reference to the key of the element that the cursor is
     /// currently pointing to.
     ///
@@ -3353,8 +3390,7 @@ impl<'a, K, V, A> CursorMut<'a, K, V, A> {
     /// If the cursor is pointing to the "ghost" non-element then this returns
     /// the first element of the `BTreeMap`. If it is pointing to the last
     /// element of the `BTreeMap` then this returns `None`.
-    #[cfg(test)]
-    pub(crate) fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
         let (k, v) = match self.current {
             None => {
                 // SAFETY: The previous borrow of root has ended.
@@ -3381,8 +3417,7 @@ impl<'a, K, V, A> CursorMut<'a, K, V, A> {
     /// If the cursor is pointing to the "ghost" non-element then this returns
     /// the last element of the `BTreeMap`. If it is pointing to the first
     /// element of the `BTreeMap` then this returns `None`.
-    #[cfg(test)]
-    pub(crate) fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
         let (k, v) = match self.current.as_mut() {
             None => {
                 // SAFETY: The previous borrow of root has ended.
@@ -3421,8 +3456,7 @@ impl<'a, K: Ord, V, A: Allocator> CursorMut<'a, K, V, A> {
     ///
     /// * The key of the newly inserted element must be unique in the tree.
     /// * All keys in the tree must remain in sorted order.
-    #[cfg(test)]
-    pub(crate) unsafe fn try_insert_after_unchecked(
+    pub unsafe fn try_insert_after_unchecked(
         &mut self,
         key: K,
         value: V,
@@ -3471,8 +3505,7 @@ impl<'a, K: Ord, V, A: Allocator> CursorMut<'a, K, V, A> {
     ///
     /// * The key of the newly inserted element must be unique in the tree.
     /// * All keys in the tree must remain in sorted order.
-    #[cfg(test)]
-    pub(crate) unsafe fn try_insert_before_unchecked(
+    pub unsafe fn try_insert_before_unchecked(
         &mut self,
         key: K,
         value: V,
@@ -3521,8 +3554,7 @@ impl<'a, K: Ord, V, A: Allocator> CursorMut<'a, K, V, A> {
     ///   any).
     /// - the given key compares greater than or equal to the next element (if
     ///   any).
-    #[cfg(test)]
-    pub(crate) fn try_insert_after(&mut self, key: K, value: V) -> Result<(), AllocError> {
+    pub fn try_insert_after(&mut self, key: K, value: V) -> Result<(), AllocError> {
         if let Some(current) = self.key() {
             if &key <= current {
                 panic!("key must be ordered above the current element");
@@ -3556,8 +3588,32 @@ impl<'a, K: Ord, V, A: Allocator> CursorMut<'a, K, V, A> {
     ///   (if any).
     /// - the given key compares less than or equal to the previous element (if
     ///   any).
-    #[cfg(test)]
-    pub(crate) fn try_insert_before(&mut self, key: K, value: V) -> Result<(), AllocError> {
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.try_insert(1, "a")?;
+    /// a.try_insert(4, "d")?;
+    ///
+    /// let mut cursor = a.lower_bound_mut(Bound::Included(&3));
+    /// assert_eq!(cursor.key(), Some(&4));
+    /// cursor.try_insert_before(2, "b")?;
+    /// cursor.try_insert_before(3, "c")?;
+    /// assert_eq!(cursor.key(), Some(&4));
+    ///
+    /// assert_eq!(cursor.remove_current(), Some((4, "d")));
+    /// assert_eq!(cursor.key(), None);
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.key(), Some(&3));
+    ///
+    /// assert!(a.into_iter().eq([(1, "a"), (2, "b"), (3, "c")]));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_insert_before(&mut self, key: K, value: V) -> Result<(), AllocError> {
         if let Some(current) = self.key() {
             if &key >= current {
                 panic!("key must be ordered below the current element");
@@ -3586,8 +3642,7 @@ impl<'a, K: Ord, V, A: Allocator> CursorMut<'a, K, V, A> {
     ///
     /// If the cursor is currently pointing to the "ghost" non-element then no element
     /// is removed and `None` is returned. The cursor is not moved in this case.
-    #[cfg(test)]
-    pub(crate) fn remove_current(&mut self) -> Option<(K, V)> {
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
         let current = self.current.take()?;
         let mut emptied_internal_root = false;
         let (kv, pos) = current.remove_kv_tracking(|| emptied_internal_root = true, self.alloc);
@@ -3609,8 +3664,7 @@ impl<'a, K: Ord, V, A: Allocator> CursorMut<'a, K, V, A> {
     ///
     /// If the cursor is currently pointing to the "ghost" non-element then no element
     /// is removed and `None` is returned. The cursor is not moved in this case.
-    #[cfg(test)]
-    pub(crate) fn remove_current_and_move_back(&mut self) -> Option<(K, V)> {
+    pub fn remove_current_and_move_back(&mut self) -> Option<(K, V)> {
         let current = self.current.take()?;
         let mut emptied_internal_root = false;
         let (kv, pos) = current.remove_kv_tracking(|| emptied_internal_root = true, self.alloc);
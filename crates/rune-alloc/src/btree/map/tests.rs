@@ -2638,3 +2638,77 @@ fn cursor_peek_prev_agrees_with_cursor_mut() {
     let prev = cursor.peek_prev();
     assert_matches!(prev, Some((&3, _)));
 }
+
+#[test]
+fn test_cursor_across_nodes() {
+    let map = (0..200).map(|i| (i, i * 10)).collect::<BTreeMap<_, _>>();
+
+    let mut cur = map.lower_bound(Bound::Unbounded);
+
+    for i in 0..200 {
+        assert_eq!(cur.key_value(), Some((&i, &(i * 10))));
+        cur.move_next();
+    }
+
+    assert_eq!(cur.key(), None);
+    cur.move_prev();
+
+    for i in (0..200).rev() {
+        assert_eq!(cur.key_value(), Some((&i, &(i * 10))));
+        cur.move_prev();
+    }
+
+    assert_eq!(cur.key(), None);
+
+    let mut cur = map.upper_bound(Bound::Excluded(&100));
+    assert_eq!(cur.key(), Some(&99));
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&100));
+}
+
+#[test]
+fn test_cursor_mut_interleaved() {
+    let mut map = (0..200).map(|i| (i * 2, i)).collect::<BTreeMap<_, _>>();
+
+    let mut cur = map.lower_bound_mut(Bound::Unbounded);
+
+    // Remove every multiple of four and fill in the odd keys around the
+    // remaining elements, forcing both splits and merges along the way.
+    while let Some(&key) = cur.key() {
+        if key % 4 == 0 {
+            assert_eq!(cur.remove_current(), Some((key, key / 2)));
+        } else {
+            cur.try_insert_before(key - 1, 0).unwrap();
+            cur.try_insert_after(key + 1, 0).unwrap();
+            *cur.value_mut().unwrap() += 1000;
+            cur.move_next();
+            cur.move_next();
+        }
+    }
+
+    map.check();
+
+    let expected = (0..200)
+        .filter(|i| i % 2 == 1)
+        .flat_map(|i| [(i * 2 - 1, 0), (i * 2, i + 1000), (i * 2 + 1, 0)]);
+
+    assert!(map.iter().map(|(k, v)| (*k, *v)).eq(expected));
+
+    let mut cur = map.lower_bound_mut(Bound::Unbounded);
+
+    while cur.key().is_some() {
+        cur.remove_current();
+    }
+
+    assert!(map.is_empty());
+    map.check();
+}
+
+#[should_panic(expected = "key must be ordered above the previous element")]
+#[test]
+fn test_cursor_mut_try_insert_before_across_nodes() {
+    let mut map = (0..200).map(|i| (i * 2, i)).collect::<BTreeMap<_, _>>();
+    let mut cur = map.lower_bound_mut(Bound::Included(&100));
+    cur.move_prev();
+    let _ = cur.try_insert_before(95, 0);
+}
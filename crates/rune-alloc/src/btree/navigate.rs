@@ -821,6 +821,22 @@ impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>
         });
         result
     }
+
+    /// Calculates the size of the allocations backing the nodes of a
+    /// (sub)tree.
+    pub(crate) fn calc_allocation_size(self) -> usize {
+        let mut result = 0;
+        self.visit_nodes_in_order(|pos| match pos {
+            Position::Leaf(_) => {
+                result += NodeRef::<marker::Immut<'a>, K, V, marker::Leaf>::ALLOCATION_SIZE
+            }
+            Position::Internal(_) => {
+                result += NodeRef::<marker::Immut<'a>, K, V, marker::Internal>::ALLOCATION_SIZE
+            }
+            Position::InternalKV(_) => (),
+        });
+        result
+    }
 }
 
 impl<BorrowType: marker::BorrowType, K, V>
@@ -785,6 +785,9 @@ impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
 }
 
 impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::Leaf> {
+    /// The size of the allocation backing a leaf node.
+    pub(crate) const ALLOCATION_SIZE: usize = mem::size_of::<LeafNode<K, V>>();
+
     /// Removes any static information asserting that this node is a `Leaf` node.
     pub(crate) fn forget_type(self) -> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
         NodeRef {
@@ -796,6 +799,9 @@ impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::Leaf> {
 }
 
 impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::Internal> {
+    /// The size of the allocation backing an internal node.
+    pub(crate) const ALLOCATION_SIZE: usize = mem::size_of::<InternalNode<K, V>>();
+
     /// Removes any static information asserting that this node is an `Internal` node.
     pub(crate) fn forget_type(self) -> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
         NodeRef {
@@ -1285,7 +1291,6 @@ impl<'a, K: 'a, V: 'a, NodeType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>
         unsafe { leaf.vals.get_unchecked_mut(self.idx).assume_init_mut() }
     }
 
-    #[cfg(test)]
     pub(crate) fn into_kv_valmut(self) -> (&'a K, &'a mut V) {
         debug_assert!(self.idx < self.node.len());
         let leaf = self.node.into_leaf_mut();
@@ -16,6 +16,7 @@ use super::Recover;
 use crate::alloc::{AllocError, Allocator, Global};
 use crate::clone::TryClone;
 use crate::error::Error;
+use crate::heap_size::HeapSize;
 use crate::iter::{TryExtend, TryFromIteratorIn};
 #[cfg(test)]
 use crate::testing::*;
@@ -118,6 +119,16 @@ where
     }
 }
 
+impl<T, A: Allocator> HeapSize for BTreeSet<T, A>
+where
+    T: HeapSize,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.map.allocation_size() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
 #[cfg(test)]
 impl<T, A: Allocator + Clone> Clone for BTreeSet<T, A>
 where
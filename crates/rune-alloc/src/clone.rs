@@ -41,9 +41,49 @@
 //! let copy = f.try_clone()?;
 //! # Ok::<_, rune::alloc::Error>(())
 //! ```
+//!
+//! The derive also implements [`try_clone_from`] field-by-field, so that the
+//! resources of the destination can be reused. Individual fields can be
+//! customized with `#[try_clone(copy)]` for plain [`Copy`] fields or
+//! `#[try_clone(with = path)]` to clone them through a custom function.
+//! Unless `#[try_clone(bound = {..})]` is specified, every type parameter is
+//! required to implement [`TryClone`]. The given bounds replace the default
+//! ones, so `#[try_clone(bound = {})]` can be used if a type parameter is only
+//! used through something like [`PhantomData`].
+//!
+//! [`PhantomData`]: core::marker::PhantomData
+//!
+//! [`try_clone_from`]: TryClone::try_clone_from
 
 use crate::error::Error;
 
+/// Derive macro generating an implementation of [`TryClone`].
+///
+/// Like `#[derive(Clone)]`, every type parameter is required to implement
+/// [`TryClone`] by default, even if it's never cloned. Bounds can be specified
+/// with `#[try_clone(bound = {..})]`, which replaces the default ones:
+///
+/// ```
+/// use core::marker::PhantomData;
+///
+/// use rune::alloc::prelude::*;
+///
+/// /// A type which doesn't implement `TryClone`.
+/// struct Opaque;
+///
+/// #[derive(TryClone)]
+/// #[try_clone(bound = {})]
+/// struct Id<T> {
+///     id: u32,
+///     #[try_clone(copy)]
+///     marker: PhantomData<T>,
+/// }
+///
+/// let id = Id::<Opaque> { id: 1, marker: PhantomData };
+/// let copy = id.try_clone()?;
+/// assert_eq!(copy.id, 1);
+/// # Ok::<_, rune::alloc::Error>(())
+/// ```
 #[doc(inline)]
 pub use rune_alloc_macros::TryClone;
 
@@ -11,9 +11,11 @@ use crate::alloc::{into_ok, into_ok_try};
 use crate::alloc::{Allocator, Global};
 use crate::clone::TryClone;
 use crate::error::{CustomError, Error};
+use crate::heap_size::HeapSize;
 use crate::iter::{TryExtend, TryFromIteratorIn};
 #[cfg(test)]
 use crate::testing::*;
+use crate::vec::Vec;
 
 use super::raw::{Bucket, RawDrain, RawIntoIter, RawIter, RawTable};
 use super::{Equivalent, ErrorOrInsertSlot, HasherFn};
@@ -229,6 +231,21 @@ where
     }
 }
 
+impl<K, V, S, A> HeapSize for HashMap<K, V, S, A>
+where
+    K: HeapSize,
+    V: HeapSize,
+    A: Allocator,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        let (_, layout) = self.table.allocation_info();
+
+        self.iter()
+            .fold(layout.size(), |n, (k, v)| n + k.heap_size() + v.heap_size())
+    }
+}
+
 #[cfg(test)]
 impl<K, V, S, A> Clone for HashMap<K, V, S, A>
 where
@@ -1000,6 +1017,50 @@ impl<K, V, S, A: Allocator> HashMap<K, V, S, A> {
         }
     }
 
+    /// Retains only the elements specified by the fallible predicate. Keeps
+    /// the allocated memory for reuse.
+    ///
+    /// This is the fallible variant of [`retain`]. If the predicate errors,
+    /// iteration stops and the error is returned. Elements for which the
+    /// predicate already returned `false` stay removed, and every other
+    /// element is retained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut map: HashMap<i32, i32> = (0..8).map(|x| (x, x * 10)).try_collect()?;
+    ///
+    /// map.try_retain(|&k, _| Ok::<_, &str>(k % 2 == 0))?;
+    /// assert_eq!(map.len(), 4);
+    ///
+    /// let result = map.try_retain(|&k, _| if k == 4 { Err("four") } else { Ok(true) });
+    /// assert_eq!(result, Err("four"));
+    /// assert_eq!(map.len(), 4);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`retain`]: HashMap::retain
+    pub fn try_retain<F, E>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&K, &mut V) -> Result<bool, E>,
+    {
+        // Here we only use `iter` as a temporary, preventing use-after-free
+        unsafe {
+            for item in self.table.iter() {
+                let &mut (ref key, ref mut value) = item.as_mut();
+
+                if !f(key, value)? {
+                    self.table.erase(item);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Drains elements which are true under the given predicate,
     /// and returns an iterator over the removed items.
     ///
@@ -1059,6 +1120,73 @@ impl<K, V, S, A: Allocator> HashMap<K, V, S, A> {
         }
     }
 
+    /// Removes all elements which are true under the given predicate and moves
+    /// them into `out`, returning the number of elements moved.
+    ///
+    /// In other words, move all pairs `(k, v)` such that `f(&k, &mut v)`
+    /// returns `true` into `out`. Unlike [`extract_if`], this walks the table
+    /// exactly once and doesn't construct an iterator, so if `out` has enough
+    /// spare capacity no allocations are performed.
+    ///
+    /// Keeps the allocated memory for reuse.
+    ///
+    /// # Errors
+    ///
+    /// Before a matching pair is removed from the map, space for it is
+    /// reserved in `out`. If that reservation fails, the pair is left in the
+    /// map and the error is returned. Pairs which have already been moved stay
+    /// in `out` and the predicate is not called for any further pairs. No
+    /// elements are lost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{HashMap, Vec};
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut map: HashMap<i32, i32> = (0..8).map(|x| (x, x * 10)).try_collect()?;
+    /// let mut out = Vec::try_with_capacity(4)?;
+    ///
+    /// let moved = map.drain_filter_into(|&k, _| k % 2 == 0, &mut out)?;
+    /// assert_eq!(moved, 4);
+    /// assert_eq!(map.len(), 4);
+    ///
+    /// out.sort_unstable();
+    /// assert_eq!(out, [(0, 0), (2, 20), (4, 40), (6, 60)]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    ///
+    /// [`extract_if`]: HashMap::extract_if
+    pub fn drain_filter_into<F, A2>(
+        &mut self,
+        mut f: F,
+        out: &mut Vec<(K, V), A2>,
+    ) -> Result<usize, Error>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+        A2: Allocator,
+    {
+        let mut moved = 0;
+
+        // Here we only use `iter` as a temporary, preventing use-after-free
+        unsafe {
+            for item in self.table.iter() {
+                let &mut (ref key, ref mut value) = item.as_mut();
+
+                if !f(key, value) {
+                    continue;
+                }
+
+                out.try_reserve(1)?;
+                let (entry, _) = self.table.remove(item);
+                out.try_push(entry)?;
+                moved += 1;
+            }
+        }
+
+        Ok(moved)
+    }
+
     /// Clears the map, removing all key-value pairs. Keeps the allocated memory
     /// for reuse.
     ///
@@ -1084,6 +1212,35 @@ impl<K, V, S, A: Allocator> HashMap<K, V, S, A> {
         self.table.clear();
     }
 
+    /// Removes and returns an arbitrary entry from the map, or `None` if the
+    /// map is empty. Keeps the allocated memory for reuse.
+    ///
+    /// Which entry is removed is unspecified, which makes this useful for
+    /// things like evicting entries from a cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(map.try_pop(), None);
+    ///
+    /// map.try_insert(1, "a")?;
+    /// map.try_insert(2, "b")?;
+    ///
+    /// let (key, _) = map.try_pop().unwrap();
+    /// assert!(key == 1 || key == 2);
+    /// assert_eq!(map.len(), 1);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_pop(&mut self) -> Option<(K, V)> {
+        unsafe {
+            let bucket = self.table.iter().next()?;
+            Some(self.table.remove(bucket).0)
+        }
+    }
+
     /// Creates a consuming iterator visiting all the keys in arbitrary order.
     /// The map cannot be used after calling this.
     /// The iterator element type is `K`.
@@ -1311,6 +1468,98 @@ where
         }
     }
 
+    /// Gets a mutable reference to the value of the given key, inserting the
+    /// default value if the key is not present.
+    ///
+    /// This is a shorthand for `map.entry(key).or_try_default()`. Calling it
+    /// repeatedly with the same key returns the same value.
+    ///
+    /// # Errors
+    ///
+    /// Errors if inserting the key requires the map to grow and the
+    /// allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut letters = HashMap::<char, usize>::new();
+    ///
+    /// for ch in "a short treatise on fungi".chars() {
+    ///     *letters.try_entry_or_default(ch)? += 1;
+    /// }
+    ///
+    /// assert_eq!(letters[&'s'], 2);
+    /// assert_eq!(letters[&'t'], 3);
+    /// assert_eq!(letters.get(&'y'), None);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_entry_or_default(&mut self, key: K) -> Result<&mut V, Error>
+    where
+        V: Default,
+    {
+        self.try_get_or_default_entry(key)
+    }
+
+    /// Gets a mutable reference to the value of the given key, inserting the
+    /// default value if the key is not present.
+    ///
+    /// Unlike going through [`HashMap::entry`], capacity is reserved up front
+    /// and the table is only probed once, which makes this suitable for hot
+    /// paths. Calling it repeatedly with the same key returns a reference to
+    /// the same slot.
+    ///
+    /// # Errors
+    ///
+    /// Errors if inserting the key requires the map to grow and the
+    /// allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{HashMap, Vec};
+    ///
+    /// let mut groups = HashMap::<bool, Vec<u32>>::new();
+    ///
+    /// for n in 0..6 {
+    ///     groups.try_get_or_default_entry(n % 2 == 0)?.try_push(n)?;
+    /// }
+    ///
+    /// assert_eq!(groups[&true], [0, 2, 4]);
+    /// assert_eq!(groups[&false], [1, 3, 5]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_get_or_default_entry(&mut self, key: K) -> Result<&mut V, Error>
+    where
+        V: Default,
+    {
+        let hasher = make_hasher::<K, S>(&self.hash_builder);
+        let hash = into_ok(hasher.hash(&mut (), &key));
+
+        let result = self.table.find_or_find_insert_slot(
+            &mut (),
+            hash,
+            equivalent_key(&key),
+            hasher.into_tuple(),
+        );
+
+        let bucket = match result {
+            Ok(bucket) => bucket,
+            Err(ErrorOrInsertSlot::InsertSlot(slot)) => unsafe {
+                self.table.insert_in_slot(hash, slot, (key, V::default()))
+            },
+            Err(ErrorOrInsertSlot::Error(error)) => match error {
+                CustomError::Custom(error) => match error {},
+                CustomError::Error(error) => return Err(error),
+            },
+        };
+
+        Ok(unsafe { &mut bucket.as_mut().1 })
+    }
+
     /// Gets the given key's corresponding entry by reference in the map for in-place manipulation.
     ///
     /// # Examples
@@ -1416,6 +1665,65 @@ where
         }
     }
 
+    /// Returns an iterator over all values associated with the supplied key.
+    ///
+    /// A map normally holds at most one value per key, but duplicate keys can
+    /// be inserted through methods like
+    /// [`try_insert_unique_unchecked`][HashMap::try_insert_unique_unchecked].
+    /// This can be used to build a multimap on top of a `HashMap`.
+    ///
+    /// The order in which values are returned is unspecified.
+    ///
+    /// The supplied key may be any borrowed form of the map's key type, but
+    /// [`Hash`] and [`Eq`] on the borrowed form *must* match those for
+    /// the key type.
+    ///
+    /// [`Eq`]: https://doc.rust-lang.org/std/cmp/trait.Eq.html
+    /// [`Hash`]: https://doc.rust-lang.org/std/hash/trait.Hash.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.try_insert_unique_unchecked(1, "a")?;
+    /// map.try_insert_unique_unchecked(1, "b")?;
+    /// map.try_insert_unique_unchecked(2, "c")?;
+    ///
+    /// let mut values = map.get_all(&1).copied().collect::<Vec<_>>();
+    /// values.sort();
+    /// assert_eq!(values, ["a", "b"]);
+    /// assert_eq!(map.get_all(&3).count(), 0);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn get_all<'a, Q>(&'a self, k: &'a Q) -> impl Iterator<Item = &'a V> + 'a
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let hash = make_hash::<Q, S>(&self.hash_builder, k);
+        let eq = equivalent(k);
+
+        // SAFETY: The returned iterator borrows the table, so it can't be
+        // modified or freed while the iterator is alive.
+        let iter = unsafe { self.table.iter_hash(hash) };
+
+        iter.filter_map(move |bucket| {
+            // SAFETY: `iter_hash` only yields full buckets, which are valid for
+            // as long as the table is borrowed.
+            let (key, value) = unsafe { bucket.as_ref() };
+
+            // `iter_hash` only matches the top bits of the hash, so every
+            // candidate has to be compared with the key.
+            if eq(key) {
+                Some(value)
+            } else {
+                None
+            }
+        })
+    }
+
     #[inline]
     fn get_inner<Q>(&self, k: &Q) -> Option<&(K, V)>
     where
@@ -1529,6 +1837,147 @@ where
         }
     }
 
+    /// Updates the value corresponding to the key in place using a fallible
+    /// closure, if it is present in the map.
+    ///
+    /// Returns `Ok(true)` if the key was present and the closure completed
+    /// successfully, `Ok(false)` if the key is not present in which case the
+    /// closure is not called, and propagates any error returned by the closure.
+    ///
+    /// The key is only hashed once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{HashMap, String};
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.try_insert(1, String::try_from("a")?)?;
+    ///
+    /// assert!(map.try_update(&1, |v| v.try_push('b'))?);
+    /// assert_eq!(map[&1], "ab");
+    ///
+    /// assert!(!map.try_update(&2, |v| v.try_push('b'))?);
+    /// assert!(!map.contains_key(&2));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_update<Q, F>(&mut self, k: &Q, f: F) -> Result<bool, Error>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+        F: FnOnce(&mut V) -> Result<(), Error>,
+    {
+        match self.get_inner_mut(k) {
+            Some(&mut (_, ref mut v)) => {
+                f(v)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Consumes the map and constructs a new one where every value has been
+    /// replaced by the result of calling a fallible closure on it.
+    ///
+    /// Keys are moved directly out of the existing table into the new one, so
+    /// they are never cloned. If the closure returns an error, it is propagated
+    /// and every entry processed so far is dropped along with the remaining
+    /// entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{HashMap, String};
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.try_insert(1, "a")?;
+    /// map.try_insert(2, "b")?;
+    ///
+    /// let map = map.try_map_values(String::try_from)?;
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map[&1], "a");
+    /// assert_eq!(map[&2], "b");
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_map_values<W, F>(self, mut f: F) -> Result<HashMap<K, W, S, A>, Error>
+    where
+        F: FnMut(V) -> Result<W, Error>,
+        A: Clone,
+    {
+        let HashMap {
+            hash_builder,
+            table,
+        } = self;
+        let alloc = table.allocator().clone();
+        let mut map = HashMap::try_with_capacity_and_hasher_in(table.len(), hash_builder, alloc)?;
+
+        for (k, v) in table {
+            let w = f(v)?;
+            map.try_insert_unique_unchecked(k, w)?;
+        }
+
+        Ok(map)
+    }
+
+    /// Moves all entries from `other` into this map, calling `resolve` for
+    /// every key which is present in both.
+    ///
+    /// The resolver is given the key, a mutable reference to the existing
+    /// value and the incoming value by move, so it can decide how the two are
+    /// combined. Keys which are only present in `other` are moved in as-is.
+    ///
+    /// Space for `other.len()` entries is reserved up front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut defaults = HashMap::new();
+    /// defaults.try_insert("width", 80)?;
+    /// defaults.try_insert("height", 24)?;
+    ///
+    /// let mut overrides = HashMap::new();
+    /// overrides.try_insert("width", 120)?;
+    /// overrides.try_insert("depth", 8)?;
+    ///
+    /// defaults.try_merge_with(overrides, |_, existing, incoming| *existing = incoming)?;
+    ///
+    /// assert_eq!(defaults.len(), 3);
+    /// assert_eq!(defaults["width"], 120);
+    /// assert_eq!(defaults["height"], 24);
+    /// assert_eq!(defaults["depth"], 8);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_merge_with<F>(
+        &mut self,
+        other: HashMap<K, V, S, A>,
+        mut resolve: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(&K, &mut V, V),
+    {
+        self.try_reserve(other.len())?;
+
+        for (k, v) in other.table.into_iter() {
+            match self.entry(k) {
+                Entry::Occupied(entry) => {
+                    // SAFETY: The bucket is valid for as long as the entry is
+                    // alive.
+                    let &mut (ref key, ref mut value) = unsafe { entry.elem.as_mut() };
+                    resolve(key, value, v);
+                }
+                Entry::Vacant(entry) => {
+                    entry.try_insert(v)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn get_inner_mut<Q>(&mut self, k: &Q) -> Option<&mut (K, V)>
     where
@@ -1857,8 +2306,69 @@ where
         self.try_insert(k, v).abort()
     }
 
-    /// Insert a key-value pair into the map without checking
-    /// if the key already exists in the map.
+    /// Inserts a key-value pair into the map, returning an [`OccupiedEntry`]
+    /// for the slot it was written to.
+    ///
+    /// If the map already had this key present, the value is replaced and the
+    /// old value is dropped. Like [`HashMap::try_insert`], the key is not
+    /// updated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// let mut entry = map.try_insert_entry("poneyland", 3)?;
+    /// assert_eq!(entry.key(), &"poneyland");
+    ///
+    /// *entry.get_mut() += 10;
+    /// assert_eq!(map["poneyland"], 13);
+    ///
+    /// let entry = map.try_insert_entry("poneyland", 7)?;
+    /// assert_eq!(entry.get(), &7);
+    /// assert_eq!(map.len(), 1);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_insert_entry(&mut self, k: K, v: V) -> Result<OccupiedEntry<'_, K, V, S, A>, Error> {
+        let hasher = make_hasher::<K, S>(&self.hash_builder);
+        let hash = into_ok(hasher.hash(&mut (), &k));
+
+        let result = self.table.find_or_find_insert_slot(
+            &mut (),
+            hash,
+            equivalent_key(&k),
+            hasher.into_tuple(),
+        );
+
+        let elem = match result {
+            Ok(bucket) => {
+                unsafe {
+                    bucket.as_mut().1 = v;
+                }
+
+                bucket
+            }
+            Err(ErrorOrInsertSlot::InsertSlot(slot)) => unsafe {
+                self.table.insert_in_slot(hash, slot, (k, v))
+            },
+            Err(ErrorOrInsertSlot::Error(error)) => match error {
+                CustomError::Custom(error) => match error {},
+                CustomError::Error(error) => return Err(error),
+            },
+        };
+
+        Ok(OccupiedEntry {
+            hash,
+            key: None,
+            elem,
+            table: self,
+        })
+    }
+
+    /// Insert a key-value pair into the map without checking
+    /// if the key already exists in the map.
     ///
     /// Returns a reference to the key and value just inserted.
     ///
@@ -1924,6 +2434,100 @@ where
         self.try_insert_unique_unchecked(k, v).abort()
     }
 
+    /// Constructs a map from an iterator of key-value pairs which are known to
+    /// have unique keys, using the given hasher and allocator.
+    ///
+    /// Capacity for the lower bound of the iterator's size hint is reserved up
+    /// front, and every pair is inserted without looking up whether the key is
+    /// already present. This is the bulk counterpart to
+    /// [`try_insert_unique_unchecked`], useful when loading a large map which
+    /// is known to be free of duplicates, like one which was serialized from
+    /// another map.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the iterator doesn't produce duplicate
+    /// keys. Violating this doesn't violate memory safety, but it is a logic
+    /// error which leaves duplicate entries in the map, after which the
+    /// behavior of the map is unspecified as described in
+    /// [`try_insert_unique_unchecked`].
+    ///
+    /// [`try_insert_unique_unchecked`]: HashMap::try_insert_unique_unchecked
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    /// use rune::alloc::alloc::Global;
+    /// use rune::alloc::hash_map::DefaultHashBuilder;
+    ///
+    /// let pairs = [(1, "a"), (2, "b"), (3, "c")];
+    ///
+    /// // SAFETY: The keys are known to be unique.
+    /// let map = unsafe {
+    ///     HashMap::from_iter_unique_in(pairs, DefaultHashBuilder::default(), Global)?
+    /// };
+    ///
+    /// assert_eq!(map.len(), 3);
+    /// assert_eq!(map[&2], "b");
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub unsafe fn from_iter_unique_in<I>(iter: I, hash_builder: S, alloc: A) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let iter = iter.into_iter();
+        let mut map =
+            Self::try_with_capacity_and_hasher_in(iter.size_hint().0, hash_builder, alloc)?;
+
+        for (k, v) in iter {
+            map.try_insert_unique_unchecked(k, v)?;
+        }
+
+        Ok(map)
+    }
+
+    /// Constructs a map by cloning the given key-value pairs, using the given
+    /// hasher and allocator.
+    ///
+    /// The table is allocated once with exactly enough capacity to hold every
+    /// pair, so inserting never has to grow it. An empty slice doesn't
+    /// allocate.
+    ///
+    /// If the same key occurs more than once, the last pair wins and the value
+    /// of any earlier pair is dropped. If cloning a key or value fails, the
+    /// pairs inserted so far are dropped along with the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    /// use rune::alloc::alloc::Global;
+    /// use rune::alloc::hash_map::DefaultHashBuilder;
+    ///
+    /// let entries = [(1, 'a'), (2, 'b'), (1, 'c')];
+    /// let map = HashMap::try_from_entries_in(&entries, DefaultHashBuilder::default(), Global)?;
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map[&1], 'c');
+    /// assert_eq!(map[&2], 'b');
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_entries_in(entries: &[(K, V)], hasher: S, alloc: A) -> Result<Self, Error>
+    where
+        K: TryClone,
+        V: TryClone,
+    {
+        let mut map = Self::try_with_capacity_and_hasher_in(entries.len(), hasher, alloc)?;
+
+        for (k, v) in entries {
+            // NB: The table has room for every entry, so this never grows it.
+            map.try_insert(k.try_clone()?, v.try_clone()?)?;
+        }
+
+        Ok(map)
+    }
+
     /// Tries to insert a key-value pair into the map, and returns
     /// a mutable reference to the value in the entry.
     ///
@@ -2042,6 +2646,35 @@ where
         let hash = make_hash::<Q, S>(&self.hash_builder, k);
         into_ok(self.table.remove_entry(&mut (), hash, equivalent_key(k)))
     }
+
+    /// Removes a key from the map, returning the stored key and value if the
+    /// key was previously in the map.
+    ///
+    /// This is a synonym for [`remove_entry`] named to match the
+    /// corresponding [`BTreeMap`] operations.
+    ///
+    /// [`remove_entry`]: HashMap::remove_entry
+    /// [`BTreeMap`]: crate::BTreeMap
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.try_insert(1, "a")?;
+    ///
+    /// assert_eq!(map.try_pop_entry(&1), Some((1, "a")));
+    /// assert_eq!(map.try_pop_entry(&1), None);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn try_pop_entry<Q>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.remove_entry(k)
+    }
 }
 
 impl<K, V, S, A: Allocator> HashMap<K, V, S, A> {
@@ -2273,8 +2906,136 @@ impl<K, V, S, A: Allocator> HashMap<K, V, S, A> {
     pub fn raw_table_mut(&mut self) -> &mut RawTable<(K, V), A> {
         &mut self.table
     }
+
+    /// Returns a handle to the bucket which holds the given key, if it's
+    /// present. This function is only available if the `raw` feature of the
+    /// crate is enabled.
+    ///
+    /// The handle gives direct access to the stored key-value pair, its
+    /// position in the underlying [`RawTable`] and allows it to be removed
+    /// without hashing the key again.
+    ///
+    /// # Unstable
+    ///
+    /// This is a low-level API which is exempt from semver guarantees, much
+    /// like the rest of the `raw` feature.
+    ///
+    /// A bucket index identifies a position in the table and not a key. Any
+    /// operation which might move elements, such as inserting, reserving,
+    /// shrinking or rehashing, invalidates previously observed indexes.
+    #[cfg(any(test, feature = "raw"))]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn raw_bucket_for<Q>(&mut self, k: &Q) -> Option<RawBucketHandle<'_, K, V, A>>
+    where
+        S: BuildHasher,
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        if self.table.is_empty() {
+            return None;
+        }
+
+        let hash = make_hash::<Q, S>(&self.hash_builder, k);
+        let elem = into_ok(self.table.find(&mut (), hash, equivalent_key(k)))?;
+
+        Some(RawBucketHandle {
+            elem,
+            table: &mut self.table,
+        })
+    }
+}
+
+/// A handle to an occupied bucket in a [`HashMap`].
+///
+/// This `struct` is created by the [`raw_bucket_for`] method on [`HashMap`].
+/// It is only available if the `raw` feature of the crate is enabled.
+///
+/// The handle mutably borrows the map, so the bucket can't be moved while the
+/// handle is alive.
+///
+/// [`raw_bucket_for`]: HashMap::raw_bucket_for
+#[cfg(any(test, feature = "raw"))]
+pub struct RawBucketHandle<'a, K, V, A: Allocator = Global> {
+    elem: Bucket<(K, V)>,
+    table: &'a mut RawTable<(K, V), A>,
 }
 
+#[cfg(any(test, feature = "raw"))]
+impl<'a, K, V, A: Allocator> RawBucketHandle<'a, K, V, A> {
+    /// Gets a reference to the key in the bucket.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn key(&self) -> &K {
+        unsafe { &self.elem.as_ref().0 }
+    }
+
+    /// Gets a reference to the value in the bucket.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn value(&self) -> &V {
+        unsafe { &self.elem.as_ref().1 }
+    }
+
+    /// Gets a mutable reference to the value in the bucket.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn value_mut(&mut self) -> &mut V {
+        unsafe { &mut self.elem.as_mut().1 }
+    }
+
+    /// Returns the index of the bucket in the underlying [`RawTable`].
+    ///
+    /// This corresponds to the position the element is visited at when
+    /// iterating over the raw table. See [`HashMap::raw_bucket_for`] for when
+    /// the index is invalidated.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn index(&self) -> usize {
+        unsafe { self.table.bucket_index(&self.elem) }
+    }
+
+    /// Removes the key-value pair from the map and returns it.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn remove(self) -> (K, V) {
+        unsafe { self.table.remove(self.elem).0 }
+    }
+}
+
+#[cfg(any(test, feature = "raw"))]
+impl<K, V, A> Debug for RawBucketHandle<'_, K, V, A>
+where
+    K: Debug,
+    V: Debug,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawBucketHandle")
+            .field("key", self.key())
+            .field("value", self.value())
+            .field("index", &self.index())
+            .finish()
+    }
+}
+
+/// Two maps are equal if they contain the same keys, and every key maps to an
+/// equal value. The capacity of the maps and the state of their hashers don't
+/// matter.
+///
+/// # Examples
+///
+/// ```
+/// use rune::alloc::HashMap;
+/// use rune::alloc::hash_map::RandomState;
+///
+/// let mut a = HashMap::try_with_capacity_and_hasher(1, RandomState::with_seeds(1, 2, 3, 4))?;
+/// a.try_insert("a", 1)?;
+/// a.try_insert("b", 2)?;
+///
+/// let mut b = HashMap::try_with_capacity_and_hasher(64, RandomState::with_seeds(5, 6, 7, 8))?;
+/// b.try_insert("b", 2)?;
+/// b.try_insert("a", 1)?;
+///
+/// assert_eq!(a, b);
+///
+/// b.try_insert("b", 3)?;
+/// assert_ne!(a, b);
+/// # Ok::<_, rune::alloc::Error>(())
+/// ```
 impl<K, V, S, A> PartialEq for HashMap<K, V, S, A>
 where
     K: Eq + Hash,
@@ -2871,6 +3632,16 @@ where
 
 impl<K, V, F> FusedIterator for ExtractIf<'_, K, V, F> where F: FnMut(&K, &mut V) -> bool {}
 
+impl<K, V, F, A> fmt::Debug for ExtractIf<'_, K, V, F, A>
+where
+    F: FnMut(&K, &mut V) -> bool,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtractIf").finish_non_exhaustive()
+    }
+}
+
 /// Portions of `ExtractIf` shared with `set::ExtractIf`
 pub(super) struct ExtractIfInner<'a, K, V, A: Allocator> {
     pub iter: RawIter<(K, V)>,
@@ -5726,12 +6497,15 @@ impl<'a, K, V, S, A: Allocator> OccupiedEntry<'a, K, V, S, A> {
     /// ```
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn replace_entry(self, value: V) -> (K, V) {
-        let entry = unsafe { self.elem.as_mut() };
-
-        let old_key = mem::replace(&mut entry.0, self.key.unwrap());
-        let old_value = mem::replace(&mut entry.1, value);
+        // NB: Take the key before touching the bucket, so that the bucket is
+        // never left in a moved-from state if unwrapping panics.
+        let key = self.key.unwrap();
 
-        (old_key, old_value)
+        unsafe {
+            let old = self.elem.read();
+            self.elem.write((key, value));
+            old
+        }
     }
 
     /// Replaces the key in the hash map with the key used to create this entry.
@@ -5946,8 +6720,28 @@ impl<'a, K, V, S, A: Allocator> VacantEntry<'a, K, V, S, A> {
         self.try_insert(value).abort()
     }
 
+    /// Sets the value of the entry with the VacantEntry's key, and returns an
+    /// [`OccupiedEntry`] for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    /// use rune::alloc::hash_map::Entry;
+    ///
+    /// let mut map: HashMap<&str, u32> = HashMap::new();
+    ///
+    /// if let Entry::Vacant(v) = map.entry("poneyland") {
+    ///     let o = v.try_insert_entry(37)?;
+    ///     assert_eq!(o.key(), &"poneyland");
+    ///     assert_eq!(o.get(), &37);
+    /// }
+    ///
+    /// assert_eq!(map["poneyland"], 37);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
     #[cfg_attr(feature = "inline-more", inline)]
-    pub(crate) fn try_insert_entry(self, value: V) -> Result<OccupiedEntry<'a, K, V, S, A>, Error>
+    pub fn try_insert_entry(self, value: V) -> Result<OccupiedEntry<'a, K, V, S, A>, Error>
     where
         K: Hash,
         S: BuildHasher,
@@ -5968,6 +6762,15 @@ impl<'a, K, V, S, A: Allocator> VacantEntry<'a, K, V, S, A> {
             table: self.table,
         })
     }
+
+    #[cfg(test)]
+    pub(crate) fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V, S, A>
+    where
+        K: Hash,
+        S: BuildHasher,
+    {
+        self.try_insert_entry(value).abort()
+    }
 }
 
 impl<'a, 'b, K, Q: ?Sized, V, S, A: Allocator> EntryRef<'a, 'b, K, Q, V, S, A> {
@@ -7056,6 +7859,7 @@ mod test_map {
     use crate::clone::TryClone;
     use crate::error::Error;
     use crate::iter::TryExtend;
+    use crate::testing::counting_alloc::CountingAlloc;
     use crate::testing::*;
 
     std::thread_local!(static DROP_VECTOR: RefCell<Vec<i32>> = const { RefCell::new(Vec::new()) });
@@ -7487,6 +8291,40 @@ mod test_map {
         assert_eq!(m.remove(&1), None);
     }
 
+    #[test]
+    fn test_try_pop() {
+        let mut m = HashMap::<i32, i32>::new();
+        assert_eq!(m.try_pop(), None);
+
+        m.insert(1, 2);
+        assert_eq!(m.try_pop(), Some((1, 2)));
+        assert_eq!(m.try_pop(), None);
+        assert!(m.is_empty());
+
+        for i in 0..32 {
+            m.insert(i, i * 2);
+        }
+
+        let mut popped = Vec::new();
+
+        while let Some((k, v)) = m.try_pop() {
+            assert_eq!(v, k * 2);
+            popped.push(k);
+        }
+
+        popped.sort();
+        assert_eq!(popped, (0..32).collect::<Vec<_>>());
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn test_try_pop_entry() {
+        let mut m = HashMap::new();
+        m.insert(1, 2);
+        assert_eq!(m.try_pop_entry(&1), Some((1, 2)));
+        assert_eq!(m.try_pop_entry(&1), None);
+    }
+
     #[test]
     fn test_iterate() {
         let mut m = HashMap::with_capacity(4);
@@ -7593,6 +8431,50 @@ mod test_map {
         assert_eq!(m1, m2);
     }
 
+    #[test]
+    fn test_eq_capacity_and_hasher() {
+        use super::RandomState;
+
+        let mut m1 =
+            HashMap::try_with_capacity_and_hasher(4, RandomState::with_seeds(1, 2, 3, 4)).abort();
+        let mut m2 =
+            HashMap::try_with_capacity_and_hasher(1024, RandomState::with_seeds(5, 6, 7, 8))
+                .abort();
+
+        for i in 0..100 {
+            m1.insert(i, i * 2);
+        }
+
+        for i in (0..100).rev() {
+            m2.insert(i, i * 2);
+        }
+
+        assert_ne!(m1.capacity(), m2.capacity());
+        assert_eq!(m1, m2);
+        assert_eq!(m2, m1);
+
+        // A single differing value makes the maps unequal.
+        *m2.get_mut(&50).unwrap() = 0;
+        assert_ne!(m1, m2);
+        assert_ne!(m2, m1);
+
+        m2.insert(50, 100);
+        assert_eq!(m1, m2);
+
+        // The same length but a different key.
+        m2.remove(&50);
+        m2.insert(100, 100);
+        assert_ne!(m1, m2);
+        assert_ne!(m2, m1);
+    }
+
+    #[test]
+    fn test_extract_if_debug() {
+        let mut map: HashMap<i32, i32> = (0..8).map(|x| (x, x)).collect();
+        let extract = map.extract_if(|&k, _| k % 2 == 0);
+        assert_eq!(format!("{extract:?}"), "ExtractIf { .. }");
+    }
+
     #[test]
     fn test_show() {
         let mut map = HashMap::new();
@@ -7988,6 +8870,377 @@ mod test_map {
         }
     }
 
+    #[test]
+    fn test_entry_or_try_insert_with_key() {
+        let mut map: HashMap<u32, u32> = HashMap::new();
+
+        let value = map.entry(4).or_try_insert_with_key(|key| key * 10).unwrap();
+        assert_eq!(*value, 40);
+
+        // The closure is not called for occupied entries.
+        let value = map
+            .entry(4)
+            .or_try_insert_with_key(|_| unreachable!())
+            .unwrap();
+        assert_eq!(*value, 40);
+
+        let value = map
+            .entry(4)
+            .and_modify(|value| *value += 1)
+            .or_try_insert_with_key(|_| unreachable!())
+            .unwrap();
+        assert_eq!(*value, 41);
+
+        match map.entry(5) {
+            Occupied(_) => unreachable!(),
+            Vacant(entry) => assert_eq!(entry.into_key(), 5),
+        }
+
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_try_entry_or_default() {
+        let mut map: HashMap<u32, u32> = HashMap::new();
+
+        let first: *mut u32 = map.try_entry_or_default(1).unwrap();
+        let second: *mut u32 = map.try_entry_or_default(1).unwrap();
+        assert_eq!(first, second);
+
+        *map.try_entry_or_default(1).unwrap() += 2;
+        *map.try_entry_or_default(1).unwrap() += 3;
+
+        assert_eq!(map[&1], 5);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_try_insert_entry() {
+        let mut map: HashMap<i32, i32> = HashMap::new();
+
+        for i in 0..32 {
+            let entry = map.try_insert_entry(i, i * 10).unwrap();
+            assert_eq!(*entry.key(), i);
+            assert_eq!(*entry.get(), i * 10);
+        }
+
+        // Replacing keeps the same slot, and the entry can be mutated.
+        let mut entry = map.try_insert_entry(7, 700).unwrap();
+        assert_eq!(*entry.key(), 7);
+        let slot = entry.get() as *const i32;
+        *entry.get_mut() += 1;
+
+        assert_eq!(map.len(), 32);
+        assert_eq!(map[&7], 701);
+        assert!(core::ptr::eq(&map[&7], slot));
+
+        let entry = map.try_insert_entry(40, 4).unwrap();
+        assert_eq!(entry.remove_entry(), (40, 4));
+        assert!(!map.contains_key(&40));
+        assert_eq!(map.len(), 32);
+    }
+
+    #[test]
+    fn test_vacant_entry_insert_entry() {
+        let mut map: HashMap<&str, u32> = HashMap::new();
+
+        let entry = match map.entry("a") {
+            Vacant(v) => v.insert_entry(10),
+            Occupied(_) => unreachable!(),
+        };
+
+        assert_eq!(entry.key(), &"a");
+        assert_eq!(entry.get(), &10);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map["a"], 10);
+
+        let entry = match map.entry("b") {
+            Vacant(v) => v.insert_entry(20),
+            Occupied(_) => unreachable!(),
+        };
+
+        assert_eq!(entry.remove(), 20);
+        assert!(!map.contains_key("b"));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_from_iter_unique_in() {
+        let pairs = (0..100).map(|i| (i, i * 2));
+        let map: HashMap<i32, i32> =
+            unsafe { HashMap::from_iter_unique_in(pairs, DefaultHashBuilder::default(), Global) }
+                .unwrap();
+
+        assert_eq!(map.len(), 100);
+        assert!(map.capacity() >= 100);
+
+        for i in 0..100 {
+            assert_eq!(map[&i], i * 2);
+        }
+
+        let empty: HashMap<i32, i32> = unsafe {
+            HashMap::from_iter_unique_in(core::iter::empty(), DefaultHashBuilder::default(), Global)
+        }
+        .unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.capacity(), 0);
+    }
+
+    #[test]
+    fn test_try_from_entries_in() {
+        let alloc = CountingAlloc::default();
+        let entries = (0..100).map(|i| (i % 90, i)).collect::<Vec<_>>();
+
+        let map =
+            HashMap::try_from_entries_in(&entries, DefaultHashBuilder::default(), &alloc).unwrap();
+
+        assert_eq!(alloc.allocations(), 1);
+        assert_eq!(map.len(), 90);
+
+        // Later duplicates win.
+        for i in 0..10 {
+            assert_eq!(map[&i], i + 90);
+        }
+
+        for i in 10..90 {
+            assert_eq!(map[&i], i);
+        }
+
+        drop(map);
+        assert_eq!(alloc.deallocations(), 1);
+
+        let empty: HashMap<i32, i32, _, _> =
+            HashMap::try_from_entries_in(&[], DefaultHashBuilder::default(), &alloc).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(alloc.allocations(), 1);
+    }
+
+    #[test]
+    fn test_try_from_entries_in_clone_failure() {
+        /// Value which fails to clone if `fail` is set, and counts drops.
+        struct Value<'a> {
+            fail: bool,
+            drops: &'a core::cell::Cell<usize>,
+        }
+
+        impl TryClone for Value<'_> {
+            fn try_clone(&self) -> Result<Self, Error> {
+                if self.fail {
+                    return Err(Error::CapacityOverflow);
+                }
+
+                Ok(Value {
+                    fail: false,
+                    drops: self.drops,
+                })
+            }
+        }
+
+        impl Drop for Value<'_> {
+            fn drop(&mut self) {
+                self.drops.set(self.drops.get() + 1);
+            }
+        }
+
+        let alloc = CountingAlloc::default();
+        let drops = core::cell::Cell::new(0);
+
+        let value = |fail| Value {
+            fail,
+            drops: &drops,
+        };
+
+        let entries = [
+            (1, value(false)),
+            (2, value(false)),
+            (3, value(true)),
+            (4, value(false)),
+        ];
+
+        let result = HashMap::try_from_entries_in(&entries, DefaultHashBuilder::default(), &alloc);
+        assert!(matches!(result, Err(Error::CapacityOverflow)));
+
+        // The two values cloned before the failure have been dropped.
+        assert_eq!(drops.get(), 2);
+        assert_eq!(alloc.allocations(), 1);
+        assert_eq!(alloc.deallocations(), 1);
+    }
+
+    #[test]
+    fn test_raw_bucket_for() {
+        let mut map: HashMap<i32, i32> = HashMap::new();
+
+        for i in 0..32 {
+            map.insert(i, i * 10);
+        }
+
+        assert!(map.raw_bucket_for(&100).is_none());
+
+        let mut handle = map.raw_bucket_for(&4).unwrap();
+        assert_eq!(*handle.key(), 4);
+        assert_eq!(*handle.value(), 40);
+        *handle.value_mut() += 1;
+        assert_eq!(map[&4], 41);
+
+        let handle = map.raw_bucket_for(&5).unwrap();
+        assert_eq!(handle.remove(), (5, 50));
+        assert_eq!(map.len(), 31);
+        assert!(map.get(&5).is_none());
+        assert!(map.raw_bucket_for(&5).is_none());
+    }
+
+    #[test]
+    fn test_raw_bucket_for_index() {
+        let mut map: HashMap<i32, i32> = HashMap::new();
+
+        for i in 0..32 {
+            map.insert(i, i);
+        }
+
+        let mut positions = Vec::new();
+
+        unsafe {
+            for bucket in map.raw_table().iter() {
+                let index = map.raw_table().bucket_index(&bucket);
+                positions.push((bucket.as_ref().0, index));
+            }
+        }
+
+        assert_eq!(positions.len(), 32);
+
+        for (key, index) in positions {
+            let handle = map.raw_bucket_for(&key).unwrap();
+            debug_assert_eq!(handle.index(), index);
+            assert_eq!(*handle.key(), key);
+        }
+    }
+
+    #[test]
+    fn test_try_get_or_default_entry() {
+        let mut map: HashMap<u32, u32> = HashMap::new();
+
+        let first: *mut u32 = map.try_get_or_default_entry(1).unwrap();
+        let second: *mut u32 = map.try_get_or_default_entry(1).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(map.len(), 1);
+
+        for i in 0..100 {
+            *map.try_get_or_default_entry(i % 10).unwrap() += 1;
+        }
+
+        assert_eq!(map.len(), 10);
+
+        for i in 0..10 {
+            assert_eq!(map[&i], 10);
+        }
+    }
+
+    #[test]
+    fn test_get_all() {
+        // A hasher where every key has the same hash, so that every lookup
+        // has to reject candidates with a different key.
+        #[derive(Default)]
+        struct Collide;
+
+        impl core::hash::Hasher for Collide {
+            fn finish(&self) -> u64 {
+                0
+            }
+
+            fn write(&mut self, _: &[u8]) {}
+        }
+
+        let mut map: HashMap<u32, u32, core::hash::BuildHasherDefault<Collide>> =
+            HashMap::default();
+
+        for n in 0..4 {
+            for v in 0..=n {
+                map.try_insert_unique_unchecked(n, n * 10 + v).unwrap();
+            }
+        }
+
+        for n in 0..4 {
+            let mut values = map.get_all(&n).copied().collect::<Vec<_>>();
+            values.sort();
+            assert_eq!(values, (0..=n).map(|v| n * 10 + v).collect::<Vec<_>>());
+        }
+
+        assert_eq!(map.get_all(&4).count(), 0);
+        assert_eq!(HashMap::<u32, u32>::new().get_all(&0).count(), 0);
+    }
+
+    #[test]
+    fn test_entry_or_try_default() {
+        let mut map: HashMap<&str, Vec<u32>> = HashMap::new();
+
+        map.entry("a").or_try_default().unwrap().push(1);
+        map.entry("a").or_try_default().unwrap().push(2);
+        map.entry_ref("b").or_try_default().unwrap().push(3);
+        map.entry_ref("b").or_try_default().unwrap().push(4);
+
+        assert_eq!(map["a"], [1, 2]);
+        assert_eq!(map["b"], [3, 4]);
+    }
+
+    #[test]
+    fn test_entry_ref_materializes_key_on_insert() {
+        use core::borrow::Borrow;
+        use core::sync::atomic::AtomicUsize;
+
+        static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Hash, PartialEq, Eq)]
+        struct Key(String);
+
+        impl From<&str> for Key {
+            fn from(key: &str) -> Self {
+                ALLOCATED.fetch_add(1, Ordering::SeqCst);
+                Key(key.to_owned())
+            }
+        }
+
+        impl Borrow<str> for Key {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        let mut map: HashMap<Key, usize> = HashMap::new();
+
+        let value = map
+            .entry_ref("poneyland")
+            .or_try_insert_with_key(|key| key.len())
+            .unwrap();
+        assert_eq!(*value, 9);
+        assert_eq!(ALLOCATED.load(Ordering::SeqCst), 1);
+
+        // Occupied entries neither call the closure nor allocate a key.
+        let value = map
+            .entry_ref("poneyland")
+            .or_try_insert_with_key(|_| unreachable!())
+            .unwrap();
+        assert_eq!(*value, 9);
+        assert_eq!(ALLOCATED.load(Ordering::SeqCst), 1);
+
+        *map.entry_ref("poneyland").or_try_default().unwrap() += 1;
+        assert_eq!(ALLOCATED.load(Ordering::SeqCst), 1);
+
+        assert_eq!(*map.entry_ref("horseland").or_try_default().unwrap(), 0);
+        assert_eq!(ALLOCATED.load(Ordering::SeqCst), 2);
+
+        match map.entry_ref("donkeyland") {
+            EntryRef::Occupied(_) => unreachable!(),
+            EntryRef::Vacant(entry) => {
+                let key = entry.into_key();
+                assert_eq!(key.0, "donkeyland");
+            }
+        }
+
+        assert_eq!(ALLOCATED.load(Ordering::SeqCst), 3);
+        assert_eq!(map["poneyland"], 10);
+        assert_eq!(map.len(), 2);
+    }
+
     #[test]
     fn test_extend_ref_k_ref_v() {
         let mut a = HashMap::new();
@@ -8078,6 +9331,29 @@ mod test_map {
         assert_eq!(a[key], value);
     }
 
+    #[test]
+    fn test_occupied_entry_replace_entry() {
+        let mut a = HashMap::new();
+        let old_key = ::rust_alloc::rc::Rc::new("hello there");
+        let new_key = ::rust_alloc::rc::Rc::new("hello there");
+
+        a.insert(old_key.clone(), 1);
+
+        let (key, value) = match a.entry(new_key.clone()) {
+            Vacant(_) => panic!(),
+            Occupied(e) => e.replace_entry(2),
+        };
+
+        assert!(::rust_alloc::rc::Rc::ptr_eq(&key, &old_key));
+        assert!(!::rust_alloc::rc::Rc::ptr_eq(&key, &new_key));
+        assert_eq!(value, 1);
+
+        let (stored, _) = a.get_key_value(&new_key).unwrap();
+        assert!(::rust_alloc::rc::Rc::ptr_eq(stored, &new_key));
+        assert_eq!(a.len(), 1);
+        assert_eq!(a[&old_key], 2);
+    }
+
     #[test]
     fn test_occupied_entry_ref_key() {
         let mut a = HashMap::new();
@@ -8494,6 +9770,174 @@ mod test_map {
         assert_eq!(map[&6], 60);
     }
 
+    #[test]
+    fn test_try_retain() {
+        let mut map: HashMap<i32, i32> = (0..100).map(|x| (x, x * 10)).collect();
+
+        map.try_retain(|&k, v| {
+            *v += 1;
+            Ok::<_, ()>(k % 2 == 0)
+        })
+        .unwrap();
+
+        assert_eq!(map.len(), 50);
+        assert_eq!(map[&2], 21);
+        assert_eq!(map[&4], 41);
+
+        let result = map.try_retain(|&k, _| if k == 4 { Err(k) } else { Ok(false) });
+        assert_eq!(result, Err(4));
+        assert!(map.contains_key(&4));
+        assert!(map.len() < 50);
+    }
+
+    #[test]
+    fn test_try_update() {
+        let mut map: HashMap<i32, i32> = (0..4).map(|x| (x, x * 10)).collect();
+
+        assert!(map
+            .try_update(&1, |v| {
+                *v += 1;
+                Ok(())
+            })
+            .unwrap());
+        assert_eq!(map[&1], 11);
+
+        let mut called = false;
+        assert!(!map
+            .try_update(&10, |_| {
+                called = true;
+                Ok(())
+            })
+            .unwrap());
+        assert!(!called);
+        assert_eq!(map.len(), 4);
+
+        let result = map.try_update(&2, |v| {
+            *v += 1;
+            Err(Error::CapacityOverflow)
+        });
+        assert!(matches!(result, Err(Error::CapacityOverflow)));
+        assert_eq!(map[&2], 21);
+    }
+
+    #[test]
+    fn test_try_map_values() {
+        let map: HashMap<i32, i32> = (0..8).map(|x| (x, x * 10)).collect();
+        let mapped = map.try_map_values(|v| Ok(v + 1)).unwrap();
+        assert_eq!(mapped.len(), 8);
+
+        for x in 0..8 {
+            assert_eq!(mapped[&x], x * 10 + 1);
+        }
+
+        let dropped = Arc::new(AtomicI8::new(0));
+        let mut map = HashMap::new();
+
+        for x in 0..8 {
+            map.try_insert(x, CountDrop(dropped.clone())).abort();
+        }
+
+        let mut calls = 0;
+
+        let result = map.try_map_values(|v| {
+            calls += 1;
+
+            if calls == 4 {
+                return Err(Error::CapacityOverflow);
+            }
+
+            Ok(v)
+        });
+
+        assert!(matches!(result, Err(Error::CapacityOverflow)));
+        assert_eq!(dropped.load(Ordering::SeqCst), 8);
+
+        struct CountDrop(Arc<AtomicI8>);
+
+        impl Drop for CountDrop {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_merge_with() {
+        let mut map: HashMap<i32, i32> = (0..8).map(|x| (x, x)).collect();
+        let other: HashMap<i32, i32> = (4..12).map(|x| (x, x * 100)).collect();
+
+        let mut conflicts = Vec::new();
+
+        map.try_merge_with(other, |k, existing, incoming| {
+            conflicts.push(*k);
+            *existing += incoming;
+        })
+        .unwrap();
+
+        conflicts.sort_unstable();
+        assert_eq!(conflicts, [4, 5, 6, 7]);
+        assert_eq!(map.len(), 12);
+
+        for x in 0..4 {
+            assert_eq!(map[&x], x);
+        }
+
+        for x in 4..8 {
+            assert_eq!(map[&x], x + x * 100);
+        }
+
+        for x in 8..12 {
+            assert_eq!(map[&x], x * 100);
+        }
+
+        map.try_merge_with(HashMap::new(), |_, _, _| unreachable!())
+            .unwrap();
+        assert_eq!(map.len(), 12);
+    }
+
+    #[test]
+    fn test_drain_filter_into() {
+        let mut map: HashMap<i32, i32> = (0..100).map(|x| (x, x * 10)).collect();
+        let mut out = crate::vec::Vec::try_with_capacity(50).unwrap();
+
+        let moved = map.drain_filter_into(|&k, _| k % 2 == 0, &mut out).unwrap();
+        assert_eq!(moved, 50);
+        assert_eq!(map.len(), 50);
+        assert_eq!(out.len(), 50);
+        assert_eq!(out.capacity(), 50);
+        assert!(out.iter().all(|&(k, v)| k % 2 == 0 && v == k * 10));
+        assert!(map.keys().all(|&k| k % 2 != 0));
+
+        let moved = map.drain_filter_into(|_, _| false, &mut out).unwrap();
+        assert_eq!(moved, 0);
+        assert_eq!(map.len(), 50);
+        assert_eq!(out.len(), 50);
+    }
+
+    #[test]
+    fn test_drain_filter_into_alloc_failure() {
+        let mut map: HashMap<i32, i32> = (0..16).map(|x| (x, x * 10)).collect();
+        let mut out = crate::vec::Vec::try_with_capacity(4).unwrap();
+
+        let result =
+            crate::limit::with(0, || map.drain_filter_into(|&k, _| k % 2 == 0, &mut out)).call();
+
+        assert!(matches!(result, Err(Error::AllocError { .. })));
+        assert_eq!(out.len(), 4);
+        assert_eq!(map.len(), 12);
+
+        let mut rest = crate::vec::Vec::new();
+        let moved = map
+            .drain_filter_into(|&k, _| k % 2 == 0, &mut rest)
+            .unwrap();
+        assert_eq!(moved, 4);
+        assert_eq!(map.len(), 8);
+
+        let mut keys: Vec<i32> = out.iter().chain(rest.iter()).map(|&(k, _)| k).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, [0, 2, 4, 6, 8, 10, 12, 14]);
+    }
+
     #[test]
     fn test_extract_if() {
         {
@@ -8647,6 +10091,37 @@ mod test_map {
         }
     }
 
+    #[test]
+    fn test_raw_entry_external_hash_resize() {
+        use super::RawEntryMut::{Occupied, Vacant};
+
+        // A key which the map itself has no way of hashing, inserted until the
+        // table has to be resized using the external hasher.
+        #[derive(Debug, PartialEq)]
+        struct Key(u64);
+
+        let hash = |k: &Key| k.0.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        let mut map: HashMap<Key, u64, ()> = HashMap::with_hasher(());
+
+        for n in 0..100 {
+            match map.raw_entry_mut().from_hash(hash(&Key(n)), |k| k.0 == n) {
+                Occupied(_) => unreachable!(),
+                Vacant(view) => {
+                    view.try_insert_with_hasher(hash(&Key(n)), Key(n), n * 10, hash)
+                        .unwrap();
+                }
+            }
+        }
+
+        assert_eq!(map.len(), 100);
+
+        for n in 0..200 {
+            let expected = (n < 100).then(|| (Key(n), n * 10));
+            let actual = map.raw_entry().from_hash(hash(&Key(n)), |k| k.0 == n);
+            assert_eq!(actual, expected.as_ref().map(|(k, v)| (k, v)));
+        }
+    }
+
     #[test]
     fn test_key_without_hash_impl() {
         #[derive(Debug)]
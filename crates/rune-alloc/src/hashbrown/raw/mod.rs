@@ -1,4 +1,6 @@
 use core::alloc::Layout;
+#[cfg(any(test, feature = "raw"))]
+use core::fmt;
 use core::iter::FusedIterator;
 use core::marker::PhantomData;
 use core::mem;
@@ -146,8 +148,8 @@ impl ProbeSeq {
 // Workaround for emscripten bug emscripten-core/emscripten-fastcomp#258
 #[cfg_attr(target_os = "emscripten", inline(never))]
 #[cfg_attr(not(target_os = "emscripten"), inline)]
-fn capacity_to_buckets(cap: usize) -> Option<usize> {
-    debug_assert_ne!(cap, 0);
+const fn capacity_to_buckets(cap: usize) -> Option<usize> {
+    debug_assert!(cap != 0);
 
     // For small tables we require at least 1 empty bucket so that lookups are
     // guaranteed to terminate if an element doesn't exist in the table.
@@ -162,7 +164,10 @@ fn capacity_to_buckets(cap: usize) -> Option<usize> {
     //
     // Be careful when modifying this, calculate_layout relies on the
     // overflow check here.
-    let adjusted_cap = cap.checked_mul(8)? / 7;
+    let adjusted_cap = match cap.checked_mul(8) {
+        Some(cap) => cap / 7,
+        None => return None,
+    };
 
     // Any overflows will have been caught by the checked_mul. Also, any
     // rounding errors from the division above will be cleaned up by
@@ -173,7 +178,7 @@ fn capacity_to_buckets(cap: usize) -> Option<usize> {
 /// Returns the maximum effective capacity for the given bucket mask, taking
 /// the maximum load factor into account.
 #[inline]
-fn bucket_mask_to_capacity(bucket_mask: usize) -> usize {
+const fn bucket_mask_to_capacity(bucket_mask: usize) -> usize {
     if bucket_mask < 8 {
         // For tables with 1/2/4/8 buckets, we always reserve one empty slot.
         // Keep in mind that the bucket mask is one less than the bucket count.
@@ -229,6 +234,43 @@ impl TableLayout {
     }
 }
 
+/// A breakdown of the memory used by a [`RawTable`] allocation.
+///
+/// See [`RawTable::memory_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct MemoryReport {
+    /// Bytes used by the data region storing the buckets.
+    pub data_bytes: usize,
+    /// Bytes used by the control bytes, including the trailing group.
+    pub ctrl_bytes: usize,
+    /// Bytes lost to padding needed to align the control bytes.
+    pub padding_bytes: usize,
+    /// The total size in bytes of the allocation.
+    pub total_bytes: usize,
+    /// The number of buckets in the table.
+    pub buckets: usize,
+    /// The number of items stored in the table.
+    pub items: usize,
+}
+
+/// Load factor and tombstone statistics for a [`RawTable`].
+///
+/// See [`RawTable::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct RawTableStats {
+    /// The number of items stored in the table.
+    pub len: usize,
+    /// The number of items the table can hold without reallocating.
+    pub capacity: usize,
+    /// The number of buckets in the table.
+    pub buckets: usize,
+    /// The number of buckets marked as deleted, which are only reclaimed once
+    /// the table is rehashed.
+    pub deleted: usize,
+}
+
 /// A reference to an empty bucket into which an can be inserted.
 pub struct InsertSlot {
     index: usize,
@@ -787,6 +829,39 @@ impl<T, A: Allocator> RawTable<T, A> {
         }
     }
 
+    /// Returns the capacity of a table which is allocated to hold `n`
+    /// elements, such as through [`RawTable::try_with_capacity`].
+    ///
+    /// The returned capacity is at least `n`, so inserting up to that many
+    /// elements into a table with this capacity never triggers a resize.
+    ///
+    /// If `n` is so large that no table could hold it, `n` is returned as-is
+    /// since allocating such a table fails with a capacity overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::hashbrown::raw::RawTable;
+    ///
+    /// assert_eq!(RawTable::<u32>::capacity_for_at_least(0), 0);
+    /// assert_eq!(RawTable::<u32>::capacity_for_at_least(1), 3);
+    /// assert_eq!(RawTable::<u32>::capacity_for_at_least(8), 14);
+    ///
+    /// let table = RawTable::<u32>::try_with_capacity(8)?;
+    /// assert_eq!(table.capacity(), RawTable::<u32>::capacity_for_at_least(8));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub const fn capacity_for_at_least(n: usize) -> usize {
+        if n == 0 {
+            return 0;
+        }
+
+        match capacity_to_buckets(n) {
+            Some(buckets) => bucket_mask_to_capacity(buckets - 1),
+            None => n,
+        }
+    }
+
     /// Allocates a new hash table with the given number of buckets.
     ///
     /// The control bytes are left uninitialized.
@@ -817,6 +892,52 @@ impl<T, A: Allocator> RawTable<T, A> {
         &self.alloc
     }
 
+    /// Constructs an empty table over an allocation previously returned by
+    /// [`RawTable::into_allocation`] or [`RawIntoIter::into_allocation`].
+    ///
+    /// The new table has the same number of buckets as the table the
+    /// allocation was taken from.
+    ///
+    /// # Safety
+    ///
+    /// The allocation must have been taken from a table with the same element
+    /// type `T`, it must have been allocated by `alloc`, and any elements
+    /// which were stored in it must already have been dropped or moved out.
+    pub unsafe fn from_allocation(ptr: NonNull<u8>, layout: Layout, alloc: A) -> Self {
+        let mut buckets = 1usize;
+
+        // The size of the allocation grows with the number of buckets, so the
+        // first one which produces a layout at least as large as the
+        // allocation is the only candidate.
+        let ctrl_offset = loop {
+            match Self::TABLE_LAYOUT.calculate_layout_for(buckets) {
+                Some((candidate, ctrl_offset)) if candidate.size() >= layout.size() => {
+                    debug_assert_eq!(candidate, layout, "allocation has the wrong layout");
+                    break ctrl_offset;
+                }
+                Some(..) => buckets *= 2,
+                None => hint::unreachable_unchecked(),
+            }
+        };
+
+        let table = RawTableInner {
+            ctrl: NonNull::new_unchecked(ptr.as_ptr().add(ctrl_offset)),
+            bucket_mask: buckets - 1,
+            items: 0,
+            growth_left: bucket_mask_to_capacity(buckets - 1),
+        };
+
+        // SAFETY: The allocation has room for `num_ctrl_bytes` control bytes
+        // since its layout is the one calculated for `buckets`.
+        table.ctrl(0).write_bytes(EMPTY, table.num_ctrl_bytes());
+
+        Self {
+            table,
+            alloc,
+            marker: PhantomData,
+        }
+    }
+
     /// Returns pointer to one past last element of data table.
     #[inline]
     pub unsafe fn data_end(&self) -> NonNull<T> {
@@ -844,6 +965,51 @@ impl<T, A: Allocator> RawTable<T, A> {
         unsafe { self.table.allocation_info_or_zero(Self::TABLE_LAYOUT) }
     }
 
+    /// Return a breakdown of the memory allocated by the table.
+    ///
+    /// Unlike [`RawTable::allocation_info`] this splits the allocation into
+    /// the data region, the control bytes and any padding introduced to align
+    /// the control bytes. The empty singleton reports all zeros since it does
+    /// not own an allocation.
+    pub fn memory_report(&self) -> MemoryReport {
+        if self.table.is_empty_singleton() {
+            return MemoryReport::default();
+        }
+
+        let buckets = self.buckets();
+
+        // Avoid `Option::unwrap_or_else` because it bloats LLVM IR.
+        let (layout, ctrl_offset) = match Self::TABLE_LAYOUT.calculate_layout_for(buckets) {
+            Some(lco) => lco,
+            None => unsafe { hint::unreachable_unchecked() },
+        };
+
+        let data_bytes = Self::TABLE_LAYOUT.size * buckets;
+        let ctrl_bytes = layout.size() - ctrl_offset;
+
+        MemoryReport {
+            data_bytes,
+            ctrl_bytes,
+            padding_bytes: ctrl_offset - data_bytes,
+            total_bytes: layout.size(),
+            buckets,
+            items: self.table.items,
+        }
+    }
+
+    /// Return load factor and tombstone statistics for the table.
+    ///
+    /// This performs a single pass over the control bytes of the table to
+    /// count the number of deleted buckets.
+    pub fn stats(&self) -> RawTableStats {
+        RawTableStats {
+            len: self.len(),
+            capacity: self.capacity(),
+            buckets: self.buckets(),
+            deleted: self.table.count_deleted(),
+        }
+    }
+
     /// Returns the index of a bucket from a `Bucket`.
     #[inline]
     pub unsafe fn bucket_index(&self, bucket: &Bucket<T>) -> usize {
@@ -909,6 +1075,30 @@ impl<T, A: Allocator> RawTable<T, A> {
         )
     }
 
+    /// Removes the element in the bucket at the given index from the table,
+    /// returning it.
+    ///
+    /// This is the index-based sibling of [`remove`], useful when the index
+    /// of the bucket is already known, such as through [`bucket_index`]. The
+    /// bucket is freed up in the same way as by [`erase`].
+    ///
+    /// # Safety
+    ///
+    /// The index must be in range of the table and refer to a full bucket.
+    /// This is only checked with debug assertions.
+    ///
+    /// [`remove`]: RawTable::remove
+    /// [`erase`]: RawTable::erase
+    /// [`bucket_index`]: RawTable::bucket_index
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub unsafe fn remove_index(&mut self, index: usize) -> T {
+        debug_assert!(index < self.buckets());
+        debug_assert!(self.is_bucket_full(index));
+        let item = self.bucket(index);
+        self.table.erase(index);
+        item.read()
+    }
+
     /// Finds and removes an element from the table, returning it.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn remove_entry<C: ?Sized, E>(
@@ -947,6 +1137,27 @@ impl<T, A: Allocator> RawTable<T, A> {
         }
     }
 
+    /// Removes all elements from the table and frees the backing memory.
+    ///
+    /// Unlike [`shrink_to`], this doesn't require a hasher since there are no
+    /// elements left to rehash.
+    ///
+    /// [`shrink_to`]: RawTable::shrink_to
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear_and_shrink(&mut self) {
+        let mut old_inner = mem::replace(&mut self.table, RawTableInner::NEW);
+
+        unsafe {
+            // SAFETY:
+            // 1. We call the function only once;
+            // 2. We know for sure that `alloc` and `table_layout` matches the [`Allocator`]
+            //    and [`TableLayout`] that were used to allocate this table.
+            // 3. If any elements' drop function panics, then there will only be a memory leak,
+            //    because we have replaced the inner table with a new one.
+            old_inner.drop_inner_table::<T, _>(&self.alloc, Self::TABLE_LAYOUT);
+        }
+    }
+
     /// Shrinks the table to fit `max(self.len(), min_size)` elements.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn shrink_to<C: ?Sized, E>(
@@ -959,16 +1170,7 @@ impl<T, A: Allocator> RawTable<T, A> {
         // space for.
         let min_size = usize::max(self.table.items, min_size);
         if min_size == 0 {
-            let mut old_inner = mem::replace(&mut self.table, RawTableInner::NEW);
-            unsafe {
-                // SAFETY:
-                // 1. We call the function only once;
-                // 2. We know for sure that `alloc` and `table_layout` matches the [`Allocator`]
-                //    and [`TableLayout`] that were used to allocate this table.
-                // 3. If any elements' drop function panics, then there will only be a memory leak,
-                //    because we have replaced the inner table with a new one.
-                old_inner.drop_inner_table::<T, _>(&self.alloc, Self::TABLE_LAYOUT);
-            }
+            self.clear_and_shrink();
             return Ok(());
         }
 
@@ -1012,6 +1214,51 @@ impl<T, A: Allocator> RawTable<T, A> {
         Ok(())
     }
 
+    /// Rehashes every element in the table in place, clearing out any
+    /// tombstones left behind by removals without changing the number of
+    /// buckets.
+    ///
+    /// This is an `O(capacity)` operation which is only useful after a large
+    /// number of removals, where tombstones would otherwise degrade lookups and
+    /// cause the table to grow earlier than necessary.
+    ///
+    /// If the hasher errors, any element which hasn't been rehashed yet is
+    /// dropped.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn rehash<C: ?Sized, E>(
+        &mut self,
+        cx: &mut C,
+        hasher: impl HasherFn<C, T, E>,
+    ) -> Result<(), CustomError<E>> {
+        if self.table.is_empty_singleton() {
+            return Ok(());
+        }
+
+        unsafe {
+            // SAFETY:
+            // 1. We checked above that the table has been allocated.
+            // 2. The `size_of` and `drop` function match the elements stored in
+            //    the table.
+            // 3. The [`RawTableInner`] must already have properly initialized
+            //    control bytes since we never exposed
+            //    RawTable::new_uninitialized in a public API.
+            self.table
+                .rehash_in_place(
+                    cx,
+                    &|cx, table, index| hasher.hash(cx, table.bucket::<T>(index).as_ref()),
+                    mem::size_of::<T>(),
+                    if T::NEEDS_DROP {
+                        Some(mem::transmute::<unsafe fn(*mut T), fn(*mut u8)>(
+                            ptr::drop_in_place::<T> as unsafe fn(*mut T),
+                        ))
+                    } else {
+                        None
+                    },
+                )
+                .map_err(CustomError::Custom)
+        }
+    }
+
     /// Ensures that at least `additional` items can be inserted into the table
     /// without reallocation.
     #[cfg_attr(feature = "inline-more", inline)]
@@ -1167,6 +1414,42 @@ impl<T, A: Allocator> RawTable<T, A> {
         }
     }
 
+    /// Inserts a batch of elements with precomputed hashes into the table.
+    ///
+    /// The table is grown at most once up front to fit every element in
+    /// `items`, after which each element is inserted without any further
+    /// growth checks.
+    ///
+    /// This does not check if the given elements already exist in the table,
+    /// nor if `items` contains duplicates. Just like with [`insert`], this is
+    /// the responsibility of the caller.
+    ///
+    /// [`insert`]: RawTable::insert
+    pub fn try_insert_batch<C: ?Sized, E, I>(
+        &mut self,
+        cx: &mut C,
+        items: I,
+        hasher: impl HasherFn<C, T, E>,
+    ) -> Result<(), CustomError<E>>
+    where
+        I: ExactSizeIterator<Item = (u64, T)>,
+    {
+        let additional = items.len();
+        self.reserve(cx, additional, hasher)?;
+
+        for (hash, value) in items.take(additional) {
+            // SAFETY: We reserved space for `additional` elements above, and
+            // inserting into a tombstone does not consume any growth, so every
+            // insert is guaranteed to find a free slot.
+            unsafe {
+                let slot = self.table.find_insert_slot(hash);
+                self.insert_in_slot(hash, slot, value);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Attempts to insert a new element without growing the table and return its raw bucket.
     ///
     /// Returns an `Err` containing the given element if inserting it would require growing the
@@ -1273,6 +1556,118 @@ impl<T, A: Allocator> RawTable<T, A> {
         Ok(unsafe { self.bucket(index) })
     }
 
+    /// Searches for an element in the table, returning the bucket of the
+    /// element if it was found alongside the position of a slot where an
+    /// element with the same hash could be inserted.
+    ///
+    /// Unlike [`find_or_find_insert_slot`], the insertion slot is provided
+    /// even if the element is found. This allows the caller to decide whether
+    /// to overwrite the existing element or to insert a new one without having
+    /// to probe the table again. Inserting into the slot when an element was
+    /// found does not replace it, so the table ends up with duplicates.
+    ///
+    /// This function may resize the table if additional space is required for
+    /// inserting an element.
+    ///
+    /// [`find_or_find_insert_slot`]: RawTable::find_or_find_insert_slot
+    #[inline]
+    pub fn find_with_slot<C: ?Sized, E>(
+        &mut self,
+        cx: &mut C,
+        hash: u64,
+        eq: impl EqFn<C, T, E>,
+        hasher: impl HasherFn<C, T, E>,
+    ) -> Result<(Option<Bucket<T>>, InsertSlot), CustomError<E>> {
+        self.reserve(cx, 1, hasher)?;
+
+        let (index, slot) = self
+            .table
+            .find_with_slot_inner(cx, hash, &|cx, index| unsafe {
+                eq.eq(cx, self.bucket(index).as_ref())
+            })
+            .map_err(CustomError::Custom)?;
+
+        // Avoid `Option::map` because it bloats LLVM IR.
+        let bucket = match index {
+            Some(index) => Some(unsafe { self.bucket(index) }),
+            None => None,
+        };
+
+        Ok((bucket, slot))
+    }
+
+    /// Inserts an element into the table, replacing an existing element which
+    /// compares equal to it.
+    ///
+    /// Returns the displaced element if one was replaced, or `None` if the
+    /// element was inserted into a fresh slot.
+    ///
+    /// This function may resize the table if additional space is required for
+    /// inserting an element.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_insert_or_replace<C: ?Sized, E>(
+        &mut self,
+        cx: &mut C,
+        hash: u64,
+        value: T,
+        eq: impl EqFn<C, T, E>,
+        hasher: impl HasherFn<C, T, E>,
+    ) -> Result<Option<T>, CustomError<E>> {
+        match self.find_or_find_insert_slot(cx, hash, eq, hasher) {
+            Ok(bucket) => Ok(Some(unsafe { mem::replace(bucket.as_mut(), value) })),
+            Err(ErrorOrInsertSlot::InsertSlot(slot)) => {
+                unsafe {
+                    self.insert_in_slot(hash, slot, value);
+                }
+
+                Ok(None)
+            }
+            Err(ErrorOrInsertSlot::Error(error)) => Err(error),
+        }
+    }
+
+    /// Moves all elements from `other` into this table, growing it as needed.
+    ///
+    /// Elements from `other` which compare equal to an element already in
+    /// this table according to `eq` replace the existing element, which is
+    /// dropped.
+    ///
+    /// Space for `other.len()` elements is reserved up front, so merging
+    /// tables which don't overlap grows the table at most once.
+    pub fn try_merge_into<C: ?Sized, E>(
+        &mut self,
+        cx: &mut C,
+        other: RawTable<T, A>,
+        eq: impl Fn(&mut C, &T, &T) -> Result<bool, E>,
+        hasher: impl HasherFn<C, T, E>,
+    ) -> Result<(), CustomError<E>> {
+        let hasher = |cx: &mut C, value: &T| hasher.hash(cx, value);
+
+        self.reserve(cx, other.len(), hasher)?;
+
+        for value in other.into_iter() {
+            let hash = hasher(cx, &value).map_err(CustomError::Custom)?;
+
+            let found = self.find_with_slot(
+                cx,
+                hash,
+                |cx: &mut C, existing: &T| eq(cx, existing, &value),
+                hasher,
+            )?;
+
+            match found {
+                (Some(bucket), _) => unsafe {
+                    *bucket.as_mut() = value;
+                },
+                (None, slot) => unsafe {
+                    self.insert_in_slot(hash, slot, value);
+                },
+            }
+        }
+
+        Ok(())
+    }
+
     /// Inserts a new element into the table in the given slot, and returns its
     /// raw bucket.
     ///
@@ -1388,6 +1783,34 @@ impl<T, A: Allocator> RawTable<T, A> {
         Ok(Some(mem::transmute_copy(&ptrs)))
     }
 
+    /// Gets mutable references to `N` elements in the table at once by their
+    /// bucket indices.
+    ///
+    /// This is the index-based sibling of [`get_many_mut`], useful when the
+    /// indices of the buckets are already known, such as through
+    /// [`bucket_index`].
+    ///
+    /// # Safety
+    ///
+    /// Every index must be in range of the table and refer to a full bucket,
+    /// and all indices must be distinct. This is only checked with debug
+    /// assertions.
+    ///
+    /// [`get_many_mut`]: RawTable::get_many_mut
+    /// [`bucket_index`]: RawTable::bucket_index
+    pub unsafe fn get_disjoint_indices_mut<const N: usize>(
+        &mut self,
+        indices: [usize; N],
+    ) -> [&'_ mut T; N] {
+        for (i, &index) in indices.iter().enumerate() {
+            debug_assert!(index < self.buckets(), "index out of bounds");
+            debug_assert!(self.is_bucket_full(index), "bucket is not full");
+            debug_assert!(!indices[..i].contains(&index), "duplicate index");
+        }
+
+        indices.map(|index| self.bucket(index).as_mut())
+    }
+
     unsafe fn get_many_mut_pointers<C: ?Sized, E, const N: usize>(
         &mut self,
         cx: &mut C,
@@ -1447,6 +1870,26 @@ impl<T, A: Allocator> RawTable<T, A> {
         self.table.is_bucket_full(index)
     }
 
+    /// Asserts that the bookkeeping of the table is consistent with its
+    /// control bytes.
+    ///
+    /// This is intended for tests and fuzzers which want to validate the
+    /// integrity of a table after a sequence of operations. Element data is
+    /// never read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the following doesn't hold:
+    /// * The number of full control bytes is the number of items.
+    /// * The number of items, tombstones and `growth_left` adds up to the
+    ///   capacity of the table.
+    /// * Every control byte is either full, empty or deleted.
+    /// * The trailing control bytes replicate the leading group.
+    #[cfg(any(test, feature = "raw"))]
+    pub fn assert_invariants(&self) {
+        self.table.assert_invariants();
+    }
+
     /// Returns an iterator over every element in the table. It is up to
     /// the caller to ensure that the `RawTable` outlives the `RawIter`.
     /// Because we cannot make the `next` method unsafe on the `RawIter`
@@ -1478,6 +1921,66 @@ impl<T, A: Allocator> RawTable<T, A> {
         RawIterHash::new(self, hash)
     }
 
+    /// Partitions the table into at most `n` disjoint ranges, which together
+    /// yield every full bucket in the table exactly once.
+    ///
+    /// Every range covers a whole number of control groups, and the number of
+    /// groups in any two ranges differs by at most one. Since a range can't
+    /// be smaller than a group, fewer than `n` ranges are returned for small
+    /// tables. At least one range is always returned, even if `n` is zero.
+    ///
+    /// This is the primitive on which parallel iteration can be built. Since
+    /// `RawIterRange` is `Send`, each range can be processed on a separate
+    /// thread.
+    ///
+    /// # Safety
+    ///
+    /// The same rules as for [`iter`] apply to every returned range:
+    ///
+    /// * The table must outlive all of the returned ranges. This includes
+    ///   not growing, shrinking or otherwise reallocating the table while any
+    ///   range is alive.
+    /// * The table must not be mutated while the ranges are alive, since no
+    ///   range can reflect insertions or removals made to the table.
+    /// * If buckets yielded by different ranges are accessed concurrently, it
+    ///   is up to the caller to ensure that `T` supports it.
+    ///
+    /// [`iter`]: RawTable::iter
+    pub unsafe fn iter_ranges(&self, n: usize) -> impl Iterator<Item = RawIterRange<T>> {
+        let buckets = self.buckets();
+        let ctrl = self.table.ctrl(0) as *const u8;
+        let data_end = self.data_end();
+
+        // Tables smaller than a group are covered by a single range whose
+        // trailing control bytes are all EMPTY.
+        let (groups, group_len) = if buckets < Group::WIDTH {
+            (1, buckets)
+        } else {
+            (buckets / Group::WIDTH, Group::WIDTH)
+        };
+
+        let n = n.clamp(1, groups);
+        let per_range = groups / n;
+        let extra = groups % n;
+
+        (0..n).map(move |i| {
+            let start = i * per_range + i.min(extra);
+            let len = per_range + usize::from(i < extra);
+
+            // SAFETY:
+            // 1. The caller upholds that the table outlives the range.
+            // 2. `start * group_len` is group aligned, so `ctrl` is properly
+            //    aligned and points to initialized control bytes.
+            // 3. `data` is the bucket at the same index as `ctrl`.
+            // 4. The range ends at or before the end of the control bytes.
+            unsafe {
+                let index = start * group_len;
+                let data = Bucket::from_base_index(data_end, index);
+                RawIterRange::new(ctrl.add(index), data, len * group_len)
+            }
+        })
+    }
+
     /// Returns an iterator which removes all elements from the table without
     /// freeing the memory.
     #[cfg_attr(feature = "inline-more", inline)]
@@ -1525,8 +2028,11 @@ impl<T, A: Allocator> RawTable<T, A> {
 
     /// Converts the table into a raw allocation. The contents of the table
     /// should be dropped using a `RawIter` before freeing the allocation.
+    ///
+    /// Returns `None` if the table hasn't allocated. The allocation can be
+    /// reused through [`RawTable::from_allocation`].
     #[cfg_attr(feature = "inline-more", inline)]
-    pub(crate) fn into_allocation(self) -> Option<(NonNull<u8>, Layout, A)> {
+    pub fn into_allocation(self) -> Option<(NonNull<u8>, Layout, A)> {
         let alloc = if self.table.is_empty_singleton() {
             None
         } else {
@@ -1778,6 +2284,65 @@ impl RawTableInner {
         }
     }
 
+    /// Searches for an element in the table, and a potential slot where that element could be
+    /// inserted.
+    ///
+    /// Unlike [`find_or_find_insert_slot_inner`], the insertion slot is tracked even if a
+    /// matching element is found, in which case probing continues past the match until a
+    /// suitable slot has been located.
+    ///
+    /// [`find_or_find_insert_slot_inner`]: RawTableInner::find_or_find_insert_slot_inner
+    #[inline]
+    fn find_with_slot_inner<C: ?Sized, E>(
+        &self,
+        cx: &mut C,
+        hash: u64,
+        eq: &dyn Fn(&mut C, usize) -> Result<bool, E>,
+    ) -> Result<(Option<usize>, InsertSlot), E> {
+        let mut found = None;
+        let mut insert_slot = None;
+
+        let h2_hash = h2(hash);
+        let mut probe_seq = self.probe_seq(hash);
+
+        loop {
+            let group = unsafe { Group::load(self.ctrl(probe_seq.pos)) };
+
+            if likely(found.is_none()) {
+                for bit in group.match_byte(h2_hash) {
+                    let index = (probe_seq.pos + bit) & self.bucket_mask;
+
+                    if likely(eq(cx, index)?) {
+                        found = Some(index);
+                        break;
+                    }
+                }
+            }
+
+            // Try to get an insertion slot from the group if we don't have one yet.
+            if likely(insert_slot.is_none()) {
+                insert_slot = self.find_insert_slot_in_group(&group, &probe_seq);
+            }
+
+            // Only stop the search if the group contains at least one empty element, or if we've
+            // found both the element and an insertion slot. Otherwise, the element or slot that
+            // we are looking for might be in a following group.
+            if likely(found.is_some() && insert_slot.is_some())
+                || likely(group.match_empty().any_bit_set())
+            {
+                // We must have found a insert slot by now, since either we checked for it above
+                // or the current group contains at least one. For tables smaller than the group
+                // width, there will still be an empty element in the current (and only) group
+                // due to the load factor.
+                unsafe {
+                    return Ok((found, self.fix_insert_slot(insert_slot.unwrap_unchecked())));
+                }
+            }
+
+            probe_seq.move_next(self.bucket_mask);
+        }
+    }
+
     /// Searches for an empty or deleted bucket which is suitable for inserting a new
     /// element and sets the hash for that slot. Returns an index of that slot and the
     /// old control byte stored in the found index.
@@ -2390,11 +2955,83 @@ impl RawTableInner {
         is_full(*self.ctrl(index))
     }
 
-    #[inline]
+    /// Count the number of control bytes which are marked as [`DELETED`].
+    fn count_deleted(&self) -> usize {
+        let mut deleted = 0;
+
+        // Tables smaller than a group are padded with `EMPTY` control bytes
+        // up to `Group::WIDTH`, and larger tables are a multiple of the group
+        // width, so the trailing mirrored bytes are never visited.
+        for index in (0..self.buckets()).step_by(Group::WIDTH) {
+            // SAFETY: `index` is a multiple of `Group::WIDTH` which is less
+            // than the number of buckets, and the control bytes are aligned
+            // to the group width.
+            let group = unsafe { Group::load_aligned(self.ctrl(index)) };
+            deleted += group.match_byte(DELETED).into_iter().count();
+        }
+
+        deleted
+    }
+
+    #[inline]
     fn num_ctrl_bytes(&self) -> usize {
         self.bucket_mask + 1 + Group::WIDTH
     }
 
+    #[cfg(any(test, feature = "raw"))]
+    fn assert_invariants(&self) {
+        if self.is_empty_singleton() {
+            // The empty singleton points to a static group, so only the
+            // bookkeeping is checked.
+            assert_eq!(self.items, 0, "empty singleton has items");
+            assert_eq!(self.growth_left, 0, "empty singleton has growth left");
+            return;
+        }
+
+        let buckets = self.buckets();
+        let mut full = 0;
+        let mut deleted = 0;
+
+        for index in 0..buckets {
+            // SAFETY: `index` is less than the number of buckets, and the
+            // mirrored index is less than `num_ctrl_bytes()`.
+            let (ctrl, mirror) = unsafe {
+                let mirror = (index.wrapping_sub(Group::WIDTH) & self.bucket_mask) + Group::WIDTH;
+                (*self.ctrl(index), *self.ctrl(mirror))
+            };
+
+            assert!(
+                is_full(ctrl) || ctrl == EMPTY || ctrl == DELETED,
+                "invalid control byte {ctrl:#04x} at {index}"
+            );
+
+            assert_eq!(ctrl, mirror, "control byte {index} is not replicated");
+
+            if is_full(ctrl) {
+                full += 1;
+            } else if ctrl == DELETED {
+                deleted += 1;
+            }
+        }
+
+        // Tables smaller than a group are padded with empty control bytes up
+        // to the group width.
+        for index in buckets..Group::WIDTH {
+            // SAFETY: `index` is less than the group width, which is less than
+            // `num_ctrl_bytes()`.
+            let ctrl = unsafe { *self.ctrl(index) };
+            assert_eq!(ctrl, EMPTY, "padding control byte {index} is not empty");
+        }
+
+        assert_eq!(full, self.items, "full control bytes don't match items");
+
+        assert_eq!(
+            self.items + deleted + self.growth_left,
+            bucket_mask_to_capacity(self.bucket_mask),
+            "items, tombstones and growth left don't add up to the capacity"
+        );
+    }
+
     #[inline]
     fn is_empty_singleton(&self) -> bool {
         self.bucket_mask == 0
@@ -3072,6 +3709,13 @@ where
                 self_.table.drop_elements::<T>();
 
                 // If necessary, resize our table to match the source.
+                //
+                // Note that this also happens if our table is larger than the
+                // source. Elements are located by `hash & bucket_mask`, so
+                // their control and data bytes can only be copied into a table
+                // with the same number of buckets. Reusing a larger table
+                // requires rehashing every element, which is what
+                // `clone_from_with_hasher` does when a hasher is available.
                 if self_.buckets() != source.buckets() {
                     let new_inner = RawTableInner::new_uninitialized(
                         &self_.alloc,
@@ -3250,6 +3894,54 @@ impl<T, A: Allocator + Default> Default for RawTable<T, A> {
     }
 }
 
+/// Dumps the state of the table, including a rendering of every control byte
+/// as `F` (full), `D` (deleted) or `E` (empty).
+///
+/// Element data is never read, so this is safe to use on a table that is
+/// suspected of being corrupt.
+#[cfg(any(test, feature = "raw"))]
+impl<T, A: Allocator> fmt::Debug for RawTable<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawTable")
+            .field("bucket_mask", &self.table.bucket_mask)
+            .field("items", &self.table.items)
+            .field("growth_left", &self.table.growth_left)
+            .field("ctrl", &CtrlBytes(&self.table))
+            .finish()
+    }
+}
+
+#[cfg(any(test, feature = "raw"))]
+struct CtrlBytes<'a>(&'a RawTableInner);
+
+#[cfg(any(test, feature = "raw"))]
+impl fmt::Debug for CtrlBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use core::fmt::Write;
+
+        f.write_char('"')?;
+
+        for index in 0..self.0.buckets() {
+            // SAFETY: `index` is less than the number of buckets, and the
+            // control bytes are always initialized, even for the empty
+            // singleton.
+            let ctrl = unsafe { *self.0.ctrl(index) };
+
+            let c = if is_full(ctrl) {
+                'F'
+            } else if special_is_empty(ctrl) {
+                'E'
+            } else {
+                'D'
+            };
+
+            f.write_char(c)?;
+        }
+
+        f.write_char('"')
+    }
+}
+
 #[cfg(rune_nightly)]
 unsafe impl<#[may_dangle] T, A: Allocator> Drop for RawTable<T, A> {
     #[cfg_attr(feature = "inline-more", inline)]
@@ -3300,7 +3992,9 @@ impl<T, A: Allocator> IntoIterator for RawTable<T, A> {
 
 /// Iterator over a sub-range of a table. Unlike `RawIter` this iterator does
 /// not track an item count.
-pub(crate) struct RawIterRange<T> {
+///
+/// See [`RawTable::iter_ranges`] for how to construct one.
+pub struct RawIterRange<T> {
     // Mask of full buckets in the current group. Bits are cleared from this
     // mask as each element is processed.
     current_group: BitMaskIter,
@@ -3742,6 +4436,29 @@ impl<T, A: Allocator> RawIntoIter<T, A> {
     pub fn iter(&self) -> RawIter<T> {
         self.iter.clone()
     }
+
+    /// Returns a reference to the allocator of the table being consumed, or
+    /// `None` if the table hadn't allocated.
+    #[inline]
+    pub fn allocator(&self) -> Option<&A> {
+        self.allocation.as_ref().map(|(_, _, alloc)| alloc)
+    }
+
+    /// Drops any elements which haven't been consumed yet, and returns the
+    /// allocation which backed the table instead of freeing it.
+    ///
+    /// Returns `None` if the table hadn't allocated. The allocation can be
+    /// reused through [`RawTable::from_allocation`].
+    pub fn into_allocation(self) -> Option<(NonNull<u8>, Layout, A)> {
+        let mut this = mem::ManuallyDrop::new(self);
+
+        // SAFETY: The iterator is not used again after its remaining elements
+        // have been dropped, and the allocation is moved out exactly once.
+        unsafe {
+            this.iter.drop_elements();
+            ptr::read(&this.allocation)
+        }
+    }
 }
 
 unsafe impl<T, A: Allocator> Send for RawIntoIter<T, A>
@@ -3998,8 +4715,9 @@ impl Iterator for RawIterHashInner {
 mod test_map {
     use super::*;
 
-    use crate::alloc::into_ok;
+    use crate::alloc::{into_ok, into_ok_try};
     use core::convert::Infallible;
+    use std::format;
 
     fn rehash_in_place<T>(
         table: &mut RawTable<T>,
@@ -4060,6 +4778,522 @@ mod test_map {
         }
     }
 
+    #[test]
+    fn memory_report() {
+        let mut table = RawTable::<u64>::new();
+        assert_eq!(table.memory_report(), MemoryReport::default());
+
+        let hasher = |_: &mut (), i: &u64| Ok::<_, Infallible>(*i);
+
+        for i in 0..100 {
+            table.insert(&mut (), i, i, hasher).abort();
+        }
+
+        let report = table.memory_report();
+        let (_, layout) = table.allocation_info();
+
+        assert_eq!(report.items, 100);
+        assert_eq!(report.buckets, table.buckets());
+        assert_eq!(report.data_bytes, table.buckets() * mem::size_of::<u64>());
+        assert_eq!(report.ctrl_bytes, table.buckets() + Group::WIDTH);
+        assert_eq!(report.total_bytes, layout.size());
+        assert_eq!(
+            report.data_bytes + report.ctrl_bytes + report.padding_bytes,
+            report.total_bytes
+        );
+    }
+
+    #[test]
+    fn capacity_for_at_least() {
+        let hasher = |_: &mut (), i: &u64| Ok::<_, Infallible>(*i);
+        let mut last = 0;
+
+        for n in 0..1000 {
+            let capacity = RawTable::<u64>::capacity_for_at_least(n);
+            assert!(capacity >= n);
+            assert!(capacity >= last);
+            last = capacity;
+
+            let mut table = RawTable::<u64>::try_with_capacity(n).abort();
+            assert_eq!(table.capacity(), capacity);
+            let buckets = table.buckets();
+
+            for i in 0..capacity as u64 {
+                table.insert(&mut (), i, i, hasher).abort();
+            }
+
+            assert_eq!(table.buckets(), buckets);
+        }
+
+        assert_eq!(
+            RawTable::<u64>::capacity_for_at_least(usize::MAX),
+            usize::MAX
+        );
+    }
+
+    #[test]
+    fn iter_ranges() {
+        use ::rust_alloc::vec::Vec;
+
+        let mut table = RawTable::<u64>::new();
+        let hasher = |_: &mut (), i: &u64| Ok::<_, Infallible>(*i);
+
+        unsafe {
+            assert_eq!(table.iter_ranges(4).count(), 1);
+            assert_eq!(table.iter_ranges(4).flatten().count(), 0);
+        }
+
+        for i in 0..1000 {
+            table.insert(&mut (), i, i, hasher).abort();
+        }
+
+        let groups = table.buckets() / Group::WIDTH;
+
+        for n in [0, 1, 2, 3, 7, groups, groups + 10] {
+            let mut values = Vec::new();
+            let mut count = 0;
+
+            unsafe {
+                for range in table.iter_ranges(n) {
+                    count += 1;
+                    values.extend(range.map(|b| *b.as_ref()));
+                }
+            }
+
+            assert_eq!(count, n.clamp(1, groups));
+            values.sort();
+            assert_eq!(values, (0..1000).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let mut table = RawTable::<u64>::new();
+        // Every item collides so that a contiguous run of full buckets forms,
+        // which forces removals to leave tombstones behind.
+        let hasher = |_: &mut (), _: &u64| Ok::<_, Infallible>(0);
+
+        let stats = table.stats();
+        assert_eq!(stats.len, 0);
+        assert_eq!(stats.capacity, 0);
+        assert_eq!(stats.deleted, 0);
+
+        let count = Group::WIDTH as u64 * 3;
+
+        for i in 0..count {
+            table.insert(&mut (), 0, i, hasher).abort();
+        }
+
+        let stats = table.stats();
+        assert_eq!(stats.len, count as usize);
+        assert_eq!(stats.capacity, table.capacity());
+        assert_eq!(stats.buckets, table.buckets());
+        assert_eq!(stats.deleted, 0);
+
+        let i = Group::WIDTH as u64;
+        let removed = into_ok(table.remove_entry(&mut (), 0, |_: &mut (), x: &u64| Ok(*x == i)));
+        assert_eq!(removed, Some(i));
+
+        let stats = table.stats();
+        assert_eq!(stats.len, count as usize - 1);
+        assert_eq!(stats.deleted, 1);
+
+        // Reserving more than what is left forces a rehash, which clears
+        // all tombstones.
+        let additional = table.capacity() - table.len() + 1;
+        table.reserve(&mut (), additional, hasher).abort();
+
+        let stats = table.stats();
+        assert_eq!(stats.len, count as usize - 1);
+        assert_eq!(stats.deleted, 0);
+    }
+
+    #[test]
+    fn find_with_slot() {
+        let mut table = RawTable::<u64>::new();
+        // Every item collides so that the matching group is full, and the
+        // insertion slot has to be found past the match.
+        let hasher = |_: &mut (), _: &u64| Ok::<_, Infallible>(0);
+        let eq = |i: u64| move |_: &mut (), x: &u64| Ok::<_, Infallible>(*x == i);
+
+        let (found, slot) = table.find_with_slot(&mut (), 0, eq(0), hasher).abort();
+        assert!(found.is_none());
+
+        unsafe {
+            table.insert_in_slot(0, slot, 0);
+        }
+
+        let count = Group::WIDTH as u64 * 2;
+
+        for i in 1..count {
+            let (found, slot) = table.find_with_slot(&mut (), 0, eq(i), hasher).abort();
+            assert!(found.is_none());
+
+            unsafe {
+                table.insert_in_slot(0, slot, i);
+            }
+        }
+
+        assert_eq!(table.len(), count as usize);
+
+        let (found, slot) = table.find_with_slot(&mut (), 0, eq(1), hasher).abort();
+        let found = found.expect("expected to find element");
+        assert_eq!(unsafe { *found.as_ref() }, 1);
+        assert!(unsafe { !table.is_bucket_full(slot.index) });
+
+        // A tombstone left behind by a removal becomes the insertion slot, even
+        // though the element being searched for is found further along.
+        let removed = into_ok(table.find(&mut (), 0, eq(0))).expect("expected to find element");
+        let removed = unsafe { table.bucket_index(&removed) };
+        assert_eq!(into_ok(table.remove_entry(&mut (), 0, eq(0))), Some(0));
+
+        let (found, slot) = table
+            .find_with_slot(&mut (), 0, eq(count - 1), hasher)
+            .abort();
+        let found = found.expect("expected to find element");
+        assert_eq!(unsafe { *found.as_ref() }, count - 1);
+        assert_eq!(slot.index, removed);
+
+        // Overwriting the found element keeps the length intact.
+        unsafe {
+            *found.as_mut() = count;
+        }
+
+        assert_eq!(table.len(), count as usize - 1);
+        assert!(into_ok(table.find(&mut (), 0, eq(count))).is_some());
+        assert!(into_ok(table.find(&mut (), 0, eq(count - 1))).is_none());
+    }
+
+    #[test]
+    fn assert_invariants() {
+        let hasher = |_: &mut (), i: &u64| Ok::<_, Infallible>(*i);
+        let eq = |k: u64| move |_: &mut (), i: &u64| Ok::<_, Infallible>(*i == k);
+
+        let mut table = RawTable::<u64>::new();
+        table.assert_invariants();
+
+        for n in [1, 3, 16, 100] {
+            into_ok_try(table.try_reserve(&mut (), n, hasher)).abort();
+            table.assert_invariants();
+            assert!(table.table.growth_left >= n);
+
+            let buckets = table.buckets();
+            let start = table.len() as u64;
+
+            for i in start..start + n as u64 {
+                table.insert(&mut (), i, i, hasher).abort();
+                table.assert_invariants();
+            }
+
+            // Reserved inserts never rehash the table.
+            assert_eq!(table.buckets(), buckets);
+        }
+
+        // Erasing leaves a mix of empty and deleted control bytes behind.
+        for i in (0..table.len() as u64).step_by(3) {
+            assert!(into_ok(table.erase_entry(&mut (), i, eq(i))));
+            table.assert_invariants();
+        }
+
+        table.clear();
+        table.assert_invariants();
+    }
+
+    #[test]
+    fn try_merge_into() {
+        let hasher = |_: &mut (), (k, _): &(u64, u64)| Ok::<_, Infallible>(*k);
+        let eq = |_: &mut (), (a, _): &(u64, u64), (b, _): &(u64, u64)| Ok::<_, Infallible>(a == b);
+
+        let table = |keys: core::ops::Range<u64>, tag: u64| {
+            let mut table = RawTable::<(u64, u64)>::new();
+
+            for k in keys {
+                table.insert(&mut (), k, (k, tag), hasher).abort();
+            }
+
+            table
+        };
+
+        // Tables which don't overlap.
+        let mut a = table(0..50, 1);
+        a.try_merge_into(&mut (), table(50..120, 2), eq, hasher)
+            .abort();
+        assert_eq!(a.len(), 120);
+
+        for k in 0..120 {
+            let tag = if k < 50 { 1 } else { 2 };
+            assert_eq!(
+                into_ok(a.get(&mut (), k, |_: &mut (), x: &(u64, u64)| Ok(x.0 == k))),
+                Some(&(k, tag))
+            );
+        }
+
+        // Tables which partially overlap, where elements from `other` win.
+        let mut a = table(0..50, 1);
+        a.try_merge_into(&mut (), table(25..75, 2), eq, hasher)
+            .abort();
+        assert_eq!(a.len(), 75);
+
+        for k in 0..75 {
+            let tag = if k < 25 { 1 } else { 2 };
+            assert_eq!(
+                into_ok(a.get(&mut (), k, |_: &mut (), x: &(u64, u64)| Ok(x.0 == k))),
+                Some(&(k, tag))
+            );
+        }
+
+        // Merging into an empty table.
+        let mut a = RawTable::new();
+        a.try_merge_into(&mut (), table(0..10, 3), eq, hasher)
+            .abort();
+        assert_eq!(a.len(), 10);
+    }
+
+    #[test]
+    fn try_insert_or_replace() {
+        let mut table = RawTable::<(u64, u64)>::new();
+        let hasher = |_: &mut (), (k, _): &(u64, u64)| Ok::<_, Infallible>(*k);
+        let eq = |k: u64| move |_: &mut (), (x, _): &(u64, u64)| Ok::<_, Infallible>(*x == k);
+
+        for i in 0..64 {
+            let old = table
+                .try_insert_or_replace(&mut (), i, (i, i), eq(i), hasher)
+                .abort();
+            assert_eq!(old, None);
+        }
+
+        assert_eq!(table.len(), 64);
+
+        for i in 0..64 {
+            let old = table
+                .try_insert_or_replace(&mut (), i, (i, i * 10), eq(i), hasher)
+                .abort();
+            assert_eq!(old, Some((i, i)));
+        }
+
+        assert_eq!(table.len(), 64);
+
+        for i in 0..64 {
+            let found = into_ok(table.get(&mut (), i, eq(i)));
+            assert_eq!(found, Some(&(i, i * 10)));
+        }
+    }
+
+    #[test]
+    fn remove_index() {
+        let mut table = RawTable::<u64>::new();
+        let hasher = |_: &mut (), i: &u64| Ok::<_, Infallible>(*i);
+        let eq = |i: u64| move |_: &mut (), x: &u64| Ok::<_, Infallible>(*x == i);
+
+        for i in 0..100 {
+            table.insert(&mut (), i, i, hasher).abort();
+        }
+
+        let growth_left = table.table.growth_left;
+
+        for i in (0..100).step_by(3) {
+            let bucket = into_ok(table.find(&mut (), i, eq(i))).expect("expected to find element");
+
+            unsafe {
+                let index = table.bucket_index(&bucket);
+                assert_eq!(table.remove_index(index), i);
+                assert!(!table.is_bucket_full(index));
+            }
+        }
+
+        assert_eq!(table.len(), 66);
+
+        // Buckets which were marked as empty can be reused right away.
+        let stats = table.stats();
+        assert_eq!(table.table.growth_left, growth_left + 34 - stats.deleted);
+
+        for i in 0..100 {
+            let found = into_ok(table.find(&mut (), i, eq(i)));
+            assert_eq!(found.is_some(), i % 3 != 0);
+        }
+
+        // Removed buckets can be inserted into again.
+        for i in (0..100).step_by(3) {
+            table.insert(&mut (), i, i, hasher).abort();
+        }
+
+        assert_eq!(table.len(), 100);
+    }
+
+    #[test]
+    fn debug_ctrl_bytes() {
+        let mut table = RawTable::<u64>::new();
+        let hasher = |_: &mut (), i: &u64| Ok::<_, Infallible>(*i);
+        let eq = |i: u64| move |_: &mut (), x: &u64| Ok::<_, Infallible>(*x == i);
+
+        assert_eq!(
+            format!("{table:?}"),
+            "RawTable { bucket_mask: 0, items: 0, growth_left: 0, ctrl: \"E\" }"
+        );
+
+        for i in 0..3 {
+            table.insert(&mut (), i, i, hasher).abort();
+        }
+
+        let bucket = into_ok(table.find(&mut (), 1, eq(1))).expect("expected to find element");
+
+        let index = unsafe {
+            let index = table.bucket_index(&bucket);
+            table.erase(bucket);
+            index
+        };
+
+        let debug = format!("{table:?}");
+        let ctrl = debug
+            .split("ctrl: \"")
+            .nth(1)
+            .and_then(|rest| rest.strip_suffix("\" }"))
+            .expect("expected control bytes");
+
+        assert_eq!(ctrl.len(), table.buckets());
+        assert_eq!(ctrl.matches('F').count(), 2);
+
+        // Whether the removed bucket becomes a tombstone or is marked as
+        // empty depends on the surrounding group.
+        let removed = ctrl.as_bytes()[index];
+        assert_eq!(removed == b'D', table.stats().deleted == 1);
+        assert!(matches!(removed, b'D' | b'E'));
+        assert!(debug.starts_with(&format!(
+            "RawTable {{ bucket_mask: {}, items: 2, growth_left: {}, ",
+            table.buckets() - 1,
+            table.table.growth_left
+        )));
+    }
+
+    #[test]
+    fn get_disjoint_indices_mut() {
+        let mut table = RawTable::<u64>::new();
+        let hasher = |_: &mut (), i: &u64| Ok::<_, Infallible>(*i);
+
+        for i in 0..100 {
+            table.insert(&mut (), i, i, hasher).abort();
+        }
+
+        let index = |table: &RawTable<u64>, i: u64| {
+            let bucket = into_ok(table.find(&mut (), i, |_: &mut (), x: &u64| Ok(*x == i)));
+            unsafe { table.bucket_index(&bucket.expect("expected to find element")) }
+        };
+
+        let indices = [index(&table, 3), index(&table, 50), index(&table, 99)];
+
+        unsafe {
+            let [a, b, c] = table.get_disjoint_indices_mut(indices);
+            assert_eq!((*a, *b, *c), (3, 50, 99));
+            core::mem::swap(a, b);
+            *c += 1000;
+        }
+
+        unsafe {
+            let values = indices.map(|index| *table.bucket(index).as_ref());
+            assert_eq!(values, [50, 3, 1099]);
+            assert_eq!(table.get_disjoint_indices_mut::<0>([]).len(), 0);
+        }
+    }
+
+    #[test]
+    fn insert_batch() {
+        let mut table = RawTable::<u64>::new();
+        let hasher = |_: &mut (), i: &u64| Ok::<_, Infallible>(*i);
+
+        table
+            .try_insert_batch(
+                &mut (),
+                (0..1000usize).map(|i| (i as u64, i as u64)),
+                hasher,
+            )
+            .abort();
+
+        assert_eq!(table.len(), 1000);
+
+        for i in 0..1000 {
+            let found = into_ok(table.find(&mut (), i, |_: &mut (), x: &u64| Ok(*x == i)));
+            assert_eq!(found.map(|b| unsafe { *b.as_ref() }), Some(i));
+        }
+
+        table
+            .try_insert_batch(&mut (), core::iter::empty(), hasher)
+            .abort();
+        assert_eq!(table.len(), 1000);
+    }
+
+    #[test]
+    fn insert_batch_tombstones() {
+        let mut table = RawTable::<u64>::new();
+        // Every item collides so that removals leave tombstones behind.
+        let hasher = |_: &mut (), _: &u64| Ok::<_, Infallible>(0);
+
+        let count = Group::WIDTH as u64 * 3;
+
+        for i in 0..count {
+            table.insert(&mut (), 0, i, hasher).abort();
+        }
+
+        for i in (0..count).step_by(2) {
+            let removed =
+                into_ok(table.remove_entry(&mut (), 0, |_: &mut (), x: &u64| Ok(*x == i)));
+            assert_eq!(removed, Some(i));
+        }
+
+        assert!(table.stats().deleted > 0);
+
+        let remaining = table.len();
+        let extra = count..count * 2;
+        let items = (extra.start as usize..extra.end as usize).map(|i| (0, i as u64));
+
+        table.try_insert_batch(&mut (), items, hasher).abort();
+
+        assert_eq!(table.len(), remaining + extra.clone().count());
+
+        for i in (0..count).filter(|i| i % 2 == 1).chain(extra) {
+            let found = into_ok(table.find(&mut (), 0, |_: &mut (), x: &u64| Ok(*x == i)));
+            assert_eq!(found.map(|b| unsafe { *b.as_ref() }), Some(i));
+        }
+
+        for i in (0..count).step_by(2) {
+            let found = into_ok(table.find(&mut (), 0, |_: &mut (), x: &u64| Ok(*x == i)));
+            assert!(found.is_none());
+        }
+    }
+
+    #[test]
+    fn rehash_clears_tombstones() {
+        let mut table = RawTable::<u64>::new();
+        let hasher = |_: &mut (), i: &u64| Ok::<_, Infallible>(*i);
+
+        table.rehash(&mut (), hasher).abort();
+
+        for i in 0..100 {
+            table.insert(&mut (), i, i, hasher).abort();
+        }
+
+        for i in (0..100).filter(|i| i % 3 != 0) {
+            assert!(into_ok(table.erase_entry(
+                &mut (),
+                i,
+                |_: &mut (), x: &u64| Ok(*x == i)
+            )));
+        }
+
+        let buckets = table.buckets();
+        let growth_left = table.table.growth_left;
+
+        table.rehash(&mut (), hasher).abort();
+
+        assert_eq!(table.buckets(), buckets);
+        assert!(table.table.growth_left >= growth_left);
+        assert_eq!(table.table.growth_left, table.capacity() - table.len());
+
+        for i in 0..100 {
+            let found = into_ok(table.find(&mut (), i, |_: &mut (), x: &u64| Ok(*x == i)));
+            assert_eq!(found.is_some(), i % 3 == 0);
+        }
+    }
+
     /// CHECKING THAT WE ARE NOT TRYING TO READ THE MEMORY OF
     /// AN UNINITIALIZED TABLE DURING THE DROP
     #[test]
@@ -4273,4 +5507,181 @@ mod test_map {
         // All allocator clones should already be dropped.
         assert_eq!(dropped.load(Ordering::SeqCst), 1);
     }
+
+    #[test]
+    fn into_allocation() {
+        use ::rust_alloc::rc::Rc;
+
+        let hasher = |_: &mut (), (i, _): &(u64, _)| Ok::<_, Infallible>(*i);
+
+        let value = Rc::new(());
+        let mut table = RawTable::new();
+
+        for i in 0..100u64 {
+            table.insert(&mut (), i, (i, value.clone()), hasher).abort();
+        }
+
+        let buckets = table.buckets();
+        let info = table.allocation_info();
+
+        let mut iter = table.into_iter();
+        assert!(iter.allocator().is_some());
+        assert!(iter.next().is_some());
+
+        // Remaining elements are dropped when the allocation is recovered.
+        let (ptr, layout, alloc) = iter.into_allocation().unwrap();
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert_eq!((ptr, layout), info);
+
+        let mut table = unsafe { RawTable::<(u64, Rc<()>)>::from_allocation(ptr, layout, alloc) };
+        assert_eq!(table.len(), 0);
+        assert_eq!(table.buckets(), buckets);
+        assert_eq!(table.allocation_info(), info);
+
+        for i in 0..100u64 {
+            table.insert(&mut (), i, (i, value.clone()), hasher).abort();
+        }
+
+        // The table is reused without having to grow.
+        assert_eq!(table.allocation_info(), info);
+        assert_eq!(Rc::strong_count(&value), 101);
+
+        for i in 0..100u64 {
+            let found = into_ok(table.get(&mut (), i, |_: &mut (), (x, _): &(u64, _)| Ok(*x == i)));
+            assert_eq!(found.map(|(x, _)| *x), Some(i));
+        }
+
+        // The same goes for allocations taken directly from a table.
+        let (ptr, layout, alloc) = table.into_allocation().unwrap();
+        let table = unsafe { RawTable::<(u64, Rc<()>)>::from_allocation(ptr, layout, alloc) };
+        assert_eq!(table.allocation_info(), info);
+
+        // Tables which haven't allocated have no allocation to recover.
+        let iter = RawTable::<u64>::new().into_iter();
+        assert!(iter.allocator().is_none());
+        assert!(iter.into_allocation().is_none());
+    }
+
+    #[test]
+    fn clear_and_shrink() {
+        use ::rust_alloc::rc::Rc;
+
+        let value = Rc::new(());
+        let mut table = RawTable::new();
+
+        for i in 0..100u64 {
+            table
+                .insert(
+                    &mut (),
+                    i,
+                    (i, value.clone()),
+                    |_: &mut (), (i, _): &(u64, _)| Ok::<_, Infallible>(*i),
+                )
+                .abort();
+        }
+
+        assert_eq!(Rc::strong_count(&value), 101);
+
+        table.clear_and_shrink();
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert_eq!(table.len(), 0);
+        assert_eq!(table.capacity(), 0);
+        assert_eq!(table.buckets(), 1);
+        assert!(table.table.is_empty_singleton());
+
+        // Shrinking an empty table is a no-op.
+        table.clear_and_shrink();
+        assert!(table.table.is_empty_singleton());
+
+        // The table can be reused afterwards.
+        table
+            .insert(
+                &mut (),
+                1,
+                (1, value.clone()),
+                |_: &mut (), (i, _): &(u64, _)| Ok::<_, Infallible>(*i),
+            )
+            .abort();
+        assert_eq!(table.len(), 1);
+        assert_eq!(Rc::strong_count(&value), 2);
+    }
+
+    fn clone_from_table(len: u64) -> RawTable<u64> {
+        let mut table = RawTable::new();
+
+        for i in 0..len {
+            table
+                .insert(&mut (), i, i, |_: &mut (), i: &u64| Ok::<_, Infallible>(*i))
+                .abort();
+        }
+
+        table
+    }
+
+    fn assert_clone_from_contents(table: &RawTable<u64>, len: u64) {
+        assert_eq!(table.len(), len as usize);
+
+        for i in 0..len * 2 {
+            let found = into_ok(table.get(&mut (), i, |_: &mut (), x: &u64| Ok(*x == i)));
+            assert_eq!(found.copied(), (i < len).then_some(i), "Index: {i}");
+        }
+    }
+
+    #[test]
+    fn clone_from_larger_dest() {
+        let source = clone_from_table(8);
+
+        // Without a hasher the destination has to match the buckets of the
+        // source, since elements can't be moved into different positions.
+        let mut table = clone_from_table(1000);
+        table.try_clone_from(&source).abort();
+        assert_eq!(table.buckets(), source.buckets());
+        assert_clone_from_contents(&table, 8);
+
+        // With a hasher the larger allocation is reused.
+        let mut table = clone_from_table(1000);
+        let buckets = table.buckets();
+        let ctrl = table.table.ctrl.as_ptr();
+
+        table
+            .clone_from_with_hasher(&mut (), &source, |_: &mut (), i: &u64| {
+                Ok::<_, Infallible>(*i)
+            })
+            .abort();
+
+        assert_eq!(table.buckets(), buckets);
+        assert_eq!(table.table.ctrl.as_ptr(), ctrl);
+        assert_clone_from_contents(&table, 8);
+    }
+
+    #[test]
+    fn clone_from_equal_dest() {
+        let source = clone_from_table(20);
+        let mut table = clone_from_table(25);
+        assert_eq!(table.buckets(), source.buckets());
+        let ctrl = table.table.ctrl.as_ptr();
+
+        table.try_clone_from(&source).abort();
+        assert_eq!(table.table.ctrl.as_ptr(), ctrl);
+        assert_clone_from_contents(&table, 20);
+    }
+
+    #[test]
+    fn clone_from_smaller_dest() {
+        let source = clone_from_table(1000);
+
+        let mut table = clone_from_table(8);
+        table.try_clone_from(&source).abort();
+        assert_eq!(table.buckets(), source.buckets());
+        assert_clone_from_contents(&table, 1000);
+
+        let mut table = clone_from_table(8);
+        table
+            .clone_from_with_hasher(&mut (), &source, |_: &mut (), i: &u64| {
+                Ok::<_, Infallible>(*i)
+            })
+            .abort();
+        assert_eq!(table.buckets(), source.buckets());
+        assert_clone_from_contents(&table, 1000);
+    }
 }
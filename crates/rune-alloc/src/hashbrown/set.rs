@@ -8,9 +8,11 @@ use crate::alloc::{Allocator, Global};
 use crate::borrow::TryToOwned;
 use crate::clone::TryClone;
 use crate::error::Error;
+use crate::heap_size::HeapSize;
 use crate::iter::{TryExtend, TryFromIteratorIn};
 #[cfg(test)]
 use crate::testing::*;
+use crate::vec::Vec;
 
 use super::map::{self, DefaultHashBuilder, ExtractIfInner, HashMap, Keys};
 use super::raw::RawTable;
@@ -143,6 +145,16 @@ where
     }
 }
 
+impl<T, S, A: Allocator> HeapSize for HashSet<T, S, A>
+where
+    T: HeapSize,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.map.heap_size()
+    }
+}
+
 #[cfg(test)]
 impl<T, S, A: Allocator + Clone> Clone for HashSet<T, S, A>
 where
@@ -421,6 +433,93 @@ impl<T, S, A: Allocator> HashSet<T, S, A> {
         self.map.retain(|k, _| f(k));
     }
 
+    /// Retains only the elements specified by the fallible predicate.
+    ///
+    /// This is the fallible variant of [`retain`]. If the predicate errors,
+    /// iteration stops and the error is returned. Elements for which the
+    /// predicate already returned `false` stay removed, and every other
+    /// element is retained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let mut set: HashSet<i32> = HashSet::try_from([1, 2, 3, 4, 5, 6])?;
+    /// set.try_retain(|&k| Ok::<_, &str>(k % 2 == 0))?;
+    /// assert_eq!(set.len(), 3);
+    ///
+    /// let result = set.try_retain(|&k| if k == 4 { Err("four") } else { Ok(true) });
+    /// assert_eq!(result, Err("four"));
+    /// assert_eq!(set.len(), 3);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`retain`]: HashSet::retain
+    pub fn try_retain<F, E>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&T) -> Result<bool, E>,
+    {
+        self.map.try_retain(|k, _| f(k))
+    }
+
+    /// Retains only the elements specified by the predicate, moving the
+    /// removed elements into `out`. Returns the number of elements moved.
+    ///
+    /// In other words, move all elements `e` such that `f(&e)` returns `false`
+    /// into `out`. If `out` has enough spare capacity no allocations are
+    /// performed.
+    ///
+    /// # Errors
+    ///
+    /// Before an element is removed from the set, space for it is reserved in
+    /// `out`. If that reservation fails, the element is left in the set and the
+    /// error is returned. Elements which have already been moved stay in `out`
+    /// and the predicate is not called for any further elements. No elements
+    /// are lost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{HashSet, Vec};
+    ///
+    /// let mut set: HashSet<i32> = HashSet::try_from([1, 2, 3, 4, 5, 6])?;
+    /// let mut out = Vec::new();
+    ///
+    /// assert_eq!(set.retain_into(|&k| k % 2 == 0, &mut out)?, 3);
+    /// assert_eq!(set.len(), 3);
+    ///
+    /// out.sort_unstable();
+    /// assert_eq!(out, [1, 3, 5]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn retain_into<F, A2>(&mut self, mut f: F, out: &mut Vec<T, A2>) -> Result<usize, Error>
+    where
+        F: FnMut(&T) -> bool,
+        A2: Allocator,
+    {
+        let table = &mut self.map.table;
+        let mut moved = 0;
+
+        // Here we only use `iter` as a temporary, preventing use-after-free
+        unsafe {
+            for item in table.iter() {
+                let (key, ()) = item.as_ref();
+
+                if f(key) {
+                    continue;
+                }
+
+                out.try_reserve(1)?;
+                let ((key, ()), _) = table.remove(item);
+                out.try_push(key)?;
+                moved += 1;
+            }
+        }
+
+        Ok(moved)
+    }
+
     /// Drains elements which are true under the given predicate, and returns an
     /// iterator over the removed items.
     ///
@@ -710,6 +809,45 @@ where
         self.map.try_reserve(additional)
     }
 
+    /// Constructs a set by cloning the given elements, using the given hasher
+    /// and allocator.
+    ///
+    /// The table is allocated once with exactly enough capacity to hold every
+    /// element, so inserting never has to grow it. An empty slice doesn't
+    /// allocate.
+    ///
+    /// If equal elements occur more than once, the last one wins and any
+    /// earlier one is dropped. If cloning an element fails, the elements
+    /// inserted so far are dropped along with the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    /// use rune::alloc::alloc::Global;
+    /// use rune::alloc::hash_map::DefaultHashBuilder;
+    ///
+    /// let set = HashSet::try_from_elements_in(&[1, 2, 1], DefaultHashBuilder::default(), Global)?;
+    ///
+    /// assert_eq!(set.len(), 2);
+    /// assert!(set.contains(&1));
+    /// assert!(set.contains(&2));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_elements_in(elements: &[T], hasher: S, alloc: A) -> Result<Self, Error>
+    where
+        T: TryClone,
+    {
+        let mut set = Self::try_with_capacity_and_hasher_in(elements.len(), hasher, alloc)?;
+
+        for value in elements {
+            // NB: The table has room for every element, so this never grows it.
+            set.try_replace(value.try_clone()?)?;
+        }
+
+        Ok(set)
+    }
+
     #[cfg(test)]
     pub(crate) fn reserve(&mut self, additional: usize) {
         self.try_reserve(additional).abort()
@@ -746,8 +884,8 @@ where
     /// down no lower than the supplied limit while maintaining the internal rules
     /// and possibly leaving some space in accordance with the resize policy.
     ///
-    /// Panics if the current capacity is smaller than the supplied
-    /// minimum capacity.
+    /// This function does nothing if the current capacity is smaller than the
+    /// supplied minimum capacity.
     ///
     /// # Examples
     ///
@@ -762,6 +900,8 @@ where
     /// assert!(set.capacity() >= 10);
     /// set.try_shrink_to(0)?;
     /// assert!(set.capacity() >= 2);
+    /// set.try_shrink_to(10)?;
+    /// assert!(set.capacity() >= 2);
     /// # Ok::<_, rune::alloc::Error>(())
     /// ```
     #[cfg_attr(feature = "inline-more", inline)]
@@ -903,6 +1043,101 @@ where
         }
     }
 
+    /// Removes all values from `self` which are also present in `other`.
+    ///
+    /// This is the in-place version of [`difference`]. Values are removed
+    /// without reallocating the set.
+    ///
+    /// [`difference`]: HashSet::difference
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let mut a: HashSet<_> = HashSet::try_from([1, 2, 3])?;
+    /// let b: HashSet<_> = HashSet::try_from([4, 2, 3, 4])?;
+    ///
+    /// a.try_difference_update(&b)?;
+    /// assert_eq!(a, HashSet::try_from([1])?);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_difference_update(&mut self, other: &Self) -> Result<(), Error> {
+        // Iterate over the smaller set in order to reduce hash lookups.
+        if other.len() < self.len() {
+            for value in other {
+                self.remove(value);
+            }
+        } else {
+            self.retain(|value| !other.contains(value));
+        }
+
+        Ok(())
+    }
+
+    /// Retains only the values in `self` which are also present in `other`.
+    ///
+    /// This is the in-place version of [`intersection`]. Values are removed
+    /// without reallocating the set.
+    ///
+    /// [`intersection`]: HashSet::intersection
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let mut a: HashSet<_> = HashSet::try_from([1, 2, 3])?;
+    /// let b: HashSet<_> = HashSet::try_from([4, 2, 3, 4])?;
+    ///
+    /// a.try_intersection_update(&b)?;
+    /// assert_eq!(a, HashSet::try_from([2, 3])?);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_intersection_update(&mut self, other: &Self) -> Result<(), Error> {
+        self.retain(|value| other.contains(value));
+        Ok(())
+    }
+
+    /// Inserts clones of all values in `other` which are not already present
+    /// in `self`.
+    ///
+    /// This is the in-place version of [`union`]. The set reserves space for
+    /// exactly the values that are missing, so it doesn't reallocate if they
+    /// already fit.
+    ///
+    /// [`union`]: HashSet::union
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let mut a: HashSet<_> = HashSet::try_from([1, 2, 3])?;
+    /// let b: HashSet<_> = HashSet::try_from([4, 2, 3, 4])?;
+    ///
+    /// a.try_union_update(&b)?;
+    /// assert_eq!(a, HashSet::try_from([1, 2, 3, 4])?);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_union_update(&mut self, other: &Self) -> Result<(), Error>
+    where
+        T: TryClone,
+    {
+        self.try_reserve(other.difference(self).count())?;
+
+        for value in other {
+            // Values in `other` are unique, so we only need to check that
+            // they're not already present in `self`.
+            if !self.contains(value) {
+                self.map
+                    .try_insert_unique_unchecked(value.try_clone()?, ())?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns `true` if the set contains a value.
     ///
     /// The value may be any borrowed form of the set's value type, but
@@ -1358,6 +1593,29 @@ impl<T, S, A: Allocator> HashSet<T, S, A> {
     }
 }
 
+/// Two sets are equal if they contain the same values. The capacity of the
+/// sets and the state of their hashers don't matter.
+///
+/// # Examples
+///
+/// ```
+/// use rune::alloc::HashSet;
+/// use rune::alloc::hash_map::RandomState;
+///
+/// let mut a = HashSet::try_with_capacity_and_hasher(1, RandomState::with_seeds(1, 2, 3, 4))?;
+/// a.try_insert("a")?;
+/// a.try_insert("b")?;
+///
+/// let mut b = HashSet::try_with_capacity_and_hasher(64, RandomState::with_seeds(5, 6, 7, 8))?;
+/// b.try_insert("b")?;
+/// b.try_insert("a")?;
+///
+/// assert_eq!(a, b);
+///
+/// b.try_insert("c")?;
+/// assert_ne!(a, b);
+/// # Ok::<_, rune::alloc::Error>(())
+/// ```
 impl<T, S, A> PartialEq for HashSet<T, S, A>
 where
     T: Eq + Hash,
@@ -1773,6 +2031,15 @@ where
 
 impl<K, F, A: Allocator> FusedIterator for ExtractIf<'_, K, F, A> where F: FnMut(&K) -> bool {}
 
+impl<K, F, A: Allocator> fmt::Debug for ExtractIf<'_, K, F, A>
+where
+    F: FnMut(&K) -> bool,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtractIf").finish_non_exhaustive()
+    }
+}
+
 impl<T, S, A: Allocator> Clone for Intersection<'_, T, S, A> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn clone(&self) -> Self {
@@ -2434,9 +2701,78 @@ fn assert_covariance() {
 mod test_set {
     use super::super::map::DefaultHashBuilder;
     use super::HashSet;
+    use crate::alloc::Global;
     use rust_alloc::vec::Vec;
     use rust_alloc::{format, vec};
 
+    #[test]
+    fn test_try_from_elements_in() {
+        let elements = (0..100).map(|i| i % 90).collect::<Vec<_>>();
+        let set = HashSet::try_from_elements_in(&elements, DefaultHashBuilder::default(), Global)
+            .unwrap();
+
+        assert_eq!(set.len(), 90);
+        assert!((0..90).all(|i| set.contains(&i)));
+
+        let empty: HashSet<i32> =
+            HashSet::try_from_elements_in(&[], DefaultHashBuilder::default(), Global).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.capacity(), 0);
+    }
+
+    #[test]
+    fn test_try_difference_update() {
+        let mut a: HashSet<i32> = (0..10).collect();
+        let b: HashSet<i32> = (5..20).collect();
+        let c: HashSet<i32> = (8..9).collect();
+        let capacity = a.capacity();
+
+        a.try_difference_update(&b).unwrap();
+        assert_eq!(a, (0..5).collect());
+        assert_eq!(a.capacity(), capacity);
+
+        // Applying the same difference again has no effect.
+        a.try_difference_update(&b).unwrap();
+        assert_eq!(a.len(), 5);
+
+        // Iterates over the smaller set.
+        let mut b = b;
+        b.try_difference_update(&c).unwrap();
+        assert_eq!(b.len(), 14);
+        assert!(!b.contains(&8));
+    }
+
+    #[test]
+    fn test_try_intersection_update() {
+        let mut a: HashSet<i32> = (0..10).collect();
+        let b: HashSet<i32> = (5..20).collect();
+        let capacity = a.capacity();
+
+        a.try_intersection_update(&b).unwrap();
+        assert_eq!(a, (5..10).collect());
+        assert_eq!(a.capacity(), capacity);
+
+        a.try_intersection_update(&b).unwrap();
+        assert_eq!(a.len(), 5);
+
+        a.try_intersection_update(&HashSet::new()).unwrap();
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn test_try_union_update() {
+        let mut a: HashSet<i32> = (0..10).collect();
+        let b: HashSet<i32> = (5..20).collect();
+
+        a.try_union_update(&b).unwrap();
+        assert_eq!(a, (0..20).collect());
+
+        let capacity = a.capacity();
+        a.try_union_update(&b).unwrap();
+        assert_eq!(a.len(), 20);
+        assert_eq!(a.capacity(), capacity);
+    }
+
     #[test]
     fn test_zero_capacities() {
         type HS = HashSet<i32>;
@@ -2469,6 +2805,29 @@ mod test_set {
         assert_eq!(s.capacity(), 0);
     }
 
+    #[test]
+    fn test_try_shrink_to() {
+        let mut set = HashSet::try_with_capacity(100).unwrap();
+        set.try_insert(1).unwrap();
+        set.try_insert(2).unwrap();
+
+        set.try_shrink_to(10).unwrap();
+        let capacity = set.capacity();
+        assert!((10..100).contains(&capacity));
+
+        // A minimum above the current capacity leaves the set untouched.
+        set.try_shrink_to(1000).unwrap();
+        assert_eq!(set.capacity(), capacity);
+
+        set.try_shrink_to(0).unwrap();
+        assert!(set.capacity() >= 2);
+        assert!(set.contains(&1) && set.contains(&2));
+
+        set.clear();
+        set.try_shrink_to_fit().unwrap();
+        assert_eq!(set.capacity(), 0);
+    }
+
     #[test]
     fn test_disjoint() {
         let mut xs = HashSet::new();
@@ -2709,6 +3068,42 @@ mod test_set {
         assert_eq!(s1, s2);
     }
 
+    #[test]
+    fn test_eq_capacity_and_hasher() {
+        use super::super::map::RandomState;
+
+        let mut s1 =
+            HashSet::try_with_capacity_and_hasher(4, RandomState::with_seeds(1, 2, 3, 4)).unwrap();
+        let mut s2 =
+            HashSet::try_with_capacity_and_hasher(1024, RandomState::with_seeds(5, 6, 7, 8))
+                .unwrap();
+
+        for i in 0..100 {
+            s1.insert(i);
+        }
+
+        for i in (0..100).rev() {
+            s2.insert(i);
+        }
+
+        assert_ne!(s1.capacity(), s2.capacity());
+        assert_eq!(s1, s2);
+        assert_eq!(s2, s1);
+
+        // The same length but a single differing value.
+        s2.remove(&50);
+        s2.insert(100);
+        assert_ne!(s1, s2);
+        assert_ne!(s2, s1);
+    }
+
+    #[test]
+    fn test_extract_if_debug() {
+        let mut set: HashSet<i32> = (0..8).collect();
+        let extract = set.extract_if(|&v| v % 2 == 0);
+        assert_eq!(format!("{extract:?}"), "ExtractIf { .. }");
+    }
+
     #[test]
     fn test_show() {
         let mut set = HashSet::new();
@@ -2791,6 +3186,39 @@ mod test_set {
         assert_eq!(it.next(), None);
     }
 
+    #[test]
+    fn test_get_and_take_borrowed() {
+        use rust_alloc::string::String;
+
+        let mut s = HashSet::new();
+        s.try_insert(String::from("a")).unwrap();
+        s.try_insert(String::from("b")).unwrap();
+
+        // Lookups through a borrowed form return the stored instance rather
+        // than the probe.
+        let probe = String::from("a");
+        let stored = s.get("a").unwrap();
+        assert_eq!(stored, &probe);
+        assert!(!core::ptr::eq(stored.as_ptr(), probe.as_ptr()));
+        assert!(core::ptr::eq(stored, s.iter().find(|v| *v == "a").unwrap()));
+
+        let ptr = stored.as_ptr();
+        let taken = s.take("a").unwrap();
+        assert!(core::ptr::eq(taken.as_ptr(), ptr));
+        assert_eq!(s.len(), 1);
+        assert_eq!(s.take("a"), None);
+        assert_eq!(s.get("a"), None);
+
+        // Replacing swaps in the new instance and hands back the old one.
+        let ptr = s.get("b").unwrap().as_ptr();
+        let replacement = String::from("b");
+        let new_ptr = replacement.as_ptr();
+        let old = s.try_replace(replacement).unwrap().unwrap();
+        assert!(core::ptr::eq(old.as_ptr(), ptr));
+        assert!(core::ptr::eq(s.get("b").unwrap().as_ptr(), new_ptr));
+        assert_eq!(s.len(), 1);
+    }
+
     #[test]
     #[allow(clippy::needless_borrow)]
     fn test_extend_ref() {
@@ -2831,6 +3259,50 @@ mod test_set {
         assert!(set.contains(&6));
     }
 
+    #[test]
+    fn test_try_retain() {
+        let mut set: HashSet<i32> = (0..16).collect();
+
+        set.try_retain(|&k| Ok::<_, ()>(k % 2 == 0)).unwrap();
+        assert_eq!(set.len(), 8);
+        assert!(set.iter().all(|&k| k % 2 == 0));
+
+        let mut visited = 0;
+
+        let result = set.try_retain(|_| {
+            visited += 1;
+
+            if visited == 4 {
+                return Err("stop");
+            }
+
+            Ok(false)
+        });
+
+        assert_eq!(result, Err("stop"));
+        assert_eq!(set.len(), 5);
+    }
+
+    #[test]
+    fn test_retain_into() {
+        let mut set: HashSet<i32> = (0..16).collect();
+        let mut out = crate::vec::Vec::try_with_capacity(4).unwrap();
+
+        let result = crate::limit::with(0, || set.retain_into(|&k| k % 2 != 0, &mut out)).call();
+        assert!(result.is_err());
+        assert_eq!(out.len(), 4);
+        assert_eq!(set.len(), 12);
+
+        let moved = set.retain_into(|&k| k % 2 != 0, &mut out).unwrap();
+        assert_eq!(moved, 4);
+        assert_eq!(set.len(), 8);
+        assert!(set.iter().all(|&k| k % 2 != 0));
+
+        let mut out = out.into_iter().collect::<Vec<_>>();
+        out.sort_unstable();
+        assert_eq!(out, [0, 2, 4, 6, 8, 10, 12, 14]);
+    }
+
     #[test]
     fn test_extract_if() {
         {
@@ -0,0 +1,193 @@
+//! The `HeapSize` trait for reporting how much heap memory a value uses.
+//!
+//! This is intended for capacity planning, where the memory used by nested
+//! containers needs to be estimated without instrumenting the allocator.
+//!
+//! ```
+//! use rune::alloc::{HashMap, String, Vec};
+//! use rune::alloc::heap_size::HeapSize;
+//! use rune::alloc::prelude::*;
+//!
+//! let mut map = HashMap::<String, Vec<u32>>::new();
+//! map.try_insert("a".try_to_owned()?, Vec::try_with_capacity(4)?)?;
+//!
+//! let (_, layout) = map.raw_table().allocation_info();
+//! assert_eq!(map.heap_size(), layout.size() + 1 + 4 * 4);
+//! # Ok::<_, rune::alloc::Error>(())
+//! ```
+//!
+//! The trait can be implemented with `#[derive(HeapSize)]`, which sums up the
+//! heap size of every field:
+//!
+//! ```
+//! use rune::alloc::{String, Vec};
+//! use rune::alloc::heap_size::HeapSize;
+//! use rune::alloc::prelude::*;
+//!
+//! #[derive(HeapSize)]
+//! struct Record {
+//!     name: String,
+//!     values: Vec<u64>,
+//!     id: u32,
+//! }
+//!
+//! let record = Record {
+//!     name: String::try_with_capacity(10)?,
+//!     values: Vec::try_with_capacity(2)?,
+//!     id: 0,
+//! };
+//!
+//! assert_eq!(record.heap_size(), 10 + 2 * 8);
+//! # Ok::<_, rune::alloc::Error>(())
+//! ```
+//!
+//! Individual fields can be excluded with `#[heap_size(skip)]`. Unless
+//! `#[heap_size(bound = {..})]` is specified, every type parameter is required
+//! to implement [`HeapSize`].
+//!
+//! Values behind shared ownership, like a reference counted pointer, are
+//! counted once for every handle which reaches them. So the reported size is
+//! an upper bound if such values are shared.
+
+#[doc(inline)]
+pub use rune_alloc_macros::HeapSize;
+
+/// Report the number of bytes a value has allocated on the heap.
+///
+/// This only includes allocations which are owned by the value, and not the
+/// size of the value itself. So a [`Vec`] reports the size of its buffer plus
+/// the heap size of its elements, but not the size of the [`Vec`] struct.
+///
+/// Sizes are computed from the layouts used to allocate, so they don't
+/// include any rounding performed by the allocator.
+///
+/// [`Vec`]: crate::Vec
+pub trait HeapSize {
+    /// Get the number of bytes allocated on the heap by this value.
+    fn heap_size(&self) -> usize;
+}
+
+impl<T: ?Sized> HeapSize for &T {
+    /// References don't own the value they point to.
+    #[inline]
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T: ?Sized> HeapSize for &mut T {
+    /// References don't own the value they point to.
+    #[inline]
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T> HeapSize for [T]
+where
+    T: HeapSize,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.iter().map(T::heap_size).sum()
+    }
+}
+
+impl<T, const N: usize> HeapSize for [T; N]
+where
+    T: HeapSize,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.as_slice().heap_size()
+    }
+}
+
+impl HeapSize for str {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T> HeapSize for ::core::option::Option<T>
+where
+    T: HeapSize,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        match self {
+            Some(value) => value.heap_size(),
+            None => 0,
+        }
+    }
+}
+
+impl<T, E> HeapSize for ::core::result::Result<T, E>
+where
+    T: HeapSize,
+    E: HeapSize,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        match self {
+            Ok(value) => value.heap_size(),
+            Err(error) => error.heap_size(),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($count:expr $(, $ty:ident $var:ident $num:expr)*) => {
+        impl<$($ty,)*> HeapSize for ($($ty,)*) where $($ty: HeapSize,)* {
+            #[inline]
+            fn heap_size(&self) -> usize {
+                let ($($var,)*) = self;
+                0 $(+ $var.heap_size())*
+            }
+        }
+    }
+}
+
+repeat_macro!(impl_tuple);
+
+macro_rules! impl_zero {
+    ($ty:ty) => {
+        impl HeapSize for $ty {
+            #[inline]
+            fn heap_size(&self) -> usize {
+                0
+            }
+        }
+    };
+}
+
+impl_zero!(char);
+impl_zero!(bool);
+impl_zero!(usize);
+impl_zero!(isize);
+impl_zero!(u8);
+impl_zero!(u16);
+impl_zero!(u32);
+impl_zero!(u64);
+impl_zero!(u128);
+impl_zero!(i8);
+impl_zero!(i16);
+impl_zero!(i32);
+impl_zero!(i64);
+impl_zero!(i128);
+impl_zero!(f32);
+impl_zero!(f64);
+
+impl_zero!(::core::num::NonZeroUsize);
+impl_zero!(::core::num::NonZeroIsize);
+impl_zero!(::core::num::NonZeroU8);
+impl_zero!(::core::num::NonZeroU16);
+impl_zero!(::core::num::NonZeroU32);
+impl_zero!(::core::num::NonZeroU64);
+impl_zero!(::core::num::NonZeroU128);
+impl_zero!(::core::num::NonZeroI8);
+impl_zero!(::core::num::NonZeroI16);
+impl_zero!(::core::num::NonZeroI32);
+impl_zero!(::core::num::NonZeroI64);
+impl_zero!(::core::num::NonZeroI128);
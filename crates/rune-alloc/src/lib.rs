@@ -116,16 +116,22 @@ pub mod vec_deque;
 pub use self::string::String;
 pub mod string;
 
+pub mod sync;
+
 pub mod alloc;
 
 pub mod clone;
 
+pub mod heap_size;
+
 pub mod borrow;
 
 pub mod iter;
 
 pub mod fmt;
 
+pub mod ops;
+
 mod option;
 
 pub(crate) mod hint;
@@ -141,6 +147,7 @@ pub mod prelude {
     pub use crate::borrow::TryToOwned;
     pub use crate::clone::{TryClone, TryCopy};
     pub use crate::iter::{IteratorExt, TryExtend, TryFromIterator, TryFromIteratorIn};
+    pub use crate::ops::TryAdd;
     pub use crate::option::OptionExt;
     pub use crate::string::TryToString;
 }
@@ -81,8 +81,7 @@ pub struct Memory<T> {
 /// ```
 ///
 /// Breaching the limit. Note that this happens because while the vector is
-/// growing it might both over-allocate, and hold onto two allocations
-/// simultaneously.
+/// growing it might over-allocate.
 ///
 /// ```
 /// use rune::alloc::limit;
@@ -91,7 +90,7 @@ pub struct Memory<T> {
 /// let f = limit::with(1024, || {
 ///     let mut vec = Vec::<u32>::new();
 ///
-///     for n in 0..256u32 {
+///     for n in 0..257u32 {
 ///         vec.try_push(n)?;
 ///     }
 ///
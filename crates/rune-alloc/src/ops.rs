@@ -0,0 +1,28 @@
+//! Fallible versions of the overloadable operators in [`core::ops`].
+
+use crate::error::Error;
+
+/// The fallible addition operator.
+///
+/// This is the equivalent of [`core::ops::Add`] for types where producing the
+/// result might require allocating, such as concatenating strings.
+///
+/// # Examples
+///
+/// ```
+/// use rune::alloc::String;
+/// use rune::alloc::prelude::*;
+///
+/// let hello = String::try_from("hello")?;
+/// let hello_world = hello.try_add(" world")?;
+/// assert_eq!(hello_world, "hello world");
+/// # Ok::<_, rune::alloc::Error>(())
+/// ```
+pub trait TryAdd<Rhs = Self> {
+    /// The resulting type after applying the addition.
+    type Output;
+
+    /// Try to perform the addition, raising an allocation error if it's
+    /// unsuccessful.
+    fn try_add(self, rhs: Rhs) -> Result<Self::Output, Error>;
+}
@@ -6,7 +6,7 @@ use core::ops::Deref;
 
 use rust_alloc::boxed::Box;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct Path {
     inner: [u8],
@@ -202,6 +202,15 @@ impl<T, A: Allocator> RawVec<T, A> {
         }
     }
 
+    /// Gets the size in bytes of the allocation, which is zero if nothing is
+    /// allocated.
+    pub(crate) fn allocation_size(&self) -> usize {
+        match self.current_memory() {
+            Some((_, layout)) => layout.size(),
+            None => 0,
+        }
+    }
+
     /// Returns a shared reference to the allocator backing this `RawVec`.
     pub(crate) fn allocator(&self) -> &A {
         &self.alloc
@@ -271,10 +280,10 @@ impl<T, A: Allocator> RawVec<T, A> {
     /// Shrinks the buffer down to the specified capacity. If the given amount
     /// is 0, actually completely deallocates.
     ///
-    /// # Aborts
-    ///
-    /// Aborts on OOM.
-    pub(crate) fn try_shrink_to_fit(&mut self, cap: usize) -> Result<(), Error> {
+    /// Shrinking goes through [`Allocator::shrink`], which allows allocators
+    /// to shrink in place. If the allocator fails to shrink, a new buffer is
+    /// allocated and the contents are copied into it instead.
+    pub(crate) fn try_shrink(&mut self, cap: usize) -> Result<(), Error> {
         self.shrink(cap)
     }
 }
@@ -373,9 +382,27 @@ impl<T, A: Allocator> RawVec<T, A> {
                 // overflowed earlier when capacity was larger.
                 let new_size = mem::size_of::<T>().wrapping_mul(cap);
                 let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
-                self.alloc
-                    .shrink(ptr, layout, new_layout)
-                    .map_err(|_| AllocError { layout: new_layout })?
+
+                match self.alloc.shrink(ptr, layout, new_layout) {
+                    Ok(ptr) => ptr,
+                    Err(..) => {
+                        // The allocator refused to shrink, so move the
+                        // contents into a new, smaller allocation instead.
+                        let new_ptr = self
+                            .alloc
+                            .allocate(new_layout)
+                            .map_err(|_| AllocError { layout: new_layout })?;
+
+                        ptr::copy_nonoverlapping(
+                            ptr.as_ptr(),
+                            new_ptr.as_ptr().cast::<u8>(),
+                            new_size,
+                        );
+
+                        self.alloc.deallocate(ptr, layout);
+                        new_ptr
+                    }
+                }
             };
             self.set_ptr_and_cap(ptr, cap);
         }
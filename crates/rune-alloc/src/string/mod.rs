@@ -65,7 +65,9 @@ use crate::boxed::Box;
 use crate::clone::TryClone;
 use crate::error::Error;
 use crate::fmt::TryWrite;
+use crate::heap_size::HeapSize;
 use crate::iter::{TryExtend, TryFromIteratorIn, TryJoin};
+use crate::ops::TryAdd;
 use crate::slice::range as slice_range;
 #[cfg(test)]
 use crate::testing::*;
@@ -461,6 +463,48 @@ impl String {
         })
     }
 
+    /// Construct a new [`String`] from an iterator of characters.
+    ///
+    /// If the iterator reports an exact size, such as through
+    /// [`ExactSizeIterator`], enough space to fit four bytes per character is
+    /// reserved up front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::String;
+    ///
+    /// let s = String::try_from_chars("héllo 🦀".chars())?;
+    /// assert_eq!(s, "héllo 🦀");
+    ///
+    /// let s = String::try_from_chars(['a', 'b', 'c'].into_iter())?;
+    /// assert_eq!(s, "abc");
+    /// assert!(s.capacity() >= 12);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_chars<I>(iter: I) -> Result<Self, Error>
+    where
+        I: Iterator<Item = char>,
+    {
+        let mut vec = Vec::new();
+
+        if let (lower, Some(upper)) = iter.size_hint() {
+            if lower == upper {
+                vec.try_reserve(lower.saturating_mul(4))?;
+            }
+        }
+
+        for c in iter {
+            match c.len_utf8() {
+                1 => vec.try_push(c as u8)?,
+                _ => vec.try_extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes())?,
+            }
+        }
+
+        // SAFETY: The vector only contains the UTF-8 encodings of characters.
+        Ok(unsafe { String::from_utf8_unchecked(vec) })
+    }
+
     /// Convert a [`String`] into a std `String`.
     ///
     /// The result is allocated on the heap, using the default global allocator
@@ -699,6 +743,123 @@ impl<A: Allocator> String<A> {
         }
     }
 
+    /// Converts a slice of bytes to a string in the provided allocator,
+    /// including invalid characters.
+    ///
+    /// Strings are made of bytes ([`u8`]), and a slice of bytes ([`&[u8]`][byteslice])
+    /// is made of bytes, so this function converts between the two. Not all
+    /// byte slices are valid strings, however: strings are required to be
+    /// valid UTF-8. During this conversion, `try_from_utf8_lossy_in()` will
+    /// replace any invalid UTF-8 sequences with
+    /// [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD], which looks like this: �
+    ///
+    /// [byteslice]: prim@slice
+    /// [U+FFFD]: core::char::REPLACEMENT_CHARACTER
+    ///
+    /// If the input is valid UTF-8 it is copied into a single allocation of
+    /// the exact size needed.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the underlying allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::String;
+    /// use rune::alloc::alloc::Global;
+    ///
+    /// let sparkle_heart = [240, 159, 146, 150];
+    /// let sparkle_heart = String::try_from_utf8_lossy_in(&sparkle_heart, Global)?;
+    /// assert_eq!("💖", sparkle_heart);
+    ///
+    /// let input = b"Hello \xF0\x90\x80World";
+    /// let output = String::try_from_utf8_lossy_in(input, Global)?;
+    /// assert_eq!("Hello �World", output);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_utf8_lossy_in(bytes: &[u8], alloc: A) -> Result<String<A>, Error> {
+        let mut error = match from_utf8(bytes) {
+            Ok(s) => {
+                let mut string = String::try_with_capacity_in(s.len(), alloc)?;
+                string.try_push_str(s)?;
+                return Ok(string);
+            }
+            Err(error) => error,
+        };
+
+        let mut string = String::try_with_capacity_in(bytes.len(), alloc)?;
+        let mut rest = bytes;
+
+        loop {
+            let (valid, after) = rest.split_at(error.valid_up_to());
+            // SAFETY: Everything up until `valid_up_to` has been validated.
+            string.try_push_str(unsafe { from_utf8_unchecked(valid) })?;
+            string.try_push(char::REPLACEMENT_CHARACTER)?;
+
+            // An unexpected end of input is replaced by a single replacement
+            // character.
+            let Some(len) = error.error_len() else {
+                break;
+            };
+
+            rest = &after[len..];
+
+            match from_utf8(rest) {
+                Ok(s) => {
+                    string.try_push_str(s)?;
+                    break;
+                }
+                Err(e) => {
+                    error = e;
+                }
+            }
+        }
+
+        Ok(string)
+    }
+
+    /// Converts a vector of bytes to a `String`, including invalid
+    /// characters.
+    ///
+    /// This behaves like [`try_from_utf8_lossy_in`], except that if the input
+    /// is valid UTF-8 the allocation of the vector is reused and no copy is
+    /// performed. Otherwise a new string is allocated using the allocator of
+    /// the vector.
+    ///
+    /// [`try_from_utf8_lossy_in`]: String::try_from_utf8_lossy_in
+    ///
+    /// # Errors
+    ///
+    /// Errors if the underlying allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{try_vec, String};
+    ///
+    /// let bytes = try_vec![b'a', b'b', b'c'];
+    /// let ptr = bytes.as_ptr();
+    /// let string = String::try_from_utf8_lossy_owned(bytes)?;
+    /// assert_eq!("abc", string);
+    /// assert_eq!(ptr, string.as_ptr());
+    ///
+    /// let bytes = try_vec![b'a', 0xff, b'c'];
+    /// let string = String::try_from_utf8_lossy_owned(bytes)?;
+    /// assert_eq!("a�c", string);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_utf8_lossy_owned(vec: Vec<u8, A>) -> Result<String<A>, Error>
+    where
+        A: Clone,
+    {
+        if from_utf8(&vec).is_ok() {
+            return Ok(String { vec });
+        }
+
+        String::try_from_utf8_lossy_in(&vec, vec.allocator().clone())
+    }
+
     /// Creates a new `String` from a length, capacity, and pointer.
     ///
     /// # Safety
@@ -1783,6 +1944,13 @@ impl<A: Allocator + Clone> TryClone for String<A> {
     }
 }
 
+impl<A: Allocator> HeapSize for String<A> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.vec.heap_size()
+    }
+}
+
 #[cfg(test)]
 impl<A: Allocator + Clone> Clone for String<A> {
     fn clone(&self) -> Self {
@@ -2450,3 +2618,68 @@ impl<'a, A: Allocator> TryExtend<&'a str> for String<A> {
         Ok(())
     }
 }
+
+impl<A: Allocator> TryAdd<&str> for String<A> {
+    type Output = String<A>;
+
+    /// Concatenate a string slice onto the end of a string, reusing its
+    /// allocation.
+    ///
+    /// ```
+    /// use rune::alloc::String;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let a = String::try_from("hello")?;
+    /// let b = a.try_add(" world")?;
+    /// assert_eq!(b, "hello world");
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    fn try_add(mut self, rhs: &str) -> Result<Self::Output, Error> {
+        self.try_push_str(rhs)?;
+        Ok(self)
+    }
+}
+
+impl<A: Allocator> TryAdd<String<A>> for String<A> {
+    type Output = String<A>;
+
+    /// Concatenate two strings, reusing the allocation of the left-hand side.
+    ///
+    /// ```
+    /// use rune::alloc::String;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let a = String::try_from("hello")?;
+    /// let b = String::try_from(" world")?;
+    /// let c = a.try_add(b)?;
+    /// assert_eq!(c, "hello world");
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    fn try_add(mut self, rhs: String<A>) -> Result<Self::Output, Error> {
+        self.try_push_str(&rhs)?;
+        Ok(self)
+    }
+}
+
+impl TryAdd<&str> for &str {
+    type Output = String;
+
+    /// Concatenate two string slices into a newly allocated string.
+    ///
+    /// ```
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let s = "hello".try_add(" world")?;
+    /// assert_eq!(s, "hello world");
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    fn try_add(self, rhs: &str) -> Result<Self::Output, Error> {
+        let mut string = String::try_with_capacity(self.len().saturating_add(rhs.len()))?;
+        string.try_push_str(self)?;
+        string.try_push_str(rhs)?;
+        Ok(string)
+    }
+}
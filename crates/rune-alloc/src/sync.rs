@@ -0,0 +1,427 @@
+//! Thread-safe reference-counting pointers.
+//!
+//! See the [`Arc<T>`][Arc] documentation for more details.
+
+use core::alloc::Layout;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::panic::{RefUnwindSafe, UnwindSafe};
+use core::ptr;
+use core::sync::atomic::{self, AtomicUsize};
+
+use crate::alloc::{AllocError, Allocator, Global};
+use crate::clone::TryClone;
+use crate::error::Error;
+use crate::ptr::NonNull;
+
+/// A soft limit on the amount of references that may be made to an `Arc`.
+///
+/// Going above this limit will abort your program (although not necessarily)
+/// at _exactly_ `MAX_REFCOUNT + 1` references.
+const MAX_REFCOUNT: usize = (isize::MAX) as usize;
+
+/// The allocation shared by every clone of an [`Arc`].
+///
+/// The weak count is not used yet, but is kept so that weak references can be
+/// added without changing the layout. All strong references collectively hold
+/// one weak reference.
+#[repr(C)]
+struct ArcInner<T> {
+    strong: AtomicUsize,
+    weak: AtomicUsize,
+    data: T,
+}
+
+/// A thread-safe reference-counting pointer. 'Arc' stands for 'Atomically
+/// Reference Counted'.
+///
+/// The type `Arc<T>` provides shared ownership of a value of type `T`,
+/// allocated in the heap. Invoking [`clone`][Clone::clone] on `Arc` produces a
+/// new `Arc` instance, which points to the same allocation on the heap as the
+/// source `Arc`, while increasing a reference count. When the last `Arc`
+/// pointer to a given allocation is destroyed, the value stored in that
+/// allocation is dropped and the memory is released through the allocator it
+/// was constructed with.
+///
+/// Unlike `std::sync::Arc`, constructing an `Arc` returns an error instead of
+/// aborting if the allocation fails. Cloning never allocates, so it is
+/// infallible.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// use rune::alloc::sync::Arc;
+///
+/// let five = Arc::try_new(5)?;
+///
+/// let threads = (0..10)
+///     .map(|_| {
+///         let five = Arc::clone(&five);
+///         thread::spawn(move || assert_eq!(*five, 5))
+///     })
+///     .collect::<Vec<_>>();
+///
+/// for thread in threads {
+///     thread.join().unwrap();
+/// }
+///
+/// assert_eq!(Arc::strong_count(&five), 1);
+/// # Ok::<_, rune::alloc::Error>(())
+/// ```
+pub struct Arc<T, A: Allocator = Global> {
+    ptr: NonNull<ArcInner<T>>,
+    alloc: A,
+    _marker: PhantomData<ArcInner<T>>,
+}
+
+unsafe impl<T: Sync + Send, A: Allocator + Send> Send for Arc<T, A> {}
+unsafe impl<T: Sync + Send, A: Allocator + Sync> Sync for Arc<T, A> {}
+
+impl<T: RefUnwindSafe, A: Allocator + UnwindSafe> UnwindSafe for Arc<T, A> {}
+
+impl<T> Arc<T> {
+    /// Constructs a new `Arc<T>`, returning an error if the allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::sync::Arc;
+    ///
+    /// let five = Arc::try_new(5)?;
+    /// assert_eq!(*five, 5);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn try_new(data: T) -> Result<Self, AllocError> {
+        Self::try_new_in(data, Global)
+    }
+}
+
+impl<T, A: Allocator> Arc<T, A> {
+    /// Constructs a new `Arc<T>` in the provided allocator, returning an error
+    /// if the allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::alloc::Global;
+    /// use rune::alloc::sync::Arc;
+    ///
+    /// let five = Arc::try_new_in(5, Global)?;
+    /// assert_eq!(*five, 5);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn try_new_in(data: T, alloc: A) -> Result<Self, AllocError> {
+        let layout = Layout::new::<ArcInner<T>>();
+        let ptr = alloc.allocate(layout)?.cast::<ArcInner<T>>();
+
+        // SAFETY: The allocation is valid for writes of `ArcInner<T>`.
+        unsafe {
+            ptr.as_ptr().write(ArcInner {
+                strong: AtomicUsize::new(1),
+                weak: AtomicUsize::new(1),
+                data,
+            });
+        }
+
+        Ok(Self {
+            ptr,
+            alloc,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the inner value, if the `Arc` has exactly one strong reference.
+    ///
+    /// Otherwise, an [`Err`] is returned with the same `Arc` that was passed
+    /// in.
+    ///
+    /// This will succeed even if there are outstanding weak references.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::sync::Arc;
+    ///
+    /// let x = Arc::try_new(3)?;
+    /// assert_eq!(Arc::try_unwrap(x), Ok(3));
+    ///
+    /// let x = Arc::try_new(4)?;
+    /// let _y = Arc::clone(&x);
+    /// assert_eq!(*Arc::try_unwrap(x).unwrap_err(), 4);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        if this
+            .inner()
+            .strong
+            .compare_exchange(1, 0, atomic::Ordering::Relaxed, atomic::Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(this);
+        }
+
+        atomic::fence(atomic::Ordering::Acquire);
+
+        let this = ManuallyDrop::new(this);
+
+        // SAFETY: We were the last strong reference, so nothing else can
+        // access the data. `this` is not dropped, so the data is only read
+        // once and the allocator is only moved out once.
+        unsafe {
+            let data = ptr::read(&this.inner().data);
+            let alloc = ptr::read(&this.alloc);
+            Self::release_weak(this.ptr, &alloc);
+            Ok(data)
+        }
+    }
+
+    /// Returns a mutable reference into the given `Arc`, if there are no other
+    /// `Arc` pointers to the same allocation.
+    ///
+    /// Returns [`None`] otherwise, because it is not safe to mutate a shared
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::sync::Arc;
+    ///
+    /// let mut x = Arc::try_new(3)?;
+    /// *Arc::get_mut(&mut x).unwrap() = 4;
+    /// assert_eq!(*x, 4);
+    ///
+    /// let _y = Arc::clone(&x);
+    /// assert!(Arc::get_mut(&mut x).is_none());
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        // Acquire synchronizes with the release decrement of other strong
+        // references being dropped, so that their accesses happen before ours.
+        if this.inner().strong.load(atomic::Ordering::Acquire) != 1 {
+            return None;
+        }
+
+        // SAFETY: This is the only strong reference, and weak references
+        // can't be constructed yet.
+        unsafe { Some(&mut (*this.ptr.as_ptr()).data) }
+    }
+
+    /// Gets the number of strong pointers to this allocation.
+    ///
+    /// Note that another thread can change the strong count at any time,
+    /// including between calling this method and acting on the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::sync::Arc;
+    ///
+    /// let five = Arc::try_new(5)?;
+    /// let _also_five = Arc::clone(&five);
+    ///
+    /// assert_eq!(2, Arc::strong_count(&five));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        this.inner().strong.load(atomic::Ordering::Acquire)
+    }
+
+    /// Returns `true` if the two `Arc`s point to the same allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::sync::Arc;
+    ///
+    /// let five = Arc::try_new(5)?;
+    /// let same_five = Arc::clone(&five);
+    /// let other_five = Arc::try_new(5)?;
+    ///
+    /// assert!(Arc::ptr_eq(&five, &same_five));
+    /// assert!(!Arc::ptr_eq(&five, &other_five));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.ptr == other.ptr
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[inline]
+    pub fn allocator(this: &Self) -> &A {
+        &this.alloc
+    }
+
+    #[inline]
+    fn inner(&self) -> &ArcInner<T> {
+        // SAFETY: The allocation is kept alive for as long as any strong
+        // reference exists.
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Release the weak reference collectively held by strong references,
+    /// deallocating if it was the last one.
+    ///
+    /// # Safety
+    ///
+    /// The data must already have been dropped or moved out.
+    unsafe fn release_weak(ptr: NonNull<ArcInner<T>>, alloc: &A) {
+        if (*ptr.as_ptr()).weak.fetch_sub(1, atomic::Ordering::Release) == 1 {
+            atomic::fence(atomic::Ordering::Acquire);
+            alloc.deallocate(ptr.cast(), Layout::new::<ArcInner<T>>());
+        }
+    }
+}
+
+impl<T, A: Allocator + Clone> Clone for Arc<T, A> {
+    /// Makes a clone of the `Arc` pointer.
+    ///
+    /// This creates another pointer to the same allocation, increasing the
+    /// strong reference count.
+    #[inline]
+    fn clone(&self) -> Self {
+        // Relaxed is enough since new references can only be created from an
+        // existing one, see the standard library for details.
+        let old = self.inner().strong.fetch_add(1, atomic::Ordering::Relaxed);
+
+        if old > MAX_REFCOUNT {
+            crate::abort();
+        }
+
+        Self {
+            ptr: self.ptr,
+            alloc: self.alloc.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, A: Allocator + Clone> TryClone for Arc<T, A> {
+    #[inline]
+    fn try_clone(&self) -> Result<Self, Error> {
+        Ok(self.clone())
+    }
+}
+
+impl<T, A: Allocator> Drop for Arc<T, A> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.inner().strong.fetch_sub(1, atomic::Ordering::Release) != 1 {
+            return;
+        }
+
+        // Synchronize with the release decrements of every other strong
+        // reference, so that their uses of the data happen before it's
+        // dropped.
+        atomic::fence(atomic::Ordering::Acquire);
+
+        // SAFETY: This was the last strong reference.
+        unsafe {
+            ptr::drop_in_place(&mut (*self.ptr.as_ptr()).data);
+            Self::release_weak(self.ptr, &self.alloc);
+        }
+    }
+}
+
+impl<T, A: Allocator> Deref for Arc<T, A> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.inner().data
+    }
+}
+
+impl<T, A: Allocator> AsRef<T> for Arc<T, A> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T, A: Allocator> Borrow<T> for Arc<T, A> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T, A: Allocator> Unpin for Arc<T, A> {}
+
+impl<T, A: Allocator> fmt::Display for Arc<T, A>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T, A: Allocator> fmt::Debug for Arc<T, A>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T, A: Allocator> fmt::Pointer for Arc<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ptr: *const T = &**self;
+        fmt::Pointer::fmt(&ptr, f)
+    }
+}
+
+impl<T, A: Allocator> PartialEq for Arc<T, A>
+where
+    T: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T, A: Allocator> Eq for Arc<T, A> where T: Eq {}
+
+impl<T, A: Allocator> PartialOrd for Arc<T, A>
+where
+    T: PartialOrd,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T, A: Allocator> Ord for Arc<T, A>
+where
+    T: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T, A: Allocator> Hash for Arc<T, A>
+where
+    T: Hash,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
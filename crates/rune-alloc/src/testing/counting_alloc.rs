@@ -0,0 +1,95 @@
+use core::alloc::Layout;
+use core::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+use crate::alloc::{AllocError, Allocator, Global};
+use crate::ptr::NonNull;
+
+/// An allocator which forwards to [`Global`] while recording how it was used.
+///
+/// Calls to `shrink` can optionally be rejected, to test how collections deal
+/// with an allocator refusing to give memory back.
+#[derive(Debug, Default)]
+pub struct CountingAlloc {
+    allocations: AtomicUsize,
+    zeroed: AtomicUsize,
+    deallocations: AtomicUsize,
+    shrinks: AtomicUsize,
+    bytes: AtomicUsize,
+    reject_shrink: bool,
+}
+
+impl CountingAlloc {
+    /// Creates an allocator which rejects every call to `shrink` if
+    /// `reject_shrink` is set.
+    pub fn new(reject_shrink: bool) -> Self {
+        Self {
+            reject_shrink,
+            ..Self::default()
+        }
+    }
+
+    /// Returns how many times memory has been allocated, not counting zeroed
+    /// allocations.
+    pub fn allocations(&self) -> usize {
+        self.allocations.load(SeqCst)
+    }
+
+    /// Returns how many times zeroed memory has been allocated.
+    pub fn zeroed(&self) -> usize {
+        self.zeroed.load(SeqCst)
+    }
+
+    /// Returns how many times memory has been deallocated.
+    pub fn deallocations(&self) -> usize {
+        self.deallocations.load(SeqCst)
+    }
+
+    /// Returns how many times `shrink` has been called, including rejected
+    /// calls.
+    pub fn shrinks(&self) -> usize {
+        self.shrinks.load(SeqCst)
+    }
+
+    /// Returns the number of bytes currently allocated.
+    pub fn bytes(&self) -> usize {
+        self.bytes.load(SeqCst)
+    }
+}
+
+unsafe impl Allocator for CountingAlloc {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocations.fetch_add(1, SeqCst);
+        self.bytes.fetch_add(layout.size(), SeqCst);
+        Global.allocate(layout)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.zeroed.fetch_add(1, SeqCst);
+        self.bytes.fetch_add(layout.size(), SeqCst);
+        Global.allocate_zeroed(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.deallocations.fetch_add(1, SeqCst);
+        self.bytes.fetch_sub(layout.size(), SeqCst);
+        Global.deallocate(ptr, layout)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.shrinks.fetch_add(1, SeqCst);
+
+        if self.reject_shrink {
+            return Err(AllocError { layout: new_layout });
+        }
+
+        let ptr = Global.shrink(ptr, old_layout, new_layout)?;
+        self.bytes
+            .fetch_sub(old_layout.size() - new_layout.size(), SeqCst);
+        Ok(ptr)
+    }
+}
@@ -1,6 +1,17 @@
+use core::cell::Cell;
+
 use crate::error::Error;
 use crate::vec::Vec;
 
+/// Increments the wrapped counter when dropped.
+struct Counted<'a>(&'a Cell<usize>);
+
+impl Drop for Counted<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
 #[test]
 fn test_vec_macro() -> Result<(), Error> {
     let vec: Vec<u32> = try_vec![1, 2, 3];
@@ -13,3 +24,1039 @@ fn test_vec_macro() -> Result<(), Error> {
     assert_eq!(vec, []);
     Ok(())
 }
+
+#[test]
+fn test_vec_try_retain_mut() -> Result<(), Error> {
+    let mut vec: Vec<u32> = try_vec![1, 2, 3, 4, 5];
+    vec.try_retain_mut(|x| {
+        *x *= 10;
+        Ok::<_, Error>(*x != 30)
+    })?;
+    assert_eq!(vec, [10, 20, 40, 50]);
+
+    let mut vec: Vec<u32> = try_vec![];
+    vec.try_retain_mut(|_| Ok::<_, Error>(false))?;
+    assert!(vec.is_empty());
+
+    let mut vec: Vec<u32> = try_vec![1, 2, 3];
+    vec.try_retain_mut(|_| Ok::<_, Error>(true))?;
+    assert_eq!(vec, [1, 2, 3]);
+
+    vec.try_retain_mut(|_| Ok::<_, Error>(false))?;
+    assert!(vec.is_empty());
+
+    let mut vec: Vec<u32> = try_vec![1, 2, 3, 4];
+    let result = vec.try_retain_mut(|x| match *x {
+        3 => Err("three"),
+        _ => Ok(false),
+    });
+    assert_eq!(result, Err("three"));
+    assert_eq!(vec, [3, 4]);
+    Ok(())
+}
+
+#[test]
+fn test_vec_try_resize_with() -> Result<(), Error> {
+    let mut vec: Vec<u32> = Vec::try_with_capacity(2)?;
+    vec.try_push(1)?;
+    let mut n = 1;
+    vec.try_resize_with(10, || {
+        n += 1;
+        n
+    })?;
+    assert!(vec.capacity() >= 10);
+    assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    vec.try_extend_with(2, || 0)?;
+    assert_eq!(vec.len(), 12);
+    assert_eq!(vec[10..], [0, 0]);
+
+    let drops = Cell::new(0);
+    let mut vec = Vec::new();
+    vec.try_resize_with(5, || Counted(&drops))?;
+    assert_eq!(drops.get(), 0);
+
+    vec.try_resize_with(2, || unreachable!())?;
+    assert_eq!(vec.len(), 2);
+    assert_eq!(drops.get(), 3);
+
+    drop(vec);
+    assert_eq!(drops.get(), 5);
+    Ok(())
+}
+
+#[test]
+fn test_vec_try_resize_with_panic() -> Result<(), Error> {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let drops = Cell::new(0);
+    let mut vec = Vec::new();
+    vec.try_push(Counted(&drops))?;
+
+    let mut calls = 0;
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        vec.try_resize_with(8, || {
+            calls += 1;
+
+            if calls == 4 {
+                panic!("boom");
+            }
+
+            Counted(&drops)
+        })
+    }));
+
+    assert!(result.is_err());
+    // Everything constructed before the panic is kept.
+    assert_eq!(vec.len(), 4);
+    assert_eq!(drops.get(), 0);
+
+    drop(vec);
+    assert_eq!(drops.get(), 4);
+    Ok(())
+}
+
+#[test]
+fn test_vec_try_extend_with_alloc_error() {
+    use crate::limit;
+
+    limit::with(16, || {
+        let mut vec = Vec::<u32>::new();
+        vec.try_push(1)?;
+
+        let mut calls = 0;
+
+        let result = vec.try_extend_with(8, || {
+            calls += 1;
+            0
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls, 0);
+        assert_eq!(vec, [1]);
+
+        assert!(vec.try_resize_with(8, || unreachable!()).is_err());
+        assert_eq!(vec, [1]);
+        Ok::<_, Error>(())
+    })
+    .call()
+    .unwrap();
+}
+
+#[test]
+fn test_vec_try_from_fn() -> Result<(), Error> {
+    let vec = Vec::try_from_fn(5, |i| Ok(i * i))?;
+    assert_eq!(vec, [0, 1, 4, 9, 16]);
+    assert_eq!(vec.capacity(), 5);
+
+    let vec = Vec::<usize>::try_from_fn(0, |_| unreachable!())?;
+    assert!(vec.is_empty());
+
+    let drops = Cell::new(0);
+    let mut calls = 0;
+
+    let result = Vec::try_from_fn(5, |i| {
+        calls += 1;
+
+        if i == 3 {
+            return Err(Error::CapacityOverflow);
+        }
+
+        Ok(Counted(&drops))
+    });
+
+    assert!(matches!(result, Err(Error::CapacityOverflow)));
+    assert_eq!(calls, 4);
+    // Everything produced before the error has been dropped.
+    assert_eq!(drops.get(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_vec_try_from_fn_alloc_error() {
+    use crate::limit;
+
+    limit::with(16, || {
+        let result = Vec::<u32>::try_from_fn(8, |_| unreachable!());
+        assert!(result.is_err());
+
+        let vec = Vec::<u32>::try_from_fn(4, |i| Ok(i as u32))?;
+        assert_eq!(vec, [0, 1, 2, 3]);
+        Ok::<_, Error>(())
+    })
+    .call()
+    .unwrap();
+}
+
+#[test]
+fn test_global_grow_shrink_limit() {
+    use crate::limit;
+
+    limit::with(1024, || {
+        let mut vec = Vec::<u8>::try_with_capacity(16)?;
+        vec.try_extend_from_slice(b"hello world")?;
+        assert_eq!(limit::get(), 1024 - 16);
+
+        vec.try_reserve_exact(512 - vec.len())?;
+        assert_eq!(vec.capacity(), 512);
+        assert_eq!(limit::get(), 1024 - 512);
+
+        // Growing only has to account for the additional memory, not for a
+        // second copy of the existing allocation.
+        assert!(vec.try_reserve_exact(1025 - vec.len()).is_err());
+        assert_eq!(vec.capacity(), 512);
+        assert_eq!(limit::get(), 1024 - 512);
+
+        vec.try_reserve_exact(1024 - vec.len())?;
+        assert_eq!(vec.capacity(), 1024);
+        assert_eq!(limit::get(), 0);
+
+        vec.try_shrink_to_fit()?;
+        assert_eq!(vec.capacity(), 11);
+        assert_eq!(limit::get(), 1024 - 11);
+        assert_eq!(vec, b"hello world");
+
+        drop(vec);
+        assert_eq!(limit::get(), 1024);
+        Ok::<_, Error>(())
+    })
+    .call()
+    .unwrap();
+}
+
+#[test]
+fn test_string_from_utf8_lossy() -> Result<(), Error> {
+    use crate::alloc::Global;
+    use crate::String;
+
+    let lossy = |bytes: &[u8]| String::try_from_utf8_lossy_in(bytes, Global);
+
+    assert_eq!(lossy(b"hello")?, "hello");
+    assert_eq!(lossy(b"")?, "");
+    assert_eq!(lossy(b"\xffhello")?, "\u{FFFD}hello");
+    assert_eq!(lossy(b"hel\xfflo")?, "hel\u{FFFD}lo");
+    assert_eq!(lossy(b"hello\xff")?, "hello\u{FFFD}");
+    assert_eq!(lossy(b"\xff\xfe")?, "\u{FFFD}\u{FFFD}");
+    // A truncated multi-byte sequence is replaced by a single character.
+    assert_eq!(lossy(b"a\xf0\x90\x80b")?, "a\u{FFFD}b");
+    assert_eq!(lossy(b"a\xf0\x90\x80")?, "a\u{FFFD}");
+
+    let bytes: Vec<u8> = try_vec![b'a', b'b', b'c'];
+    let ptr = bytes.as_ptr();
+    let string = String::try_from_utf8_lossy_owned(bytes)?;
+    assert_eq!(string, "abc");
+    assert_eq!(string.as_ptr(), ptr);
+
+    let bytes: Vec<u8> = try_vec![b'a', 0xff, b'c'];
+    let string = String::try_from_utf8_lossy_owned(bytes)?;
+    assert_eq!(string, "a\u{FFFD}c");
+    Ok(())
+}
+
+#[test]
+fn test_string_from_utf8_lossy_alloc_error() {
+    use crate::alloc::Global;
+    use crate::limit;
+    use crate::String;
+
+    limit::with(4, || {
+        assert!(String::try_from_utf8_lossy_in(b"hello", Global).is_err());
+        // Invalid input needs to grow to fit the replacement character.
+        assert!(String::try_from_utf8_lossy_in(b"\xff\xff", Global).is_err());
+        // Valid input only allocates exactly what's needed.
+        assert_eq!(String::try_from_utf8_lossy_in(b"abcd", Global)?, "abcd");
+        Ok::<_, Error>(())
+    })
+    .call()
+    .unwrap();
+}
+
+#[test]
+fn test_string_try_add() -> Result<(), Error> {
+    use crate::ops::TryAdd;
+    use crate::String;
+
+    let s = String::try_from("hello")?.try_add(" world")?;
+    assert_eq!(s, "hello world");
+
+    let s = String::try_from("hello")?.try_add(String::try_from(" world")?)?;
+    assert_eq!(s, "hello world");
+
+    let s = "hello".try_add(" world")?;
+    assert_eq!(s, "hello world");
+
+    // Empty operands on either side.
+    assert_eq!(String::new().try_add("")?, "");
+    assert_eq!(String::new().try_add("abc")?, "abc");
+    assert_eq!(String::try_from("abc")?.try_add("")?, "abc");
+    assert_eq!(String::new().try_add(String::new())?, "");
+    assert_eq!("".try_add("")?, "");
+    assert_eq!("".try_add("abc")?, "abc");
+    assert_eq!("abc".try_add("")?, "abc");
+
+    // Adding an empty string doesn't allocate.
+    let s = "".try_add("")?;
+    assert_eq!(s.capacity(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_string_try_add_alloc_error() {
+    use crate::limit;
+    use crate::ops::TryAdd;
+    use crate::String;
+
+    let a = String::try_from("hello").unwrap();
+
+    // Growing beyond the limit fails, without the left-hand side having to be
+    // allocated inside of it.
+    limit::with(4, || {
+        assert!(a.try_add(" world").is_err());
+        assert!("hello".try_add(" world").is_err());
+        assert_eq!("ab".try_add("cd")?, "abcd");
+        Ok::<_, Error>(())
+    })
+    .call()
+    .unwrap();
+}
+
+#[test]
+fn test_string_try_from_chars() -> Result<(), Error> {
+    use crate::String;
+
+    let s = String::try_from_chars("hello".chars())?;
+    assert_eq!(s, "hello");
+
+    let s = String::try_from_chars("åäö€".chars())?;
+    assert_eq!(s, "åäö€");
+    assert_eq!(s.len(), 9);
+
+    let s = String::try_from_chars(['a', '\u{1F980}', '\u{10FFFF}'].into_iter())?;
+    assert_eq!(s, "a\u{1F980}\u{10FFFF}");
+    assert_eq!(s.len(), 9);
+    // Exact size iterators reserve space for the widest encoding up front.
+    assert!(s.capacity() >= 12);
+
+    let s = String::try_from_chars(core::iter::empty())?;
+    assert!(s.is_empty());
+    assert_eq!(s.capacity(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_vec_deque_from_into_vec() -> Result<(), Error> {
+    use crate::VecDeque;
+
+    let vec: Vec<u32> = try_vec![1, 2, 3, 4];
+    let ptr = vec.as_ptr();
+    let deque = VecDeque::try_from_vec(vec)?;
+    assert_eq!(deque, [1, 2, 3, 4]);
+    assert_eq!(deque.as_slices().0.as_ptr(), ptr);
+
+    // A deque which doesn't wrap converts without moving anything.
+    let vec = deque.into_vec();
+    assert_eq!(vec, [1, 2, 3, 4]);
+    assert_eq!(vec.as_ptr(), ptr);
+
+    // A deque which wraps around is rotated in place.
+    let mut deque = VecDeque::try_with_capacity(8)?;
+    let ptr = deque.as_slices().0.as_ptr();
+
+    for n in 0..6u32 {
+        deque.try_push_back(n)?;
+    }
+
+    for _ in 0..4 {
+        deque.pop_front();
+    }
+
+    for n in 6..11u32 {
+        deque.try_push_back(n)?;
+    }
+
+    assert!(!deque.as_slices().1.is_empty());
+    let capacity = deque.capacity();
+    let vec = deque.into_vec();
+    assert_eq!(vec, [4, 5, 6, 7, 8, 9, 10]);
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), capacity);
+
+    // Zero-sized types.
+    let vec: Vec<()> = try_vec![(); 5];
+    let mut deque = VecDeque::try_from_vec(vec)?;
+    deque.try_push_front(())?;
+    assert_eq!(deque.len(), 6);
+    assert_eq!(deque.into_vec().len(), 6);
+    Ok(())
+}
+
+#[test]
+fn test_vec_deque_from_into_vec_drops() -> Result<(), Error> {
+    use crate::VecDeque;
+
+    let drops = Cell::new(0);
+
+    let mut vec = Vec::new();
+
+    for _ in 0..4 {
+        vec.try_push(Counted(&drops))?;
+    }
+
+    let mut deque = VecDeque::try_from_vec(vec)?;
+    deque.try_push_front(Counted(&drops))?;
+    assert_eq!(drops.get(), 0);
+
+    let vec = deque.into_vec();
+    assert_eq!(vec.len(), 5);
+    assert_eq!(drops.get(), 0);
+
+    drop(vec);
+    assert_eq!(drops.get(), 5);
+    Ok(())
+}
+
+#[test]
+fn test_vec_deque_insert_remove_wrapped() -> Result<(), Error> {
+    use crate::VecDeque;
+
+    // Construct a deque whose contents wrap around the end of the buffer.
+    fn wrapped() -> Result<VecDeque<u32>, Error> {
+        let mut deque = VecDeque::try_with_capacity(8)?;
+
+        for n in 0..6 {
+            deque.try_push_back(n)?;
+        }
+
+        for _ in 0..4 {
+            deque.pop_front();
+        }
+
+        for n in 6..10 {
+            deque.try_push_back(n)?;
+        }
+
+        assert!(!deque.as_slices().1.is_empty());
+        assert_eq!(deque, [4, 5, 6, 7, 8, 9]);
+        Ok(deque)
+    }
+
+    let mut deque = wrapped()?;
+    let capacity = deque.capacity();
+    deque.try_insert(1, 10)?;
+    deque.try_insert(6, 11)?;
+    assert_eq!(deque, [4, 10, 5, 6, 7, 8, 11, 9]);
+    assert_eq!(deque.capacity(), capacity);
+
+    // Inserting into a full deque grows it.
+    deque.try_insert(4, 12)?;
+    assert_eq!(deque, [4, 10, 5, 6, 12, 7, 8, 11, 9]);
+    assert!(deque.capacity() > capacity);
+
+    let mut deque = wrapped()?;
+    assert_eq!(deque.remove(1), Some(5));
+    assert_eq!(deque.remove(3), Some(8));
+    assert_eq!(deque.remove(4), None);
+    assert_eq!(deque, [4, 6, 7, 9]);
+
+    let mut deque = wrapped()?;
+    assert_eq!(deque.swap_remove_front(4), Some(8));
+    assert_eq!(deque, [5, 6, 7, 4, 9]);
+    assert_eq!(deque.swap_remove_back(0), Some(5));
+    assert_eq!(deque, [9, 6, 7, 4]);
+    assert_eq!(deque.swap_remove_back(3), Some(4));
+    assert_eq!(deque.swap_remove_front(4), None);
+    assert_eq!(deque, [9, 6, 7]);
+    Ok(())
+}
+
+#[test]
+#[should_panic = "index out of bounds"]
+fn test_vec_deque_insert_out_of_bounds() {
+    let mut deque = crate::VecDeque::<u32>::new();
+    let _ = deque.try_insert(1, 0);
+}
+
+#[test]
+fn test_vec_deque_remove_drops() -> Result<(), Error> {
+    use crate::VecDeque;
+
+    let drops = Cell::new(0);
+    let mut deque = VecDeque::new();
+
+    for _ in 0..6 {
+        deque.try_push_back(Counted(&drops))?;
+    }
+
+    deque.try_insert(3, Counted(&drops))?;
+    assert_eq!(drops.get(), 0);
+
+    drop(deque.remove(1));
+    drop(deque.remove(4));
+    assert!(deque.remove(5).is_none());
+    assert_eq!(drops.get(), 2);
+
+    drop(deque.swap_remove_front(2));
+    drop(deque.swap_remove_back(0));
+    assert_eq!(drops.get(), 4);
+    assert_eq!(deque.len(), 3);
+
+    drop(deque);
+    assert_eq!(drops.get(), 7);
+    Ok(())
+}
+
+#[test]
+fn test_vec_deque_model() -> Result<(), Error> {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+    use std::collections::VecDeque as StdVecDeque;
+
+    use crate::VecDeque;
+
+    const OPERATIONS: usize = if cfg!(miri) { 200 } else { 10_000 };
+
+    let mut rng = SmallRng::seed_from_u64(0x5eed);
+    let mut deque = VecDeque::new();
+    let mut model = StdVecDeque::new();
+
+    for n in 0..OPERATIONS {
+        match rng.gen_range(0..6) {
+            0 => {
+                deque.try_push_front(n)?;
+                model.push_front(n);
+            }
+            1 => {
+                deque.try_push_back(n)?;
+                model.push_back(n);
+            }
+            2 => {
+                let index = rng.gen_range(0..=model.len());
+                deque.try_insert(index, n)?;
+                model.insert(index, n);
+            }
+            3 => {
+                let index = rng.gen_range(0..=model.len());
+                assert_eq!(deque.remove(index), model.remove(index));
+            }
+            4 => {
+                let index = rng.gen_range(0..=model.len());
+                assert_eq!(
+                    deque.swap_remove_front(index),
+                    model.swap_remove_front(index)
+                );
+            }
+            _ => {
+                let index = rng.gen_range(0..=model.len());
+                assert_eq!(deque.swap_remove_back(index), model.swap_remove_back(index));
+            }
+        }
+
+        assert!(deque.iter().eq(model.iter()));
+    }
+
+    Ok(())
+}
+
+mod shrink {
+    use crate::error::Error;
+    use crate::testing::counting_alloc::CountingAlloc;
+    use crate::{String, Vec, VecDeque};
+
+    #[test]
+    fn test_vec_try_shrink() -> Result<(), Error> {
+        for reject in [false, true] {
+            let alloc = CountingAlloc::new(reject);
+
+            let mut vec = Vec::try_with_capacity_in(100, &alloc)?;
+            vec.try_extend_from_slice(&[1u32, 2, 3, 4])?;
+
+            vec.try_shrink_to(10)?;
+            assert_eq!(vec.capacity(), 10);
+            assert_eq!(alloc.shrinks(), 1);
+            assert_eq!(vec, [1, 2, 3, 4]);
+
+            vec.try_shrink_to_fit()?;
+            assert_eq!(vec.capacity(), 4);
+            assert_eq!(alloc.shrinks(), 2);
+            assert_eq!(vec, [1, 2, 3, 4]);
+
+            // Shrinking an empty vector deallocates without shrinking.
+            vec.clear();
+            vec.try_shrink_to_fit()?;
+            assert_eq!(vec.capacity(), 0);
+            assert_eq!(alloc.shrinks(), 2);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_try_shrink() -> Result<(), Error> {
+        for reject in [false, true] {
+            let alloc = CountingAlloc::new(reject);
+
+            let mut string = String::try_with_capacity_in(100, &alloc)?;
+            string.try_push_str("hello")?;
+
+            string.try_shrink_to_fit()?;
+            assert_eq!(string.capacity(), 5);
+            assert_eq!(alloc.shrinks(), 1);
+            assert_eq!(string.as_str(), "hello");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vec_deque_try_shrink() -> Result<(), Error> {
+        for reject in [false, true] {
+            let alloc = CountingAlloc::new(reject);
+
+            let mut deque = VecDeque::try_with_capacity_in(16, &alloc)?;
+
+            // Make the contents wrap around the end of the buffer.
+            for n in 0..12u32 {
+                deque.try_push_back(n)?;
+            }
+
+            for _ in 0..10 {
+                deque.pop_front();
+            }
+
+            for n in 12..16u32 {
+                deque.try_push_back(n)?;
+            }
+
+            deque.try_shrink_to_fit()?;
+            assert_eq!(deque.capacity(), 6);
+            assert_eq!(alloc.shrinks(), 1);
+            assert!(deque.iter().copied().eq([10, 11, 12, 13, 14, 15]));
+        }
+
+        Ok(())
+    }
+}
+
+mod bump {
+    use crate::alloc::BumpAllocator;
+    use crate::error::Error;
+    use crate::Vec;
+
+    fn assert_send<T: Send>() {}
+
+    #[test]
+    fn test_bump_vec() -> Result<(), Error> {
+        assert_send::<BumpAllocator>();
+
+        let bump = BumpAllocator::try_with_capacity(1024)?;
+        let mut vec = Vec::<u8, BumpAllocator>::new_in(bump);
+
+        for n in 0..100u8 {
+            vec.try_push(n)?;
+        }
+
+        assert!(vec.iter().copied().eq(0..100u8));
+        assert!(vec.allocator().used() <= 128);
+
+        // The slab is exhausted.
+        assert!(vec.try_reserve_exact(1024).is_err());
+        assert!(vec.iter().copied().eq(0..100u8));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bump_reset() -> Result<(), Error> {
+        let mut bump = BumpAllocator::try_with_capacity(256)?;
+
+        let first = {
+            let mut vec = Vec::try_with_capacity_in(200, &bump)?;
+            vec.try_extend_from_slice(&[1u8; 200])?;
+            vec.as_ptr()
+        };
+
+        // Deallocating doesn't release any memory.
+        assert_eq!(bump.used(), 200);
+        assert!(Vec::<u8, _>::try_with_capacity_in(200, &bump).is_err());
+
+        bump.reset();
+        assert_eq!(bump.used(), 0);
+
+        let mut vec = Vec::try_with_capacity_in(200, &bump)?;
+        vec.try_extend_from_slice(&[2u8; 200])?;
+        assert_eq!(vec.as_ptr(), first);
+        assert!(vec.iter().all(|&b| b == 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bump_alignment() -> Result<(), Error> {
+        let bump = BumpAllocator::try_with_capacity(256)?;
+
+        let a = Vec::<u8, _>::try_with_capacity_in(1, &bump)?;
+        let b = Vec::<u64, _>::try_with_capacity_in(4, &bump)?;
+        let c = Vec::<u128, _>::try_with_capacity_in(1, &bump)?;
+
+        assert_eq!(b.as_ptr() as usize % core::mem::align_of::<u64>(), 0);
+        assert_eq!(c.as_ptr() as usize % core::mem::align_of::<u128>(), 0);
+        assert!(a.as_ptr() < b.as_ptr().cast());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bump_zero_capacity() -> Result<(), Error> {
+        let bump = BumpAllocator::try_with_capacity(0)?;
+
+        let mut vec = Vec::<(), _>::new_in(&bump);
+        vec.try_push(())?;
+
+        let mut vec = Vec::<u8, _>::new_in(&bump);
+        assert!(vec.try_push(1).is_err());
+        Ok(())
+    }
+}
+
+mod sync {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use std::thread;
+    use std::vec::Vec;
+
+    use crate::error::Error;
+    use crate::sync::Arc;
+    use crate::testing::counting_alloc::CountingAlloc;
+
+    /// Counts the number of times it has been dropped.
+    struct Dropped<'a>(&'a AtomicUsize);
+
+    impl Drop for Dropped<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_arc_allocations() -> Result<(), Error> {
+        let alloc = CountingAlloc::default();
+        let drops = AtomicUsize::new(0);
+
+        let a = Arc::try_new_in(Dropped(&drops), &alloc)?;
+        assert_eq!(alloc.allocations(), 1);
+
+        let b = a.clone();
+        let c = b.clone();
+        assert_eq!(Arc::strong_count(&a), 3);
+        assert_eq!(alloc.allocations(), 1);
+
+        drop(a);
+        drop(b);
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        assert_eq!(alloc.deallocations(), 0);
+
+        drop(c);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        assert_eq!(alloc.deallocations(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arc_try_unwrap() -> Result<(), Error> {
+        let alloc = CountingAlloc::default();
+        let drops = AtomicUsize::new(0);
+
+        let a = Arc::try_new_in(Dropped(&drops), &alloc)?;
+        let b = a.clone();
+
+        // Shared, so the same arc is handed back.
+        let a = Arc::try_unwrap(a).map(drop).unwrap_err();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(Arc::strong_count(&a), 2);
+        drop(b);
+
+        // Unique, so the value is moved out and the allocation is released
+        // without dropping it.
+        let value = Arc::try_unwrap(a).map_err(drop).unwrap();
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        assert_eq!(alloc.deallocations(), 1);
+
+        drop(value);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arc_get_mut() -> Result<(), Error> {
+        let mut a = Arc::try_new(1u32)?;
+        *Arc::get_mut(&mut a).unwrap() += 1;
+
+        let b = a.clone();
+        assert!(Arc::get_mut(&mut a).is_none());
+        drop(b);
+
+        *Arc::get_mut(&mut a).unwrap() += 1;
+        assert_eq!(*a, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arc_zst() -> Result<(), Error> {
+        let alloc = CountingAlloc::default();
+
+        let a = Arc::try_new_in((), &alloc)?;
+        let b = a.clone();
+        assert_eq!(alloc.allocations(), 1);
+        assert_eq!(*b, ());
+
+        drop(a);
+        assert_eq!(Arc::try_unwrap(b).ok(), Some(()));
+        assert_eq!(alloc.deallocations(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arc_threads() -> Result<(), Error> {
+        const THREADS: usize = if cfg!(miri) { 4 } else { 16 };
+        const CLONES: usize = if cfg!(miri) { 10 } else { 1000 };
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        let arc = Arc::try_new(Dropped(&DROPS))?;
+
+        let threads = (0..THREADS)
+            .map(|_| {
+                let arc = arc.clone();
+
+                thread::spawn(move || {
+                    let mut clones = Vec::new();
+
+                    for _ in 0..CLONES {
+                        clones.push(arc.clone());
+                    }
+
+                    assert!(Arc::strong_count(&arc) > CLONES);
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(Arc::strong_count(&arc), 1);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+        drop(arc);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+}
+
+mod boxed {
+    use crate::boxed::Box;
+    use crate::error::Error;
+    use crate::testing::counting_alloc::CountingAlloc;
+
+    #[test]
+    fn test_box_try_new_zeroed() -> Result<(), Error> {
+        let alloc = CountingAlloc::default();
+
+        let value = Box::<u64, _>::try_new_zeroed_in(&alloc)?;
+        let value = unsafe { value.assume_init() };
+        assert_eq!(*value, 0);
+        assert_eq!(alloc.zeroed(), 1);
+        assert_eq!(alloc.allocations(), 0);
+
+        drop(value);
+        assert_eq!(alloc.deallocations(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_box_try_new_zeroed_slice() -> Result<(), Error> {
+        let alloc = CountingAlloc::default();
+
+        let values = Box::<[u8], _>::try_new_zeroed_slice_in(4096, &alloc)?;
+        let values = unsafe { values.assume_init() };
+        assert_eq!(values.len(), 4096);
+        assert!(values.iter().all(|&b| b == 0));
+        assert_eq!(alloc.zeroed(), 1);
+        assert_eq!(alloc.allocations(), 0);
+
+        drop(values);
+        assert_eq!(alloc.deallocations(), 1);
+
+        let values = Box::<[u32]>::try_new_zeroed_slice(3)?;
+        assert_eq!(*unsafe { values.assume_init() }, [0, 0, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_box_try_new_uninit_slice() -> Result<(), Error> {
+        let alloc = CountingAlloc::default();
+
+        let mut values = Box::<[u32], _>::try_new_uninit_slice_in(3, &alloc)?;
+
+        for (n, value) in values.iter_mut().enumerate() {
+            value.write(n as u32 + 1);
+        }
+
+        let values = unsafe { values.assume_init() };
+        assert_eq!(*values, [1, 2, 3]);
+        assert_eq!(alloc.allocations(), 1);
+        assert_eq!(alloc.zeroed(), 0);
+
+        let mut value = Box::<u32>::try_new_uninit()?;
+        value.write(42);
+        assert_eq!(*unsafe { value.assume_init() }, 42);
+        Ok(())
+    }
+
+    #[test]
+    fn test_box_zst_slices() -> Result<(), Error> {
+        let values = Box::<[()]>::try_new_zeroed_slice(usize::MAX)?;
+        assert_eq!(unsafe { values.assume_init() }.len(), usize::MAX);
+
+        let values = Box::<[()]>::try_new_uninit_slice(10)?;
+        assert_eq!(unsafe { values.assume_init() }.len(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_box_slice_capacity_overflow() {
+        let alloc = CountingAlloc::default();
+
+        let result = Box::<[u64], _>::try_new_zeroed_slice_in(usize::MAX, &alloc);
+        assert!(matches!(result, Err(Error::CapacityOverflow)));
+
+        let result = Box::<[u8], _>::try_new_uninit_slice_in(isize::MAX as usize + 1, &alloc);
+        assert!(matches!(result, Err(Error::CapacityOverflow)));
+
+        assert_eq!(alloc.allocations(), 0);
+        assert_eq!(alloc.zeroed(), 0);
+    }
+}
+
+mod heap_size {
+    use crate::boxed::Box;
+    use crate::error::Error;
+    use crate::hashbrown::map::DefaultHashBuilder;
+    use crate::heap_size::HeapSize;
+    use crate::testing::counting_alloc::CountingAlloc;
+    use crate::{BTreeMap, BTreeSet, HashMap, HashSet, String, Vec, VecDeque};
+
+    #[test]
+    fn test_heap_size_vec() -> Result<(), Error> {
+        let mut vec = Vec::<u8>::try_with_capacity(10)?;
+        assert_eq!(vec.heap_size(), 10);
+
+        vec.try_extend_from_slice(&[1, 2, 3])?;
+        assert_eq!(vec.heap_size(), 10);
+
+        assert_eq!(Vec::<u8>::new().heap_size(), 0);
+        assert_eq!(Vec::<u64>::try_with_capacity(4)?.heap_size(), 32);
+
+        let mut zst = Vec::<()>::new();
+        zst.try_push(())?;
+        assert_eq!(zst.heap_size(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_heap_size_map_of_strings() -> Result<(), Error> {
+        let mut map = HashMap::<String, String>::new();
+        assert_eq!(map.heap_size(), 0);
+
+        let mut expected = 0;
+
+        for n in 0..10 {
+            let mut key = String::try_with_capacity(n + 1)?;
+            key.try_push_str(&try_format!("{n}"))?;
+            let value = String::try_with_capacity(n * 2)?;
+            expected += key.capacity() + value.capacity();
+            map.try_insert(key, value)?;
+        }
+
+        let (_, layout) = map.raw_table().allocation_info();
+        assert_eq!(map.heap_size(), layout.size() + expected);
+        Ok(())
+    }
+
+    struct Nested<'a> {
+        map: HashMap<
+            String<&'a CountingAlloc>,
+            Vec<u32, &'a CountingAlloc>,
+            DefaultHashBuilder,
+            &'a CountingAlloc,
+        >,
+        tree: BTreeMap<u32, VecDeque<u64, &'a CountingAlloc>, &'a CountingAlloc>,
+        set: HashSet<Box<[u16], &'a CountingAlloc>, DefaultHashBuilder, &'a CountingAlloc>,
+        keys: BTreeSet<u64, &'a CountingAlloc>,
+        name: Option<Box<String<&'a CountingAlloc>, &'a CountingAlloc>>,
+    }
+
+    impl HeapSize for Nested<'_> {
+        fn heap_size(&self) -> usize {
+            self.map.heap_size()
+                + self.tree.heap_size()
+                + self.set.heap_size()
+                + self.keys.heap_size()
+                + self.name.heap_size()
+        }
+    }
+
+    #[test]
+    fn test_heap_size_nested() -> Result<(), Error> {
+        let alloc = CountingAlloc::default();
+
+        let mut nested = Nested {
+            map: HashMap::new_in(&alloc),
+            tree: BTreeMap::new_in(&alloc),
+            set: HashSet::new_in(&alloc),
+            keys: BTreeSet::new_in(&alloc),
+            name: None,
+        };
+
+        for n in 0..100u32 {
+            let mut key = String::new_in(&alloc);
+            key.try_push_str(&try_format!("{n}"))?;
+
+            let mut values = Vec::new_in(&alloc);
+
+            for v in 0..n % 7 {
+                values.try_push(v)?;
+            }
+
+            nested.map.try_insert(key, values)?;
+
+            let mut deque = VecDeque::new_in(&alloc);
+
+            for v in 0..n % 5 {
+                deque.try_push_back(u64::from(v))?;
+            }
+
+            nested.tree.try_insert(n, deque)?;
+
+            let mut slice = Vec::try_with_capacity_in(n as usize % 3, &alloc)?;
+
+            for v in 0..n % 3 {
+                slice.try_push(v as u16)?;
+            }
+
+            nested.set.try_insert(slice.try_into_boxed_slice()?)?;
+            nested.keys.try_insert(u64::from(n))?;
+        }
+
+        let mut name = String::new_in(&alloc);
+        name.try_push_str("a name which is long enough")?;
+        nested.name = Some(Box::try_new_in(name, &alloc)?);
+
+        // Every byte allocated is owned by exactly one container.
+        assert_eq!(nested.heap_size(), alloc.bytes());
+
+        nested.map.clear();
+        nested.tree.clear();
+        assert_eq!(nested.heap_size(), alloc.bytes());
+
+        drop(nested);
+        assert_eq!(alloc.bytes(), 0);
+        Ok(())
+    }
+}
@@ -103,6 +103,7 @@ use core::slice::{self, SliceIndex};
 use crate::alloc::{Allocator, Global, SizedTypeProperties};
 use crate::clone::TryClone;
 use crate::error::Error;
+use crate::heap_size::HeapSize;
 use crate::iter::{TryExtend, TryFromIteratorIn};
 use crate::ptr::{self, NonNull};
 use crate::raw_vec::RawVec;
@@ -480,6 +481,43 @@ impl<T> Vec<T> {
         Self::try_with_capacity_in(capacity, Global)
     }
 
+    /// Constructs a new `Vec<T>` with `len` elements, where each element is
+    /// produced by calling `f` with its index.
+    ///
+    /// Space for all the elements is allocated up front. If `f` returns an
+    /// error, the elements which were produced so far are dropped and the error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{Error, Vec};
+    ///
+    /// let vec = Vec::try_from_fn(4, |i| Ok(i * 2))?;
+    /// assert_eq!(vec, [0, 2, 4, 6]);
+    /// assert_eq!(vec.capacity(), 4);
+    ///
+    /// let result = Vec::<usize>::try_from_fn(4, |i| match i {
+    ///     2 => Err(Error::CapacityOverflow),
+    ///     i => Ok(i),
+    /// });
+    ///
+    /// assert!(result.is_err());
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_fn<F>(len: usize, mut f: F) -> Result<Self, Error>
+    where
+        F: FnMut(usize) -> Result<T, Error>,
+    {
+        let mut vec = Self::try_with_capacity(len)?;
+
+        for index in 0..len {
+            vec.try_push(f(index)?)?;
+        }
+
+        Ok(vec)
+    }
+
     /// Convert a [`Vec<T>`] into a std `Vec<T>`.
     ///
     /// The result is allocated on the heap, using the default global allocator
@@ -887,7 +925,7 @@ impl<T, A: Allocator> Vec<T, A> {
         // they are equal, so we can avoid the panic case in `RawVec::shrink_to_fit`
         // by only calling it with a greater capacity.
         if self.capacity() > self.len {
-            self.buf.try_shrink_to_fit(self.len)?;
+            self.buf.try_shrink(self.len)?;
         }
 
         Ok(())
@@ -917,8 +955,7 @@ impl<T, A: Allocator> Vec<T, A> {
     /// ```
     pub fn try_shrink_to(&mut self, min_capacity: usize) -> Result<(), Error> {
         if self.capacity() > min_capacity {
-            self.buf
-                .try_shrink_to_fit(cmp::max(self.len, min_capacity))?;
+            self.buf.try_shrink(cmp::max(self.len, min_capacity))?;
         }
 
         Ok(())
@@ -1562,6 +1599,56 @@ impl<T, A: Allocator> Vec<T, A> {
         drop(g);
     }
 
+    /// Retains only the elements specified by the fallible predicate, passing
+    /// a mutable reference to it.
+    ///
+    /// This is the fallible variant of [`retain_mut`]. If the predicate
+    /// errors, no further elements are visited and the error is returned.
+    /// Elements for which the predicate already returned `false` stay
+    /// removed, and every other element is retained in its original order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::try_vec;
+    ///
+    /// let mut vec = try_vec![1, 2, 3, 4];
+    /// vec.try_retain_mut(|x| {
+    ///     *x += 1;
+    ///     Ok::<_, &str>(*x % 2 == 0)
+    /// })?;
+    /// assert_eq!(vec, [2, 4]);
+    ///
+    /// let result = vec.try_retain_mut(|x| if *x == 4 { Err("four") } else { Ok(false) });
+    /// assert_eq!(result, Err("four"));
+    /// assert_eq!(vec, [4]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`retain_mut`]: Vec::retain_mut
+    pub fn try_retain_mut<F, E>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut T) -> Result<bool, E>,
+    {
+        let mut result = Ok(());
+
+        self.retain_mut(|elem| {
+            if result.is_err() {
+                return true;
+            }
+
+            match f(elem) {
+                Ok(keep) => keep,
+                Err(error) => {
+                    result = Err(error);
+                    true
+                }
+            }
+        });
+
+        result
+    }
+
     /// Removes all but the first of consecutive elements in the vector that resolve to the same
     /// key.
     ///
@@ -2112,7 +2199,7 @@ impl<T, A: Allocator> Vec<T, A> {
         let len = self.len();
 
         if new_len > len {
-            self.try_extend_trusted(iter::repeat_with(f).take(new_len - len))?;
+            self.try_extend_with(new_len - len, f)?;
         } else {
             self.truncate(new_len);
         }
@@ -2120,6 +2207,31 @@ impl<T, A: Allocator> Vec<T, A> {
         Ok(())
     }
 
+    /// Extend the `Vec` with `n` values, each of which is produced by calling
+    /// the closure `f`.
+    ///
+    /// Space for all the values is reserved up front, so if allocation fails
+    /// `f` is never called. If `f` panics, the values which were produced
+    /// before it are kept in the `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::try_vec;
+    ///
+    /// let mut vec = try_vec![1, 2];
+    /// let mut n = 2;
+    /// vec.try_extend_with(3, || { n += 1; n })?;
+    /// assert_eq!(vec, [1, 2, 3, 4, 5]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_extend_with<F>(&mut self, n: usize, f: F) -> Result<(), Error>
+    where
+        F: FnMut() -> T,
+    {
+        self.try_extend_trusted(iter::repeat_with(f).take(n))
+    }
+
     /// Consumes and leaks the `Vec`, returning a mutable reference to the contents,
     /// `&'a mut [T]`. Note that the type `T` must outlive the chosen lifetime
     /// `'a`. If the type has only static references, or none at all, then this
@@ -2377,7 +2489,7 @@ where
         let len = self.len();
 
         if new_len > len {
-            self.try_extend_with(new_len - len, value)?;
+            self.try_extend_with_clones(new_len - len, value)?;
         } else {
             self.truncate(new_len);
         }
@@ -2513,7 +2625,7 @@ where
     T: TryClone,
 {
     /// Extend the vector by `n` clones of value.
-    fn try_extend_with(&mut self, n: usize, value: T) -> Result<(), Error> {
+    fn try_extend_with_clones(&mut self, n: usize, value: T) -> Result<(), Error> {
         self.try_reserve(n)?;
 
         unsafe {
@@ -2599,6 +2711,16 @@ where
     }
 }
 
+impl<T, A: Allocator> HeapSize for Vec<T, A>
+where
+    T: HeapSize,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.buf.allocation_size() + self.as_slice().heap_size()
+    }
+}
+
 #[cfg(test)]
 impl<T, A: Allocator + Clone> Clone for Vec<T, A>
 where
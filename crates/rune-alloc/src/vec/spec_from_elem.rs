@@ -23,7 +23,7 @@ where
     default_fn! {
         fn from_elem<A: Allocator>(elem: Self, n: usize, alloc: A) -> Result<Vec<Self, A>, Error> {
             let mut v = Vec::try_with_capacity_in(n, alloc)?;
-            v.try_extend_with(n, elem)?;
+            v.try_extend_with_clones(n, elem)?;
             Ok(v)
         }
     }
@@ -44,7 +44,7 @@ where
         }
 
         let mut v = Vec::try_with_capacity_in(n, alloc)?;
-        v.try_extend_with(n, elem)?;
+        v.try_extend_with_clones(n, elem)?;
         Ok(v)
     }
 }
@@ -24,6 +24,7 @@ use core::mem;
 use crate::alloc::{Allocator, Global, SizedTypeProperties};
 use crate::clone::TryClone;
 use crate::error::Error;
+use crate::heap_size::HeapSize;
 use crate::iter::{TryExtend, TryFromIteratorIn};
 use crate::raw_vec::RawVec;
 use crate::slice::range as slice_range;
@@ -112,6 +113,16 @@ impl<T: TryClone, A: Allocator + Clone> TryClone for VecDeque<T, A> {
     }
 }
 
+impl<T, A: Allocator> HeapSize for VecDeque<T, A>
+where
+    T: HeapSize,
+{
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.buf.allocation_size() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
 #[cfg(rune_nightly)]
 unsafe impl<#[may_dangle] T, A: Allocator> Drop for VecDeque<T, A> {
     fn drop(&mut self) {
@@ -550,6 +561,57 @@ impl<T, A: Allocator> VecDeque<T, A> {
         })
     }
 
+    /// Construct a deque from a [`Vec`], reusing its allocation.
+    ///
+    /// The deque starts at the beginning of the buffer of the vector, so no
+    /// elements are moved and no memory is allocated. Since the deque places
+    /// no requirements on its capacity, this currently never fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{Vec, VecDeque};
+    ///
+    /// let vec = rune::alloc::try_vec![1, 2, 3];
+    /// let ptr = vec.as_ptr();
+    ///
+    /// let deque = VecDeque::try_from_vec(vec)?;
+    /// assert_eq!(deque, [1, 2, 3]);
+    /// assert_eq!(deque.as_slices().0.as_ptr(), ptr);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_vec(vec: Vec<T, A>) -> Result<Self, Error> {
+        Ok(Self::from(vec))
+    }
+
+    /// Convert the deque into a [`Vec`], reusing its allocation.
+    ///
+    /// The elements are rotated in place so that they start at the beginning
+    /// of the buffer, after which the buffer is handed over to the vector. This
+    /// never allocates, and no elements are moved if the deque already starts
+    /// at the beginning of its buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque = VecDeque::try_with_capacity(4)?;
+    /// deque.try_push_back(2)?;
+    /// deque.try_push_back(3)?;
+    /// deque.try_push_front(1)?;
+    ///
+    /// let ptr = deque.as_slices().1.as_ptr();
+    /// let vec = deque.into_vec();
+    ///
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// assert_eq!(vec.as_ptr(), ptr);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn into_vec(self) -> Vec<T, A> {
+        Vec::from(self)
+    }
+
     /// Provides a reference to the element at the given index.
     ///
     /// Element at index 0 is the front of the queue.
@@ -887,7 +949,7 @@ impl<T, A: Allocator> VecDeque<T, A> {
             self.head = new_head;
         }
 
-        self.buf.try_shrink_to_fit(target_cap)?;
+        self.buf.try_shrink(target_cap)?;
 
         debug_assert!(self.head < self.capacity() || self.capacity() == 0);
         debug_assert!(self.len <= self.capacity());
@@ -1649,11 +1711,15 @@ impl<T, A: Allocator> VecDeque<T, A> {
     /// Inserts an element at `index` within the deque, shifting all elements
     /// with indices greater than or equal to `index` towards the back.
     ///
+    /// Whichever end is closer to the insertion point will be moved to make
+    /// room, so this is *O*(min(`index`, `len - index`)).
+    ///
     /// Element at index 0 is the front of the queue.
     ///
     /// # Panics
     ///
-    /// Panics if `index` is greater than deque's length
+    /// Panics if `index` is greater than deque's length, like
+    /// [`Vec::try_insert`].
     ///
     /// # Examples
     ///
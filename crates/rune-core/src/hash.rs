@@ -24,6 +24,7 @@ const ASSOCIATED_FUNCTION_HASH: u64 = 0x5ea77ffbcdf5f302;
 const OBJECT_KEYS: u64 = 0x4473d7017aef7645;
 const IDENT: u64 = 0x1a095090689d4647;
 const INDEX: u64 = 0xe1b2378d7a937035;
+const FIELD_DEFAULT: u64 = 0x8f3a61c52b07d94e;
 
 // Salt for type parameters.
 const TYPE_PARAMETERS: u64 = 0x9d30e58b77e4599;
@@ -108,6 +109,17 @@ impl Hash {
         Self::associated_function(Hash(type_hash.0 ^ protocol.0), name)
     }
 
+    /// Construct a hash corresponding to the default value of a field.
+    ///
+    /// This is distinct from any associated function of the same name.
+    #[inline]
+    pub fn field_default<N>(type_hash: Hash, name: N) -> Self
+    where
+        N: IntoHash,
+    {
+        Self::associated_function(Hash(type_hash.0 ^ FIELD_DEFAULT), name)
+    }
+
     /// Construct an index function.
     #[inline]
     pub fn index_function(protocol: Protocol, type_hash: Hash, index: Hash) -> Self {
@@ -28,29 +28,118 @@
 //!     println("Message after 10 seconds!");
 //! }
 //! ```
+//!
+//! ## Deterministic clocks
+//!
+//! Embeddings which need reproducible output can construct the module with
+//! [`module_with_clock`] and provide their own [`Clock`], which is then used
+//! by `Instant::now()` and `Instant::elapsed()`.
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! use rune_modules::time::Clock;
+//!
+//! struct Frozen;
+//!
+//! impl Clock for Frozen {
+//!     fn now(&self) -> Duration {
+//!         Duration::from_secs(42)
+//!     }
+//! }
+//!
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::time::module_with_clock(Arc::new(Frozen))?)?;
+//! # Ok::<_, rune::support::Error>(())
+//! ```
 
+use core::cmp::Ordering;
+
+use std::sync::Arc;
+
+use rune::alloc::fmt::TryWrite;
+use rune::runtime::{Formatter, VmError, VmResult};
 use rune::{Any, ContextError, Module};
 
-/// Construct the `time` module.
+/// A source of monotonic time used by the `time` module.
+///
+/// The returned duration is measured from an arbitrary but fixed origin
+/// chosen by the clock, and must never decrease between calls.
+pub trait Clock: Send + Sync {
+    /// Get the time which has passed since the origin of the clock.
+    fn now(&self) -> std::time::Duration;
+}
+
+/// A [`Clock`] backed by the monotonic system clock.
+struct SystemClock {
+    origin: std::time::Instant,
+}
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> std::time::Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// Construct the `time` module using the system clock.
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    module_with_clock(Arc::new(SystemClock {
+        origin: std::time::Instant::now(),
+    }))
+}
+
+/// Construct the `time` module using the given [`Clock`] as the source of
+/// `Instant::now()`.
+pub fn module_with_clock(clock: Arc<dyn Clock>) -> Result<Module, ContextError> {
     let mut module = Module::with_crate("time")?;
+
     module.ty::<Duration>()?;
     module.function_meta(Duration::from_secs__meta)?;
+    module.function_meta(Duration::from_millis__meta)?;
+    module.function_meta(Duration::from_micros__meta)?;
+    module.function_meta(Duration::as_secs_f64__meta)?;
+    module.function_meta(Duration::add__meta)?;
+    module.function_meta(Duration::sub__meta)?;
+    module.function_meta(Duration::partial_eq__meta)?;
+    module.function_meta(Duration::eq__meta)?;
+    module.function_meta(Duration::partial_cmp__meta)?;
+    module.function_meta(Duration::cmp__meta)?;
+    module.function_meta(Duration::string_display__meta)?;
+    module.function_meta(Duration::string_debug__meta)?;
+
+    module.ty::<Instant>()?;
+    module
+        .function("now", move || Instant::now(&clock))
+        .build_associated::<Instant>()?
+        .docs(["Get the current instant according to the clock of the module."])?;
+    module.function_meta(Instant::elapsed__meta)?;
+    module.function_meta(Instant::duration_since__meta)?;
+    module.function_meta(Instant::add__meta)?;
+    module.function_meta(Instant::sub__meta)?;
+    module.function_meta(Instant::partial_eq__meta)?;
+    module.function_meta(Instant::eq__meta)?;
+    module.function_meta(Instant::partial_cmp__meta)?;
+    module.function_meta(Instant::cmp__meta)?;
+    module.function_meta(Instant::string_debug__meta)?;
+
     module.function_meta(sleep)?;
     Ok(module)
 }
 
-#[derive(Debug, Clone, Copy, Any)]
+/// A span of time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Any)]
 #[rune(item = ::time)]
-struct Duration {
-    inner: tokio::time::Duration,
+pub struct Duration {
+    inner: std::time::Duration,
 }
 
 impl Duration {
     /// Construct a duration from the given number of seconds.
-    /// 
+    ///
     /// # Examples
-    /// 
+    ///
     /// ```rune
     /// use time::Duration;
     ///
@@ -59,15 +148,249 @@ impl Duration {
     #[rune::function(keep, path = Self::from_secs)]
     fn from_secs(secs: u64) -> Self {
         Self {
-            inner: tokio::time::Duration::from_secs(secs),
+            inner: std::time::Duration::from_secs(secs),
+        }
+    }
+
+    /// Construct a duration from the given number of milliseconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let d = Duration::from_millis(1500);
+    /// assert_eq!(d.as_secs_f64(), 1.5);
+    /// ```
+    #[rune::function(keep, path = Self::from_millis)]
+    fn from_millis(millis: u64) -> Self {
+        Self {
+            inner: std::time::Duration::from_millis(millis),
+        }
+    }
+
+    /// Construct a duration from the given number of microseconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let d = Duration::from_micros(250);
+    /// assert_eq!(d.as_secs_f64(), 0.00025);
+    /// ```
+    #[rune::function(keep, path = Self::from_micros)]
+    fn from_micros(micros: u64) -> Self {
+        Self {
+            inner: std::time::Duration::from_micros(micros),
+        }
+    }
+
+    /// Get the number of seconds in the duration, including fractional
+    /// seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let d = Duration::from_millis(2500);
+    /// assert_eq!(d.as_secs_f64(), 2.5);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn as_secs_f64(&self) -> f64 {
+        self.inner.as_secs_f64()
+    }
+
+    /// Add two durations, raising an error on overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let d = Duration::from_secs(1) + Duration::from_millis(500);
+    /// assert_eq!(d, Duration::from_millis(1500));
+    /// ```
+    #[rune::function(keep, instance, protocol = ADD)]
+    fn add(&self, rhs: &Duration) -> VmResult<Duration> {
+        match self.inner.checked_add(rhs.inner) {
+            Some(inner) => VmResult::Ok(Self { inner }),
+            None => VmResult::Err(VmError::overflow()),
+        }
+    }
+
+    /// Subtract one duration from another, raising an error if the result
+    /// would be negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let d = Duration::from_secs(2) - Duration::from_millis(500);
+    /// assert_eq!(d, Duration::from_millis(1500));
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB)]
+    fn sub(&self, rhs: &Duration) -> VmResult<Duration> {
+        match self.inner.checked_sub(rhs.inner) {
+            Some(inner) => VmResult::Ok(Self { inner }),
+            None => VmResult::Err(VmError::underflow()),
+        }
+    }
+
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, rhs: &Duration) -> bool {
+        self == rhs
+    }
+
+    #[rune::function(keep, instance, protocol = EQ)]
+    fn eq(&self, rhs: &Duration) -> bool {
+        self == rhs
+    }
+
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    fn partial_cmp(&self, rhs: &Duration) -> Option<Ordering> {
+        PartialOrd::partial_cmp(self, rhs)
+    }
+
+    #[rune::function(keep, instance, protocol = CMP)]
+    fn cmp(&self, rhs: &Duration) -> Ordering {
+        Ord::cmp(self, rhs)
+    }
+
+    /// Format the duration in a human readable form.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// assert_eq!(format!("{}", Duration::from_millis(1500)), "1.5s");
+    /// ```
+    #[rune::function(keep, instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> VmResult<()> {
+        rune::vm_write!(f, "{:?}", self.inner);
+        VmResult::Ok(())
+    }
+
+    #[rune::function(keep, instance, protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> VmResult<()> {
+        rune::vm_write!(f, "{:?}", self);
+        VmResult::Ok(())
+    }
+}
+
+/// A measurement of the clock the `time` module was constructed with.
+#[derive(Clone, Any)]
+#[rune(item = ::time)]
+pub struct Instant {
+    at: std::time::Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl Instant {
+    fn now(clock: &Arc<dyn Clock>) -> Self {
+        Self {
+            at: clock.now(),
+            clock: clock.clone(),
+        }
+    }
+
+    /// Get the time which has passed since this instant.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Duration, Instant};
+    ///
+    /// let start = Instant::now();
+    /// assert!(start.elapsed() >= Duration::from_secs(0));
+    /// ```
+    #[rune::function(keep, instance)]
+    fn elapsed(&self) -> Duration {
+        Duration {
+            inner: self.clock.now().saturating_sub(self.at),
+        }
+    }
+
+    /// Get the time which has passed from `earlier` to this instant, raising
+    /// an error if `earlier` is later than this instant.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Duration, Instant};
+    ///
+    /// let start = Instant::now();
+    /// let later = start + Duration::from_secs(1);
+    /// assert_eq!(later.duration_since(start), Duration::from_secs(1));
+    /// ```
+    #[rune::function(keep, instance)]
+    fn duration_since(&self, earlier: &Instant) -> VmResult<Duration> {
+        match self.at.checked_sub(earlier.at) {
+            Some(inner) => VmResult::Ok(Duration { inner }),
+            None => VmResult::Err(VmError::underflow()),
+        }
+    }
+
+    /// Add a duration to an instant, raising an error on overflow.
+    #[rune::function(keep, instance, protocol = ADD)]
+    fn add(&self, rhs: &Duration) -> VmResult<Instant> {
+        match self.at.checked_add(rhs.inner) {
+            Some(at) => VmResult::Ok(self.with_at(at)),
+            None => VmResult::Err(VmError::overflow()),
+        }
+    }
+
+    /// Subtract a duration from an instant, raising an error if the result
+    /// would be before the origin of the clock.
+    #[rune::function(keep, instance, protocol = SUB)]
+    fn sub(&self, rhs: &Duration) -> VmResult<Instant> {
+        match self.at.checked_sub(rhs.inner) {
+            Some(at) => VmResult::Ok(self.with_at(at)),
+            None => VmResult::Err(VmError::underflow()),
+        }
+    }
+
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, rhs: &Instant) -> bool {
+        self.at == rhs.at
+    }
+
+    #[rune::function(keep, instance, protocol = EQ)]
+    fn eq(&self, rhs: &Instant) -> bool {
+        self.at == rhs.at
+    }
+
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    fn partial_cmp(&self, rhs: &Instant) -> Option<Ordering> {
+        self.at.partial_cmp(&rhs.at)
+    }
+
+    #[rune::function(keep, instance, protocol = CMP)]
+    fn cmp(&self, rhs: &Instant) -> Ordering {
+        self.at.cmp(&rhs.at)
+    }
+
+    #[rune::function(keep, instance, protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> VmResult<()> {
+        rune::vm_write!(f, "Instant({:?})", self.at);
+        VmResult::Ok(())
+    }
+
+    fn with_at(&self, at: std::time::Duration) -> Self {
+        Self {
+            at,
+            clock: self.clock.clone(),
         }
     }
 }
 
 /// Sleep for the given [`Duration`].
-/// 
+///
 /// # Examples
-/// 
+///
 /// ```rune,no_run
 /// use time::Duration;
 ///
@@ -79,3 +402,123 @@ impl Duration {
 async fn sleep(duration: Duration) {
     tokio::time::sleep(duration.inner).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    use rune::runtime::Value;
+    use rune::{Context, Diagnostics, Source, Sources, Vm};
+
+    use super::{module_with_clock, Clock};
+
+    /// A clock which advances by one millisecond every time it's read.
+    struct StepClock {
+        millis: AtomicU64,
+    }
+
+    impl Clock for StepClock {
+        fn now(&self) -> std::time::Duration {
+            std::time::Duration::from_millis(self.millis.fetch_add(1, Ordering::SeqCst))
+        }
+    }
+
+    fn run(source: &str) -> rune::support::Result<Value> {
+        let clock = Arc::new(StepClock {
+            millis: AtomicU64::new(100),
+        });
+
+        let mut context = Context::with_default_modules()?;
+        context.install(module_with_clock(clock)?)?;
+        let runtime = Arc::new(context.runtime()?);
+
+        let mut sources = Sources::new();
+        sources.insert(Source::memory(source)?)?;
+
+        let mut diagnostics = Diagnostics::new();
+
+        let unit = rune::prepare(&mut sources)
+            .with_context(&context)
+            .with_diagnostics(&mut diagnostics)
+            .build()?;
+
+        let mut vm = Vm::new(runtime, Arc::new(unit));
+        Ok(vm.call(["main"], ())?)
+    }
+
+    #[test]
+    fn test_arithmetic_and_comparisons() {
+        let value = run(r#"
+            use time::Duration;
+
+            pub fn main() {
+                let a = Duration::from_secs(1);
+                let b = Duration::from_millis(250);
+                assert_eq!(a + b, Duration::from_micros(1_250_000));
+                assert_eq!(a - b, Duration::from_millis(750));
+                assert!(b < a);
+                assert!(a >= a);
+                assert!(a != b);
+                (a + b).as_secs_f64()
+            }
+        "#)
+        .unwrap();
+
+        assert_eq!(rune::from_value::<f64>(value).unwrap(), 1.25);
+    }
+
+    #[test]
+    fn test_underflow() {
+        let error = run(r#"
+            use time::Duration;
+
+            pub fn main() {
+                Duration::from_millis(250) - Duration::from_secs(1)
+            }
+        "#)
+        .unwrap_err();
+
+        assert!(error.to_string().contains("Numerical underflow"));
+    }
+
+    #[test]
+    fn test_display() {
+        let value = run(r#"
+            use time::Duration;
+
+            pub fn main() {
+                [
+                    format!("{}", Duration::from_secs(2)),
+                    format!("{}", Duration::from_millis(1500)),
+                    format!("{}", Duration::from_micros(42)),
+                ]
+            }
+        "#)
+        .unwrap();
+
+        let value = rune::from_value::<Vec<String>>(value).unwrap();
+        assert_eq!(value, ["2s", "1.5s", "42µs"]);
+    }
+
+    #[test]
+    fn test_mock_clock() {
+        let value = run(r#"
+            use time::{Duration, Instant};
+
+            pub fn main() {
+                let a = Instant::now();
+                let b = Instant::now();
+                assert!(a < b);
+                assert_eq!(b.duration_since(a), Duration::from_millis(1));
+                assert_eq!(a + Duration::from_millis(1), b);
+                assert_eq!(b - Duration::from_millis(1), a);
+                // Reading the clock advances it, so this observes 102ms.
+                a.elapsed().as_secs_f64()
+            }
+        "#)
+        .unwrap();
+
+        assert_eq!(rune::from_value::<f64>(value).unwrap(), 0.002);
+    }
+}
@@ -10,6 +10,7 @@ fn ast_parse() {
         "#[repr(Rune)] enum Foo { Bar(a), Baz(b), #[default_value = \"zombie\"] Empty() }",
     );
     rt::<ast::ItemEnum>("pub enum Color { Blue, Red, Green }");
+    rt::<ast::ItemEnum>("enum Flag { A = 1, B, D = 1 << 3 }");
 
     rt::<ast::Fields>("( a, b, c )");
     rt::<ast::Fields>("{ a, b, c }");
@@ -53,4 +54,7 @@ pub struct ItemVariant {
     /// The body of the variant.
     #[rune(iter)]
     pub body: ast::Fields,
+    /// The explicit discriminant of the variant.
+    #[rune(iter)]
+    pub discriminant: Option<(T![=], ast::Expr)>,
 }
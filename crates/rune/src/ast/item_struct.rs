@@ -9,6 +9,7 @@ fn ast_parse() {
     rt::<ast::ItemStruct>("struct Foo { a, b, c }");
     rt::<ast::ItemStruct>("struct Foo { #[default_value = 1] a, b, c }");
     rt::<ast::ItemStruct>("#[alpha] struct Foo ( #[default_value = \"x\" ] a, b, c )");
+    rt::<ast::ItemStruct>("struct Foo { a = 1, b, c = \"x\" }");
 
     rt::<ast::Fields>("");
 
@@ -24,6 +25,7 @@ fn ast_parse() {
 
     rt::<ast::Field>("a");
     rt::<ast::Field>("#[x] a");
+    rt::<ast::Field>("a = 1 + 2");
 }
 
 /// A struct item.
@@ -70,4 +72,7 @@ pub struct Field {
     pub visibility: ast::Visibility,
     /// Name of the field.
     pub name: ast::Ident,
+    /// The default value of the field.
+    #[rune(iter)]
+    pub default: Option<(T![=], ast::Expr)>,
 }
@@ -7,6 +7,7 @@ fn ast_parse() {
 
     rt::<ast::LitByteStr>("b\"hello world\"");
     rt::<ast::LitByteStr>("b\"hello\\nworld\"");
+    rt::<ast::LitByteStr>("b\"\\x00\\x7f\\xff\"");
 }
 
 /// A string literal.
@@ -51,7 +52,14 @@ impl LitByteStr {
                         }
                     }
                 }
-                c => Some(c as u8),
+                c if c.is_ascii() => Some(c as u8),
+                c => {
+                    let end = start + c.len_utf8();
+                    return Err(compile::Error::new(
+                        Span::new(start, end),
+                        ErrorKind::BadByteStringChar,
+                    ));
+                }
             })?;
         }
 
@@ -105,6 +113,16 @@ impl<'a> Resolve<'a> for LitByteStr {
         Ok(if text.escaped {
             Cow::Owned(self.parse_escaped(span, string)?)
         } else {
+            if let Some((n, c)) = string.char_indices().find(|(_, c)| !c.is_ascii()) {
+                let start = span.start.into_usize() + n;
+                let end = start + c.len_utf8();
+
+                return Err(compile::Error::new(
+                    Span::new(start, end),
+                    ErrorKind::BadByteStringChar,
+                ));
+            }
+
             Cow::Borrowed(string.as_bytes())
         })
     }
@@ -603,6 +603,14 @@ struct SharedFlags {
     /// macros[=<true/false>] - Enable or disable macros (experimental).
     ///
     /// bytecode[=<true/false>] - Enable or disable bytecode caching (experimental).
+    ///
+    /// target-arch=<x86_64/aarch64/wasm32/unknown> - Set the architecture being compiled for.
+    ///
+    /// match-string-table=<n> - Dispatch matches with at least `n` string literal arms through a table, `0` disables it.
+    ///
+    /// shadowing-limit=<n> - Warn when a variable shadows at least `n` other variables with the same name, `0` disables it.
+    ///
+    /// recursion-limit=<n> - Fail to compile expressions which are nested more than `n` levels deep, `0` disables it.
     #[arg(name = "option", short = 'O', number_of_values = 1)]
     compiler_options: Vec<String>,
 
@@ -48,7 +48,7 @@ pub(crate) use self::unit_builder::UnitBuilder;
 pub(crate) mod v1;
 
 mod options;
-pub use self::options::{Options, ParseOptionError};
+pub use self::options::{Options, ParseOptionError, TargetArch};
 
 mod location;
 pub(crate) use self::location::DynLocation;
@@ -75,6 +75,8 @@ pub use self::with_span::{HasSpan, WithSpan};
 
 mod compile;
 pub(crate) use self::compile::compile;
+#[cfg(test)]
+pub(crate) use self::compile::compile_query;
 
 /// Helper alias for compile results.
 pub type Result<T, E = Error> = ::core::result::Result<T, E>;
@@ -1,4 +1,12 @@
 //! Helpers for building assembly.
+//!
+//! Jumps never refer to raw instruction offsets. A [`Label`] is allocated
+//! through [`Assembly::new_label`], bound to the offset of the next
+//! instruction with [`Assembly::label`], and referenced by jump instructions
+//! such as [`Assembly::jump`]. Labels can be referenced before they are bound,
+//! and are resolved into concrete offsets once the assembly is translated into
+//! the unit. The set of bound labels is available through
+//! [`Assembly::labels`], which identifies every jump target.
 
 use core::fmt;
 
@@ -16,11 +24,13 @@ use crate::{Hash, SourceId};
 pub(crate) enum AssemblyInst {
     Jump { label: Label },
     JumpIf { label: Label },
+    JumpIfNot { label: Label },
     JumpIfOrPop { label: Label },
     JumpIfNotOrPop { label: Label },
     JumpIfBranch { branch: i64, label: Label },
     PopAndJumpIfNot { count: usize, label: Label },
     IterNext { offset: usize, label: Label },
+    JumpStringTable { entries: Vec<(String, Label)> },
     Raw { raw: Inst },
 }
 
@@ -54,6 +64,14 @@ impl Assembly {
         }
     }
 
+    /// Construct an empty assembly which continues the labels of this one.
+    ///
+    /// This is used to assemble code which is checked for errors but then
+    /// discarded.
+    pub(crate) fn scratch(&self) -> Self {
+        Self::new(self.location, self.label_count)
+    }
+
     /// Construct and return a new label.
     pub(crate) fn new_label(&mut self, name: &'static str) -> Label {
         let label = Label::new(name, self.label_count);
@@ -104,6 +122,19 @@ impl Assembly {
         Ok(())
     }
 
+    /// Add a conditional jump to the given label if the top of the stack is
+    /// `false`.
+    pub(crate) fn jump_if_not(&mut self, label: &Label, span: &dyn Spanned) -> compile::Result<()> {
+        self.inner_push(
+            AssemblyInst::JumpIfNot {
+                label: label.try_clone()?,
+            },
+            span,
+        )?;
+
+        Ok(())
+    }
+
     /// Add a conditional jump to the given label. Only pops the top of the
     /// stack if the jump is not executed.
     pub(crate) fn jump_if_or_pop(
@@ -192,6 +223,17 @@ impl Assembly {
         Ok(())
     }
 
+    /// Add a jump through a table of strings, where each string is associated
+    /// with the label to jump to.
+    pub(crate) fn jump_string_table(
+        &mut self,
+        entries: Vec<(String, Label)>,
+        span: &dyn Spanned,
+    ) -> compile::Result<()> {
+        self.inner_push(AssemblyInst::JumpStringTable { entries }, span)?;
+        Ok(())
+    }
+
     /// Push a raw instruction.
     pub(crate) fn push(&mut self, raw: Inst, span: &dyn Spanned) -> compile::Result<()> {
         if let Inst::Call { hash, .. } = raw {
@@ -54,6 +54,15 @@ pub(crate) fn compile(
         &mut inner,
     );
 
+    compile_query(q, options, unit_storage)
+}
+
+/// Index and build every source available to the given query engine.
+pub(crate) fn compile_query(
+    q: Query<'_, '_>,
+    options: &Options,
+    unit_storage: &mut dyn UnitEncoder,
+) -> alloc::Result<()> {
     // The worker queue.
     let mut worker = Worker::new(q);
 
@@ -143,6 +152,7 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
             contexts: try_vec![span.span()],
             loops: self::v1::Loops::new(),
             options: self.options,
+            depth: 0,
         })
     }
 
@@ -17,8 +17,8 @@ use crate::module::{
     TypeSpecification,
 };
 use crate::runtime::{
-    AttributeMacroHandler, ConstValue, FunctionHandler, MacroHandler, Protocol, RuntimeContext,
-    StaticType, TypeCheck, TypeInfo, VariantRtti,
+    AttributeMacroHandler, ConstValue, FunctionHandler, FunctionSignature, MacroHandler, Protocol,
+    RuntimeContext, StaticType, TypeCheck, TypeInfo, VariantRtti,
 };
 use crate::Hash;
 
@@ -194,9 +194,34 @@ impl Context {
     /// # Ok::<_, rune::support::Error>(())
     /// ```
     pub fn runtime(&self) -> alloc::Result<RuntimeContext> {
+        let mut signatures = hash::Map::default();
+
+        for meta in &self.meta {
+            let meta::Kind::Function {
+                associated,
+                signature,
+                ..
+            } = &meta.kind
+            else {
+                continue;
+            };
+
+            if self.functions.contains_key(&meta.hash) {
+                signatures.try_insert(
+                    meta.hash,
+                    FunctionSignature {
+                        item: meta.item.try_clone()?,
+                        args: signature.args,
+                        is_method: associated.is_some(),
+                    },
+                )?;
+            }
+        }
+
         Ok(RuntimeContext::new(
             self.functions.try_clone()?,
             self.constants.try_clone()?,
+            signatures,
         ))
     }
 
@@ -448,7 +473,6 @@ impl Context {
                             let signature = meta::Signature {
                                 #[cfg(feature = "doc")]
                                 is_async: false,
-                                #[cfg(feature = "doc")]
                                 args: Some(match fields {
                                     Fields::Named(names) => names.len(),
                                     Fields::Unnamed(args) => *args,
@@ -476,7 +500,10 @@ impl Context {
                                     .map(|(position, name)| {
                                         Ok((
                                             Box::<str>::try_from(name)?,
-                                            meta::FieldMeta { position },
+                                            meta::FieldMeta {
+                                                position,
+                                                default: None,
+                                            },
                                         ))
                                     })
                                     .try_collect::<alloc::Result<_>>()??,
@@ -505,6 +532,7 @@ impl Context {
                                 enum_hash: ty.hash,
                                 hash,
                                 item: item.try_clone()?,
+                                discriminant: None,
                             })),
                             type_parameters: Hash::EMPTY,
                         })?;
@@ -513,7 +541,6 @@ impl Context {
                             let signature = meta::Signature {
                                 #[cfg(feature = "doc")]
                                 is_async: false,
-                                #[cfg(feature = "doc")]
                                 args: Some(match fields {
                                     Fields::Named(names) => names.len(),
                                     Fields::Unnamed(args) => *args,
@@ -547,7 +574,10 @@ impl Context {
                                                 .map(|(position, name)| {
                                                     Ok((
                                                         Box::<str>::try_from(name)?,
-                                                        meta::FieldMeta { position },
+                                                        meta::FieldMeta {
+                                                            position,
+                                                            default: None,
+                                                        },
                                                     ))
                                                 })
                                                 .try_collect::<alloc::Result<_>>()??,
@@ -557,6 +587,7 @@ impl Context {
                                     Fields::Empty => meta::Fields::Empty,
                                 },
                                 constructor,
+                                discriminant: None,
                             },
                             #[cfg(feature = "doc")]
                             deprecated: variant.deprecated.try_clone()?,
@@ -627,7 +658,7 @@ impl Context {
         let kind = match &module_item.kind {
             rune::module::ModuleItemKind::Constant(value) => {
                 self.constants.try_insert(hash, value.try_clone()?)?;
-                meta::Kind::Const
+                meta::Kind::Const(value.try_clone()?)
             }
             rune::module::ModuleItemKind::Function(f) => {
                 self.constants.try_insert(
@@ -638,7 +669,6 @@ impl Context {
                 let signature = meta::Signature {
                     #[cfg(feature = "doc")]
                     is_async: f.is_async,
-                    #[cfg(feature = "doc")]
                     args: f.args,
                     #[cfg(feature = "doc")]
                     return_type: f.return_type.as_ref().map(|f| f.hash),
@@ -723,7 +753,6 @@ impl Context {
                         Some(meta::Signature {
                             #[cfg(feature = "doc")]
                             is_async: false,
-                            #[cfg(feature = "doc")]
                             args: Some(match fields {
                                 Fields::Named(names) => names.len(),
                                 Fields::Unnamed(args) => *args,
@@ -753,7 +782,10 @@ impl Context {
                                         .map(|(position, name)| {
                                             Ok((
                                                 Box::<str>::try_from(name)?,
-                                                meta::FieldMeta { position },
+                                                meta::FieldMeta {
+                                                    position,
+                                                    default: None,
+                                                },
                                             ))
                                         })
                                         .try_collect::<alloc::Result<_>>()??,
@@ -762,6 +794,7 @@ impl Context {
                                 Fields::Empty => meta::Fields::Empty,
                             },
                             constructor,
+                            discriminant: None,
                         },
                         #[cfg(feature = "doc")]
                         deprecated: variant.deprecated.try_clone()?,
@@ -829,13 +862,12 @@ impl Context {
                 }
 
                 self.constants.try_insert(hash, value.try_clone()?)?;
-                meta::Kind::Const
+                meta::Kind::Const(value.try_clone()?)
             }
             ModuleAssociatedKind::Function(f) => {
                 let signature = meta::Signature {
                     #[cfg(feature = "doc")]
                     is_async: f.is_async,
-                    #[cfg(feature = "doc")]
                     args: f.args,
                     #[cfg(feature = "doc")]
                     return_type: f.return_type.as_ref().map(|f| f.hash),
@@ -242,11 +242,23 @@ pub(crate) enum ErrorKind {
     ModNotFound {
         path: PathBuf,
     },
+    #[cfg(feature = "std")]
+    AmbiguousModFile {
+        first: PathBuf,
+        second: PathBuf,
+    },
     ModAlreadyLoaded {
         item: ItemBuf,
         #[cfg(feature = "emit")]
         existing: (SourceId, Span),
     },
+    ModSourceAlreadyLoaded {
+        item: ItemBuf,
+    },
+    MaxModSourceDepth {
+        item: ItemBuf,
+        max: usize,
+    },
     MissingMacro {
         item: ItemBuf,
     },
@@ -285,7 +297,7 @@ pub(crate) enum ErrorKind {
         meta: MetaInfo,
     },
     LitObjectMissingField {
-        field: Box<str>,
+        fields: Box<[Box<str>]>,
         item: ItemBuf,
     },
     LitObjectNotField {
@@ -299,6 +311,7 @@ pub(crate) enum ErrorKind {
     UnsupportedArgumentCount {
         expected: usize,
         actual: usize,
+        signature: Option<DebugSignature>,
     },
     UnsupportedPatternExpr,
     UnsupportedBinding,
@@ -308,6 +321,11 @@ pub(crate) enum ErrorKind {
         #[cfg(feature = "emit")]
         object: Span,
     },
+    DuplicateDiscriminant {
+        discriminant: i64,
+        #[cfg(feature = "emit")]
+        existing: Span,
+    },
     InstanceFunctionOutsideImpl,
     UnsupportedTupleIndex {
         number: ast::Number,
@@ -408,6 +426,9 @@ pub(crate) enum ErrorKind {
         depth: usize,
         max: usize,
     },
+    RecursionLimit {
+        limit: usize,
+    },
     YieldInConst,
     AwaitInConst,
     AwaitOutsideAsync,
@@ -451,6 +472,7 @@ pub(crate) enum ErrorKind {
     },
     BadCharLiteral,
     BadByteLiteral,
+    BadByteStringChar,
     BadNumberLiteral,
     AmbiguousItem {
         item: ItemBuf,
@@ -483,8 +505,7 @@ pub(crate) enum ErrorKind {
     MissingMod {
         item: ItemBuf,
     },
-    ImportCycle {
-        #[cfg(feature = "emit")]
+    Cycle {
         path: Vec<ImportStep>,
     },
     ImportRecursionLimit {
@@ -608,9 +629,30 @@ impl fmt::Display for ErrorKind {
                     path = path.display()
                 )?;
             }
+            #[cfg(feature = "std")]
+            ErrorKind::AmbiguousModFile { first, second } => {
+                write!(
+                    f,
+                    "Module file is ambiguous, both `{first}` and `{second}` exist",
+                    first = first.display(),
+                    second = second.display()
+                )?;
+            }
             ErrorKind::ModAlreadyLoaded { item, .. } => {
                 write!(f, "Module `{item}` has already been loaded")?;
             }
+            ErrorKind::ModSourceAlreadyLoaded { item } => {
+                write!(
+                    f,
+                    "Source for module `{item}` has already been loaded, which would cause a cycle"
+                )?;
+            }
+            ErrorKind::MaxModSourceDepth { item, max } => {
+                write!(
+                    f,
+                    "Loading source for module `{item}` exceeds the limit of {max} nested module sources"
+                )?;
+            }
             ErrorKind::MissingMacro { item } => {
                 write!(f, "Missing macro `{item}`")?;
             }
@@ -663,8 +705,21 @@ impl fmt::Display for ErrorKind {
             ErrorKind::UnsupportedLitObject { meta } => {
                 write!(f, "Item `{meta}` is not an object")?;
             }
-            ErrorKind::LitObjectMissingField { field, item } => {
-                write!(f, "Missing field `{field}` in declaration of `{item}`",)?;
+            ErrorKind::LitObjectMissingField { fields, item } => {
+                let s = if fields.len() == 1 { "" } else { "s" };
+                write!(f, "Missing field{s} ")?;
+
+                let mut it = fields.iter().peekable();
+
+                while let Some(field) = it.next() {
+                    write!(f, "`{field}`")?;
+
+                    if it.peek().is_some() {
+                        write!(f, ", ")?;
+                    }
+                }
+
+                write!(f, " in declaration of `{item}`",)?;
             }
             ErrorKind::LitObjectNotField { field, item } => {
                 write!(f, "Field `{field}` is not a field in `{item}`",)?;
@@ -681,12 +736,26 @@ impl fmt::Display for ErrorKind {
             ErrorKind::UnsupportedSelectPattern => {
                 write!(f, "Unsupported select pattern")?;
             }
-            ErrorKind::UnsupportedArgumentCount { expected, actual } => {
+            ErrorKind::UnsupportedArgumentCount {
+                expected,
+                actual,
+                signature: None,
+            } => {
                 write!(
                     f,
                     "Wrong number of arguments, expected `{expected}` but got `{actual}`",
                 )?;
             }
+            ErrorKind::UnsupportedArgumentCount {
+                expected,
+                actual,
+                signature: Some(signature),
+            } => {
+                write!(
+                    f,
+                    "Wrong number of arguments to `{signature}`, expected `{expected}` but got `{actual}`",
+                )?;
+            }
             ErrorKind::UnsupportedPatternExpr => {
                 write!(f, "This kind of expression is not supported as a pattern")?;
             }
@@ -696,6 +765,9 @@ impl fmt::Display for ErrorKind {
             ErrorKind::DuplicateObjectKey { .. } => {
                 write!(f, "Duplicate key in literal object")?;
             }
+            ErrorKind::DuplicateDiscriminant { discriminant, .. } => {
+                write!(f, "Duplicate discriminant `{discriminant}` in enum")?;
+            }
             ErrorKind::InstanceFunctionOutsideImpl => {
                 write!(f, "Instance function declared outside of `impl` block")?;
             }
@@ -870,6 +942,12 @@ impl fmt::Display for ErrorKind {
                     "Reached macro recursion limit at {depth}, limit is {max}",
                 )?;
             }
+            ErrorKind::RecursionLimit { limit } => {
+                write!(
+                    f,
+                    "Expression is nested too deeply, recursion limit is {limit}",
+                )?;
+            }
             ErrorKind::YieldInConst => {
                 write!(f, "Expression `yield` inside of constant function")?;
             }
@@ -957,6 +1035,12 @@ impl fmt::Display for ErrorKind {
             ErrorKind::BadByteLiteral => {
                 write!(f, "Bad byte literal")?;
             }
+            ErrorKind::BadByteStringChar => {
+                write!(
+                    f,
+                    "Non-ASCII character in byte string, use an escape like `\\xff` instead"
+                )?;
+            }
             ErrorKind::BadNumberLiteral => {
                 write!(f, "Number literal not valid")?;
             }
@@ -988,8 +1072,18 @@ impl fmt::Display for ErrorKind {
             ErrorKind::MissingMod { item } => {
                 write!(f, "Missing query meta for module {item}")?;
             }
-            ErrorKind::ImportCycle { .. } => {
-                write!(f, "Cycle in import")?;
+            ErrorKind::Cycle { path } => {
+                write!(f, "Cycle detected: ")?;
+
+                let mut it = path.iter();
+
+                if let Some(step) = it.next() {
+                    write!(f, "`{}`", step.item)?;
+                }
+
+                for step in it {
+                    write!(f, " -> `{}`", step.item)?;
+                }
             }
             ErrorKind::ImportRecursionLimit { count, .. } => {
                 write!(f, "Import recursion limit reached ({count})", count = count)?;
@@ -1178,6 +1272,13 @@ pub(crate) enum IrErrorKind {
         /// The index that was missing.
         index: usize,
     },
+    /// An index in a constant expression is out of bounds.
+    IndexOutOfBounds {
+        /// The index that was used.
+        index: i64,
+        /// The length of the collection being indexed.
+        length: usize,
+    },
     /// Missing an object field.
     MissingField {
         /// The field that was missing.
@@ -1221,6 +1322,12 @@ impl fmt::Display for IrErrorKind {
             IrErrorKind::MissingIndex { index } => {
                 write!(f, "Missing index {index}",)?;
             }
+            IrErrorKind::IndexOutOfBounds { index, length } => {
+                write!(
+                    f,
+                    "Index {index} is out of bounds for a collection of length {length}",
+                )?;
+            }
             IrErrorKind::MissingField { field } => {
                 write!(f, "Missing field `{field}`",)?;
             }
@@ -1242,7 +1349,8 @@ impl fmt::Display for IrErrorKind {
     }
 }
 
-/// A single step in an import.
+/// A single step in an import, or in a cycle of items which depend on each
+/// other.
 ///
 /// This is used to indicate a step in an import chain in an error message.
 #[derive(Debug, TryClone)]
@@ -51,13 +51,10 @@ impl ast::Expr {
             compiler::expr(&hir, &mut cx)?
         };
 
-        let mut ir_interpreter = Interpreter {
-            budget: Budget::new(1_000_000),
-            scopes: Scopes::new()?,
-            module: cx.item_meta.module,
-            item: cx.item_meta.item,
-            q: cx.idx.q.borrow(),
-        };
+        let mut ir_interpreter = cx
+            .idx
+            .q
+            .const_interpreter(cx.item_meta.module, cx.item_meta.item)?;
 
         ir_interpreter.eval_value(&ir, Used::Used)
     }
@@ -165,6 +162,10 @@ decl_kind! {
         Object(IrObject),
         /// A call.
         Call(IrCall),
+        /// Indexing into a collection.
+        Index(IrIndex),
+        /// The length of a collection or string.
+        Len(IrLen),
     }
 }
 
@@ -466,6 +467,28 @@ pub(crate) struct IrCall {
     pub(crate) args: Vec<Ir>,
 }
 
+/// Index expression `<target>[<index>]`.
+#[derive(Debug, TryClone, Spanned)]
+pub(crate) struct IrIndex {
+    /// Span of the index expression.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The collection being indexed.
+    pub(crate) target: Box<Ir>,
+    /// The index.
+    pub(crate) index: Box<Ir>,
+}
+
+/// Length expression `<target>.len()`.
+#[derive(Debug, TryClone, Spanned)]
+pub(crate) struct IrLen {
+    /// Span of the length expression.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The collection or string to get the length of.
+    pub(crate) target: Box<Ir>,
+}
+
 /// Vector expression.
 #[derive(Debug, TryClone, Spanned)]
 pub(crate) struct IrVec {
@@ -8,7 +8,7 @@ use crate::compile::{self, ErrorKind, WithSpan};
 use crate::hir;
 use crate::query::Query;
 use crate::runtime::{Bytes, Value};
-use crate::SourceId;
+use crate::{Hash, SourceId};
 
 use rune_macros::instrument;
 
@@ -31,7 +31,8 @@ pub(crate) fn expr(hir: &hir::Expr<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result
         hir::ExprKind::Group(hir) => expr(hir, c)?,
         hir::ExprKind::Binary(hir) => expr_binary(span, c, hir)?,
         hir::ExprKind::Assign(hir) => expr_assign(span, c, hir)?,
-        hir::ExprKind::Call(hir) => ir::Ir::new(span, expr_call(span, c, hir)?),
+        hir::ExprKind::Call(hir) => expr_call(span, c, hir)?,
+        hir::ExprKind::Index(hir) => ir::Ir::new(span, expr_index(span, c, hir)?),
         hir::ExprKind::If(hir) => ir::Ir::new(span, expr_if(span, c, hir)?),
         hir::ExprKind::Loop(hir) => ir::Ir::new(span, expr_loop(span, c, hir)?),
         hir::ExprKind::Lit(hir) => lit(c, span, hir)?,
@@ -120,11 +121,14 @@ fn expr_assign(
 }
 
 #[instrument]
-fn expr_call(
-    span: Span,
-    c: &mut Ctxt<'_, '_>,
-    hir: &hir::ExprCall<'_>,
-) -> compile::Result<ir::IrCall> {
+fn expr_call(span: Span, c: &mut Ctxt<'_, '_>, hir: &hir::ExprCall<'_>) -> compile::Result<ir::Ir> {
+    if let hir::Call::Associated { target, hash } = hir.call {
+        if hash == Hash::ident("len") && hir.args.is_empty() {
+            let target = Box::try_new(expr(target, c)?)?;
+            return Ok(ir::Ir::new(span, ir::IrLen { span, target }));
+        }
+    }
+
     let mut args = Vec::try_with_capacity(hir.args.len())?;
 
     for e in hir.args {
@@ -132,7 +136,7 @@ fn expr_call(
     }
 
     if let hir::Call::ConstFn { id, .. } = hir.call {
-        return Ok(ir::IrCall { span, id, args });
+        return Ok(ir::Ir::new(span, ir::IrCall { span, id, args }));
     }
 
     Err(compile::Error::msg(
@@ -141,6 +145,19 @@ fn expr_call(
     ))
 }
 
+#[instrument]
+fn expr_index(
+    span: Span,
+    c: &mut Ctxt<'_, '_>,
+    hir: &hir::ExprIndex<'_>,
+) -> compile::Result<ir::IrIndex> {
+    Ok(ir::IrIndex {
+        span,
+        target: Box::try_new(expr(&hir.target, c)?)?,
+        index: Box::try_new(expr(&hir.index, c)?)?,
+    })
+}
+
 #[instrument]
 fn expr_binary(
     span: Span,
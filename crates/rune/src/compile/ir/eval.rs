@@ -5,7 +5,7 @@ use crate::alloc::prelude::*;
 use crate::alloc::{Box, String, Vec};
 use crate::ast::{Span, Spanned};
 use crate::compile::ir::{self};
-use crate::compile::{self, WithSpan};
+use crate::compile::{self, IrErrorKind, WithSpan};
 use crate::query::Used;
 use crate::runtime::{Object, OwnedTuple, Value, ValueKind};
 
@@ -217,6 +217,63 @@ fn eval_ir_decl(
     Ok(Value::empty().with_span(ir)?)
 }
 
+fn eval_ir_index(
+    ir: &ir::IrIndex,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<Value, EvalOutcome> {
+    interp.budget.take(ir)?;
+
+    let target = eval_ir(&ir.target, interp, used)?;
+    let index = eval_ir(&ir.index, interp, used)?;
+    let index = index.as_integer().with_span(&ir.index)?;
+
+    let target = target.borrow_kind_ref().with_span(ir)?;
+
+    let items = match &*target {
+        ValueKind::Vec(vec) => vec.as_slice(),
+        ValueKind::Tuple(tuple) => &tuple[..],
+        _ => return Err(EvalOutcome::not_const(ir)),
+    };
+
+    let Some(value) = usize::try_from(index).ok().and_then(|n| items.get(n)) else {
+        return Err(EvalOutcome::from(compile::Error::new(
+            ir,
+            IrErrorKind::IndexOutOfBounds {
+                index,
+                length: items.len(),
+            },
+        )));
+    };
+
+    Ok(value.try_clone()?)
+}
+
+fn eval_ir_len(
+    ir: &ir::IrLen,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<Value, EvalOutcome> {
+    interp.budget.take(ir)?;
+
+    let target = eval_ir(&ir.target, interp, used)?;
+
+    let len = match &*target.borrow_kind_ref().with_span(ir)? {
+        ValueKind::String(string) => string.len(),
+        ValueKind::Bytes(bytes) => bytes.len(),
+        ValueKind::Vec(vec) => vec.len(),
+        ValueKind::Tuple(tuple) => tuple.len(),
+        ValueKind::Object(object) => object.len(),
+        _ => return Err(EvalOutcome::not_const(ir)),
+    };
+
+    let len = i64::try_from(len)
+        .map_err(|_| "length out of bounds")
+        .with_span(ir)?;
+
+    Ok(Value::try_from(len).with_span(ir)?)
+}
+
 fn eval_ir_loop(
     ir: &ir::IrLoop,
     interp: &mut ir::Interpreter<'_, '_>,
@@ -415,5 +472,7 @@ pub(crate) fn eval_ir(
         ir::IrKind::Tuple(ir) => eval_ir_tuple(ir, interp, used),
         ir::IrKind::Object(ir) => eval_ir_object(ir, interp, used),
         ir::IrKind::Call(ir) => eval_ir_call(ir, interp, used),
+        ir::IrKind::Index(ir) => eval_ir_index(ir, interp, used),
+        ir::IrKind::Len(ir) => eval_ir_len(ir, interp, used),
     }
 }
@@ -1,9 +1,8 @@
 use crate::alloc::prelude::*;
-use crate::alloc::{try_format, Vec};
+use crate::alloc::Vec;
 use crate::ast::Spanned;
 use crate::compile::ir;
 use crate::compile::ir::scopes::MissingLocal;
-use crate::compile::meta;
 use crate::compile::{self, IrErrorKind, ItemId, ModId, WithSpan};
 use crate::hir;
 use crate::parse::NonZeroId;
@@ -110,26 +109,16 @@ impl Interpreter<'_, '_> {
             }
 
             if let Some(meta) = self.q.query_meta(span, item, used)? {
-                match &meta.kind {
-                    meta::Kind::Const => {
-                        let Some(const_value) = self.q.get_const_value(meta.hash) else {
-                            return Err(compile::Error::msg(
-                                span,
-                                try_format!("Missing constant for hash {}", meta.hash),
-                            ));
-                        };
+                let Some(const_value) = meta.as_const() else {
+                    return Err(compile::Error::new(
+                        span,
+                        IrErrorKind::UnsupportedMeta {
+                            meta: meta.info(self.q.pool)?,
+                        },
+                    ));
+                };
 
-                        return Ok(const_value.as_value().with_span(span)?);
-                    }
-                    _ => {
-                        return Err(compile::Error::new(
-                            span,
-                            IrErrorKind::UnsupportedMeta {
-                                meta: meta.info(self.q.pool)?,
-                            },
-                        ));
-                    }
-                }
+                return Ok(const_value.as_value().with_span(span)?);
             }
 
             if base.is_empty() {
@@ -13,7 +13,7 @@ use crate::compile::attrs::Parser;
 use crate::compile::{self, Item, ItemId, Location, MetaInfo, ModId, Pool, Visibility};
 use crate::hash::Hash;
 use crate::parse::{NonZeroId, ResolveContext};
-use crate::runtime::{Call, Protocol};
+use crate::runtime::{Call, ConstValue, Protocol};
 
 /// A meta reference to an item being compiled.
 #[derive(Debug, TryClone, Clone, Copy)]
@@ -107,6 +107,26 @@ impl Meta {
         }
     }
 
+    /// Get the value of the constant described by this meta.
+    ///
+    /// Returns `None` if the meta doesn't describe a constant.
+    pub(crate) fn as_const(&self) -> Option<&ConstValue> {
+        match &self.kind {
+            Kind::Const(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Convert into the value of the constant described by this meta.
+    ///
+    /// Returns `None` if the meta doesn't describe a constant.
+    pub(crate) fn try_into_const(self) -> Option<ConstValue> {
+        match self.kind {
+            Kind::Const(value) => Some(value),
+            _ => None,
+        }
+    }
+
     /// Get the type hash of the base type (the one to type check for) for the
     /// given compile meta.
     ///
@@ -171,6 +191,8 @@ pub enum Kind {
         fields: Fields,
         /// Native constructor for this variant.
         constructor: Option<Signature>,
+        /// The discriminant of the variant, if it has one.
+        discriminant: Option<i64>,
     },
     /// An enum item.
     Enum {
@@ -215,8 +237,8 @@ pub enum Kind {
         /// If the async block moves its environment.
         do_move: bool,
     },
-    /// The constant expression.
-    Const,
+    /// The constant expression and its value.
+    Const(ConstValue),
     /// A constant function.
     ConstFn {
         /// Opaque identifier for the constant function.
@@ -288,6 +310,9 @@ pub struct FieldsNamed {
 pub struct FieldMeta {
     /// Position of the field in its containing type declaration.
     pub(crate) position: usize,
+    /// Hash of the constant holding the default value of the field, if it
+    /// has one.
+    pub(crate) default: Option<Hash>,
 }
 
 /// Item and the module that the item belongs to.
@@ -321,7 +346,6 @@ pub struct Signature {
     #[cfg(feature = "doc")]
     pub(crate) is_async: bool,
     /// Arguments.
-    #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
     /// Return type of the function.
     #[cfg(feature = "doc")]
@@ -18,6 +18,44 @@ cfg_std! {
     impl std::error::Error for ParseOptionError {}
 }
 
+/// The architecture being compiled for.
+///
+/// This affects compile-time constants which depend on the target, like
+/// `size_of::<usize>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TargetArch {
+    /// The `x86_64` architecture.
+    X86_64,
+    /// The `aarch64` architecture.
+    AArch64,
+    /// The `wasm32` architecture.
+    Wasm32,
+    /// An unknown architecture, which uses the properties of the host.
+    Unknown,
+}
+
+impl TargetArch {
+    /// Get the width of a pointer in bytes for the target architecture.
+    pub fn pointer_width(self) -> usize {
+        match self {
+            TargetArch::X86_64 | TargetArch::AArch64 => 8,
+            TargetArch::Wasm32 => 4,
+            TargetArch::Unknown => core::mem::size_of::<usize>(),
+        }
+    }
+
+    fn parse(arch: &str) -> Option<Self> {
+        match arch {
+            "x86_64" => Some(TargetArch::X86_64),
+            "aarch64" => Some(TargetArch::AArch64),
+            "wasm32" => Some(TargetArch::Wasm32),
+            "unknown" => Some(TargetArch::Unknown),
+            _ => None,
+        }
+    }
+}
+
 /// Options that can be provided to the compiler.
 ///
 /// See [Build::with_options][crate::Build::with_options].
@@ -39,6 +77,17 @@ pub struct Options {
     pub(crate) v2: bool,
     /// Build sources as function bodies.
     pub(crate) function_body: bool,
+    /// The architecture being compiled for, if it differs from the host.
+    pub(crate) target_arch: Option<TargetArch>,
+    /// The number of leading string literal arms a match needs for it to be
+    /// dispatched through a string table. Zero disables it.
+    pub(crate) match_string_table: usize,
+    /// The number of times a variable can shadow another variable with the
+    /// same name before a warning is emitted. Zero disables it.
+    pub(crate) shadowing_limit: usize,
+    /// The maximum depth of nested expressions the assembler recurses into
+    /// before giving up. Zero disables it.
+    pub(crate) recursion_limit: usize,
 }
 
 impl Options {
@@ -76,6 +125,42 @@ impl Options {
             Some("function-body") => {
                 self.function_body = it.next() == Some("true");
             }
+            Some("target-arch") => {
+                let Some(arch) = it.next().and_then(TargetArch::parse) else {
+                    return Err(ParseOptionError {
+                        option: option.into(),
+                    });
+                };
+
+                self.target_arch = Some(arch);
+            }
+            Some("match-string-table") => {
+                let Some(threshold) = it.next().and_then(|n| n.parse().ok()) else {
+                    return Err(ParseOptionError {
+                        option: option.into(),
+                    });
+                };
+
+                self.match_string_table = threshold;
+            }
+            Some("shadowing-limit") => {
+                let Some(limit) = it.next().and_then(|n| n.parse().ok()) else {
+                    return Err(ParseOptionError {
+                        option: option.into(),
+                    });
+                };
+
+                self.shadowing_limit = limit;
+            }
+            Some("recursion-limit") => {
+                let Some(limit) = it.next().and_then(|n| n.parse().ok()) else {
+                    return Err(ParseOptionError {
+                        option: option.into(),
+                    });
+                };
+
+                self.recursion_limit = limit;
+            }
             _ => {
                 return Err(ParseOptionError {
                     option: option.into(),
@@ -117,6 +202,41 @@ impl Options {
     pub fn memoize_instance_fn(&mut self, enabled: bool) {
         self.memoize_instance_fn = enabled;
     }
+
+    /// Set the architecture being compiled for. Defaults to `None`, which
+    /// uses the architecture of the host.
+    pub fn target_arch(&mut self, arch: Option<TargetArch>) {
+        self.target_arch = arch;
+    }
+
+    /// Set the number of string literal arms a match needs before it's
+    /// dispatched through a string table rather than testing each arm in turn.
+    /// Setting it to `0` disables string tables. Defaults to `8`.
+    pub fn match_string_table(&mut self, threshold: usize) {
+        self.match_string_table = threshold;
+    }
+
+    /// Set the number of times a variable can shadow other variables with the
+    /// same name before a warning is emitted. Setting it to `0` disables the
+    /// warning. Defaults to `3`.
+    pub fn shadowing_limit(&mut self, limit: usize) {
+        self.shadowing_limit = limit;
+    }
+
+    /// Set the maximum depth of nested expressions which can be assembled.
+    /// Deeper expressions result in a compile error rather than overflowing
+    /// the stack. Setting it to `0` disables the limit. Defaults to `256`.
+    pub fn recursion_limit(&mut self, limit: usize) {
+        self.recursion_limit = limit;
+    }
+
+    /// Get the width of a pointer in bytes for the architecture being compiled
+    /// for.
+    pub(crate) fn pointer_width(&self) -> usize {
+        self.target_arch
+            .unwrap_or(TargetArch::Unknown)
+            .pointer_width()
+    }
 }
 
 impl Default for Options {
@@ -130,6 +250,10 @@ impl Default for Options {
             cfg_test: false,
             v2: false,
             function_body: false,
+            target_arch: None,
+            match_string_table: 8,
+            shadowing_limit: 3,
+            recursion_limit: 256,
         }
     }
 }
@@ -65,20 +65,24 @@ cfg_std! {
 
             let candidates = [base.join("mod.rn"), base.with_extension("rn")];
 
-            let mut found = None;
-
-            for path in &candidates[..] {
-                if path.is_file() {
-                    found = Some(path);
-                    break;
+            let path = match &candidates {
+                [first, second] if first.is_file() && second.is_file() => {
+                    return Err(compile::Error::new(
+                        span,
+                        ErrorKind::AmbiguousModFile {
+                            first: first.clone(),
+                            second: second.clone(),
+                        },
+                    ));
+                }
+                [first, _] if first.is_file() => first,
+                [_, second] if second.is_file() => second,
+                _ => {
+                    return Err(compile::Error::new(
+                        span,
+                        ErrorKind::ModNotFound { path: base },
+                    ));
                 }
-            }
-
-            let Some(path) = found else {
-                return Err(compile::Error::new(
-                    span,
-                    ErrorKind::ModNotFound { path: base },
-                ));
             };
 
             match Source::from_path(path) {
@@ -9,14 +9,13 @@ use ::rust_alloc::sync::Arc;
 
 use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
-use crate::alloc::{self, try_format, Box, HashMap, String, Vec};
+use crate::alloc::{self, Box, HashMap, String, Vec};
 use crate::ast::{Span, Spanned};
 use crate::compile::meta;
 use crate::compile::{self, Assembly, AssemblyInst, ErrorKind, Item, Location, Pool, WithSpan};
-use crate::hash;
-use crate::query::QueryInner;
+use crate::hash::{self, IntoHash};
 use crate::runtime::debug::{DebugArgs, DebugSignature};
-use crate::runtime::unit::UnitEncoder;
+use crate::runtime::unit::{StringTable, UnitEncoder};
 use crate::runtime::{
     Call, ConstValue, DebugInfo, DebugInst, Inst, Protocol, Rtti, StaticString, Unit, UnitFn,
     VariantRtti,
@@ -84,8 +83,14 @@ pub(crate) struct UnitBuilder {
     required_functions: HashMap<Hash, Vec<(Span, SourceId)>>,
     /// Debug info if available for unit.
     debug: Option<Box<DebugInfo>>,
-    /// Constant values
-    constants: hash::Map<ConstValue>,
+    /// Constant values, as slots into the constant pool.
+    constants: hash::Map<usize>,
+    /// Deduplicated constant values.
+    constant_pool: Vec<ConstValue>,
+    /// Reverse lookup for the constant pool.
+    constant_pool_rev: HashMap<ConstValue, usize>,
+    /// Tables used to dispatch on string values.
+    string_tables: Vec<StringTable>,
     /// Hash to identifiers.
     hash_to_ident: HashMap<Hash, Box<str>>,
 }
@@ -124,12 +129,10 @@ impl UnitBuilder {
                 continue;
             }
 
-            if let Some(value) = self.constants.get(&to) {
-                let const_value = value.try_clone()?;
-
+            if let Some(slot) = self.constants.get(&to).copied() {
                 if self
                     .constants
-                    .try_insert(from, const_value)
+                    .try_insert(from, slot)
                     .with_span(span)?
                     .is_some()
                 {
@@ -158,9 +161,36 @@ impl UnitBuilder {
             self.variant_rtti,
             self.debug,
             self.constants,
+            self.constant_pool,
+            self.string_tables,
         ))
     }
 
+    /// Insert a named constant.
+    ///
+    /// Identical constant values share a single slot in the constant pool.
+    fn new_constant(&mut self, hash: Hash, value: ConstValue) -> alloc::Result<()> {
+        let slot = self.new_constant_slot(value)?;
+        self.constants.try_insert(hash, slot)?;
+        Ok(())
+    }
+
+    /// Insert a value into the constant pool and return its associated slot
+    /// that can later be looked up through
+    /// [lookup_constant][Unit::lookup_constant].
+    ///
+    /// Only uses up space if the constant value is unique.
+    pub(crate) fn new_constant_slot(&mut self, value: ConstValue) -> alloc::Result<usize> {
+        if let Some(slot) = self.constant_pool_rev.get(&value) {
+            return Ok(*slot);
+        }
+
+        let slot = self.constant_pool.len();
+        self.constant_pool.try_push(value.try_clone()?)?;
+        self.constant_pool_rev.try_insert(value, slot)?;
+        Ok(slot)
+    }
+
     /// Insert a static string and return its associated slot that can later be
     /// looked up through [lookup_string][Unit::lookup_string].
     ///
@@ -311,7 +341,6 @@ impl UnitBuilder {
         span: &dyn Spanned,
         meta: &meta::Meta,
         pool: &Pool,
-        query: &mut QueryInner,
     ) -> compile::Result<()> {
         match meta.kind {
             meta::Kind::Type { .. } => {
@@ -322,12 +351,11 @@ impl UnitBuilder {
                     item: pool.item(meta.item_meta.item).try_to_owned()?,
                 });
 
-                self.constants
-                    .try_insert(
-                        Hash::associated_function(hash, Protocol::INTO_TYPE_NAME),
-                        ConstValue::String(rtti.item.try_to_string()?),
-                    )
-                    .with_span(span)?;
+                self.new_constant(
+                    Hash::associated_function(hash, Protocol::INTO_TYPE_NAME),
+                    ConstValue::String(rtti.item.try_to_string()?),
+                )
+                .with_span(span)?;
 
                 if self.rtti.try_insert(hash, rtti).with_span(span)?.is_some() {
                     return Err(compile::Error::new(
@@ -378,12 +406,11 @@ impl UnitBuilder {
                     ));
                 }
 
-                self.constants
-                    .try_insert(
-                        Hash::associated_function(meta.hash, Protocol::INTO_TYPE_NAME),
-                        ConstValue::String(signature.path.try_to_string()?),
-                    )
-                    .with_span(span)?;
+                self.new_constant(
+                    Hash::associated_function(meta.hash, Protocol::INTO_TYPE_NAME),
+                    ConstValue::String(signature.path.try_to_string()?),
+                )
+                .with_span(span)?;
 
                 self.debug_mut()?
                     .functions
@@ -434,12 +461,11 @@ impl UnitBuilder {
                     ));
                 }
 
-                self.constants
-                    .try_insert(
-                        Hash::associated_function(meta.hash, Protocol::INTO_TYPE_NAME),
-                        ConstValue::String(signature.path.try_to_string()?),
-                    )
-                    .with_span(span)?;
+                self.new_constant(
+                    Hash::associated_function(meta.hash, Protocol::INTO_TYPE_NAME),
+                    ConstValue::String(signature.path.try_to_string()?),
+                )
+                .with_span(span)?;
 
                 self.debug_mut()?
                     .functions
@@ -453,12 +479,11 @@ impl UnitBuilder {
                     item: pool.item(meta.item_meta.item).try_to_owned()?,
                 });
 
-                self.constants
-                    .try_insert(
-                        Hash::associated_function(hash, Protocol::INTO_TYPE_NAME),
-                        ConstValue::String(rtti.item.try_to_string()?),
-                    )
-                    .with_span(span)?;
+                self.new_constant(
+                    Hash::associated_function(hash, Protocol::INTO_TYPE_NAME),
+                    ConstValue::String(rtti.item.try_to_string()?),
+                )
+                .with_span(span)?;
 
                 if self.rtti.try_insert(hash, rtti).with_span(span)?.is_some() {
                     return Err(compile::Error::new(
@@ -469,11 +494,13 @@ impl UnitBuilder {
             }
             meta::Kind::Variant {
                 enum_hash,
+                discriminant,
                 fields: meta::Fields::Empty,
                 ..
             } => {
                 let rtti = Arc::new(VariantRtti {
                     enum_hash,
+                    discriminant,
                     hash: meta.hash,
                     item: pool.item(meta.item_meta.item).try_to_owned()?,
                 });
@@ -517,11 +544,13 @@ impl UnitBuilder {
             }
             meta::Kind::Variant {
                 enum_hash,
+                discriminant,
                 fields: meta::Fields::Unnamed(args),
                 ..
             } => {
                 let rtti = Arc::new(VariantRtti {
                     enum_hash,
+                    discriminant,
                     hash: meta.hash,
                     item: pool.item(meta.item_meta.item).try_to_owned()?,
                 });
@@ -568,6 +597,7 @@ impl UnitBuilder {
             }
             meta::Kind::Variant {
                 enum_hash,
+                discriminant,
                 fields: meta::Fields::Named(..),
                 ..
             } => {
@@ -575,6 +605,7 @@ impl UnitBuilder {
 
                 let rtti = Arc::new(VariantRtti {
                     enum_hash,
+                    discriminant,
                     hash,
                     item: pool.item(meta.item_meta.item).try_to_owned()?,
                 });
@@ -597,26 +628,15 @@ impl UnitBuilder {
                     .try_to_string()
                     .with_span(span)?;
 
-                self.constants
-                    .try_insert(
-                        Hash::associated_function(meta.hash, Protocol::INTO_TYPE_NAME),
-                        ConstValue::String(name),
-                    )
-                    .with_span(span)?;
+                self.new_constant(
+                    Hash::associated_function(meta.hash, Protocol::INTO_TYPE_NAME),
+                    ConstValue::String(name),
+                )
+                .with_span(span)?;
             }
-            meta::Kind::Const { .. } => {
-                let Some(const_value) = query.get_const_value(meta.hash) else {
-                    return Err(compile::Error::msg(
-                        span,
-                        try_format!("Missing constant for hash {}", meta.hash),
-                    ));
-                };
-
+            meta::Kind::Const(ref const_value) => {
                 let value = const_value.try_clone().with_span(span)?;
-
-                self.constants
-                    .try_insert(meta.hash, value)
-                    .with_span(span)?;
+                self.new_constant(meta.hash, value).with_span(span)?;
             }
             meta::Kind::Macro { .. } => (),
             meta::Kind::AttributeMacro { .. } => (),
@@ -719,12 +739,11 @@ impl UnitBuilder {
             ));
         }
 
-        self.constants
-            .try_insert(
-                Hash::associated_function(hash, Protocol::INTO_TYPE_NAME),
-                ConstValue::String(signature.path.try_to_string().with_span(location.span)?),
-            )
-            .with_span(location.span)?;
+        self.new_constant(
+            Hash::associated_function(hash, Protocol::INTO_TYPE_NAME),
+            ConstValue::String(signature.path.try_to_string().with_span(location.span)?),
+        )
+        .with_span(location.span)?;
 
         self.debug_mut()?.functions.try_insert(hash, signature)?;
         self.functions_rev.try_insert(offset, hash)?;
@@ -833,6 +852,19 @@ impl UnitBuilder {
 
                     storage.encode(Inst::JumpIf { jump }).with_span(span)?;
                 }
+                AssemblyInst::JumpIfNot { label } => {
+                    let jump = label
+                        .jump()
+                        .ok_or(ErrorKind::MissingLabelLocation {
+                            name: label.name,
+                            index: label.index,
+                        })
+                        .with_span(span)?;
+
+                    write!(comment, "label:{}", label)?;
+
+                    storage.encode(Inst::JumpIfNot { jump }).with_span(span)?;
+                }
                 AssemblyInst::JumpIfOrPop { label } => {
                     let jump = label
                         .jump()
@@ -906,6 +938,29 @@ impl UnitBuilder {
                         .encode(Inst::IterNext { offset, jump })
                         .with_span(span)?;
                 }
+                AssemblyInst::JumpStringTable { entries } => {
+                    let mut table = Vec::try_with_capacity(entries.len())?;
+
+                    for (string, label) in entries {
+                        let jump = label
+                            .jump()
+                            .ok_or(ErrorKind::MissingLabelLocation {
+                                name: label.name,
+                                index: label.index,
+                            })
+                            .with_span(span)?;
+
+                        table.try_push((string.as_str().into_hash(), string, jump))?;
+                    }
+
+                    let slot = self.string_tables.len();
+                    write!(comment, "string-table:{slot}")?;
+                    self.string_tables.try_push(StringTable::new(table))?;
+
+                    storage
+                        .encode(Inst::JumpStringTable { slot })
+                        .with_span(span)?;
+                }
                 AssemblyInst::Raw { raw } => {
                     // Optimization to avoid performing lookups for recursive
                     // function calls.
@@ -1,17 +1,17 @@
-use core::mem::{replace, take};
+use core::mem::{discriminant, replace, take, Discriminant};
 
 use crate as rune;
 use crate::alloc::prelude::*;
-use crate::alloc::{try_format, Vec};
+use crate::alloc::{try_format, String, Vec};
 use crate::ast::{self, Span, Spanned};
 use crate::compile::ir;
 use crate::compile::v1::{Layer, Loop, Loops, ScopeGuard, Scopes, Var};
-use crate::compile::{self, Assembly, ErrorKind, ItemId, ModId, Options, WithSpan};
+use crate::compile::{self, Assembly, ErrorKind, ItemId, ItemMeta, ModId, Options, WithSpan};
 use crate::hir;
 use crate::query::{ConstFn, Query, Used};
 use crate::runtime::{
-    ConstValue, Inst, InstAddress, InstAssignOp, InstOp, InstRange, InstTarget, InstValue,
-    InstVariant, Label, PanicReason, Protocol, TypeCheck,
+    static_type, ConstValue, Inst, InstAddress, InstAssignOp, InstOp, InstRange, InstTarget,
+    InstValue, InstVariant, Label, PanicReason, Protocol, Type, TypeCheck, Value,
 };
 use crate::{Hash, SourceId};
 
@@ -51,6 +51,8 @@ pub(crate) struct Ctxt<'a, 'hir, 'arena> {
     pub(crate) loops: Loops<'hir>,
     /// Enabled optimizations.
     pub(crate) options: &'a Options,
+    /// The depth of nested expressions currently being assembled.
+    pub(crate) depth: usize,
 }
 
 impl<'a, 'hir, 'arena> Ctxt<'a, 'hir, 'arena> {
@@ -113,6 +115,20 @@ impl<'a, 'hir, 'arena> Ctxt<'a, 'hir, 'arena> {
         self.contexts.last().copied()
     }
 
+    /// Evaluate a constant expression in the context of the given item and
+    /// return the resulting value.
+    ///
+    /// The expression must already have been indexed.
+    #[allow(dead_code)]
+    pub(crate) fn eval_const_expr(
+        &mut self,
+        expr: &ast::Expr,
+        from: &ItemMeta,
+    ) -> compile::Result<ConstValue> {
+        let ir = self.q.expr_to_ir(*from, expr)?;
+        self.eval_const_ir(expr, from.module, from.item, Vec::new(), &ir)
+    }
+
     /// Calling a constant function by id and return the resuling value.
     pub(crate) fn call_const_fn(
         &mut self,
@@ -128,6 +144,7 @@ impl<'a, 'hir, 'arena> Ctxt<'a, 'hir, 'arena> {
                 ErrorKind::UnsupportedArgumentCount {
                     expected: query_const_fn.ir_fn.args.len(),
                     actual: args.len(),
+                    signature: None,
                 },
             ));
         }
@@ -144,22 +161,42 @@ impl<'a, 'hir, 'arena> Ctxt<'a, 'hir, 'arena> {
             compiled.try_push((ir::compiler::expr(hir, &mut compiler)?, name))?;
         }
 
-        let mut interpreter = ir::Interpreter {
-            budget: ir::Budget::new(1_000_000),
-            scopes: ir::Scopes::new()?,
-            module: from_module,
-            item: from_item,
-            q: self.q.borrow(),
-        };
+        // Arguments are evaluated in the context of the caller.
+        let mut interpreter = self.q.const_interpreter(from_module, from_item)?;
+        let mut locals = Vec::new();
 
         for (ir, name) in compiled {
-            let value = interpreter.eval_value(&ir, Used::Used)?;
+            locals.try_push((name, interpreter.eval_value(&ir, Used::Used)?))?;
+        }
+
+        let item_meta = &query_const_fn.item_meta;
+
+        self.eval_const_ir(
+            span,
+            item_meta.module,
+            item_meta.item,
+            locals,
+            &query_const_fn.ir_fn.ir,
+        )
+    }
+
+    /// Evaluate intermediate representation in the context of the given
+    /// module and item, with `locals` declared as variables.
+    fn eval_const_ir(
+        &mut self,
+        span: &dyn Spanned,
+        module: ModId,
+        item: ItemId,
+        locals: Vec<(&hir::OwnedName, Value)>,
+        ir: &ir::Ir,
+    ) -> compile::Result<ConstValue> {
+        let mut interpreter = self.q.const_interpreter(module, item)?;
+
+        for (name, value) in locals {
             interpreter.scopes.decl(name, value).with_span(span)?;
         }
 
-        interpreter.module = query_const_fn.item_meta.module;
-        interpreter.item = query_const_fn.item_meta.item;
-        let value = interpreter.eval_value(&query_const_fn.ir_fn.ir, Used::Used)?;
+        let value = interpreter.eval_value(ir, Used::Used)?;
         Ok(crate::from_value(value).with_span(span)?)
     }
 }
@@ -388,6 +425,47 @@ fn pat_with_offset<'hir>(
     Ok(())
 }
 
+/// Define a named variable, warning if it shadows too many other variables with
+/// the same name.
+fn define_named<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    name: &'hir str,
+    span: &'hir dyn Spanned,
+) -> compile::Result<usize> {
+    let offset = cx.scopes.define(hir::Name::Str(name), span)?;
+    check_shadowing(cx, name, span)?;
+    Ok(offset)
+}
+
+/// Declare a named variable from the value at the top of the stack, reusing
+/// the slot of a variable which is no longer live if possible.
+fn declare_named<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    name: &'hir str,
+    span: &'hir dyn Spanned,
+) -> compile::Result<usize> {
+    let offset = cx.scopes.declare(cx.asm, hir::Name::Str(name), span)?;
+    check_shadowing(cx, name, span)?;
+    Ok(offset)
+}
+
+/// Warn if the variable `name` shadows too many other variables.
+fn check_shadowing(
+    cx: &mut Ctxt<'_, '_, '_>,
+    name: &str,
+    span: &dyn Spanned,
+) -> compile::Result<()> {
+    let count = cx.scopes.shadow_count(name);
+    let limit = cx.options.shadowing_limit;
+
+    if limit != 0 && count >= limit {
+        cx.q.diagnostics
+            .excessive_shadowing(cx.source_id, span, cx.context(), name, count)?;
+    }
+
+    Ok(())
+}
+
 /// Encode a pattern.
 ///
 /// Returns a boolean indicating if the label was used.
@@ -417,7 +495,7 @@ fn pat<'hir>(
             }
             hir::PatPathKind::Ident(name) => {
                 load(cx, Needs::Value)?;
-                cx.scopes.define(hir::Name::Str(name), hir)?;
+                define_named(cx, name, hir)?;
                 Ok(false)
             }
         },
@@ -663,7 +741,7 @@ fn pat_object<'hir>(
             hir::Binding::Ident(span, name) => {
                 cx.asm
                     .push(Inst::ObjectIndexGetAt { offset, slot }, &span)?;
-                cx.scopes.define(hir::Name::Str(name), binding)?;
+                define_named(cx, name, binding)?;
             }
         }
     }
@@ -705,16 +783,26 @@ fn block<'hir>(
         }
     }
 
-    let produced = if let Some((e, semi)) = last {
-        if semi {
+    let produced = match last {
+        Some((e, true)) => {
             expr(cx, e, Needs::None)?.apply(cx)?;
             false
-        } else {
+        }
+        // A trailing `()` is treated like an empty tail, so that we don't
+        // push a unit only to pop it again. If a value is needed, the unit is
+        // pushed after the locals have been popped.
+        Some((
+            hir::Expr {
+                kind: hir::ExprKind::Tuple(hir::ExprSeq { items: [], .. }),
+                ..
+            },
+            false,
+        )) => false,
+        Some((e, false)) => {
             expr(cx, e, needs)?.apply(cx)?;
             true
         }
-    } else {
-        false
+        None => false,
     };
 
     let scope = cx.scopes.pop(scopes_count, hir)?;
@@ -815,6 +903,36 @@ fn builtin_template<'hir>(
     Ok(Asm::top(span))
 }
 
+/// The number of values a constant has to consist of for it to be loaded from
+/// the constant pool rather than being built element by element.
+const CONSTANT_POOL_THRESHOLD: usize = 16;
+
+/// Count the number of values which make up a constant, stopping once the
+/// threshold for loading it from the constant pool has been reached.
+fn const_value_count(value: &ConstValue) -> usize {
+    fn count<'a>(values: impl IntoIterator<Item = &'a ConstValue>) -> usize {
+        let mut total = 1;
+
+        for value in values {
+            total += const_value_count(value);
+
+            if total >= CONSTANT_POOL_THRESHOLD {
+                break;
+            }
+        }
+
+        total
+    }
+
+    match value {
+        ConstValue::Option(Some(value)) => 1 + const_value_count(value),
+        ConstValue::Vec(vec) => count(vec.iter()),
+        ConstValue::Tuple(tuple) => count(tuple.iter()),
+        ConstValue::Object(object) => count(object.values()),
+        _ => 1,
+    }
+}
+
 /// Assemble a constant value.
 #[instrument(span = span)]
 fn const_<'hir>(
@@ -829,6 +947,12 @@ fn const_<'hir>(
         return Ok(());
     }
 
+    if const_value_count(value) >= CONSTANT_POOL_THRESHOLD {
+        let slot = cx.q.unit.new_constant_slot(value.try_clone()?)?;
+        cx.asm.push(Inst::Constant { slot }, span)?;
+        return Ok(());
+    }
+
     match value {
         ConstValue::EmptyTuple => {
             cx.asm.push(Inst::unit(), span)?;
@@ -914,9 +1038,36 @@ fn expr<'hir>(
     cx: &mut Ctxt<'_, 'hir, '_>,
     hir: &'hir hir::Expr<'hir>,
     needs: Needs,
+) -> compile::Result<Asm<'hir>> {
+    let limit = cx.options.recursion_limit;
+
+    if limit != 0 && cx.depth >= limit {
+        return Err(compile::Error::new(
+            hir,
+            ErrorKind::RecursionLimit { limit },
+        ));
+    }
+
+    cx.depth += 1;
+    let result = expr_inner(cx, hir, needs);
+    cx.depth -= 1;
+    result
+}
+
+fn expr_inner<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    hir: &'hir hir::Expr<'hir>,
+    needs: Needs,
 ) -> compile::Result<Asm<'hir>> {
     let span = hir;
 
+    // Elide the entire expression if it's not needed and evaluating it can't
+    // have any side effects.
+    if !needs.value() && is_pure(hir) {
+        pure_elided(cx, hir)?;
+        return Ok(Asm::top(span));
+    }
+
     let asm = match hir.kind {
         hir::ExprKind::Variable(name) => {
             let var = cx.scopes.get(&mut cx.q, name, span)?;
@@ -976,6 +1127,64 @@ fn expr<'hir>(
     Ok(asm)
 }
 
+/// Test if the given expression is free of side effects.
+///
+/// This is conservative, so anything which might call a function or raise an
+/// error, like calls, indexing or arithmetic, is treated as impure.
+fn is_pure(hir: &hir::Expr<'_>) -> bool {
+    match hir.kind {
+        hir::ExprKind::Lit(..) | hir::ExprKind::Variable(..) | hir::ExprKind::Const(..) => true,
+        hir::ExprKind::Group(hir) => is_pure(hir),
+        hir::ExprKind::Binary(..) => pure_lit_kind(hir).is_some(),
+        _ => false,
+    }
+}
+
+/// Get the kind of literal an expression evaluates to, if it's built out of
+/// literals using operators which can't fail.
+fn pure_lit_kind<'hir>(hir: &hir::Expr<'hir>) -> Option<Discriminant<hir::Lit<'hir>>> {
+    match hir.kind {
+        hir::ExprKind::Lit(lit) => Some(discriminant(&lit)),
+        hir::ExprKind::Group(hir) => pure_lit_kind(hir),
+        hir::ExprKind::Binary(hir) => {
+            let lhs = pure_lit_kind(&hir.lhs)?;
+            let rhs = pure_lit_kind(&hir.rhs)?;
+            let bool = discriminant(&hir::Lit::Bool(false));
+
+            match hir.op {
+                ast::BinOp::Eq(..) | ast::BinOp::Neq(..) if lhs == rhs => Some(bool),
+                ast::BinOp::And(..) | ast::BinOp::Or(..) if lhs == bool && rhs == bool => {
+                    Some(bool)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Handle a pure expression which is being elided, so that the same errors
+/// and diagnostics are raised as if it had been assembled.
+fn pure_elided<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    hir: &'hir hir::Expr<'hir>,
+) -> compile::Result<()> {
+    match hir.kind {
+        hir::ExprKind::Variable(name) => {
+            cx.scopes.get(&mut cx.q, name, hir)?;
+        }
+        hir::ExprKind::Lit(..) | hir::ExprKind::Const(..) => {
+            cx.q.diagnostics.not_used(cx.source_id, hir, cx.context())?;
+        }
+        hir::ExprKind::Group(hir) => {
+            pure_elided(cx, hir)?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
 /// Assemble an assign expression.
 #[instrument(span = span)]
 fn expr_assign<'hir>(
@@ -984,17 +1193,44 @@ fn expr_assign<'hir>(
     span: &'hir dyn Spanned,
     needs: Needs,
 ) -> compile::Result<Asm<'hir>> {
-    let supported = match hir.lhs.kind {
+    let load = |cx: &mut Ctxt<'_, 'hir, '_>| {
+        expr(cx, &hir.rhs, Needs::Value)?.apply(cx)?;
+        Ok(())
+    };
+
+    // NB: destructuring allocates anonymous variables, which are cleaned up
+    // along with the scope.
+    let guard = cx.scopes.child(span)?;
+    assign(cx, &hir.lhs, span, &load)?;
+    cx.clean_last_scope(span, guard, Needs::None)?;
+
+    if needs.value() {
+        cx.asm.push(Inst::unit(), span)?;
+    }
+
+    Ok(Asm::top(span))
+}
+
+/// Assign the value produced by `load` to the place `lhs`, destructuring it if
+/// `lhs` is a tuple, vector or object.
+///
+/// The `span` is used to report places which can't be assigned to.
+fn assign<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    lhs: &'hir hir::Expr<'hir>,
+    span: &dyn Spanned,
+    load: &dyn Fn(&mut Ctxt<'_, 'hir, '_>) -> compile::Result<()>,
+) -> compile::Result<()> {
+    match lhs.kind {
         // <var> = <value>
         hir::ExprKind::Variable(name) => {
-            expr(cx, &hir.rhs, Needs::Value)?.apply(cx)?;
+            load(cx)?;
             let var = cx.scopes.get(&mut cx.q, name, span)?;
             cx.asm.push_with_comment(
                 Inst::Replace { offset: var.offset },
                 span,
                 &format_args!("var `{var}`"),
             )?;
-            true
         }
         // <expr>.<field> = <value>
         hir::ExprKind::FieldAccess(field_access) => {
@@ -1003,24 +1239,22 @@ fn expr_assign<'hir>(
                 hir::ExprField::Ident(ident) => {
                     let slot = cx.q.unit.new_static_string(span, ident)?;
 
-                    expr(cx, &hir.rhs, Needs::Value)?.apply(cx)?;
-                    cx.scopes.alloc(&hir.rhs)?;
+                    load(cx)?;
+                    cx.scopes.alloc(span)?;
 
                     expr(cx, &field_access.expr, Needs::Value)?.apply(cx)?;
                     cx.scopes.alloc(span)?;
 
                     cx.asm.push(Inst::ObjectIndexSet { slot }, span)?;
                     cx.scopes.free(span, 2)?;
-                    true
                 }
                 hir::ExprField::Index(index) => {
-                    expr(cx, &hir.rhs, Needs::Value)?.apply(cx)?;
-                    cx.scopes.alloc(&hir.rhs)?;
+                    load(cx)?;
+                    cx.scopes.alloc(span)?;
 
                     expr(cx, &field_access.expr, Needs::Value)?.apply(cx)?;
                     cx.asm.push(Inst::TupleIndexSet { index }, span)?;
                     cx.scopes.free(span, 1)?;
-                    true
                 }
                 _ => {
                     return Err(compile::Error::new(span, ErrorKind::BadFieldAccess));
@@ -1028,7 +1262,7 @@ fn expr_assign<'hir>(
             }
         }
         hir::ExprKind::Index(expr_index_get) => {
-            expr(cx, &hir.rhs, Needs::Value)?.apply(cx)?;
+            load(cx)?;
             cx.scopes.alloc(span)?;
 
             expr(cx, &expr_index_get.target, Needs::Value)?.apply(cx)?;
@@ -1039,20 +1273,119 @@ fn expr_assign<'hir>(
 
             cx.asm.push(Inst::IndexSet, span)?;
             cx.scopes.free(span, 3)?;
-            true
         }
-        _ => false,
-    };
+        hir::ExprKind::Group(hir) => {
+            assign(cx, hir, span, load)?;
+        }
+        // (<a>, <b>) = <value>
+        hir::ExprKind::Tuple(hir) => {
+            assign_sequence(cx, hir.items, TypeCheck::Tuple, span, load)?;
+        }
+        // [<a>, <b>] = <value>
+        hir::ExprKind::Vec(hir) => {
+            assign_sequence(cx, hir.items, TypeCheck::Vec, span, load)?;
+        }
+        // Struct { <a>, <b> } = <value>
+        hir::ExprKind::Object(hir) => {
+            let inst = match hir.kind {
+                hir::ExprObjectKind::Struct { hash }
+                | hir::ExprObjectKind::EmptyStruct { hash } => Inst::MatchType { hash },
+                hir::ExprObjectKind::Anonymous => {
+                    let keys = cx.q.unit.new_static_object_keys_iter(
+                        span,
+                        hir.assignments.iter().map(|a| a.key.1),
+                    )?;
+
+                    Inst::MatchObject {
+                        slot: keys,
+                        exact: true,
+                    }
+                }
+                _ => {
+                    return Err(compile::Error::new(span, ErrorKind::UnsupportedAssignExpr));
+                }
+            };
+
+            let offset = assign_check(cx, inst, span, load)?;
 
-    if !supported {
-        return Err(compile::Error::new(span, ErrorKind::UnsupportedAssignExpr));
+            for field in hir.assignments {
+                // Fields which are omitted are filled in with their default
+                // value by lowering, and are left alone.
+                if field.is_default {
+                    continue;
+                }
+
+                let slot = cx.q.unit.new_static_string(span, field.key.1)?;
+
+                let load = move |cx: &mut Ctxt<'_, 'hir, '_>| {
+                    cx.asm
+                        .push(Inst::ObjectIndexGetAt { offset, slot }, &field.key.0)?;
+                    Ok(())
+                };
+
+                assign(cx, &field.assign, &field.assign, &load)?;
+            }
+        }
+        _ => {
+            return Err(compile::Error::new(span, ErrorKind::UnsupportedAssignExpr));
+        }
     }
 
-    if needs.value() {
-        cx.asm.push(Inst::unit(), span)?;
+    return Ok(());
+
+    fn assign_sequence<'hir>(
+        cx: &mut Ctxt<'_, 'hir, '_>,
+        items: &'hir [hir::Expr<'hir>],
+        type_check: TypeCheck,
+        span: &dyn Spanned,
+        load: &dyn Fn(&mut Ctxt<'_, 'hir, '_>) -> compile::Result<()>,
+    ) -> compile::Result<()> {
+        let inst = Inst::MatchSequence {
+            type_check,
+            len: items.len(),
+            exact: true,
+        };
+
+        let offset = assign_check(cx, inst, span, load)?;
+
+        for (index, item) in items.iter().enumerate() {
+            let load = move |cx: &mut Ctxt<'_, 'hir, '_>| {
+                cx.asm.push(Inst::TupleIndexGetAt { offset, index }, item)?;
+                Ok(())
+            };
+
+            assign(cx, item, item, &load)?;
+        }
+
+        Ok(())
     }
 
-    Ok(Asm::top(span))
+    /// Load the value being destructured into an anonymous variable and
+    /// check that it matches the shape being assigned to with `inst`,
+    /// panicking if it doesn't.
+    fn assign_check<'hir>(
+        cx: &mut Ctxt<'_, 'hir, '_>,
+        inst: Inst,
+        span: &dyn Spanned,
+        load: &dyn Fn(&mut Ctxt<'_, 'hir, '_>) -> compile::Result<()>,
+    ) -> compile::Result<usize> {
+        load(cx)?;
+        let offset = cx.scopes.alloc(span)?;
+
+        cx.asm.push(Inst::Copy { offset }, span)?;
+        cx.asm.push(inst, span)?;
+
+        let ok_label = cx.asm.new_label("assign_ok");
+        cx.asm.jump_if(&ok_label, span)?;
+        cx.asm.push(
+            Inst::Panic {
+                reason: PanicReason::UnmatchedPattern,
+            },
+            span,
+        )?;
+        cx.asm.label(&ok_label)?;
+        Ok(offset)
+    }
 }
 
 /// Assemble an `.await` expression.
@@ -1092,6 +1425,14 @@ fn expr_binary<'hir>(
         return Ok(Asm::top(span));
     }
 
+    // Concatenation of constant strings is folded into a single constant.
+    if let ast::BinOp::Add(..) = hir.op {
+        if let Some(string) = const_string_concat(cx, hir, span)? {
+            const_(cx, &ConstValue::String(string), span, needs)?;
+            return Ok(Asm::top(span));
+        }
+    }
+
     let guard = cx.scopes.child(span)?;
 
     // NB: need to declare these as anonymous local variables so that they
@@ -1141,6 +1482,46 @@ fn expr_binary<'hir>(
     cx.scopes.pop(guard, span)?;
     return Ok(Asm::top(span));
 
+    /// Try to fold `a + b` where both operands are constant strings.
+    fn const_string_concat(
+        cx: &mut Ctxt<'_, '_, '_>,
+        hir: &hir::ExprBinary<'_>,
+        span: &dyn Spanned,
+    ) -> compile::Result<Option<String>> {
+        let Some(a) = const_string(cx, &hir.lhs, span)? else {
+            return Ok(None);
+        };
+
+        let Some(b) = const_string(cx, &hir.rhs, span)? else {
+            return Ok(None);
+        };
+
+        let mut out = String::try_with_capacity(a.len() + b.len()).with_span(span)?;
+        out.try_push_str(&a).with_span(span)?;
+        out.try_push_str(&b).with_span(span)?;
+        Ok(Some(out))
+    }
+
+    /// Get the value of an expression if it's a constant string.
+    fn const_string(
+        cx: &mut Ctxt<'_, '_, '_>,
+        hir: &hir::Expr<'_>,
+        span: &dyn Spanned,
+    ) -> compile::Result<Option<String>> {
+        match hir.kind {
+            hir::ExprKind::Lit(hir::Lit::Str(s)) => Ok(Some(s.try_to_owned().with_span(span)?)),
+            hir::ExprKind::Const(hash) => match cx.q.get_const_value(hash) {
+                Some(ConstValue::String(s)) => Ok(Some(s.try_clone().with_span(span)?)),
+                _ => Ok(None),
+            },
+            hir::ExprKind::Group(hir) => const_string(cx, hir, span),
+            hir::ExprKind::Binary(hir) if matches!(hir.op, ast::BinOp::Add(..)) => {
+                const_string_concat(cx, hir, span)
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn compile_conditional_binop<'hir>(
         cx: &mut Ctxt<'_, 'hir, '_>,
         lhs: &'hir hir::Expr<'hir>,
@@ -1369,6 +1750,12 @@ fn expr_call<'hir>(
     span: &dyn Spanned,
     needs: Needs,
 ) -> compile::Result<Asm<'hir>> {
+    // Getting the length of a constant is folded into a constant.
+    if let Some(len) = const_len(cx, hir) {
+        const_(cx, &ConstValue::Integer(len), span, needs)?;
+        return Ok(Asm::top(span));
+    }
+
     let args = hir.args.len();
 
     match hir.call {
@@ -1436,7 +1823,33 @@ fn expr_call<'hir>(
         cx.asm.push(Inst::Pop, span)?;
     }
 
-    Ok(Asm::top(span))
+    return Ok(Asm::top(span));
+
+    /// Try to fold `CONST.len()` into the length of the constant.
+    fn const_len(cx: &mut Ctxt<'_, '_, '_>, hir: &hir::ExprCall<'_>) -> Option<i64> {
+        let hir::Call::Associated { target, hash } = hir.call else {
+            return None;
+        };
+
+        if hash != Hash::ident("len") || !hir.args.is_empty() {
+            return None;
+        }
+
+        let hir::ExprKind::Const(hash) = target.kind else {
+            return None;
+        };
+
+        let len = match cx.q.get_const_value(hash)? {
+            ConstValue::String(string) => string.len(),
+            ConstValue::Bytes(bytes) => bytes.len(),
+            ConstValue::Vec(vec) => vec.len(),
+            ConstValue::Tuple(tuple) => tuple.len(),
+            ConstValue::Object(object) => object.len(),
+            _ => return None,
+        };
+
+        i64::try_from(len).ok()
+    }
 }
 
 /// Assemble a closure expression.
@@ -1584,6 +1997,22 @@ fn expr_for<'hir>(
     span: &dyn Spanned,
     needs: Needs,
 ) -> compile::Result<Asm<'hir>> {
+    // A range with an integer literal bound, like `0..n`, can only be iterated
+    // over if both bounds are integers, so it doesn't need an iterator.
+    if let hir::ExprKind::Range(range) = hir.iter.kind {
+        let bounds = match range {
+            hir::ExprRange::Range { start, end } => Some((start, end, false)),
+            hir::ExprRange::RangeInclusive { start, end } => Some((start, end, true)),
+            _ => None,
+        };
+
+        if let Some((start, end, inclusive)) = bounds {
+            if is_integer_lit(start) || is_integer_lit(end) {
+                return expr_for_range(cx, hir, start, end, inclusive, span, needs);
+            }
+        }
+    }
+
     let continue_label = cx.asm.new_label("for_continue");
     let end_label = cx.asm.new_label("for_end");
     let break_label = cx.asm.new_label("for_break");
@@ -1740,6 +2169,212 @@ fn expr_for<'hir>(
     Ok(Asm::top(span))
 }
 
+/// Test if the expression is an integer literal.
+fn is_integer_lit(hir: &hir::Expr<'_>) -> bool {
+    matches!(hir.kind, hir::ExprKind::Lit(hir::Lit::Integer(..)))
+}
+
+/// Assemble a for loop over an integer range.
+///
+/// The next value is kept in a counter which is advanced in place, instead of
+/// constructing an iterator and calling its `next` function on every step.
+#[instrument(span = span)]
+fn expr_for_range<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    hir: &'hir hir::ExprFor<'hir>,
+    start: &'hir hir::Expr<'hir>,
+    end: &'hir hir::Expr<'hir>,
+    inclusive: bool,
+    span: &dyn Spanned,
+    needs: Needs,
+) -> compile::Result<Asm<'hir>> {
+    let continue_label = cx.asm.new_label("for_continue");
+    let end_label = cx.asm.new_label("for_end");
+    let break_label = cx.asm.new_label("for_break");
+
+    let break_var_count = cx.scopes.total(span)?;
+    let loop_scope_expected = cx.scopes.child(span)?;
+
+    expr(cx, start, Needs::Value)?.apply(cx)?;
+    let counter_offset = cx.scopes.alloc(start)?;
+
+    expr(cx, end, Needs::Value)?.apply(cx)?;
+    let end_offset = cx.scopes.alloc(end)?;
+
+    cx.asm.push(Inst::unit(), &hir.iter)?;
+    let binding_offset = cx.scopes.alloc(&hir.binding)?;
+
+    // Store `offset + n` in a new value at `to`. Copying an integer shares
+    // it, so this ensures that the counter and the loop variable can be
+    // modified without affecting each other.
+    let add = |cx: &mut Ctxt<'_, 'hir, '_>, offset, n, to| {
+        cx.asm.push(Inst::integer(n), &hir.iter)?;
+
+        cx.asm.push(
+            Inst::Op {
+                op: InstOp::Add,
+                a: InstAddress::Offset(offset),
+                b: InstAddress::Top,
+            },
+            &hir.iter,
+        )?;
+
+        cx.asm.push(Inst::Replace { offset: to }, &hir.iter)
+    };
+
+    // The bound which isn't a literal might not be an integer, in which case
+    // the range is passed to `into_iter` to raise the same error as a loop
+    // using the iterator protocol would.
+    let unchecked = if !is_integer_lit(start) {
+        Some(counter_offset)
+    } else if !is_integer_lit(end) {
+        Some(end_offset)
+    } else {
+        None
+    };
+
+    if let Some(offset) = unchecked {
+        let ok_label = cx.asm.new_label("for_range_ok");
+
+        cx.asm.push(
+            Inst::Push {
+                value: InstValue::Type(Type::new(static_type::INTEGER_TYPE_HASH)),
+            },
+            &hir.iter,
+        )?;
+
+        cx.asm.push(
+            Inst::Op {
+                op: InstOp::Is,
+                a: InstAddress::Offset(offset),
+                b: InstAddress::Top,
+            },
+            &hir.iter,
+        )?;
+
+        cx.asm.jump_if(&ok_label, &hir.iter)?;
+
+        cx.asm.push(
+            Inst::Copy {
+                offset: counter_offset,
+            },
+            &hir.iter,
+        )?;
+
+        cx.asm.push(Inst::Copy { offset: end_offset }, &hir.iter)?;
+
+        let range = if inclusive {
+            InstRange::RangeInclusive
+        } else {
+            InstRange::Range
+        };
+
+        cx.asm.push(Inst::Range { range }, &hir.iter)?;
+
+        cx.asm.push_with_comment(
+            Inst::CallAssociated {
+                hash: *Protocol::INTO_ITER,
+                args: 0,
+            },
+            &hir.iter,
+            &"into_iter (unsupported range)",
+        )?;
+
+        cx.asm.push(Inst::Pop, &hir.iter)?;
+        cx.asm.jump(&end_label, &hir.iter)?;
+        cx.asm.label(&ok_label)?;
+
+        // Detach the bound from the value it was evaluated from.
+        add(cx, offset, 0, offset)?;
+    }
+
+    let compare = |cx: &mut Ctxt<'_, 'hir, '_>, op| {
+        cx.asm.push(
+            Inst::Op {
+                op,
+                a: InstAddress::Offset(counter_offset),
+                b: InstAddress::Offset(end_offset),
+            },
+            &hir.iter,
+        )
+    };
+
+    let continue_var_count = cx.scopes.total(span)?;
+
+    if inclusive {
+        // The counter is only advanced if it's below the end of the range, so
+        // an inclusive range ending at `i64::MAX` doesn't overflow.
+        let body_label = cx.asm.new_label("for_body");
+
+        compare(cx, InstOp::Lte)?;
+        cx.asm.jump_if_not(&end_label, &hir.iter)?;
+        cx.asm.jump(&body_label, &hir.iter)?;
+
+        cx.asm.label(&continue_label)?;
+        compare(cx, InstOp::Eq)?;
+        cx.asm.jump_if(&end_label, &hir.iter)?;
+        add(cx, counter_offset, 1, counter_offset)?;
+        cx.asm.label(&body_label)?;
+
+        // The counter is kept, so the loop variable is a new value.
+        add(cx, counter_offset, 0, binding_offset)?;
+    } else {
+        cx.asm.label(&continue_label)?;
+        compare(cx, InstOp::Lt)?;
+        cx.asm.jump_if_not(&end_label, &hir.iter)?;
+
+        // The counter is replaced when advanced, so the loop variable can be
+        // the current value.
+        cx.asm.push(
+            Inst::Copy {
+                offset: counter_offset,
+            },
+            &hir.binding,
+        )?;
+
+        cx.asm.push(
+            Inst::Replace {
+                offset: binding_offset,
+            },
+            &hir.binding,
+        )?;
+
+        add(cx, counter_offset, 1, counter_offset)?;
+    }
+
+    cx.loops.push(Loop {
+        label: hir.label,
+        continue_label: continue_label.try_clone()?,
+        continue_var_count,
+        break_label: break_label.try_clone()?,
+        break_var_count,
+        needs,
+        drop: None,
+    })?;
+
+    let guard = cx.scopes.child(&hir.body)?;
+
+    pat_with_offset(cx, &hir.binding, binding_offset)?;
+
+    block(cx, &hir.body, Needs::None)?.apply(cx)?;
+    cx.clean_last_scope(span, guard, Needs::None)?;
+
+    cx.asm.jump(&continue_label, span)?;
+    cx.asm.label(&end_label)?;
+
+    cx.clean_last_scope(span, loop_scope_expected, Needs::None)?;
+
+    // NB: If a value is needed from a for loop, encode it as a unit.
+    if needs.value() {
+        cx.asm.push(Inst::unit(), span)?;
+    }
+
+    // NB: breaks produce their own value.
+    cx.asm.label(&break_label)?;
+    cx.loops.pop();
+    Ok(Asm::top(span))
+}
+
 /// Assemble an if expression.
 #[instrument(span = span)]
 fn expr_if<'hir>(
@@ -1750,11 +2385,33 @@ fn expr_if<'hir>(
 ) -> compile::Result<Asm<'hir>> {
     let end_label = cx.asm.new_label("if_end");
 
+    // A single conditional branch without a fallback, such as a guard, can be
+    // encoded as one jump over the body.
+    if let ([branch], false) = (hir.branches, needs.value()) {
+        if let Some(hir::Condition::Expr(e)) = branch
+            .condition
+            .filter(|c| const_condition(cx, c).is_none())
+        {
+            let guard = cx.scopes.child(e)?;
+            expr(cx, e, Needs::Value)?.apply(cx)?;
+            cx.asm.jump_if_not(&end_label, e)?;
+            let scope = cx.scopes.pop(guard, e)?;
+
+            let scopes = cx.scopes.push(scope)?;
+            block(cx, &branch.block, needs)?.apply(cx)?;
+            cx.clean_last_scope(branch, scopes, needs)?;
+
+            cx.asm.label(&end_label)?;
+            return Ok(Asm::top(span));
+        }
+    }
+
     let mut branches = Vec::new();
     let mut fallback = None;
 
     for branch in hir.branches {
         if fallback.is_some() {
+            dead_branch(cx, branch, needs)?;
             continue;
         }
 
@@ -1763,6 +2420,20 @@ fn expr_if<'hir>(
             continue;
         };
 
+        // A branch with a constant condition is either never taken, or always
+        // taken in which case it's the fallback and later branches are dead.
+        match const_condition(cx, cond) {
+            Some(false) => {
+                dead_branch(cx, branch, needs)?;
+                continue;
+            }
+            Some(true) => {
+                fallback = Some(&branch.block);
+                continue;
+            }
+            None => {}
+        }
+
         let label = cx.asm.new_label("if_branch");
         let scope = condition(cx, cond, &label)?;
         branches.try_push((branch, label, scope))?;
@@ -1779,7 +2450,9 @@ fn expr_if<'hir>(
         }
     }
 
-    cx.asm.jump(&end_label, span)?;
+    if !branches.is_empty() {
+        cx.asm.jump(&end_label, span)?;
+    }
 
     let mut it = branches.into_iter().peekable();
 
@@ -1796,7 +2469,127 @@ fn expr_if<'hir>(
     }
 
     cx.asm.label(&end_label)?;
-    Ok(Asm::top(span))
+    return Ok(Asm::top(span));
+
+    /// Assemble a branch which is never taken so that errors in it are still
+    /// reported, but discard the instructions it generates.
+    fn dead_branch<'hir>(
+        cx: &mut Ctxt<'_, 'hir, '_>,
+        branch: &'hir hir::ConditionalBranch<'hir>,
+        needs: Needs,
+    ) -> compile::Result<()> {
+        let scratch = cx.asm.scratch();
+        let asm = replace(&mut *cx.asm, scratch);
+        let result = assemble_branch(cx, branch, needs);
+        let scratch = replace(&mut *cx.asm, asm);
+        cx.asm.label_count = scratch.label_count;
+        result?;
+
+        // Calls in dead code still have to refer to functions which exist.
+        for (hash, spans) in scratch.required_functions {
+            cx.asm
+                .required_functions
+                .entry(hash)
+                .or_try_default()?
+                .try_extend(spans)?;
+        }
+
+        Ok(())
+    }
+
+    fn assemble_branch<'hir>(
+        cx: &mut Ctxt<'_, 'hir, '_>,
+        branch: &'hir hir::ConditionalBranch<'hir>,
+        needs: Needs,
+    ) -> compile::Result<()> {
+        let Some(cond) = branch.condition else {
+            block(cx, &branch.block, needs)?.apply(cx)?;
+            return Ok(());
+        };
+
+        let label = cx.asm.new_label("if_branch");
+        let scope = condition(cx, cond, &label)?;
+        let scopes = cx.scopes.push(scope)?;
+        block(cx, &branch.block, needs)?.apply(cx)?;
+        cx.clean_last_scope(branch, scopes, needs)?;
+        Ok(())
+    }
+
+    /// Try to resolve a condition which only depends on constants, like
+    /// `if DEBUG && !VERBOSE`, to whether its branch is taken.
+    fn const_condition(cx: &Ctxt<'_, '_, '_>, condition: &hir::Condition<'_>) -> Option<bool> {
+        let hir::Condition::Expr(e) = condition else {
+            return None;
+        };
+
+        const_bool(cx, e)
+    }
+
+    fn const_bool(cx: &Ctxt<'_, '_, '_>, hir: &hir::Expr<'_>) -> Option<bool> {
+        match hir.kind {
+            hir::ExprKind::Lit(hir::Lit::Bool(boolean)) => Some(boolean),
+            hir::ExprKind::Const(hash) => match cx.q.get_const_value(hash)? {
+                ConstValue::Bool(boolean) => Some(*boolean),
+                _ => None,
+            },
+            hir::ExprKind::Group(hir) => const_bool(cx, hir),
+            hir::ExprKind::Unary(hir::ExprUnary {
+                op: ast::UnOp::Not(..),
+                expr,
+            }) => Some(!const_bool(cx, expr)?),
+            hir::ExprKind::Binary(hir) => {
+                // NB: The right-hand side is never evaluated if the left-hand
+                // side short circuits, so it doesn't have to be constant.
+                match hir.op {
+                    ast::BinOp::And(..) => match const_bool(cx, &hir.lhs)? {
+                        true => const_bool(cx, &hir.rhs),
+                        false => Some(false),
+                    },
+                    ast::BinOp::Or(..) => match const_bool(cx, &hir.lhs)? {
+                        true => Some(true),
+                        false => const_bool(cx, &hir.rhs),
+                    },
+                    op => {
+                        if let (Some(a), Some(b)) =
+                            (const_bool(cx, &hir.lhs), const_bool(cx, &hir.rhs))
+                        {
+                            return match op {
+                                ast::BinOp::Eq(..) => Some(a == b),
+                                ast::BinOp::Neq(..) => Some(a != b),
+                                _ => None,
+                            };
+                        }
+
+                        let a = const_integer(cx, &hir.lhs)?;
+                        let b = const_integer(cx, &hir.rhs)?;
+
+                        match op {
+                            ast::BinOp::Eq(..) => Some(a == b),
+                            ast::BinOp::Neq(..) => Some(a != b),
+                            ast::BinOp::Gt(..) => Some(a > b),
+                            ast::BinOp::Lt(..) => Some(a < b),
+                            ast::BinOp::Gte(..) => Some(a >= b),
+                            ast::BinOp::Lte(..) => Some(a <= b),
+                            _ => None,
+                        }
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn const_integer(cx: &Ctxt<'_, '_, '_>, hir: &hir::Expr<'_>) -> Option<i64> {
+        match hir.kind {
+            hir::ExprKind::Lit(hir::Lit::Integer(integer)) => Some(integer),
+            hir::ExprKind::Const(hash) => match cx.q.get_const_value(hash)? {
+                ConstValue::Integer(integer) => Some(*integer),
+                _ => None,
+            },
+            hir::ExprKind::Group(hir) => const_integer(cx, hir),
+            _ => None,
+        }
+    }
 }
 
 /// Assemble an expression.
@@ -1807,6 +2600,12 @@ fn expr_index<'hir>(
     span: &dyn Spanned,
     needs: Needs,
 ) -> compile::Result<Asm<'hir>> {
+    // Indexing a constant with a literal is folded into a constant.
+    if let Some(value) = const_index(cx, hir, span)? {
+        const_(cx, &value, span, needs)?;
+        return Ok(Asm::top(span));
+    }
+
     let guard = cx.scopes.child(span)?;
 
     let target = expr(cx, &hir.target, Needs::Value)?.apply_targeted(cx)?;
@@ -1821,7 +2620,40 @@ fn expr_index<'hir>(
     }
 
     cx.scopes.pop(guard, span)?;
-    Ok(Asm::top(span))
+    return Ok(Asm::top(span));
+
+    /// Try to fold `CONST[<integer>]` into the element being indexed.
+    ///
+    /// Indexes which are out of bounds are left to raise an error at runtime.
+    fn const_index(
+        cx: &mut Ctxt<'_, '_, '_>,
+        hir: &hir::ExprIndex<'_>,
+        span: &dyn Spanned,
+    ) -> compile::Result<Option<ConstValue>> {
+        let hir::ExprKind::Const(hash) = hir.target.kind else {
+            return Ok(None);
+        };
+
+        let hir::ExprKind::Lit(hir::Lit::Integer(index)) = hir.index.kind else {
+            return Ok(None);
+        };
+
+        let Ok(index) = usize::try_from(index) else {
+            return Ok(None);
+        };
+
+        let value = match cx.q.get_const_value(hash) {
+            Some(ConstValue::Vec(vec)) => vec.get(index),
+            Some(ConstValue::Tuple(tuple)) => tuple.get(index),
+            _ => None,
+        };
+
+        let Some(value) = value else {
+            return Ok(None);
+        };
+
+        Ok(Some(value.try_clone().with_span(span)?))
+    }
 }
 
 /// Assemble a let expression.
@@ -1871,16 +2703,71 @@ fn expr_match<'hir>(
     span: &dyn Spanned,
     needs: Needs,
 ) -> compile::Result<Asm<'hir>> {
+    emit_match_pattern(cx, &hir.expr, hir.branches, span, needs)?;
+    Ok(Asm::top(span))
+}
+
+/// Emit the dispatch sequence for matching `scrutinee` against a collection
+/// of branches.
+///
+/// Every branch tests its pattern and guard in order, jumping to its body on
+/// success and falling through to the next branch otherwise. If no branch
+/// unconditionally matches, the final fallthrough panics with an unmatched
+/// pattern.
+#[instrument(span = span)]
+fn emit_match_pattern<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    scrutinee: &'hir hir::Expr<'hir>,
+    branches: &'hir [hir::ExprMatchBranch<'hir>],
+    span: &dyn Spanned,
+    needs: Needs,
+) -> compile::Result<()> {
     let expected_scopes = cx.scopes.child(span)?;
 
-    expr(cx, &hir.expr, Needs::Value)?.apply(cx)?;
+    expr(cx, scrutinee, Needs::Value)?.apply(cx)?;
     // Offset of the expression.
     let offset = cx.scopes.alloc(span)?;
 
     let end_label = cx.asm.new_label("match_end");
-    let mut branches = Vec::new();
+    let mut labels = Vec::new();
+    let mut exhaustive = false;
 
-    for branch in hir.branches {
+    // Leading branches which only match a string literal are dispatched
+    // through a string table instead of being tested one by one. This is
+    // equivalent since such branches can't overlap with each other.
+    let mut strings = Vec::new();
+
+    for branch in branches {
+        let Some(string) = match_string_literal(branch) else {
+            break;
+        };
+
+        strings.try_push(string)?;
+    }
+
+    let threshold = cx.options.match_string_table;
+
+    if threshold == 0 || strings.len() < threshold {
+        strings.clear();
+    }
+
+    if !strings.is_empty() {
+        let mut entries = Vec::try_with_capacity(strings.len())?;
+
+        for (branch, string) in branches.iter().zip(strings.iter().copied()) {
+            let branch_label = cx.asm.new_label("match_branch");
+            let guard = cx.scopes.child(branch)?;
+            let scope = cx.scopes.pop(guard, branch)?;
+
+            entries.try_push((string.try_to_owned()?, branch_label.try_clone()?))?;
+            labels.try_push((branch_label, scope))?;
+        }
+
+        cx.asm.push(Inst::Copy { offset }, span)?;
+        cx.asm.jump_string_table(entries, span)?;
+    }
+
+    for branch in &branches[strings.len()..] {
         let span = branch;
 
         let branch_label = cx.asm.new_label("match_branch");
@@ -1896,7 +2783,7 @@ fn expr_match<'hir>(
             Ok(())
         };
 
-        pat(cx, &branch.pat, &match_false, &load)?;
+        let refutable = pat(cx, &branch.pat, &match_false, &load)?;
 
         let scope = if let Some(condition) = branch.condition {
             let span = condition;
@@ -1909,35 +2796,37 @@ fn expr_match<'hir>(
 
             cx.asm
                 .pop_and_jump_if_not(scope.local, &match_false, span)?;
-
-            cx.asm.jump(&branch_label, span)?;
             scope
         } else {
+            exhaustive |= !refutable;
             cx.scopes.pop(parent_guard, span)?
         };
 
         cx.asm.jump(&branch_label, span)?;
         cx.asm.label(&match_false)?;
 
-        branches.try_push((branch_label, scope))?;
+        labels.try_push((branch_label, scope))?;
     }
 
-    // what to do in case nothing matches and the pattern doesn't have any
-    // default match branch.
-    if needs.value() {
-        cx.asm.push(Inst::unit(), span)?;
+    // What to do in case nothing matches and the match doesn't have a
+    // branch which unconditionally matches.
+    if !exhaustive {
+        cx.asm.push(
+            Inst::Panic {
+                reason: PanicReason::UnmatchedPattern,
+            },
+            span,
+        )?;
     }
 
-    cx.asm.jump(&end_label, span)?;
-
-    let mut it = hir.branches.iter().zip(&branches).peekable();
+    let mut it = branches.iter().zip(labels).peekable();
 
     while let Some((branch, (label, scope))) = it.next() {
         let span = branch;
 
-        cx.asm.label(label)?;
+        cx.asm.label(&label)?;
 
-        let expected = cx.scopes.push(scope.try_clone()?)?;
+        let expected = cx.scopes.push(scope)?;
         expr(cx, &branch.body, needs)?.apply(cx)?;
         cx.clean_last_scope(span, expected, needs)?;
 
@@ -1950,7 +2839,24 @@ fn expr_match<'hir>(
 
     // pop the implicit scope where we store the anonymous match variable.
     cx.clean_last_scope(span, expected_scopes, needs)?;
-    Ok(Asm::top(span))
+    Ok(())
+}
+
+/// Get the string matched by a branch, if it only matches a string literal.
+fn match_string_literal<'hir>(branch: &hir::ExprMatchBranch<'hir>) -> Option<&'hir str> {
+    if branch.condition.is_some() {
+        return None;
+    }
+
+    let hir::PatKind::Lit(&hir::Expr {
+        kind: hir::ExprKind::Lit(hir::Lit::Str(string)),
+        ..
+    }) = branch.pat.kind
+    else {
+        return None;
+    };
+
+    Some(string)
 }
 
 /// Compile a literal object.
@@ -2188,7 +3094,7 @@ fn expr_select<'hir>(
 
         match branch.pat.kind {
             hir::PatKind::Path(&hir::PatPathKind::Ident(name)) => {
-                cx.scopes.define(hir::Name::Str(name), &branch.pat)?;
+                define_named(cx, name, &branch.pat)?;
             }
             hir::PatKind::Ignore => {
                 cx.asm.push(Inst::Pop, &branch.body)?;
@@ -2518,6 +3424,19 @@ fn local<'hir>(
         Ok(())
     };
 
+    // A plain binding can't fail, so the value can be moved into a slot which
+    // is no longer in use.
+    if let hir::PatKind::Path(&hir::PatPathKind::Ident(name)) = hir.pat.kind {
+        load(cx, Needs::Value)?;
+        declare_named(cx, name, &hir.pat)?;
+
+        if needs.value() {
+            cx.asm.push(Inst::unit(), hir)?;
+        }
+
+        return Ok(Asm::top(hir));
+    }
+
     let false_label = cx.asm.new_label("let_panic");
 
     if pat(cx, &hir.pat, &false_label, &load)? {
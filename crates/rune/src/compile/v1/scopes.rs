@@ -24,6 +24,8 @@ pub struct Var<'hir> {
     span: &'hir dyn Spanned,
     /// Variable has been taken at the given position.
     moved_at: Option<&'hir dyn Spanned>,
+    /// The number of variables with the same name this variable shadows.
+    shadows: usize,
 }
 
 impl<'hir> fmt::Debug for Var<'hir> {
@@ -33,6 +35,7 @@ impl<'hir> fmt::Debug for Var<'hir> {
             .field("name", &self.name)
             .field("span", &self.span.span())
             .field("moved_at", &self.moved_at.map(|s| s.span()))
+            .field("shadows", &self.shadows)
             .finish()
     }
 }
@@ -86,6 +89,9 @@ pub(crate) struct Layer<'hir> {
     pub(crate) total: usize,
     /// The number of variables local to this scope.
     pub(crate) local: usize,
+    /// Slots in this scope which belong to variables that are no longer live,
+    /// and can be reused by subsequent declarations.
+    free: Vec<usize>,
 }
 
 impl<'hir> Layer<'hir> {
@@ -95,15 +101,29 @@ impl<'hir> Layer<'hir> {
             variables: HashMap::new(),
             total: 0,
             local: 0,
+            free: Vec::new(),
         }
     }
 
+    /// Insert a variable, marking the slot of any variable it shadows in this
+    /// scope as free since it can no longer be referenced.
+    fn insert(&mut self, name: hir::Name<'hir>, var: Var<'hir>) -> alloc::Result<()> {
+        if let Some(old) = self.variables.try_insert(name, var)? {
+            if old.moved_at.is_none() {
+                self.free.try_push(old.offset)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Construct a new child scope.
     fn child(&self) -> Self {
         Self {
             variables: HashMap::new(),
             total: self.total,
             local: 0,
+            free: Vec::new(),
         }
     }
 }
@@ -194,6 +214,9 @@ impl<'hir> Scopes<'hir> {
                 }
 
                 var.moved_at = Some(span);
+                // A moved variable can never be used again, so its slot is
+                // free to be reused.
+                layer.free.try_push(var.offset)?;
                 return Ok(var);
             }
         }
@@ -211,6 +234,13 @@ impl<'hir> Scopes<'hir> {
         name: hir::Name<'hir>,
         span: &'hir dyn Spanned,
     ) -> compile::Result<usize> {
+        let shadows = self
+            .layers
+            .iter()
+            .rev()
+            .find_map(|layer| layer.variables.get(&name))
+            .map_or(0, |var| var.shadows + 1);
+
         let Some(layer) = self.layers.last_mut() else {
             return Err(compile::Error::msg(span, "Missing head layer"));
         };
@@ -224,14 +254,87 @@ impl<'hir> Scopes<'hir> {
             name,
             span,
             moved_at: None,
+            shadows,
         };
 
         layer.total += 1;
         layer.local += 1;
-        layer.variables.try_insert(name, local)?;
+        layer.insert(name, local)?;
         Ok(offset)
     }
 
+    /// Declare a new variable from the value at the top of the stack.
+    ///
+    /// Unlike [define][Scopes::define], this reuses the slot of a variable in
+    /// the current scope which is no longer live if there is one, by moving the
+    /// value into it.
+    #[tracing::instrument(skip_all, fields(variable, name))]
+    pub(crate) fn declare(
+        &mut self,
+        asm: &mut Assembly,
+        name: hir::Name<'hir>,
+        span: &'hir dyn Spanned,
+    ) -> compile::Result<usize> {
+        let shadows = self
+            .layers
+            .iter()
+            .rev()
+            .find_map(|layer| layer.variables.get(&name))
+            .map_or(0, |var| var.shadows + 1);
+
+        let Some(layer) = self.layers.last_mut() else {
+            return Err(compile::Error::msg(span, "Missing head layer"));
+        };
+
+        // The value being declared has already been evaluated, so a variable
+        // it shadows in this scope is dead from here on.
+        if let Some(old) = layer.variables.remove(&name) {
+            if old.moved_at.is_none() {
+                layer.free.try_push(old.offset)?;
+            }
+        }
+
+        let Some(offset) = layer.free.pop() else {
+            return self.define(name, span);
+        };
+
+        tracing::trace!(?layer, offset, "reusing slot");
+
+        asm.push_with_comment(
+            Inst::Replace { offset },
+            span,
+            &format_args!("var `{name}`; reuse slot"),
+        )?;
+
+        let local = Var {
+            offset,
+            name,
+            span,
+            moved_at: None,
+            shadows,
+        };
+
+        let Some(layer) = self.layers.last_mut() else {
+            return Err(compile::Error::msg(span, "Missing head layer"));
+        };
+
+        layer.insert(name, local)?;
+        Ok(offset)
+    }
+
+    /// Get the number of variables named `name` which are shadowed by the one
+    /// currently in scope, across all live scopes.
+    ///
+    /// Returns `0` if the variable doesn't shadow anything, or if there's no
+    /// such variable.
+    pub(crate) fn shadow_count(&self, name: &str) -> usize {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|layer| layer.variables.get(&hir::Name::Str(name)))
+            .map_or(0, |var| var.shadows)
+    }
+
     /// Declare an anonymous variable.
     #[tracing::instrument(skip_all)]
     pub(crate) fn alloc(&mut self, span: &dyn Spanned) -> compile::Result<usize> {
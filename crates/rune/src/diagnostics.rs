@@ -302,6 +302,29 @@ impl Diagnostics {
         )
     }
 
+    /// Indicate that a variable shadows too many other variables with the same
+    /// name.
+    ///
+    /// Like `let a = 1; let a = a + 1; let a = a + 1; let a = a + 1;`.
+    pub(crate) fn excessive_shadowing(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+        context: Option<Span>,
+        name: &str,
+        count: usize,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::ExcessiveShadowing {
+                span: span.span(),
+                context,
+                name: name.try_into()?,
+                count,
+            },
+        )
+    }
+
     /// Add a warning about using a deprecated function
     pub(crate) fn runtime_used_deprecated(&mut self, ip: usize, hash: Hash) -> alloc::Result<()> {
         self.runtime_warning(ip, RuntimeWarningDiagnosticKind::UsedDeprecated { hash })
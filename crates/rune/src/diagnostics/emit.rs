@@ -218,7 +218,10 @@ impl VmError {
                         format!("Right hand side has type `{}`", rhs),
                     ]);
                 }
-                VmErrorKind::BadArgumentCount { actual, expected } => {
+                VmErrorKind::BadArgumentCount { actual, expected }
+                | VmErrorKind::BadFunctionArgumentCount {
+                    actual, expected, ..
+                } => {
                     notes.extend([
                         format!("Expected `{}`", expected),
                         format!("Got `{}`", actual),
@@ -673,7 +676,7 @@ where
         notes: &mut ::rust_alloc::vec::Vec<rust_alloc::string::String>,
     ) -> Result<(), EmitError> {
         match kind {
-            ErrorKind::ImportCycle { path } => {
+            ErrorKind::Cycle { path } => {
                 let mut it = path.iter();
                 let last = it.next_back();
 
@@ -751,6 +754,12 @@ where
                         .with_message("Object being defined here"),
                 );
             }
+            ErrorKind::DuplicateDiscriminant { existing, .. } => {
+                labels.push(
+                    d::Label::secondary(this.source_id(), existing.range())
+                        .with_message("Previously used here"),
+                );
+            }
             ErrorKind::ModAlreadyLoaded { existing, .. } => {
                 let (existing_source_id, existing_span) = *existing;
 
@@ -40,7 +40,8 @@ impl WarningDiagnostic {
             | WarningDiagnosticKind::RemoveTupleCallParams { context, .. }
             | WarningDiagnosticKind::NotUsed { context, .. }
             | WarningDiagnosticKind::UsedDeprecated { context, .. }
-            | WarningDiagnosticKind::TemplateWithoutExpansions { context, .. } => *context,
+            | WarningDiagnosticKind::TemplateWithoutExpansions { context, .. }
+            | WarningDiagnosticKind::ExcessiveShadowing { context, .. } => *context,
             WarningDiagnosticKind::UnnecessarySemiColon { .. } => None,
         }
     }
@@ -56,6 +57,7 @@ impl Spanned for WarningDiagnostic {
             WarningDiagnosticKind::RemoveTupleCallParams { span, .. } => *span,
             WarningDiagnosticKind::UnnecessarySemiColon { span, .. } => *span,
             WarningDiagnosticKind::UsedDeprecated { span, .. } => *span,
+            WarningDiagnosticKind::ExcessiveShadowing { span, .. } => *span,
         }
     }
 }
@@ -125,6 +127,17 @@ pub enum WarningDiagnosticKind {
         /// The defined deprecation message
         message: String,
     },
+    /// A variable shadows too many other variables with the same name.
+    ExcessiveShadowing {
+        /// The span of the variable.
+        span: Span,
+        /// The context in which it is defined.
+        context: Option<Span>,
+        /// The name of the variable.
+        name: String,
+        /// The number of variables it shadows.
+        count: usize,
+    },
 }
 
 impl fmt::Display for WarningDiagnosticKind {
@@ -147,6 +160,9 @@ impl fmt::Display for WarningDiagnosticKind {
             WarningDiagnosticKind::UsedDeprecated { .. } => {
                 write!(f, "Used deprecated function")
             }
+            WarningDiagnosticKind::ExcessiveShadowing { name, count, .. } => {
+                write!(f, "Variable `{name}` shadows {count} other variables")
+            }
         }
     }
 }
@@ -365,6 +365,7 @@ impl<'a> Printer<'a> {
             attributes,
             name,
             body,
+            discriminant,
         } = ast;
 
         for attribute in attributes {
@@ -376,6 +377,12 @@ impl<'a> Printer<'a> {
 
         self.visit_variant_body(body)?;
 
+        if let Some((eq, expr)) = discriminant {
+            self.writer.write_unspanned(" ")?;
+            self.writer.write_spanned_raw(eq.span, false, true)?;
+            self.visit_expr(expr)?;
+        }
+
         Ok(())
     }
 
@@ -429,6 +436,7 @@ impl<'a> Printer<'a> {
             attributes,
             visibility,
             name,
+            default,
         } = ast;
 
         for attribute in attributes {
@@ -439,6 +447,12 @@ impl<'a> Printer<'a> {
         self.emit_visibility(visibility)?;
         self.writer.write_spanned_raw(name.span, false, false)?;
 
+        if let Some((eq, expr)) = default {
+            self.writer.write_unspanned(" ")?;
+            self.writer.write_spanned_raw(eq.span, false, true)?;
+            self.visit_expr(expr)?;
+        }
+
         Ok(())
     }
 
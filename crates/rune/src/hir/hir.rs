@@ -643,6 +643,8 @@ pub(crate) struct FieldAssign<'hir> {
     pub(crate) assign: Expr<'hir>,
     /// The position of the field in its containing type declaration.
     pub(crate) position: Option<usize>,
+    /// Whether the field was omitted and filled in with its default value.
+    pub(crate) is_default: bool,
 }
 
 /// A literal vector.
@@ -5,7 +5,7 @@ use num::ToPrimitive;
 
 use crate::alloc::prelude::*;
 use crate::alloc::try_format;
-use crate::alloc::{self, Box, HashMap, HashSet};
+use crate::alloc::{self, Box, HashMap, HashSet, Vec};
 use crate::ast::{self, Spanned};
 use crate::compile::meta;
 use crate::compile::{self, DynLocation, ErrorKind, Item, ItemId, WithSpan};
@@ -14,6 +14,7 @@ use crate::hir;
 use crate::indexing;
 use crate::parse::Resolve;
 use crate::query::{self, Build, BuildEntry, GenericsParameters, Named, Query};
+use crate::runtime::debug::{DebugArgs, DebugSignature};
 use crate::runtime::ConstValue;
 use crate::runtime::{Type, TypeCheck};
 use crate::SourceId;
@@ -370,7 +371,9 @@ pub(crate) fn expr_object<'hir>(
     let span = ast;
     let mut keys_dup = HashMap::new();
 
-    let assignments = &mut *iter!(&ast.assignments, |(ast, _)| {
+    let mut assignments = Vec::try_with_capacity(ast.assignments.len())?;
+
+    for (ast, _) in &ast.assignments {
         let key = object_key(cx, &ast.key)?;
 
         if let Some(_existing) = keys_dup.try_insert(key.1, key.0)? {
@@ -404,14 +407,15 @@ pub(crate) fn expr_object<'hir>(
             }
         };
 
-        hir::FieldAssign {
+        assignments.try_push(hir::FieldAssign {
             key: (key.0.span(), key.1),
             assign,
             position: None,
-        }
-    });
+            is_default: false,
+        })?;
+    }
 
-    let mut check_object_fields = |fields: &HashMap<_, meta::FieldMeta>, item: &Item| {
+    let mut check_object_fields = |fields: &HashMap<Box<str>, meta::FieldMeta>, item: &Item| {
         let mut fields = fields.try_clone()?;
 
         for assign in assignments.iter_mut() {
@@ -431,11 +435,38 @@ pub(crate) fn expr_object<'hir>(
             };
         }
 
-        if let Some(field) = fields.into_keys().next() {
+        let mut missing = Vec::new();
+
+        // Omitted fields are filled in from their default value if they have
+        // one.
+        for (field, meta) in fields {
+            let Some(hash) = meta.default else {
+                missing.try_push((meta.position, field))?;
+                continue;
+            };
+
+            assignments.try_push(hir::FieldAssign {
+                key: (span.span(), alloc_str!(&field)),
+                assign: hir::Expr {
+                    span: span.span(),
+                    kind: hir::ExprKind::Const(hash),
+                },
+                position: Some(meta.position),
+                is_default: true,
+            })?;
+        }
+
+        if !missing.is_empty() {
+            missing.sort_by_key(|&(position, _)| position);
+
             return Err(compile::Error::new(
                 span,
                 ErrorKind::LitObjectMissingField {
-                    field,
+                    fields: missing
+                        .into_iter()
+                        .map(|(_, field)| field)
+                        .try_collect::<Vec<_>>()?
+                        .try_into_boxed_slice()?,
                     item: item.try_to_owned()?,
                 },
             ));
@@ -496,7 +527,7 @@ pub(crate) fn expr_object<'hir>(
 
     Ok(hir::ExprKind::Object(alloc!(hir::ExprObject {
         kind,
-        assignments,
+        assignments: iter!(assignments),
     })))
 }
 
@@ -603,7 +634,10 @@ pub(crate) fn expr<'hir>(
                 }
             }),
         })),
-        ast::Expr::Call(ast) => hir::ExprKind::Call(alloc!(expr_call(cx, ast)?)),
+        ast::Expr::Call(ast) => match expr_size_of(cx, ast)? {
+            Some(size) => hir::ExprKind::Lit(hir::Lit::Integer(size)),
+            None => hir::ExprKind::Call(alloc!(expr_call(cx, ast)?)),
+        },
         ast::Expr::FieldAccess(ast) => {
             hir::ExprKind::FieldAccess(alloc!(expr_field_access(cx, ast)?))
         }
@@ -1187,23 +1221,12 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
 
                 let path = 'path: {
                     if let Some(meta) = cx.try_lookup_meta(&ast, named.item, &parameters)? {
-                        match meta.kind {
-                            meta::Kind::Const => {
-                                let Some(const_value) = cx.q.get_const_value(meta.hash) else {
-                                    return Err(compile::Error::msg(
-                                        ast,
-                                        try_format!("Missing constant for hash {}", meta.hash),
-                                    ));
-                                };
-
-                                let const_value = const_value.try_clone().with_span(ast)?;
-                                return pat_const_value(cx, &const_value, ast);
-                            }
-                            _ => {
-                                if let Some((0, kind)) = tuple_match_for(cx, &meta) {
-                                    break 'path hir::PatPathKind::Kind(alloc!(kind));
-                                }
-                            }
+                        if let Some((0, kind)) = tuple_match_for(cx, &meta) {
+                            break 'path hir::PatPathKind::Kind(alloc!(kind));
+                        }
+
+                        if let Some(const_value) = meta.try_into_const() {
+                            return pat_const_value(cx, &const_value, ast);
                         }
                     };
 
@@ -1265,6 +1288,7 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
                             ErrorKind::UnsupportedArgumentCount {
                                 expected: args,
                                 actual: count,
+                                signature: None,
                             },
                         ));
                     }
@@ -1716,6 +1740,95 @@ fn generics_parameters(
     Ok(parameters)
 }
 
+/// Resolve a call on the form `size_of::<T>()` where `T` is a primitive type
+/// into its size on the target architecture.
+///
+/// The called path must resolve to `std::mem::size_of`, so local items which
+/// share its name are called as usual.
+///
+/// Returns `None` if the call doesn't have this form.
+fn expr_size_of(cx: &mut Ctxt<'_, '_, '_>, ast: &ast::ExprCall) -> compile::Result<Option<i64>> {
+    const SIZE_OF: Hash = ::rune_macros::hash!(::std::mem::size_of);
+
+    let ast::Expr::Path(path) = &*ast.expr else {
+        return Ok(None);
+    };
+
+    if !ast.args.is_empty() || path.trailing.is_some() {
+        return Ok(None);
+    }
+
+    let Some((_, ast::PathSegment::Generics(generics))) = path.rest.last() else {
+        return Ok(None);
+    };
+
+    let [(
+        ast::PathSegmentExpr {
+            expr: ast::Expr::Path(ty),
+        },
+        _,
+    )] = generics.as_slice()
+    else {
+        return Ok(None);
+    };
+
+    let named = cx.q.convert_path(path)?;
+
+    if cx.q.pool.item_type_hash(named.item) != SIZE_OF {
+        return Ok(None);
+    }
+
+    let Some(ty) = ty.try_as_ident() else {
+        return Ok(None);
+    };
+
+    let size = match ty.resolve(resolve_context!(cx.q))? {
+        "u8" | "i8" | "bool" => 1,
+        "u16" | "i16" => 2,
+        "u32" | "i32" | "f32" | "char" => 4,
+        "u64" | "i64" | "f64" => 8,
+        "u128" | "i128" => 16,
+        "usize" | "isize" => cx.q.options.pointer_width(),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(size as i64))
+}
+
+/// Construct the signature of a native function, used when reporting that it
+/// was called with the wrong number of arguments.
+fn native_signature(
+    cx: &Ctxt<'_, '_, '_>,
+    meta: &meta::Meta,
+    args: usize,
+) -> compile::Result<DebugSignature> {
+    let item = cx.q.pool.item(meta.item_meta.item).try_to_owned()?;
+
+    #[cfg(feature = "doc")]
+    let names =
+        cx.q.context
+            .lookup_meta_by_hash(meta.hash)
+            .find_map(|meta| meta.docs.args())
+            .filter(|names| names.len() == args);
+
+    #[cfg(not(feature = "doc"))]
+    let names = None::<&[alloc::String]>;
+
+    let mut debug_args = Vec::new();
+
+    for n in 0..args {
+        let name = names
+            .and_then(|names| names.get(n))
+            .map_or("*", |name| name.as_str());
+        debug_args.try_push(Box::try_from(name)?)?;
+    }
+
+    Ok(DebugSignature::new(
+        item,
+        DebugArgs::Named(debug_args.try_into_boxed_slice()?),
+    ))
+}
+
 /// Convert into a call expression.
 #[instrument(span = ast)]
 fn expr_call<'hir>(
@@ -1772,6 +1885,7 @@ fn expr_call<'hir>(
                                 ErrorKind::UnsupportedArgumentCount {
                                     expected: 0,
                                     actual: ast.args.len(),
+                                    signature: None,
                                 },
                             ));
                         }
@@ -1790,6 +1904,7 @@ fn expr_call<'hir>(
                                 ErrorKind::UnsupportedArgumentCount {
                                     expected: *args,
                                     actual: ast.args.len(),
+                                    signature: None,
                                 },
                             ));
                         }
@@ -1803,7 +1918,7 @@ fn expr_call<'hir>(
                             )?;
                         }
                     }
-                    meta::Kind::Function { .. } => {
+                    meta::Kind::Function { signature, .. } => {
                         if let Some(message) = cx.q.lookup_deprecation(meta.hash) {
                             cx.q.diagnostics.used_deprecated(
                                 cx.source_id,
@@ -1812,6 +1927,22 @@ fn expr_call<'hir>(
                                 message.try_into()?,
                             )?;
                         };
+
+                        // Native functions which aren't variadic register the
+                        // number of arguments they accept, so calls to them
+                        // can be checked up front.
+                        if let (true, Some(args)) = (meta.context, signature.args) {
+                            if args != ast.args.len() {
+                                return Err(compile::Error::new(
+                                    &ast.args,
+                                    ErrorKind::UnsupportedArgumentCount {
+                                        expected: args,
+                                        actual: ast.args.len(),
+                                        signature: Some(native_signature(cx, &meta, args)?),
+                                    },
+                                ));
+                            }
+                        }
                     }
                     meta::Kind::ConstFn { id, .. } => {
                         let id = *id;
@@ -4,7 +4,7 @@ mod scopes;
 
 use crate as rune;
 use crate::alloc::prelude::*;
-use crate::alloc::Box;
+use crate::alloc::{Box, Vec};
 use crate::ast::{self, Span, Spanned};
 use crate::compile::meta;
 use crate::compile::{ItemId, ItemMeta};
@@ -37,7 +37,7 @@ impl Entry {
 #[derive(Debug, TryClone)]
 pub(crate) enum Indexed {
     /// An enum.
-    Enum,
+    Enum(Enum),
     /// A struct.
     Struct(Struct),
     /// A variant.
@@ -67,7 +67,6 @@ pub(crate) enum FunctionAst {
 
 impl FunctionAst {
     /// Get the number of arguments for the function ast.
-    #[cfg(feature = "doc")]
     pub(crate) fn args(&self) -> usize {
         match self {
             FunctionAst::Empty(..) => 0,
@@ -104,6 +103,20 @@ pub(crate) struct Import {
     pub(crate) wildcard: bool,
 }
 
+#[derive(Debug, TryClone)]
+pub(crate) struct Enum {
+    /// The discriminants of the variants in declaration order.
+    pub(crate) discriminants: Vec<Discriminant>,
+}
+
+#[derive(Debug, TryClone)]
+pub(crate) struct Discriminant {
+    /// The span of the variant the discriminant belongs to.
+    pub(crate) span: Span,
+    /// The explicit discriminant expression, if any.
+    pub(crate) expr: Option<Box<ast::Expr>>,
+}
+
 #[derive(Debug, TryClone)]
 pub(crate) struct Struct {
     /// The ast of the struct.
@@ -115,7 +128,7 @@ pub(crate) struct Variant {
     /// Id of of the enum type.
     pub(crate) enum_id: NonZeroId,
     /// Ast for declaration.
-    pub(crate) ast: ast::ItemVariant,
+    pub(crate) ast: Box<ast::ItemVariant>,
     /// The index of the variant in its source.
     pub(crate) index: usize,
 }
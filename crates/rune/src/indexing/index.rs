@@ -27,6 +27,9 @@ use rune_macros::instrument;
 /// Macros are only allowed to expand recursively into other macros 64 times.
 const MAX_MACRO_RECURSION: usize = 64;
 
+/// Modules loaded from separate sources are only allowed to nest 64 times.
+const MAX_MOD_SOURCE_DEPTH: usize = 64;
+
 pub(crate) struct Indexer<'a, 'arena> {
     /// Query engine.
     pub(crate) q: Query<'a, 'arena>,
@@ -455,11 +458,51 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
             ));
         };
 
+        // Sources without a path can't be identified as already loaded below,
+        // so bound the chain of modules loaded from separate sources instead.
+        if let Some(loaded) = self.loaded.as_deref() {
+            let mut depth = 0;
+            let mut current = self.q.pool.module(mod_item).parent;
+
+            while let Some(module) = current {
+                if loaded.contains_key(&module) {
+                    depth += 1;
+                }
+
+                current = self.q.pool.module(module).parent;
+            }
+
+            if depth >= MAX_MOD_SOURCE_DEPTH {
+                return Err(compile::Error::new(
+                    &*item_mod,
+                    ErrorKind::MaxModSourceDepth {
+                        item: self.q.pool.module_item(mod_item).try_to_owned()?,
+                        max: MAX_MOD_SOURCE_DEPTH,
+                    },
+                ));
+            }
+        }
+
         let source =
             self.q
                 .source_loader
                 .load(root, self.q.pool.module_item(mod_item), &*item_mod)?;
 
+        // A source which has already been loaded from the same path would
+        // declare the same modules again, recursing indefinitely.
+        if let Some(path) = source.path() {
+            let mut ids = self.q.sources.source_ids();
+
+            if ids.any(|id| self.q.sources.path(id) == Some(path)) {
+                return Err(compile::Error::new(
+                    &*item_mod,
+                    ErrorKind::ModSourceAlreadyLoaded {
+                        item: self.q.pool.module_item(mod_item).try_to_owned()?,
+                    },
+                ));
+            }
+        }
+
         if let Some(loaded) = self.loaded.as_mut() {
             if let Some(_existing) =
                 loaded.try_insert(mod_item, (self.source_id, item_mod.span()))?
@@ -1363,7 +1406,7 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
         &docs,
     )?;
 
-    idx.q.index_enum(enum_item)?;
+    let mut discriminants = Vec::new();
 
     for (index, (mut variant, _)) in ast.variants.drain().enumerate() {
         let mut p = attrs::Parser::new(&variant.attributes)?;
@@ -1420,12 +1463,39 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
             }
         }
 
+        field_defaults(idx, &mut variant.body)?;
+
+        let expr = match variant.discriminant.take() {
+            Some((_, mut ast)) => {
+                if !matches!(variant.body, ast::Fields::Empty) {
+                    return Err(compile::Error::msg(
+                        &ast,
+                        "Discriminants are only supported on unit variants",
+                    ));
+                }
+
+                let last = idx.nested_item.replace(variant.name.span());
+                expr(idx, &mut ast)?;
+                idx.nested_item = last;
+                Some(Box::try_new(ast)?)
+            }
+            None => None,
+        };
+
+        discriminants.try_push(indexing::Discriminant {
+            span: variant.name.span(),
+            expr,
+        })?;
+
         idx.item = idx_item;
         idx.items.pop(guard).with_span(&variant)?;
         idx.q
             .index_variant(item_meta, enum_item.id, variant, index)?;
     }
 
+    idx.q
+        .index_enum(enum_item, indexing::Enum { discriminants })?;
+
     idx.item = idx_item;
     idx.items.pop(guard).with_span(&ast)?;
     Ok(())
@@ -1494,12 +1564,38 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
         }
     }
 
+    field_defaults(idx, &mut ast.body)?;
+
     idx.item = idx_item;
     idx.items.pop(guard).with_span(&ast)?;
     idx.q.index_struct(item_meta, Box::try_new(ast)?)?;
     Ok(())
 }
 
+/// Index the default values of named fields.
+fn field_defaults(idx: &mut Indexer<'_, '_>, body: &mut ast::Fields) -> compile::Result<()> {
+    let named = matches!(body, ast::Fields::Named(..));
+
+    for (field, _) in body.fields_mut() {
+        let Some((_, ast)) = &mut field.default else {
+            continue;
+        };
+
+        if !named {
+            return Err(compile::Error::msg(
+                &*ast,
+                "Default values are only supported on named fields",
+            ));
+        }
+
+        let last = idx.nested_item.replace(field.name.span());
+        expr(idx, ast)?;
+        idx.nested_item = last;
+    }
+
+    Ok(())
+}
+
 #[instrument(span = ast)]
 fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Result<()> {
     if let Some(first) = ast.attributes.first() {
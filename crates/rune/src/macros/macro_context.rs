@@ -284,6 +284,20 @@ impl<'a, 'b, 'arena> MacroContext<'a, 'b, 'arena> {
     pub fn input_span(&self) -> Span {
         self.input_span
     }
+
+    /// The name of the source and the 1-indexed line at which the macro is
+    /// being called.
+    pub(crate) fn macro_location(&self) -> Option<(&str, usize)> {
+        let source = self.idx.q.sources.get(self.idx.source_id)?;
+        let (line, _) = source.pos_to_utf16cu_linecol(self.macro_span.start.into_usize());
+        Some((source.name(), line + 1))
+    }
+
+    /// Access the text of the given span in the source the macro is being
+    /// called from.
+    pub(crate) fn source_text(&self, span: Span) -> Option<&str> {
+        self.idx.q.sources.source(self.idx.source_id, span)
+    }
 }
 
 pub struct Stringify<'cx, 'a, 'b, 'arena> {
@@ -209,7 +209,6 @@ pub(crate) struct ModuleFunction {
     pub(crate) handler: Arc<FunctionHandler>,
     #[cfg(feature = "doc")]
     pub(crate) is_async: bool,
-    #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
     #[cfg(feature = "doc")]
     pub(crate) return_type: Option<FullTypeOf>,
@@ -322,7 +321,6 @@ pub struct ItemFnMut<'a> {
     deprecated: &'a mut Option<Box<str>>,
     #[cfg(feature = "doc")]
     is_async: &'a mut bool,
-    #[cfg(feature = "doc")]
     args: &'a mut Option<usize>,
     #[cfg(feature = "doc")]
     return_type: &'a mut Option<FullTypeOf>,
@@ -370,12 +368,12 @@ impl ItemFnMut<'_> {
     }
 
     /// Indicate the number of arguments this function accepts.
-    pub fn args(self, #[cfg_attr(not(feature = "doc"), allow(unused))] args: usize) -> Self {
-        #[cfg(feature = "doc")]
-        {
-            *self.args = Some(args);
-        }
-
+    ///
+    /// This is used to check the number of arguments passed in calls to the
+    /// function, so it should only be set for functions which don't accept a
+    /// variable number of arguments.
+    pub fn args(self, args: usize) -> Self {
+        *self.args = Some(args);
         self
     }
 
@@ -56,7 +56,6 @@ pub struct FunctionData {
     pub(crate) handler: Arc<FunctionHandler>,
     #[cfg(feature = "doc")]
     pub(crate) is_async: bool,
-    #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
     #[cfg(feature = "doc")]
     pub(crate) return_type: Option<FullTypeOf>,
@@ -71,7 +70,6 @@ impl FunctionData {
             handler,
             #[cfg(feature = "doc")]
             is_async: false,
-            #[cfg(feature = "doc")]
             args: None,
             #[cfg(feature = "doc")]
             return_type: None,
@@ -94,7 +92,6 @@ impl FunctionData {
             handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
             #[cfg(feature = "doc")]
             is_async: K::is_async(),
-            #[cfg(feature = "doc")]
             args: Some(F::args()),
             #[cfg(feature = "doc")]
             return_type: F::Return::maybe_type_of(),
@@ -265,7 +262,6 @@ pub struct AssociatedFunctionData {
     pub(crate) handler: Arc<FunctionHandler>,
     #[cfg(feature = "doc")]
     pub(crate) is_async: bool,
-    #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
     #[cfg(feature = "doc")]
     pub(crate) return_type: Option<FullTypeOf>,
@@ -280,7 +276,6 @@ impl AssociatedFunctionData {
             handler,
             #[cfg(feature = "doc")]
             is_async: false,
-            #[cfg(feature = "doc")]
             args: None,
             #[cfg(feature = "doc")]
             return_type: None,
@@ -302,7 +297,6 @@ impl AssociatedFunctionData {
             handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
             #[cfg(feature = "doc")]
             is_async: K::is_async(),
-            #[cfg(feature = "doc")]
             args: Some(F::args()),
             #[cfg(feature = "doc")]
             return_type: F::Return::maybe_type_of(),
@@ -324,7 +318,6 @@ impl AssociatedFunctionData {
             handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
             #[cfg(feature = "doc")]
             is_async: K::is_async(),
-            #[cfg(feature = "doc")]
             args: Some(F::args()),
             #[cfg(feature = "doc")]
             return_type: F::Return::maybe_type_of(),
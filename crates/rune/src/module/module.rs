@@ -1741,7 +1741,6 @@ impl Module {
                 handler: data.handler,
                 #[cfg(feature = "doc")]
                 is_async: data.is_async,
-                #[cfg(feature = "doc")]
                 args: data.args,
                 #[cfg(feature = "doc")]
                 return_type: data.return_type,
@@ -1752,7 +1751,6 @@ impl Module {
 
         let last = self.items.last_mut().unwrap();
 
-        #[cfg(feature = "doc")]
         let last_fn = match &mut last.kind {
             ModuleItemKind::Function(f) => f,
             _ => unreachable!(),
@@ -1764,7 +1762,6 @@ impl Module {
             deprecated: &mut last.common.deprecated,
             #[cfg(feature = "doc")]
             is_async: &mut last_fn.is_async,
-            #[cfg(feature = "doc")]
             args: &mut last_fn.args,
             #[cfg(feature = "doc")]
             return_type: &mut last_fn.return_type,
@@ -1791,7 +1788,6 @@ impl Module {
                 handler: data.handler,
                 #[cfg(feature = "doc")]
                 is_async: data.is_async,
-                #[cfg(feature = "doc")]
                 args: data.args,
                 #[cfg(feature = "doc")]
                 return_type: data.return_type,
@@ -1802,7 +1798,6 @@ impl Module {
 
         let last = self.associated.last_mut().unwrap();
 
-        #[cfg(feature = "doc")]
         let last_fn = match &mut last.kind {
             ModuleAssociatedKind::Function(f) => f,
             _ => unreachable!(),
@@ -1814,7 +1809,6 @@ impl Module {
             deprecated: &mut last.common.deprecated,
             #[cfg(feature = "doc")]
             is_async: &mut last_fn.is_async,
-            #[cfg(feature = "doc")]
             args: &mut last_fn.args,
             #[cfg(feature = "doc")]
             return_type: &mut last_fn.return_type,
@@ -2,8 +2,8 @@
 
 use crate as rune;
 use crate::alloc::prelude::*;
-use crate::alloc::Vec;
-use crate::runtime::{Bytes, VmResult};
+use crate::alloc::{String, Vec};
+use crate::runtime::{Bytes, Iterator, Ref, TypeOf, Value, VmErrorKind, VmIntegerRepr, VmResult};
 use crate::{ContextError, Module};
 
 /// The bytes module.
@@ -20,6 +20,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.function_meta(extend)?;
     module.function_meta(extend_str)?;
     module.function_meta(pop)?;
+    module.function_meta(first)?;
     module.function_meta(last)?;
     module.function_meta(len)?;
     module.function_meta(is_empty)?;
@@ -29,6 +30,20 @@ pub fn module() -> Result<Module, ContextError> {
     module.function_meta(reserve_exact)?;
     module.function_meta(clone)?;
     module.function_meta(shrink_to_fit)?;
+    module.function_meta(get)?;
+    module.function_meta(slice)?;
+    module.function_meta(split_at)?;
+    module.function_meta(starts_with)?;
+    module.function_meta(ends_with)?;
+    module.function_meta(find)?;
+    module.function_meta(to_vec)?;
+    module.function_meta(to_hex)?;
+    module.function_meta(from_hex)?;
+    module.function_meta(iter)?;
+    module.function_meta(into_iter)?;
+    module.function_meta(index_get)?;
+    module.function_meta(partial_eq)?;
+    module.function_meta(eq)?;
     Ok(module)
 }
 
@@ -333,3 +348,332 @@ fn shrink_to_fit(this: &mut Bytes) -> VmResult<()> {
     vm_try!(this.shrink_to_fit());
     VmResult::Ok(())
 }
+
+/// Returns a byte or a byte array depending on the type of index.
+///
+/// - If given a position, returns the byte at that position or `None` if out
+///   of bounds.
+/// - If given a range, returns the bytes corresponding to that range, or
+///   `None` if out of bounds.
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"abcd";
+/// assert_eq!(bytes.get(1), Some(b'b'));
+/// assert_eq!(bytes.get(1..3), Some(b"bc"));
+/// assert_eq!(bytes.get(..), Some(b"abcd"));
+/// assert_eq!(bytes.get(4), None);
+/// assert_eq!(bytes.get(2..5), None);
+/// ```
+#[rune::function(instance)]
+fn get(this: &Bytes, index: Value) -> VmResult<Option<Value>> {
+    Bytes::index_get(this, index)
+}
+
+/// Returns a new byte array containing the bytes from `start` up to, but not
+/// including, `end`.
+///
+/// # Errors
+///
+/// Errors if `end` is larger than the length of the byte array, or if `start`
+/// is larger than `end`.
+///
+/// ```rune,should_panic
+/// let bytes = b"abcd";
+/// bytes.slice(2, 5);
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"abcd";
+/// assert_eq!(bytes.slice(1, 3), b"bc");
+/// assert_eq!(bytes.slice(4, 4), b"");
+/// ```
+#[rune::function(instance)]
+fn slice(this: &Bytes, start: usize, end: usize) -> VmResult<Bytes> {
+    if end > this.len() {
+        return VmResult::err(VmErrorKind::OutOfRange {
+            index: VmIntegerRepr::from(end),
+            length: VmIntegerRepr::from(this.len()),
+        });
+    }
+
+    if start > end {
+        return VmResult::err(VmErrorKind::SliceStartAfterEnd {
+            start: VmIntegerRepr::from(start),
+            end: VmIntegerRepr::from(end),
+        });
+    }
+
+    VmResult::Ok(vm_try!(Bytes::from_slice(&this[start..end])))
+}
+
+/// Divides the byte array into two at an index.
+///
+/// The first will contain all bytes from `[0, mid)` and the second will contain
+/// all bytes from `[mid, len)`.
+///
+/// # Errors
+///
+/// Errors if `mid` is larger than the length of the byte array.
+///
+/// ```rune,should_panic
+/// let bytes = b"abcd";
+/// bytes.split_at(5);
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"abcd";
+/// let (a, b) = bytes.split_at(1);
+/// assert_eq!(a, b"a");
+/// assert_eq!(b, b"bcd");
+/// ```
+#[rune::function(instance)]
+fn split_at(this: &Bytes, mid: usize) -> VmResult<(Bytes, Bytes)> {
+    if mid > this.len() {
+        return VmResult::err(VmErrorKind::OutOfRange {
+            index: VmIntegerRepr::from(mid),
+            length: VmIntegerRepr::from(this.len()),
+        });
+    }
+
+    let (a, b) = this.split_at(mid);
+    VmResult::Ok((vm_try!(Bytes::from_slice(a)), vm_try!(Bytes::from_slice(b))))
+}
+
+/// Returns `true` if `needle` is a prefix of the byte array.
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"\xf0\x9f\x92\x96 love";
+/// assert!(bytes.starts_with(b"\xf0\x9f"));
+/// assert!(bytes.starts_with(b""));
+/// assert!(!bytes.starts_with(b"love"));
+/// ```
+#[rune::function(instance)]
+fn starts_with(this: &Bytes, needle: &[u8]) -> bool {
+    this.starts_with(needle)
+}
+
+/// Returns `true` if `needle` is a suffix of the byte array.
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"\xf0\x9f\x92\x96 love";
+/// assert!(bytes.ends_with(b"love"));
+/// assert!(!bytes.ends_with(b"\xf0\x9f"));
+/// ```
+#[rune::function(instance)]
+fn ends_with(this: &Bytes, needle: &[u8]) -> bool {
+    this.ends_with(needle)
+}
+
+/// Returns the index of the first occurrence of `needle` in the byte array, or
+/// `None` if it doesn't occur.
+///
+/// An empty `needle` is found at index `0`.
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"hello \xf0\x9f\x92\x96 world";
+/// assert_eq!(bytes.find(b"\x9f\x92"), Some(7));
+/// assert_eq!(bytes.find(b"world"), Some(11));
+/// assert_eq!(bytes.find(b""), Some(0));
+/// assert_eq!(bytes.find(b"worlds"), None);
+/// ```
+#[rune::function(instance)]
+fn find(this: &Bytes, needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+
+    this.windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Copy the byte array into a vector of bytes.
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"abc";
+/// assert_eq!(bytes.to_vec(), [b'a', b'b', b'c']);
+/// ```
+#[rune::function(instance)]
+fn to_vec(this: &Bytes) -> VmResult<Vec<u8>> {
+    VmResult::Ok(vm_try!(Vec::try_from(this.as_slice())))
+}
+
+/// Encode the byte array as a string of lowercase hexadecimal digits.
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"\x00\x7f\xff";
+/// assert_eq!(bytes.to_hex(), "007fff");
+/// ```
+#[rune::function(instance)]
+fn to_hex(this: &Bytes) -> VmResult<String> {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let mut string = vm_try!(String::try_with_capacity(this.len() * 2));
+
+    for &b in this.as_slice() {
+        vm_try!(string.try_push(char::from(HEX[usize::from(b >> 4)])));
+        vm_try!(string.try_push(char::from(HEX[usize::from(b & 0xf)])));
+    }
+
+    VmResult::Ok(string)
+}
+
+/// Decode a string of hexadecimal digits into a byte array.
+///
+/// Both uppercase and lowercase digits are accepted. Returns `None` if the
+/// string has an odd length or contains a character which is not a hexadecimal
+/// digit.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(Bytes::from_hex("007fFF"), Some(b"\x00\x7f\xff"));
+/// assert_eq!(Bytes::from_hex("abc"), None);
+/// assert_eq!(Bytes::from_hex("zz"), None);
+/// ```
+#[rune::function(free, path = Bytes::from_hex)]
+fn from_hex(string: &str) -> VmResult<Option<Bytes>> {
+    fn digit(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    let string = string.as_bytes();
+
+    if string.len() % 2 != 0 {
+        return VmResult::Ok(None);
+    }
+
+    let mut bytes = vm_try!(Vec::try_with_capacity(string.len() / 2));
+
+    for pair in string.chunks_exact(2) {
+        let (Some(hi), Some(lo)) = (digit(pair[0]), digit(pair[1])) else {
+            return VmResult::Ok(None);
+        };
+
+        vm_try!(bytes.try_push((hi << 4) | lo));
+    }
+
+    VmResult::Ok(Some(Bytes::from_vec(bytes)))
+}
+
+/// Iterate over the bytes in the byte array.
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"abc";
+/// let it = bytes.iter();
+///
+/// assert_eq!(it.next(), Some(b'a'));
+/// assert_eq!(it.next_back(), Some(b'c'));
+/// assert_eq!(it.next(), Some(b'b'));
+/// assert_eq!(it.next(), None);
+/// ```
+#[rune::function(instance)]
+fn iter(this: Ref<Bytes>) -> Iterator {
+    Bytes::iter_ref(this)
+}
+
+/// Construct an iterator over the bytes in the byte array.
+///
+/// # Examples
+///
+/// ```rune
+/// let out = [];
+///
+/// for b in b"abc" {
+///     out.push(b);
+/// }
+///
+/// assert_eq!(out, [b'a', b'b', b'c']);
+/// ```
+#[rune::function(instance, protocol = INTO_ITER)]
+fn into_iter(this: Ref<Bytes>) -> Iterator {
+    Bytes::iter_ref(this)
+}
+
+/// Returns a byte or a byte array depending on the type of index.
+///
+/// # Panics
+///
+/// Panics if the specified `index` is out of range.
+///
+/// ```rune,should_panic
+/// let bytes = b"abcd";
+/// bytes[4];
+/// ```
+///
+/// ```rune,should_panic
+/// let bytes = b"abcd";
+/// bytes[2..5];
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"abcd";
+/// assert_eq!(bytes[1], b'b');
+/// assert_eq!(bytes[1..3], b"bc");
+/// ```
+#[rune::function(instance, protocol = INDEX_GET)]
+fn index_get(this: &Bytes, index: Value) -> VmResult<Value> {
+    let Some(value) = vm_try!(Bytes::index_get(this, index)) else {
+        return VmResult::err(VmErrorKind::MissingIndex {
+            target: Bytes::type_info(),
+        });
+    };
+
+    VmResult::Ok(value)
+}
+
+/// Perform a partial equality check with another byte array.
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = b"abc";
+///
+/// assert!(bytes == b"abc");
+/// assert!(bytes != b"abd");
+/// ```
+#[rune::function(instance, protocol = PARTIAL_EQ)]
+fn partial_eq(this: &[u8], other: &[u8]) -> bool {
+    this == other
+}
+
+/// Perform a total equality check with another byte array.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::ops::eq;
+///
+/// let bytes = b"abc";
+///
+/// assert!(eq(bytes, b"abc"));
+/// assert!(!eq(bytes, b"abd"));
+/// ```
+#[rune::function(instance, protocol = EQ)]
+fn eq(this: &[u8], other: &[u8]) -> bool {
+    this == other
+}
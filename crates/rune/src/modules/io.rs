@@ -6,6 +6,8 @@ use std::io::{self, Write as _};
 use crate as rune;
 #[cfg(feature = "std")]
 use crate::alloc::fmt::TryWrite;
+use crate::alloc::{try_format, Vec};
+use crate::ast::{self, Spanned};
 use crate::compile;
 use crate::macros::{quote, FormatArgs, MacroContext, TokenStream};
 use crate::parse::Parser;
@@ -97,30 +99,88 @@ fn dbg_impl(stack: &mut Stack, args: usize) -> VmResult<()> {
     VmResult::Ok(())
 }
 
-/// Debug print the given argument.
+/// Debug print the given arguments, along with their source location and the
+/// text of the expression that produced them.
 ///
 /// Everything in rune can be "debug printed" in one way or another. This is
 /// provided as a cheap an dirty way to introspect values.
 ///
-/// See also the [`dbg!`] macro.
+/// Each argument is evaluated exactly once and printed through the
+/// [`println()`] function on the form `[file:line] expr = value`. The macro
+/// then evaluates to the value of its argument, so it can be used inline.
+/// Multiple arguments evaluate to a tuple of their values, and calling it
+/// without arguments prints only the source location.
+///
+/// See also the [`dbg()`] function.
 ///
 /// # Examples
 ///
 /// ```rune
-/// let number = 10;
-/// let number = number * 4;
+/// let number = dbg!(10 * 4);
+/// assert_eq!(number, 40);
 ///
 /// let who = "World";
 /// let string = format!("Hello {}", who);
 ///
-/// dbg!(number, string);
+/// let (a, b) = dbg!(number, string);
+/// assert_eq!(a, 40);
+/// assert_eq!(b, "Hello World");
+///
+/// dbg!();
 /// ```
 #[rune::macro_(path = dbg)]
 pub(crate) fn dbg_macro(
     cx: &mut MacroContext<'_, '_, '_>,
     stream: &TokenStream,
 ) -> compile::Result<TokenStream> {
-    Ok(quote!(::std::io::dbg(#stream)).into_token_stream(cx)?)
+    let mut p = Parser::from_token_stream(stream, cx.input_span());
+
+    let mut exprs = Vec::new();
+
+    while !p.is_eof()? {
+        exprs.try_push(p.parse::<ast::Expr>()?)?;
+
+        if p.parse::<Option<T![,]>>()?.is_none() {
+            break;
+        }
+    }
+
+    p.eof()?;
+
+    let location = match cx.macro_location() {
+        Some((file, line)) => try_format!("[{file}:{line}]"),
+        None => try_format!("[<unknown>]"),
+    };
+
+    if exprs.is_empty() {
+        let location = cx.lit(&location)?;
+        return Ok(quote!(::std::io::println(#location)).into_token_stream(cx)?);
+    }
+
+    let mut args = Vec::new();
+
+    for expr in &exprs {
+        let prefix = match cx.source_text(expr.span()) {
+            Some(text) => try_format!("{location} {text} = "),
+            None => try_format!("{location} {} = ", cx.stringify(expr)?),
+        };
+
+        let prefix = cx.lit(&prefix)?;
+
+        args.try_push(quote! {{
+            let value = #expr;
+            ::std::io::println(::std::fmt::format!("{}{:?}", #prefix, value));
+            value
+        }})?;
+    }
+
+    let output = if args.len() == 1 {
+        quote!(#(args),*)
+    } else {
+        quote!((#(args),*))
+    };
+
+    Ok(output.into_token_stream(cx)?)
 }
 
 /// Prints to output.
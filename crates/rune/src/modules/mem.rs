@@ -9,6 +9,7 @@ use crate::{ContextError, Module};
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::from_meta(self::module_meta)?;
     module.function_meta(drop)?;
+    module.function_meta(size_of)?;
     Ok(module)
 }
 
@@ -30,3 +31,20 @@ fn drop(value: Value) -> VmResult<()> {
     vm_try!(value.drop());
     VmResult::Ok(())
 }
+
+/// Get the size in bytes of a primitive type on the target architecture.
+///
+/// This is evaluated when the script is compiled, so that `usize` and `isize`
+/// are sized according to the architecture the script is compiled for. Only
+/// primitive types like `u8`, `i64` and `usize` are supported.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(size_of::<u8>(), 1);
+/// assert_eq!(size_of::<i64>(), 8);
+/// ```
+#[rune::function]
+fn size_of() -> VmResult<i64> {
+    VmResult::panic("size_of::<T>() can only be used with primitive types")
+}
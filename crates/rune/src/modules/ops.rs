@@ -262,7 +262,7 @@ fn hash(value: Value) -> VmResult<i64> {
 /// assert_eq!(g.next(), Some(1));
 /// assert_eq!(g.next(), Some(2));
 /// assert_eq!(g.next(), None);
-/// ``
+/// ```
 #[rune::function(instance, path = next)]
 fn generator_next(this: &mut Generator<Vm>) -> VmResult<Option<Value>> {
     this.next()
@@ -285,17 +285,49 @@ fn generator_next(this: &mut Generator<Vm>) -> VmResult<Option<Value>> {
 /// assert_eq!(g.resume(()), GeneratorState::Yielded(1));
 /// assert_eq!(g.resume(1), GeneratorState::Yielded(3));
 /// assert_eq!(g.resume(()), GeneratorState::Complete(()));
-/// ``
+/// ```
 #[rune::function(instance, path = resume)]
 fn generator_resume(this: &mut Generator<Vm>, value: Value) -> VmResult<GeneratorState> {
     this.resume(value)
 }
 
+/// Convert a generator into an iterator over the values it yields.
+///
+/// # Examples
+///
+/// ```rune
+/// fn generate() {
+///     yield 1;
+///     yield 2;
+/// }
+///
+/// let values = generate().iter().collect::<Vec>();
+/// assert_eq!(values, [1, 2]);
+/// ```
 #[rune::function(instance, path = iter)]
 fn generator_iter(this: Generator<Vm>) -> Iterator {
     this.rune_iter()
 }
 
+/// Construct an iterator over the values yielded by a generator, which allows
+/// it to be used directly in a `for` loop.
+///
+/// # Examples
+///
+/// ```rune
+/// fn generate() {
+///     yield 1;
+///     yield 2;
+/// }
+///
+/// let out = [];
+///
+/// for value in generate() {
+///     out.push(value);
+/// }
+///
+/// assert_eq!(out, [1, 2]);
+/// ```
 #[rune::function(instance, protocol = INTO_ITER)]
 fn generator_into_iter(this: Generator<Vm>) -> Iterator {
     this.rune_iter()
@@ -318,7 +350,7 @@ fn generator_into_iter(this: Generator<Vm>) -> Iterator {
 /// assert_eq!(g.resume(()), GeneratorState::Yielded(1));
 /// assert_eq!(g.resume(1), GeneratorState::Yielded(3));
 /// assert_eq!(g.resume(()), GeneratorState::Complete(()));
-/// ``
+/// ```
 #[rune::function(instance, protocol = PARTIAL_EQ)]
 fn generator_state_partial_eq(this: &GeneratorState, other: &GeneratorState) -> VmResult<bool> {
     this.partial_eq_with(other, &mut EnvProtocolCaller)
@@ -342,7 +374,7 @@ fn generator_state_partial_eq(this: &GeneratorState, other: &GeneratorState) ->
 /// assert!(eq(g.resume(()), GeneratorState::Yielded(1)));
 /// assert!(eq(g.resume(1), GeneratorState::Yielded(3)));
 /// assert!(eq(g.resume(()), GeneratorState::Complete(())));
-/// ``
+/// ```
 #[rune::function(instance, protocol = EQ)]
 fn generator_state_eq(this: &GeneratorState, other: &GeneratorState) -> VmResult<bool> {
     this.eq_with(other, &mut EnvProtocolCaller)
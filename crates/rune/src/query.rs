@@ -6,7 +6,7 @@ mod query;
 use core::fmt;
 use core::num::NonZeroUsize;
 
-pub(crate) use self::query::{MissingId, Query, QueryInner};
+pub(crate) use self::query::{MissingId, Query};
 
 use crate as rune;
 use crate::alloc::path::PathBuf;
@@ -23,7 +23,7 @@ use crate::parse::NonZeroId;
 use crate::runtime::format;
 
 /// Indication whether a value is being evaluated because it's being used or not.
-#[derive(Debug, TryClone, Clone, Copy)]
+#[derive(Debug, TryClone, Clone, Copy, PartialEq, Eq)]
 #[try_clone(copy)]
 pub(crate) enum Used {
     /// The value is not being used.
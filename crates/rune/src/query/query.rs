@@ -21,7 +21,7 @@ use crate::compile::{
 use crate::hir;
 use crate::indexing::{self, FunctionAst, Indexed, Items};
 use crate::macros::Storage;
-use crate::parse::{Id, NonZeroId, Opaque, Resolve, ResolveContext};
+use crate::parse::{Id, NonZeroId, Opaque, Resolve};
 use crate::query::{
     Build, BuildEntry, BuiltInMacro, ConstFn, GenericsParameters, ItemImplEntry, Named,
     QueryImplFn, QueryPath, Used,
@@ -72,6 +72,8 @@ pub(crate) struct QueryInner<'arena> {
     const_fns: HashMap<NonZeroId, Rc<ConstFn<'arena>>>,
     /// Indexed constant values.
     constants: HashMap<Hash, ConstValue>,
+    /// Evaluated discriminants of enums, in variant declaration order.
+    discriminants: HashMap<NonZeroId, Vec<i64>>,
     /// Query paths.
     pub(crate) query_paths: HashMap<NonZeroId, QueryPath>,
     /// Functions associated with impl blocks.
@@ -90,13 +92,9 @@ pub(crate) struct QueryInner<'arena> {
     names: Names,
     /// Recorded captures.
     captures: HashMap<Hash, Vec<hir::OwnedName>>,
-}
-
-impl QueryInner<'_> {
-    /// Get a constant value but only from the dynamic query system.
-    pub(crate) fn get_const_value(&self, hash: Hash) -> Option<&ConstValue> {
-        self.constants.get(&hash)
-    }
+    /// Stack of indexed items which are currently being built, used to detect
+    /// items which depend on themselves.
+    building: Vec<(ItemId, Location)>,
 }
 
 /// Query system of the rune compiler.
@@ -206,6 +204,27 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(())
     }
 
+    /// Iterate over every item known to the query engine alongside whether it
+    /// has been used.
+    ///
+    /// This is intended to be called once compilation has finished, at which
+    /// point items which are still [`Used::Unused`] are dead code.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn used_items(&self) -> impl Iterator<Item = (&Item, Used)> + '_ {
+        let inner = &*self.inner;
+        let pool = &*self.pool;
+
+        inner.items.values().map(move |item_meta| {
+            let used = if inner.used.contains(&item_meta.id) {
+                Used::Used
+            } else {
+                Used::Unused
+            };
+
+            (pool.item(item_meta.item), used)
+        })
+    }
+
     /// Get the next impl item in queue to process.
     pub(crate) fn next_impl_item_entry(&mut self) -> Option<ItemImplEntry> {
         self.inner.impl_item_queue.pop_front()
@@ -735,12 +754,16 @@ impl<'a, 'arena> Query<'a, 'arena> {
 
     /// Add a new enum item.
     #[tracing::instrument(skip_all)]
-    pub(crate) fn index_enum(&mut self, item_meta: ItemMeta) -> compile::Result<()> {
+    pub(crate) fn index_enum(
+        &mut self,
+        item_meta: ItemMeta,
+        enum_: indexing::Enum,
+    ) -> compile::Result<()> {
         tracing::trace!(item = ?self.pool.item(item_meta.item));
 
         self.index(indexing::Entry {
             item_meta,
-            indexed: Indexed::Enum,
+            indexed: Indexed::Enum(enum_),
         })?;
 
         Ok(())
@@ -778,7 +801,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
             item_meta,
             indexed: Indexed::Variant(indexing::Variant {
                 enum_id,
-                ast,
+                ast: Box::try_new(ast)?,
                 index,
             }),
         })?;
@@ -814,7 +837,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
             parameters: Hash::EMPTY,
         };
 
-        self.unit.insert_meta(span, &meta, self.pool, self.inner)?;
+        self.unit.insert_meta(span, &meta, self.pool)?;
         self.insert_meta(meta).with_span(span)?;
         Ok(())
     }
@@ -886,9 +909,13 @@ impl<'a, 'arena> Query<'a, 'arena> {
     ) -> compile::Result<Option<meta::Meta>> {
         tracing::trace!("query indexed meta");
 
+        if let Some(index) = self.inner.building.iter().position(|&(id, _)| id == item) {
+            return Err(self.cycle_error(span, index)?);
+        }
+
         if let Some(entry) = self.remove_indexed(span, item)? {
             let meta = self.build_indexed_entry(span, entry, used)?;
-            self.unit.insert_meta(span, &meta, self.pool, self.inner)?;
+            self.unit.insert_meta(span, &meta, self.pool)?;
             self.insert_meta(meta.try_clone()?).with_span(span)?;
             tracing::trace!(item = ?item, meta = ?meta, "build");
             return Ok(Some(meta));
@@ -897,6 +924,23 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(None)
     }
 
+    /// Construct an error for a cycle of items which starts at the given index
+    /// in the stack of items being built.
+    fn cycle_error(&self, span: &dyn Spanned, index: usize) -> compile::Result<compile::Error> {
+        let mut path = Vec::new();
+
+        let cycle = &self.inner.building[index..];
+
+        for &(item, location) in cycle.iter().chain(cycle.first()) {
+            path.try_push(ImportStep {
+                location,
+                item: self.pool.item(item).try_to_owned()?,
+            })?;
+        }
+
+        Ok(compile::Error::new(span, ErrorKind::Cycle { path }))
+    }
+
     /// Perform a default path conversion.
     pub(crate) fn convert_path<'ast>(
         &mut self,
@@ -1209,13 +1253,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
                 })?;
 
                 if !visited.try_insert(self.pool.alloc_item(&item)?)? {
-                    return Err(compile::Error::new(
-                        span,
-                        ErrorKind::ImportCycle {
-                            #[cfg(feature = "emit")]
-                            path,
-                        },
-                    ));
+                    return Err(compile::Error::new(span, ErrorKind::Cycle { path }));
                 }
 
                 module = update.module;
@@ -1290,34 +1328,189 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(Some((*item_meta, import)))
     }
 
+    /// Evaluate the discriminants of an enum in declaration order.
+    ///
+    /// Variants without an explicit discriminant are assigned one more than
+    /// the variant preceding them, starting at zero.
+    fn eval_discriminants(
+        &mut self,
+        item_meta: ItemMeta,
+        enum_: indexing::Enum,
+        used: Used,
+    ) -> compile::Result<Vec<i64>> {
+        let mut discriminants = Vec::try_with_capacity(enum_.discriminants.len())?;
+        let mut existing = HashMap::<i64, Span>::new();
+        let mut next = Some(0i64);
+
+        for d in enum_.discriminants {
+            let discriminant = match &d.expr {
+                Some(expr) => {
+                    let ir = self.expr_to_ir(item_meta, expr)?;
+                    let mut const_compiler =
+                        self.const_interpreter(item_meta.module, item_meta.item)?;
+                    let value = const_compiler.eval_value(&ir, used)?;
+                    crate::from_value::<i64>(value).with_span(&**expr)?
+                }
+                None => {
+                    let Some(next) = next else {
+                        return Err(compile::Error::msg(d.span, "Enum discriminant overflowed"));
+                    };
+
+                    next
+                }
+            };
+
+            if let Some(_existing) = existing.try_insert(discriminant, d.span)? {
+                return Err(compile::Error::new(
+                    d.span,
+                    ErrorKind::DuplicateDiscriminant {
+                        discriminant,
+                        #[cfg(feature = "emit")]
+                        existing: _existing,
+                    },
+                ));
+            }
+
+            next = discriminant.checked_add(1);
+            discriminants.try_push(discriminant)?;
+        }
+
+        Ok(discriminants)
+    }
+
+    /// Convert the fields of a struct or variant into metadata.
+    ///
+    /// Default values of named fields are evaluated as constants and stored
+    /// so that they can be used when the field is omitted in a literal.
+    fn convert_fields(
+        &mut self,
+        item_meta: ItemMeta,
+        body: ast::Fields,
+        used: Used,
+    ) -> compile::Result<meta::Fields> {
+        Ok(match body {
+            ast::Fields::Empty => meta::Fields::Empty,
+            ast::Fields::Unnamed(tuple) => meta::Fields::Unnamed(tuple.len()),
+            ast::Fields::Named(st) => {
+                let type_hash = self.pool.item_type_hash(item_meta.item);
+                let mut fields = HashMap::try_with_capacity(st.len())?;
+
+                for (position, (ast::Field { name, default, .. }, _)) in st.iter().enumerate() {
+                    let name: Box<str> = name.resolve(resolve_context!(self))?.try_into()?;
+
+                    let default = match default {
+                        Some((_, expr)) => {
+                            let ir = self.expr_to_ir(item_meta, expr)?;
+                            let mut const_compiler =
+                                self.const_interpreter(item_meta.module, item_meta.item)?;
+                            let value = const_compiler.eval_value(&ir, used)?;
+                            let const_value: ConstValue =
+                                crate::from_value(value).with_span(expr)?;
+
+                            let hash = Hash::field_default(type_hash, &*name);
+                            self.inner.constants.try_insert(hash, const_value)?;
+                            Some(hash)
+                        }
+                        None => None,
+                    };
+
+                    fields.try_insert(name, FieldMeta { position, default })?;
+                }
+
+                meta::Fields::Named(meta::FieldsNamed { fields })
+            }
+        })
+    }
+
+    /// Lower a constant expression into its intermediate representation.
+    pub(crate) fn expr_to_ir(
+        &mut self,
+        item_meta: ItemMeta,
+        expr: &ast::Expr,
+    ) -> compile::Result<ir::Ir> {
+        let arena = crate::hir::Arena::new();
+        let mut hir_ctx = crate::hir::lowering::Ctxt::with_const(
+            &arena,
+            self.borrow(),
+            item_meta.location.source_id,
+        )?;
+        let hir = crate::hir::lowering::expr(&mut hir_ctx, expr)?;
+
+        let mut cx = ir::Ctxt {
+            source_id: item_meta.location.source_id,
+            q: self.borrow(),
+        };
+
+        ir::compiler::expr(&hir, &mut cx)
+    }
+
+    /// Lower a constant block into a single scoped node of its intermediate
+    /// representation, where local bindings are visible to the statements
+    /// which follow them.
+    pub(crate) fn block_to_ir(
+        &mut self,
+        item_meta: ItemMeta,
+        block: &ast::Block,
+    ) -> compile::Result<ir::Ir> {
+        let arena = crate::hir::Arena::new();
+        let mut hir_ctx = crate::hir::lowering::Ctxt::with_const(
+            &arena,
+            self.borrow(),
+            item_meta.location.source_id,
+        )?;
+        let hir = crate::hir::lowering::block(&mut hir_ctx, block)?;
+
+        let mut cx = ir::Ctxt {
+            source_id: item_meta.location.source_id,
+            q: self.borrow(),
+        };
+
+        Ok(ir::Ir::new(
+            item_meta.location.span,
+            ir::compiler::block(&hir, &mut cx)?,
+        ))
+    }
+
+    /// Construct an interpreter used to evaluate constants in the context of
+    /// the given module and item.
+    pub(crate) fn const_interpreter(
+        &mut self,
+        module: ModId,
+        item: ItemId,
+    ) -> compile::Result<ir::Interpreter<'_, 'arena>> {
+        Ok(ir::Interpreter {
+            budget: ir::Budget::new(1_000_000),
+            scopes: ir::Scopes::new()?,
+            module,
+            item,
+            q: self.borrow(),
+        })
+    }
+
     /// Build a single, indexed entry and return its metadata.
+    ///
+    /// The entry is tracked as being built while this is in progress, so that
+    /// querying for it again reports a cycle.
     fn build_indexed_entry(
         &mut self,
         span: &dyn Spanned,
         entry: indexing::Entry,
         used: Used,
     ) -> compile::Result<meta::Meta> {
-        /// Convert AST fields into meta fields.
-        fn convert_fields(
-            cx: ResolveContext<'_>,
-            body: ast::Fields,
-        ) -> compile::Result<meta::Fields> {
-            Ok(match body {
-                ast::Fields::Empty => meta::Fields::Empty,
-                ast::Fields::Unnamed(tuple) => meta::Fields::Unnamed(tuple.len()),
-                ast::Fields::Named(st) => {
-                    let mut fields = HashMap::try_with_capacity(st.len())?;
-
-                    for (position, (ast::Field { name, .. }, _)) in st.iter().enumerate() {
-                        let name = name.resolve(cx)?;
-                        fields.try_insert(name.try_into()?, FieldMeta { position })?;
-                    }
+        let ItemMeta { item, location, .. } = entry.item_meta;
 
-                    meta::Fields::Named(meta::FieldsNamed { fields })
-                }
-            })
-        }
+        self.inner.building.try_push((item, location))?;
+        let result = self.build_indexed_entry_inner(span, entry, used);
+        self.inner.building.pop();
+        result
+    }
 
+    fn build_indexed_entry_inner(
+        &mut self,
+        span: &dyn Spanned,
+        entry: indexing::Entry,
+        used: Used,
+    ) -> compile::Result<meta::Meta> {
         let indexing::Entry { item_meta, indexed } = entry;
 
         if let Used::Used = used {
@@ -1325,9 +1518,16 @@ impl<'a, 'arena> Query<'a, 'arena> {
         }
 
         let kind = match indexed {
-            Indexed::Enum => meta::Kind::Enum {
-                parameters: Hash::EMPTY,
-            },
+            Indexed::Enum(enum_) => {
+                let discriminants = self.eval_discriminants(item_meta, enum_, used)?;
+                self.inner
+                    .discriminants
+                    .try_insert(item_meta.id, discriminants)?;
+
+                meta::Kind::Enum {
+                    parameters: Hash::EMPTY,
+                }
+            }
             Indexed::Variant(variant) => {
                 let enum_ = self.item_for(variant.enum_id).with_span(span)?;
 
@@ -1339,15 +1539,27 @@ impl<'a, 'arena> Query<'a, 'arena> {
                     ));
                 };
 
+                let discriminant = self
+                    .inner
+                    .discriminants
+                    .get(&variant.enum_id)
+                    .and_then(|d| d.get(variant.index))
+                    .copied();
+
                 meta::Kind::Variant {
                     enum_hash: enum_meta.hash,
                     index: variant.index,
-                    fields: convert_fields(resolve_context!(self), variant.ast.body)?,
+                    fields: self.convert_fields(
+                        item_meta,
+                        Box::into_inner(variant.ast).body,
+                        used,
+                    )?,
                     constructor: None,
+                    discriminant,
                 }
             }
             Indexed::Struct(st) => meta::Kind::Struct {
-                fields: convert_fields(resolve_context!(self), Box::into_inner(st.ast).body)?,
+                fields: self.convert_fields(item_meta, Box::into_inner(st.ast).body, used)?,
                 constructor: None,
                 parameters: Hash::EMPTY,
             },
@@ -1366,7 +1578,6 @@ impl<'a, 'arena> Query<'a, 'arena> {
                     signature: meta::Signature {
                         #[cfg(feature = "doc")]
                         is_async: matches!(f.call, Call::Async | Call::Stream),
-                        #[cfg(feature = "doc")]
                         args: Some(f.ast.args()),
                         #[cfg(feature = "doc")]
                         return_type: None,
@@ -1418,18 +1629,15 @@ impl<'a, 'arena> Query<'a, 'arena> {
                     ir::compiler::expr(&hir, &mut cx)?
                 };
 
-                let mut const_compiler = ir::Interpreter {
-                    budget: ir::Budget::new(1_000_000),
-                    scopes: ir::Scopes::new()?,
-                    module: item_meta.module,
-                    item: item_meta.item,
-                    q: self.borrow(),
-                };
+                let mut const_compiler =
+                    self.const_interpreter(item_meta.module, item_meta.item)?;
 
                 let const_value = const_compiler.eval_const(&ir, used)?;
 
                 let hash = self.pool.item_type_hash(item_meta.item);
-                self.inner.constants.try_insert(hash, const_value)?;
+                self.inner
+                    .constants
+                    .try_insert(hash, const_value.try_clone()?)?;
 
                 if used.is_unused() {
                     self.inner.queue.try_push_back(BuildEntry {
@@ -1438,37 +1646,20 @@ impl<'a, 'arena> Query<'a, 'arena> {
                     })?;
                 }
 
-                meta::Kind::Const
+                meta::Kind::Const(const_value)
             }
             Indexed::ConstBlock(c) => {
-                let ir = {
-                    let arena = crate::hir::Arena::new();
-                    let mut hir_ctx = crate::hir::lowering::Ctxt::with_const(
-                        &arena,
-                        self.borrow(),
-                        item_meta.location.source_id,
-                    )?;
-                    let hir = crate::hir::lowering::block(&mut hir_ctx, &c.ast)?;
+                let ir = self.block_to_ir(item_meta, &c.ast)?;
 
-                    let mut cx = ir::Ctxt {
-                        source_id: item_meta.location.source_id,
-                        q: self.borrow(),
-                    };
-                    ir::Ir::new(item_meta.location.span, ir::compiler::block(&hir, &mut cx)?)
-                };
-
-                let mut const_compiler = ir::Interpreter {
-                    budget: ir::Budget::new(1_000_000),
-                    scopes: ir::Scopes::new()?,
-                    module: item_meta.module,
-                    item: item_meta.item,
-                    q: self.borrow(),
-                };
+                let mut const_compiler =
+                    self.const_interpreter(item_meta.module, item_meta.item)?;
 
                 let const_value = const_compiler.eval_const(&ir, used)?;
 
                 let hash = self.pool.item_type_hash(item_meta.item);
-                self.inner.constants.try_insert(hash, const_value)?;
+                self.inner
+                    .constants
+                    .try_insert(hash, const_value.try_clone()?)?;
 
                 if used.is_unused() {
                     self.inner.queue.try_push_back(BuildEntry {
@@ -1477,7 +1668,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
                     })?;
                 }
 
-                meta::Kind::Const
+                meta::Kind::Const(const_value)
             }
             Indexed::ConstFn(c) => {
                 let (ir_fn, hir) = {
@@ -1569,7 +1760,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
         let entry = indexing::Entry { item_meta, indexed };
 
         let meta = self.build_indexed_entry(span, entry, used)?;
-        self.unit.insert_meta(span, &meta, self.pool, self.inner)?;
+        self.unit.insert_meta(span, &meta, self.pool)?;
         self.insert_meta(meta).with_span(span)?;
         Ok(())
     }
@@ -24,6 +24,9 @@ pub use self::bytes::Bytes;
 mod call;
 pub use self::call::Call;
 
+mod cancellation;
+pub use self::cancellation::CancellationToken;
+
 mod const_value;
 pub use self::const_value::ConstValue;
 
@@ -106,8 +109,10 @@ pub use self::range::Range;
 pub use rune_core::RawStr;
 
 mod runtime_context;
-pub use self::runtime_context::RuntimeContext;
-pub(crate) use self::runtime_context::{AttributeMacroHandler, FunctionHandler, MacroHandler};
+pub(crate) use self::runtime_context::{
+    AttributeMacroHandler, FunctionHandler, FunctionSignature, MacroHandler,
+};
+pub use self::runtime_context::{FunctionMeta, RuntimeContext};
 
 mod select;
 pub(crate) use self::select::Select;
@@ -146,7 +151,9 @@ pub use self::unit::{Unit, UnitStorage};
 
 mod value;
 pub(crate) use self::value::ValueKind;
-pub use self::value::{EmptyStruct, Rtti, Struct, TupleStruct, TypeValue, Value, VariantRtti};
+pub use self::value::{
+    EmptyStruct, Rtti, SortedValue, Struct, TupleStruct, TypeValue, Value, VariantRtti,
+};
 
 mod variant;
 pub use self::variant::{Variant, VariantData};
@@ -157,8 +164,11 @@ pub use self::vec::Vec;
 mod vec_tuple;
 pub use self::vec_tuple::VecTuple;
 
+mod via;
+pub use self::via::{FromProtocol, Via};
+
 mod vm;
-pub use self::vm::{CallFrame, Vm};
+pub use self::vm::{CallFrame, StackFrame, Vm};
 
 mod vm_call;
 pub(crate) use self::vm_call::VmCall;
@@ -171,7 +181,7 @@ mod vm_error;
 pub(crate) use self::vm_error::VmErrorAt;
 pub(crate) use self::vm_error::VmErrorKind;
 pub use self::vm_error::{
-    try_result, RuntimeError, TryFromResult, VmError, VmIntegerRepr, VmResult,
+    try_result, RuntimeError, TryFromResult, VmError, VmErrorFrame, VmIntegerRepr, VmResult,
 };
 
 mod vm_execution;
@@ -181,6 +191,10 @@ pub use self::vm_execution::{VmExecution, VmSendExecution};
 mod vm_halt;
 pub(crate) use self::vm_halt::{VmHalt, VmHaltInfo};
 
+mod vm_tracer;
+pub(crate) use self::vm_tracer::VmTracerObj;
+pub use self::vm_tracer::{CountingTracer, VmTracer};
+
 mod fmt;
 pub use self::fmt::Formatter;
 
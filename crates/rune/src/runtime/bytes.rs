@@ -4,6 +4,7 @@
 
 use core::cmp;
 use core::fmt;
+use core::iter;
 use core::ops;
 
 use serde::de;
@@ -12,7 +13,9 @@ use serde::ser;
 use crate as rune;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, Box, Vec};
-use crate::runtime::{RawRef, Ref, UnsafeToRef, Value, ValueKind, VmErrorKind, VmResult};
+use crate::runtime::{
+    Iterator, RawRef, Ref, TypeOf, UnsafeToRef, Value, ValueKind, VmErrorKind, VmResult,
+};
 use crate::Any;
 
 /// A vector of bytes.
@@ -261,6 +264,113 @@ impl Bytes {
     pub fn last(&self) -> Option<u8> {
         self.bytes.last().copied()
     }
+
+    /// Get a byte or a range of bytes depending on the type of index.
+    ///
+    /// An integer index produces a byte, while a range produces a new byte
+    /// array. Returns `None` if the index is out of bounds.
+    pub(crate) fn index_get(this: &[u8], index: Value) -> VmResult<Option<Value>> {
+        let slice = match &*vm_try!(index.borrow_kind_ref()) {
+            ValueKind::RangeFrom(range) => {
+                let start = vm_try!(range.start.as_usize());
+                this.get(start..)
+            }
+            ValueKind::RangeFull(..) => this.get(..),
+            ValueKind::RangeInclusive(range) => {
+                let start = vm_try!(range.start.as_usize());
+                let end = vm_try!(range.end.as_usize());
+                this.get(start..=end)
+            }
+            ValueKind::RangeToInclusive(range) => {
+                let end = vm_try!(range.end.as_usize());
+                this.get(..=end)
+            }
+            ValueKind::RangeTo(range) => {
+                let end = vm_try!(range.end.as_usize());
+                this.get(..end)
+            }
+            ValueKind::Range(range) => {
+                let start = vm_try!(range.start.as_usize());
+                let end = vm_try!(range.end.as_usize());
+                this.get(start..end)
+            }
+            ValueKind::Integer(index) => {
+                let Some(byte) = usize::try_from(*index).ok().and_then(|n| this.get(n)) else {
+                    return VmResult::Ok(None);
+                };
+
+                return VmResult::Ok(Some(vm_try!(Value::try_from(*byte))));
+            }
+            index => {
+                return VmResult::err(VmErrorKind::UnsupportedIndexGet {
+                    target: Bytes::type_info(),
+                    index: index.type_info(),
+                })
+            }
+        };
+
+        let Some(slice) = slice else {
+            return VmResult::Ok(None);
+        };
+
+        VmResult::Ok(Some(vm_try!(Value::try_from(vm_try!(Bytes::from_slice(
+            slice
+        ))))))
+    }
+
+    /// Convert into a rune iterator over the bytes.
+    pub(crate) fn iter_ref(this: Ref<Bytes>) -> Iterator {
+        let back = this.len();
+
+        Iterator::from_double_ended(
+            "std::bytes::Iter",
+            Iter {
+                bytes: this,
+                front: 0,
+                back,
+            },
+        )
+    }
+}
+
+/// A reference counted iterator over bytes.
+struct Iter {
+    bytes: Ref<Bytes>,
+    front: usize,
+    back: usize,
+}
+
+impl iter::Iterator for Iter {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let byte = *self.bytes.bytes.get(self.front)?;
+        self.front += 1;
+        Some(byte)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl iter::DoubleEndedIterator for Iter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        self.bytes.bytes.get(self.back).copied()
+    }
 }
 
 impl TryClone for Bytes {
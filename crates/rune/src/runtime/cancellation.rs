@@ -0,0 +1,231 @@
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll};
+
+use ::rust_alloc::sync::Arc;
+
+use futures_util::task::AtomicWaker;
+
+use crate::runtime::{VmErrorKind, VmResult};
+
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// A token used to cooperatively cancel an execution.
+///
+/// The token is installed with [`VmExecution::with_cancellation`], after which
+/// the virtual machine checks it at every backward jump and whenever it is
+/// waiting for a future. Once cancelled, the execution errors with an error
+/// for which [`VmError::is_cancelled`] returns `true`, and every value which
+/// was live in the execution is dropped.
+///
+/// Cloning the token produces a handle to the same token, which can be used to
+/// cancel the execution from somewhere else. A token should only be installed
+/// in one execution at a time, since it can only wake up one waiting task.
+///
+/// Async functions, generators and streams called by the execution observe
+/// the cancellation whenever they check for it, but they don't register to be
+/// woken up by it. An execution waiting for one of them is still woken up, at
+/// which point the value being waited for is dropped.
+///
+/// [`VmExecution::with_cancellation`]: crate::runtime::VmExecution::with_cancellation
+/// [`VmError::is_cancelled`]: crate::runtime::VmError::is_cancelled
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::CancellationToken;
+/// use rune::{Context, Vm};
+///
+/// use std::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime()?);
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             loop {}
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+///
+/// let token = CancellationToken::new();
+/// token.cancel();
+///
+/// let mut execution = vm.execute(["main"], ())?.with_cancellation(token);
+/// let error = execution.complete().into_result().unwrap_err();
+/// assert!(error.is_cancelled());
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// Whether the token has been cancelled.
+    cancelled: AtomicBool,
+    /// The task waiting for the token to be cancelled.
+    waker: AtomicWaker,
+    /// A parent token, the cancellation of which also cancels this one.
+    parent: Option<CancellationToken>,
+    /// Whether waiting for this token doesn't register with the parent token,
+    /// which is the case for tokens inherited by other executions.
+    detached: bool,
+    /// The instant at which the token is automatically cancelled.
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    /// Construct a new token which hasn't been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct a token which is automatically cancelled once the given
+    /// deadline has passed.
+    ///
+    /// Passing the deadline doesn't wake up an execution which is waiting for
+    /// a future, it's only noticed the next time the token is checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::CancellationToken;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let token = CancellationToken::with_deadline(Instant::now());
+    /// assert!(token.is_cancelled());
+    ///
+    /// let token = CancellationToken::with_deadline(Instant::now() + Duration::from_secs(60));
+    /// assert!(!token.is_cancelled());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                deadline: Some(deadline),
+                ..Inner::default()
+            }),
+        }
+    }
+
+    /// Construct a token which is cancelled when either it or this token is
+    /// cancelled, or once the given deadline has passed.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn child_with_deadline(&self, deadline: Instant) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                parent: Some(self.clone()),
+                deadline: Some(deadline),
+                ..Inner::default()
+            }),
+        }
+    }
+
+    /// Construct a token which is cancelled along with this token, to be
+    /// inherited by executions started by the execution this token is
+    /// installed in.
+    ///
+    /// Waiting for the returned token never registers with this one, so that
+    /// it keeps waking up the execution it was installed in.
+    pub(crate) fn detached_child(&self) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                parent: Some(self.clone()),
+                detached: true,
+                ..Inner::default()
+            }),
+        }
+    }
+
+    /// Cancel the token, waking up the execution if it's waiting for a
+    /// future.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    /// let handle = token.clone();
+    /// assert!(!token.is_cancelled());
+    ///
+    /// handle.cancel();
+    /// assert!(token.is_cancelled());
+    /// ```
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+        self.inner.waker.wake();
+    }
+
+    /// Test if the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.check(true)
+    }
+
+    /// Test if the token has been cancelled, only comparing against the
+    /// deadline if `deadline` is set since reading the clock is comparatively
+    /// expensive.
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    pub(crate) fn check(&self, deadline: bool) -> bool {
+        if self.inner.cancelled.load(Ordering::Acquire) {
+            return true;
+        }
+
+        #[cfg(feature = "std")]
+        if let (true, Some(deadline)) = (deadline, self.inner.deadline) {
+            if Instant::now() >= deadline {
+                self.inner.cancelled.store(true, Ordering::Release);
+                return true;
+            }
+        }
+
+        match &self.inner.parent {
+            Some(parent) => parent.check(deadline),
+            None => false,
+        }
+    }
+
+    /// Poll for the token to be cancelled.
+    fn poll_cancelled(&self, cx: &mut Context<'_>) -> Poll<()> {
+        self.inner.waker.register(cx.waker());
+
+        if let (Some(parent), false) = (&self.inner.parent, self.inner.detached) {
+            if parent.poll_cancelled(cx).is_ready() {
+                return Poll::Ready(());
+            }
+        }
+
+        if self.is_cancelled() {
+            return Poll::Ready(());
+        }
+
+        Poll::Pending
+    }
+
+    /// Drive the given future to completion unless the token is cancelled
+    /// first, in which case the future is dropped.
+    pub(crate) async fn or_cancelled<F>(&self, future: F) -> VmResult<()>
+    where
+        F: Future<Output = VmResult<()>>,
+    {
+        let mut future = pin!(future);
+
+        poll_fn(|cx| {
+            if self.poll_cancelled(cx).is_ready() {
+                return Poll::Ready(VmResult::err(VmErrorKind::Cancelled));
+            }
+
+            future.as_mut().poll(cx)
+        })
+        .await
+    }
+}
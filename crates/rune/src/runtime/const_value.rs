@@ -1,5 +1,9 @@
+use core::hash::{BuildHasher, Hash, Hasher};
+use core::mem;
+
 use serde::{Deserialize, Serialize};
 
+use crate::alloc::hash_map::DefaultHashBuilder;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, Box, HashMap, String, Vec};
 use crate::runtime::{
@@ -133,6 +137,79 @@ impl TryClone for ConstValue {
     }
 }
 
+/// Constant values are compared structurally.
+///
+/// Floats are compared by their bit patterns so that equality is reflexive,
+/// meaning `0.0` and `-0.0` are distinct while every NaN is equal to every
+/// other NaN. Objects are compared independently of their iteration order.
+impl PartialEq for ConstValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::EmptyTuple, Self::EmptyTuple) => true,
+            (Self::Byte(a), Self::Byte(b)) => a == b,
+            (Self::Char(a), Self::Char(b)) => a == b,
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Integer(a), Self::Integer(b)) => a == b,
+            (Self::Float(a), Self::Float(b)) => float_bits(*a) == float_bits(*b),
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Bytes(a), Self::Bytes(b)) => a == b,
+            (Self::Vec(a), Self::Vec(b)) => a == b,
+            (Self::Tuple(a), Self::Tuple(b)) => a == b,
+            (Self::Object(a), Self::Object(b)) => a == b,
+            (Self::Option(a), Self::Option(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ConstValue {}
+
+impl Hash for ConstValue {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        mem::discriminant(self).hash(state);
+
+        match self {
+            Self::EmptyTuple => {}
+            Self::Byte(b) => b.hash(state),
+            Self::Char(c) => c.hash(state),
+            Self::Bool(b) => b.hash(state),
+            Self::Integer(n) => n.hash(state),
+            Self::Float(f) => float_bits(*f).hash(state),
+            Self::String(s) => s.hash(state),
+            Self::Bytes(b) => b.hash(state),
+            Self::Vec(vec) => vec.hash(state),
+            Self::Tuple(tuple) => tuple.hash(state),
+            Self::Object(object) => {
+                // Entries are hashed individually and combined with a
+                // commutative operation, since the iteration order of an
+                // object is unspecified.
+                let build = DefaultHashBuilder::default();
+                let mut combined = 0u64;
+
+                for entry in object {
+                    combined = combined.wrapping_add(build.hash_one(entry));
+                }
+
+                object.len().hash(state);
+                combined.hash(state);
+            }
+            Self::Option(option) => option.hash(state),
+        }
+    }
+}
+
+/// Get the bit pattern of a float, treating all NaNs as the same value.
+fn float_bits(value: f64) -> u64 {
+    if value.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        value.to_bits()
+    }
+}
+
 impl FromValue for ConstValue {
     fn from_value(value: Value) -> VmResult<Self> {
         VmResult::Ok(match vm_try!(value.take_kind()) {
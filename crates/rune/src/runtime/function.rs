@@ -9,7 +9,7 @@ use crate::alloc::{self, Box, Vec};
 use crate::module;
 use crate::runtime::{
     Args, Call, ConstValue, FromValue, FunctionHandler, OwnedTuple, Rtti, RuntimeContext, Stack,
-    Unit, Value, ValueKind, VariantRtti, Vm, VmCall, VmErrorKind, VmHalt, VmResult,
+    Unit, Value, ValueKind, VariantRtti, Vm, VmCall, VmError, VmErrorKind, VmHalt, VmResult,
 };
 use crate::shared::AssertSend;
 use crate::Any;
@@ -182,6 +182,49 @@ impl Function {
         self.0.call(args)
     }
 
+    /// Perform a call over the function, taking the `args` values on top of
+    /// the given [`Stack`] as arguments.
+    ///
+    /// Unlike [`Function::call`], the arguments are not copied into a new
+    /// stack. Instead the given stack is used directly by the call, which
+    /// makes it possible to cheaply chain calls where the arguments of one
+    /// call are produced by another.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Vm;
+    /// use rune::runtime::{Function, Stack};
+    /// use std::sync::Arc;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         fn add(a, b) {
+    ///             a + b
+    ///         }
+    ///
+    ///         pub fn main() { add }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let mut vm = Vm::without_runtime(Arc::new(unit));
+    /// let value = vm.call(["main"], ())?;
+    ///
+    /// let value: Function = rune::from_value(value)?;
+    ///
+    /// let mut stack = Stack::new();
+    /// stack.push(rune::to_value(1u32)?)?;
+    /// stack.push(rune::to_value(2u32)?)?;
+    ///
+    /// let value = value.try_call_with_stack(stack, 2)?;
+    /// assert_eq!(rune::from_value::<u32>(value)?, 3);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn try_call_with_stack(&self, stack: Stack, args: usize) -> Result<Value, VmError> {
+        self.0.call_with_stack(stack, args).into_result()
+    }
+
     /// Call with the given virtual machine. This allows for certain
     /// optimizations, like avoiding the allocation of a new vm state in case
     /// the call is internal.
@@ -541,6 +584,47 @@ where
         T::from_value(value)
     }
 
+    fn call_with_stack(&self, mut stack: Stack, args: usize) -> VmResult<Value> {
+        let value = match &self.inner {
+            Inner::FnHandler(handler) => {
+                vm_try!((handler.handler)(&mut stack, args));
+                vm_try!(stack.pop())
+            }
+            Inner::FnOffset(fn_offset) => vm_try!(fn_offset.call_with_stack(stack, args, ())),
+            Inner::FnClosureOffset(closure) => {
+                let environment = vm_try!(closure.environment.try_clone());
+                let environment = vm_try!(OwnedTuple::try_from(environment));
+                vm_try!(closure
+                    .fn_offset
+                    .call_with_stack(stack, args, (environment,)))
+            }
+            Inner::FnUnitStruct(empty) => {
+                vm_try!(check_args(args, 0));
+                vm_try!(Value::empty_struct(empty.rtti.clone()))
+            }
+            Inner::FnTupleStruct(tuple) => {
+                vm_try!(check_args(args, tuple.args));
+                vm_try!(Value::tuple_struct(
+                    tuple.rtti.clone(),
+                    vm_try!(Vec::try_from_iter(vm_try!(stack.drain(args))))
+                ))
+            }
+            Inner::FnUnitVariant(unit) => {
+                vm_try!(check_args(args, 0));
+                vm_try!(Value::unit_variant(unit.rtti.clone()))
+            }
+            Inner::FnTupleVariant(tuple) => {
+                vm_try!(check_args(args, tuple.args));
+                vm_try!(Value::tuple_variant(
+                    tuple.rtti.clone(),
+                    vm_try!(Vec::try_from_iter(vm_try!(stack.drain(args))))
+                ))
+            }
+        };
+
+        VmResult::Ok(value)
+    }
+
     fn async_send_call<'a, A, T>(&'a self, args: A) -> impl Future<Output = VmResult<T>> + Send + 'a
     where
         A: 'a + Send + Args,
@@ -886,6 +970,21 @@ impl FnOffset {
         self.call.call_with_vm(vm)
     }
 
+    /// Perform a call into the specified offset using the top `args` values of
+    /// the given stack as arguments, and return the produced value.
+    #[tracing::instrument(skip_all, fields(args, extra = extra.count(), ?self.offset, ?self.call, ?self.args, ?self.hash))]
+    fn call_with_stack(&self, mut stack: Stack, args: usize, extra: impl Args) -> VmResult<Value> {
+        vm_try!(check_args(args.wrapping_add(extra.count()), self.args));
+        vm_try!(stack.swap_stack_bottom(args));
+
+        let mut vm = Vm::with_stack(self.context.clone(), self.unit.clone(), stack);
+
+        vm.set_ip(self.offset);
+        vm_try!(extra.into_stack(vm.stack_mut()));
+
+        self.call.call_with_vm(vm)
+    }
+
     /// Perform a potentially optimized call into the specified vm.
     ///
     /// This will cause a halt in case the vm being called into isn't the same
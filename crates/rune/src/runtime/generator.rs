@@ -51,9 +51,15 @@ where
         }
     }
 
-    /// Get the next value produced by this stream.
+    /// Get the next value produced by this generator.
+    ///
+    /// Once the generator has completed, this keeps returning `None`.
     #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> VmResult<Option<Value>> {
+        if self.execution.is_none() {
+            return VmResult::Ok(None);
+        }
+
         VmResult::Ok(match vm_try!(self.resume(vm_try!(Value::empty()))) {
             GeneratorState::Yielded(value) => Some(value),
             GeneratorState::Complete(_) => None,
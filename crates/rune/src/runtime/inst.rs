@@ -1076,6 +1076,54 @@ pub enum Inst {
         #[inst_display(display_with = PanicReason::ident)]
         reason: PanicReason,
     },
+    /// Jump to `offset` relative to the current instruction pointer if the
+    /// condition is `false`.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <boolean>
+    /// => *nothing*
+    /// ```
+    #[musli(packed)]
+    JumpIfNot {
+        /// Offset to jump to.
+        jump: usize,
+    },
+    /// Load a value from a constant pool slot.
+    ///
+    /// This is used instead of building large constant values element by
+    /// element, so that the whole value is constructed in a single
+    /// instruction.
+    ///
+    /// Every load constructs a new value, so mutating a loaded value never
+    /// affects later loads of the same constant.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <value>
+    /// ```
+    #[musli(packed)]
+    Constant {
+        /// The constant pool slot to load the value from.
+        slot: usize,
+    },
+    /// Pop the top of the stack, and if it's a string which is present in the
+    /// string table identified by `slot`, jump to the associated offset.
+    /// Otherwise execution continues with the next instruction.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => *nothing*
+    /// ```
+    #[musli(packed)]
+    JumpStringTable {
+        /// The slot of the string table to dispatch on.
+        slot: usize,
+    },
 }
 
 impl Inst {
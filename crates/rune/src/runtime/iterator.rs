@@ -125,8 +125,10 @@ impl Iterator {
         self.iter.size_hint()
     }
 
+    /// Advance the iterator and return the next value.
     #[inline]
-    pub(crate) fn next(&mut self) -> VmResult<Option<Value>> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> VmResult<Option<Value>> {
         self.iter.next()
     }
 
@@ -283,6 +283,68 @@ impl Object {
         self.inner.values()
     }
 
+    /// Collect references to all keys, sorted lexicographically.
+    ///
+    /// Unlike [`keys`], the order of the returned keys is deterministic.
+    ///
+    /// [`keys`]: Object::keys
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::String;
+    /// use rune::runtime::Object;
+    ///
+    /// let mut object = Object::new();
+    /// object.insert_value(String::try_from("b")?, 2).into_result()?;
+    /// object.insert_value(String::try_from("a")?, 1).into_result()?;
+    ///
+    /// assert_eq!(object.try_keys_sorted()?, ["a", "b"]);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn try_keys_sorted(&self) -> alloc::Result<alloc::Vec<&str>> {
+        let mut keys = alloc::Vec::try_with_capacity(self.inner.len())?;
+
+        for key in self.inner.keys() {
+            keys.try_push(key.as_str())?;
+        }
+
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    /// Collect references to all key-value pairs, sorted lexicographically
+    /// by key.
+    ///
+    /// Unlike [`iter`], the order of the returned pairs is deterministic.
+    ///
+    /// [`iter`]: Object::iter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::String;
+    /// use rune::runtime::Object;
+    ///
+    /// let mut object = Object::new();
+    /// object.insert_value(String::try_from("b")?, 2).into_result()?;
+    /// object.insert_value(String::try_from("a")?, 1).into_result()?;
+    ///
+    /// let keys = object.try_iter_sorted()?.into_iter().map(|(k, _)| k);
+    /// assert!(keys.eq(["a", "b"]));
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn try_iter_sorted(&self) -> alloc::Result<alloc::Vec<(&str, &Value)>> {
+        let mut entries = alloc::Vec::try_with_capacity(self.inner.len())?;
+
+        for (key, value) in self.inner.iter() {
+            entries.try_push((key.as_str(), value))?;
+        }
+
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        Ok(entries)
+    }
+
     /// An iterator visiting all key-value pairs in arbitrary order,
     /// with mutable references to the values.
     ///
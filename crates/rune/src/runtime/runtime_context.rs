@@ -4,7 +4,7 @@ use ::rust_alloc::sync::Arc;
 
 use crate as rune;
 use crate::alloc::prelude::*;
-use crate::compile;
+use crate::compile::{self, Item, ItemBuf};
 use crate::hash;
 use crate::macros::{MacroContext, TokenStream};
 use crate::runtime::{ConstValue, Stack, VmResult};
@@ -22,6 +22,37 @@ pub(crate) type AttributeMacroHandler = dyn Fn(&mut MacroContext, &TokenStream,
     + Send
     + Sync;
 
+/// The signature of a native function registered in the context.
+#[derive(Debug, TryClone)]
+pub(crate) struct FunctionSignature {
+    /// The item of the function, if it has one.
+    pub(crate) item: Option<ItemBuf>,
+    /// The number of arguments the function accepts, or `None` if it accepts
+    /// a variable number of arguments.
+    pub(crate) args: Option<usize>,
+    /// Whether the function is called as a method on an instance.
+    pub(crate) is_method: bool,
+}
+
+/// Metadata about a native function registered in a [`RuntimeContext`].
+///
+/// See [`RuntimeContext::iter_functions`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct FunctionMeta<'a> {
+    /// The hash of the function.
+    pub hash: Hash,
+    /// The item of the function. This is `None` for functions which don't
+    /// have a name, like protocol functions.
+    pub item: Option<&'a Item>,
+    /// The number of arguments the function accepts, including the instance
+    /// for methods. This is `None` if the function accepts a variable number
+    /// of arguments.
+    pub args: Option<usize>,
+    /// Whether the function is called as a method on an instance.
+    pub is_method: bool,
+}
+
 /// Static run context visible to the virtual machine.
 ///
 /// This contains:
@@ -34,16 +65,20 @@ pub struct RuntimeContext {
     functions: hash::Map<Arc<FunctionHandler>>,
     /// Named constant values
     constants: hash::Map<ConstValue>,
+    /// Signatures of registered native functions.
+    signatures: hash::Map<FunctionSignature>,
 }
 
 impl RuntimeContext {
     pub(crate) fn new(
         functions: hash::Map<Arc<FunctionHandler>>,
         constants: hash::Map<ConstValue>,
+        signatures: hash::Map<FunctionSignature>,
     ) -> Self {
         Self {
             functions,
             constants,
+            signatures,
         }
     }
 
@@ -52,6 +87,48 @@ impl RuntimeContext {
         self.functions.get(&hash)
     }
 
+    /// Iterate over every native function registered in the context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Module};
+    ///
+    /// let mut m = Module::new();
+    /// m.function("add", |a: i64, b: i64| a + b).build()?;
+    ///
+    /// let mut context = Context::new();
+    /// context.install(m)?;
+    ///
+    /// let runtime = context.runtime()?;
+    ///
+    /// let add = runtime
+    ///     .iter_functions()
+    ///     .find(|f| f.item.is_some_and(|item| item.to_string() == "add"))
+    ///     .expect("add is registered");
+    ///
+    /// assert_eq!(add.args, Some(2));
+    /// assert!(!add.is_method);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn iter_functions(&self) -> impl Iterator<Item = FunctionMeta<'_>> + '_ {
+        self.functions.keys().map(|&hash| {
+            let signature = self.signatures.get(&hash);
+
+            FunctionMeta {
+                hash,
+                item: signature.and_then(|s| s.item.as_deref()),
+                args: signature.and_then(|s| s.args),
+                is_method: signature.is_some_and(|s| s.is_method),
+            }
+        })
+    }
+
+    /// Lookup the signature of the given native function.
+    pub(crate) fn signature(&self, hash: Hash) -> Option<&FunctionSignature> {
+        self.signatures.get(&hash)
+    }
+
     /// Read a constant value from the unit.
     pub fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
@@ -224,3 +224,130 @@ fn shared_is_writable() -> crate::support::Result<()> {
     assert!(shared.is_writable());
     Ok(())
 }
+
+#[test]
+fn value_try_partial_cmp() -> crate::support::Result<()> {
+    use core::cmp::Ordering;
+
+    use crate::alloc::{try_vec, String};
+    use crate::runtime::Bytes;
+
+    let a = Value::try_from(1i64)?;
+    let b = Value::try_from(2i64)?;
+    assert_eq!(a.try_partial_cmp(&b)?, Some(Ordering::Less));
+    assert_eq!(b.try_partial_cmp(&a)?, Some(Ordering::Greater));
+    assert_eq!(a.try_partial_cmp(&a)?, Some(Ordering::Equal));
+
+    let nan = Value::try_from(f64::NAN)?;
+    let one = Value::try_from(1.0f64)?;
+    assert_eq!(nan.try_partial_cmp(&nan)?, None);
+    assert_eq!(nan.try_partial_cmp(&one)?, None);
+    assert_eq!(one.try_partial_cmp(&nan)?, None);
+
+    let apple = Value::try_from(String::try_from("apple")?)?;
+    let banana = Value::try_from(String::try_from("banana")?)?;
+    let app = Value::try_from(String::try_from("app")?)?;
+    assert_eq!(apple.try_partial_cmp(&banana)?, Some(Ordering::Less));
+    assert_eq!(app.try_partial_cmp(&apple)?, Some(Ordering::Less));
+
+    let a = Value::try_from(Bytes::from_vec(try_vec![1, 2]))?;
+    let b = Value::try_from(Bytes::from_vec(try_vec![1, 3]))?;
+    assert_eq!(a.try_partial_cmp(&b)?, Some(Ordering::Less));
+
+    // Mismatched types cannot be compared.
+    assert!(a.try_partial_cmp(&apple).is_err());
+    Ok(())
+}
+
+#[test]
+fn object_sorted() -> crate::support::Result<()> {
+    use crate::alloc::String;
+    use crate::runtime::Object;
+
+    let keys = ["delta", "alpha", "charlie", "bravo", "echo"];
+
+    let mut a = Object::new();
+    let mut b = Object::new();
+
+    for (n, key) in keys.iter().enumerate() {
+        a.insert(String::try_from(*key)?, Value::try_from(n as i64)?)?;
+    }
+
+    for (n, key) in keys.iter().enumerate().rev() {
+        b.insert(String::try_from(*key)?, Value::try_from(n as i64)?)?;
+    }
+
+    let expected = ["alpha", "bravo", "charlie", "delta", "echo"];
+
+    for _ in 0..3 {
+        assert_eq!(a.try_keys_sorted()?, expected);
+        assert_eq!(b.try_keys_sorted()?, expected);
+    }
+
+    for object in [&a, &b] {
+        let entries = object.try_iter_sorted()?;
+        assert!(entries.iter().map(|&(k, _)| k).eq(expected));
+
+        for (key, value) in entries {
+            let n = keys.iter().position(|k| *k == key).unwrap();
+            assert_eq!(value.as_integer()?, n as i64);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn const_value_eq_hash() -> crate::support::Result<()> {
+    use core::hash::BuildHasher;
+
+    use crate::alloc::hash_map::DefaultHashBuilder;
+    use crate::alloc::{Box, HashMap, String};
+    use crate::runtime::ConstValue;
+
+    let build = DefaultHashBuilder::default();
+
+    let assert_eq_hash = |a: &ConstValue, b: &ConstValue| {
+        assert_eq!(a, b);
+        assert_eq!(build.hash_one(a), build.hash_one(b));
+    };
+
+    assert_eq_hash(&ConstValue::Float(f64::NAN), &ConstValue::Float(-f64::NAN));
+    assert_eq_hash(&ConstValue::Float(1.5), &ConstValue::Float(1.5));
+    assert_ne!(ConstValue::Float(0.0), ConstValue::Float(-0.0));
+    assert_ne!(ConstValue::Integer(1), ConstValue::Float(1.0));
+    assert_ne!(ConstValue::EmptyTuple, ConstValue::Tuple(Box::default()));
+
+    let keys = ["alpha", "bravo", "charlie", "delta"];
+
+    let mut a = HashMap::new();
+    let mut b = HashMap::new();
+
+    for (n, key) in keys.iter().enumerate() {
+        a.try_insert(String::try_from(*key)?, ConstValue::Integer(n as i64))?;
+    }
+
+    for (n, key) in keys.iter().enumerate().rev() {
+        b.try_insert(String::try_from(*key)?, ConstValue::Integer(n as i64))?;
+    }
+
+    let a = ConstValue::Tuple(Box::try_from([
+        ConstValue::Object(a),
+        ConstValue::Option(Some(Box::try_new(ConstValue::Float(f64::NAN))?)),
+    ])?);
+
+    let b = ConstValue::Tuple(Box::try_from([
+        ConstValue::Object(b),
+        ConstValue::Option(Some(Box::try_new(ConstValue::Float(f64::NAN))?)),
+    ])?);
+
+    assert_eq_hash(&a, &b);
+
+    let ConstValue::Tuple(mut tuple) = b else {
+        panic!("expected tuple");
+    };
+
+    tuple[1] = ConstValue::Option(None);
+    assert_ne!(a, ConstValue::Tuple(tuple));
+    Ok(())
+}
@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 use crate as rune;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, Box, String, Vec};
-use crate::hash;
+use crate::hash::{self, IntoHash};
 use crate::runtime::{
     Call, ConstValue, DebugInfo, Inst, Rtti, StaticString, VariantRtti, VmError, VmErrorKind,
 };
@@ -72,8 +72,12 @@ pub struct Logic<S = DefaultStorage> {
     rtti: hash::Map<Arc<Rtti>>,
     /// Runtime information for variants.
     variant_rtti: hash::Map<Arc<VariantRtti>>,
-    /// Named constants
-    constants: hash::Map<ConstValue>,
+    /// Named constants, as slots into the constant pool.
+    constants: hash::Map<usize>,
+    /// Deduplicated constant values.
+    constant_pool: Vec<ConstValue>,
+    /// Tables used to dispatch on string values.
+    string_tables: Vec<StringTable>,
 }
 
 impl<S> Unit<S> {
@@ -96,7 +100,9 @@ impl<S> Unit<S> {
         rtti: hash::Map<Arc<Rtti>>,
         variant_rtti: hash::Map<Arc<VariantRtti>>,
         debug: Option<Box<DebugInfo>>,
-        constants: hash::Map<ConstValue>,
+        constants: hash::Map<usize>,
+        constant_pool: Vec<ConstValue>,
+        string_tables: Vec<StringTable>,
     ) -> Self {
         Self {
             logic: Logic {
@@ -108,6 +114,8 @@ impl<S> Unit<S> {
                 rtti,
                 variant_rtti,
                 constants,
+                constant_pool,
+                string_tables,
             },
             debug,
         }
@@ -138,7 +146,10 @@ impl<S> Unit<S> {
     /// Iterate over all constants in the unit.
     #[cfg(feature = "cli")]
     pub(crate) fn iter_constants(&self) -> impl Iterator<Item = (&Hash, &ConstValue)> + '_ {
-        self.logic.constants.iter()
+        self.logic
+            .constants
+            .iter()
+            .filter_map(|(hash, slot)| Some((hash, self.logic.constant_pool.get(*slot)?)))
     }
 
     /// Iterate over all static object keys in the unit.
@@ -179,6 +190,24 @@ impl<S> Unit<S> {
             .as_ref())
     }
 
+    /// Lookup a value in the constant pool by slot, if it exists.
+    pub(crate) fn lookup_constant(&self, slot: usize) -> Result<&ConstValue, VmError> {
+        Ok(self
+            .logic
+            .constant_pool
+            .get(slot)
+            .ok_or(VmErrorKind::MissingConstant { slot })?)
+    }
+
+    /// Lookup a string table by slot, if it exists.
+    pub(crate) fn lookup_string_table(&self, slot: usize) -> Result<&StringTable, VmError> {
+        Ok(self
+            .logic
+            .string_tables
+            .get(slot)
+            .ok_or(VmErrorKind::MissingStringTable { slot })?)
+    }
+
     /// Lookup the static object keys by slot, if it exists.
     pub(crate) fn lookup_object_keys(&self, slot: usize) -> Option<&[String]> {
         self.logic
@@ -204,7 +233,8 @@ impl<S> Unit<S> {
 
     /// Lookup a constant from the unit.
     pub(crate) fn constant(&self, hash: Hash) -> Option<&ConstValue> {
-        self.logic.constants.get(&hash)
+        let slot = *self.logic.constants.get(&hash)?;
+        self.logic.constant_pool.get(slot)
     }
 }
 
@@ -230,6 +260,108 @@ where
     pub(crate) fn iter_instructions(&self) -> impl Iterator<Item = (usize, Inst)> + '_ {
         self.logic.storage.iter()
     }
+
+    /// Write a human-readable disassembly of the unit to `out`.
+    ///
+    /// Each instruction is written on its own line, prefixed with its offset
+    /// in hex. If the unit has debug info, functions and labels are written
+    /// before the instructions they start at, and each instruction is
+    /// followed by the source and span it was compiled from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Context;
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let mut sources = rune::sources!(entry => { pub fn main() { 1 + 2 } });
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    ///
+    /// let mut out = String::new();
+    /// unit.try_disassemble(&mut out)?;
+    /// assert!(out.contains("fn main()"));
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn try_disassemble(&self, out: &mut impl fmt::Write) -> Result<(), fmt::Error> {
+        let debug = self.debug_info();
+
+        for (n, inst) in self.logic.storage.iter() {
+            let inst_debug = debug.and_then(|d| d.instruction_at(n));
+
+            if let Some((hash, signature)) = debug.and_then(|d| d.function_at(n)) {
+                if n != 0 {
+                    writeln!(out)?;
+                }
+
+                writeln!(out, "fn {signature} ({hash}):")?;
+            }
+
+            for label in inst_debug.map(|d| d.labels.as_slice()).unwrap_or_default() {
+                writeln!(out, "{label}:")?;
+            }
+
+            write!(out, "  {n:#06x}: {inst}")?;
+
+            if let Some(d) = inst_debug {
+                write!(out, " ; source={} span={}", d.source_id, d.span)?;
+
+                if let Some(comment) = &d.comment {
+                    write!(out, " // {comment}")?;
+                }
+            }
+
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A table mapping strings to jumps, used to dispatch a `match` over many
+/// string literals without testing every arm in turn.
+///
+/// Entries are sorted by hash, so that a lookup only has to compare the
+/// strings which share the hash of the value being looked up.
+#[derive(Debug, TryClone, Default, Serialize, Deserialize)]
+pub(crate) struct StringTable {
+    entries: Vec<(Hash, String, usize)>,
+}
+
+impl StringTable {
+    /// Construct a new table out of `(hash, string, jump)` entries.
+    ///
+    /// If multiple entries have the same string, the first one takes
+    /// precedence.
+    pub(crate) fn new(mut entries: Vec<(Hash, String, usize)>) -> Self {
+        // NB: stable sort, so that the first of any duplicate strings is the
+        // one found.
+        entries.sort_by_key(|e| e.0);
+        Self { entries }
+    }
+
+    /// Find the jump associated with the given string.
+    pub(crate) fn lookup(&self, string: &str) -> Option<usize> {
+        self.lookup_hashed(string.into_hash(), string)
+    }
+
+    /// Find the jump associated with the given string, whose hash has already
+    /// been calculated.
+    pub(crate) fn lookup_hashed(&self, hash: Hash, string: &str) -> Option<usize> {
+        let start = self.entries.partition_point(|e| e.0 < hash);
+
+        for (h, s, jump) in &self.entries[start..] {
+            if *h != hash {
+                break;
+            }
+
+            // Different strings might have the same hash.
+            if s.as_str() == string {
+                return Some(*jump);
+            }
+        }
+
+        None
+    }
 }
 
 /// The kind and necessary information on registered functions.
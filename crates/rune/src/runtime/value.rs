@@ -1,4 +1,5 @@
 mod serde;
+pub use self::serde::SortedValue;
 
 use core::any;
 use core::borrow::Borrow;
@@ -20,7 +21,7 @@ use crate::runtime::{
     Generator, GeneratorState, Iterator, MaybeTypeOf, Mut, Object, OwnedTuple, Protocol,
     ProtocolCaller, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Ref,
     RuntimeError, Shared, SharedPointerGuard, Snapshot, Stream, ToValue, Type, TypeInfo, Variant,
-    Vec, Vm, VmErrorKind, VmIntegerRepr, VmResult,
+    Vec, Vm, VmError, VmErrorKind, VmIntegerRepr, VmResult,
 };
 #[cfg(feature = "alloc")]
 use crate::runtime::{Hasher, Tuple};
@@ -370,6 +371,8 @@ pub struct VariantRtti {
     pub hash: Hash,
     /// The name of the variant.
     pub item: ItemBuf,
+    /// The discriminant of the variant, if it has one.
+    pub discriminant: Option<i64>,
 }
 
 impl PartialEq for VariantRtti {
@@ -664,6 +667,60 @@ impl Value {
         VmResult::Ok(())
     }
 
+    /// Format the value into a [`fmt::Formatter`] using the
+    /// [`Protocol::STRING_DISPLAY`] protocol.
+    ///
+    /// Primitive values like integers and strings are written directly to the
+    /// formatter without allocating, which makes this suitable for
+    /// implementing [`fmt::Display`] for types which wrap a value.
+    ///
+    /// You must use [Vm::with] to specify which virtual machine this function
+    /// is called inside if the value is of a type which requires calling the
+    /// protocol.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the protocol has to be called outside of a
+    /// virtual machine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::fmt;
+    ///
+    /// use rune::Value;
+    ///
+    /// struct Display(Value);
+    ///
+    /// impl fmt::Display for Display {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         self.0.try_display_fmt(f).map_err(|_| fmt::Error)
+    ///     }
+    /// }
+    ///
+    /// let value = rune::to_value(42i64)?;
+    /// assert_eq!(Display(value).to_string(), "42");
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn try_display_fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), VmError> {
+        let result = match &*self.borrow_kind_ref()? {
+            ValueKind::Char(c) => fmt::Display::fmt(c, f),
+            ValueKind::Integer(integer) => fmt::Display::fmt(integer, f),
+            ValueKind::Float(float) => f.write_str(ryu::Buffer::new().format(*float)),
+            ValueKind::Bool(bool) => fmt::Display::fmt(bool, f),
+            ValueKind::Byte(byte) => fmt::Display::fmt(byte, f),
+            ValueKind::String(string) => f.write_str(string),
+            _ => {
+                let mut o = Formatter::new();
+                self.string_display(&mut o).into_result()?;
+                f.write_str(o.as_str())
+            }
+        };
+
+        result.map_err(|fmt::Error| alloc::Error::FormatError)?;
+        Ok(())
+    }
+
     /// Perform a shallow clone of the value using the [`CLONE`] protocol.
     ///
     /// This requires read access to the underlying value.
@@ -733,6 +790,91 @@ impl Value {
         })
     }
 
+    /// Perform a deep clone of the value.
+    ///
+    /// In contrast to [`Clone::clone`], which only increments the reference
+    /// count of the shared value, this constructs a new independent value.
+    /// Vectors, tuples and objects are cloned recursively, so modifying any
+    /// value nested inside of the clone doesn't affect the original. Every
+    /// other value is cloned as with [`Value::clone_`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`VmErrorKind::RecursionLimitExceeded`] if values are
+    /// nested deeper than the recursion limit, which is always the case for
+    /// values which contain themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::try_vec;
+    ///
+    /// let original = rune::to_value(try_vec![try_vec![1i64]])?;
+    /// let copy = original.try_clone_deep()?;
+    ///
+    /// copy.borrow_vec_ref()?[0].borrow_vec_mut()?.push(rune::to_value(2i64)?)?;
+    ///
+    /// assert_eq!(original.borrow_vec_ref()?[0].borrow_vec_ref()?.len(), 1);
+    /// assert_eq!(copy.borrow_vec_ref()?[0].borrow_vec_ref()?.len(), 2);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn try_clone_deep(&self) -> Result<Self, VmError> {
+        self.try_clone_deep_with(&mut EnvProtocolCaller, 0)
+    }
+
+    fn try_clone_deep_with(
+        &self,
+        caller: &mut impl ProtocolCaller,
+        depth: usize,
+    ) -> Result<Self, VmError> {
+        /// The maximum depth of nested values which can be deep cloned.
+        const LIMIT: usize = 256;
+
+        if depth >= LIMIT {
+            return Err(VmError::from(VmErrorKind::RecursionLimitExceeded {
+                limit: LIMIT,
+            }));
+        }
+
+        let inner = match &*self.inner.borrow_ref()? {
+            ValueKind::Vec(vec) => {
+                let mut copy = Vec::with_capacity(vec.len())?;
+
+                for value in vec.iter() {
+                    copy.push(value.try_clone_deep_with(caller, depth + 1)?)?;
+                }
+
+                ValueKind::Vec(copy)
+            }
+            ValueKind::Tuple(tuple) => {
+                let mut copy = alloc::Vec::try_with_capacity(tuple.len())?;
+
+                for value in tuple.iter() {
+                    copy.try_push(value.try_clone_deep_with(caller, depth + 1)?)?;
+                }
+
+                ValueKind::Tuple(OwnedTuple::try_from(copy)?)
+            }
+            ValueKind::Object(object) => {
+                let mut copy = Object::with_capacity(object.len())?;
+
+                for (key, value) in object.iter() {
+                    copy.insert(
+                        key.try_clone()?,
+                        value.try_clone_deep_with(caller, depth + 1)?,
+                    )?;
+                }
+
+                ValueKind::Object(copy)
+            }
+            _ => return self.clone_with(caller).into_result(),
+        };
+
+        Ok(Self {
+            inner: Shared::new(inner)?,
+        })
+    }
+
     /// Debug format the value using the [`STRING_DEBUG`] protocol.
     ///
     /// You must use [Vm::with] to specify which virtual machine this function
@@ -974,6 +1116,30 @@ impl Value {
         })
     }
 
+    /// Detach a primitive value from its shared storage.
+    ///
+    /// Primitives such as integers are updated in place by assignment
+    /// operators like `+=`, so a value which is handed over to a different
+    /// execution needs to be detached for such updates not to be observed
+    /// across it. Other values are returned as-is.
+    pub(crate) fn detach(&self) -> VmResult<Self> {
+        let inner = match &*vm_try!(self.inner.borrow_ref()) {
+            ValueKind::EmptyTuple => ValueKind::EmptyTuple,
+            ValueKind::Bool(value) => ValueKind::Bool(*value),
+            ValueKind::Byte(value) => ValueKind::Byte(*value),
+            ValueKind::Char(value) => ValueKind::Char(*value),
+            ValueKind::Integer(value) => ValueKind::Integer(*value),
+            ValueKind::Float(value) => ValueKind::Float(*value),
+            ValueKind::Type(value) => ValueKind::Type(*value),
+            ValueKind::Ordering(value) => ValueKind::Ordering(*value),
+            _ => return VmResult::Ok(self.clone()),
+        };
+
+        VmResult::Ok(Self {
+            inner: vm_try!(Shared::new(inner)),
+        })
+    }
+
     /// Try to coerce value into a usize.
     #[inline]
     pub fn as_usize(&self) -> Result<usize, RuntimeError> {
@@ -1097,6 +1263,60 @@ impl Value {
         as_float,
     }
 
+    /// Test if the value is a float which is NaN.
+    ///
+    /// Returns `false` for any value which is not a float.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let nan = rune::to_value(f64::NAN)?;
+    /// let int = rune::to_value(1i64)?;
+    ///
+    /// assert!(nan.is_nan());
+    /// assert!(!int.is_nan());
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn is_nan(&self) -> bool {
+        matches!(self.as_float(), Ok(value) if value.is_nan())
+    }
+
+    /// Test if the value is a float which is positive or negative infinity.
+    ///
+    /// Returns `false` for any value which is not a float.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let inf = rune::to_value(f64::INFINITY)?;
+    /// let int = rune::to_value(1i64)?;
+    ///
+    /// assert!(inf.is_infinite());
+    /// assert!(!int.is_infinite());
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn is_infinite(&self) -> bool {
+        matches!(self.as_float(), Ok(value) if value.is_infinite())
+    }
+
+    /// Test if the value is a float which is neither infinite nor NaN.
+    ///
+    /// Returns `false` for any value which is not a float.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let f = rune::to_value(1.0f64)?;
+    /// let nan = rune::to_value(f64::NAN)?;
+    ///
+    /// assert!(f.is_finite());
+    /// assert!(!nan.is_finite());
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn is_finite(&self) -> bool {
+        matches!(self.as_float(), Ok(value) if value.is_finite())
+    }
+
     copy_into! {
         /// Coerce into [`Type`].
         Type(Type),
@@ -1802,6 +2022,37 @@ impl Value {
         Value::partial_cmp_with(a, b, &mut EnvProtocolCaller)
     }
 
+    /// Perform a partial ordering comparison between this and another value,
+    /// returning a [`Result`] instead of a [`VmResult`].
+    ///
+    /// Built-in values such as integers, floats, strings, bytes and sequences
+    /// are compared directly. External types will use the
+    /// [`Protocol::PARTIAL_CMP`] protocol.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if the two values cannot be compared, or if
+    /// a protocol needs to be called outside of a virtual machine context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    ///
+    /// use rune::runtime::Value;
+    ///
+    /// let a = Value::try_from(1i64)?;
+    /// let b = Value::try_from(2i64)?;
+    /// assert_eq!(a.try_partial_cmp(&b)?, Some(Ordering::Less));
+    ///
+    /// let nan = Value::try_from(f64::NAN)?;
+    /// assert_eq!(nan.try_partial_cmp(&nan)?, None);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn try_partial_cmp(&self, other: &Value) -> Result<Option<Ordering>, VmError> {
+        Value::partial_cmp(self, other).into_result()
+    }
+
     /// Perform a partial ordering comparison between two values.
     ///
     /// This is the basis for the comparison operation.
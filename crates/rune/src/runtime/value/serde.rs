@@ -20,85 +20,153 @@ impl<'de> de::Deserialize<'de> for Value {
 }
 
 /// Serialize implementation for value pointers.
+///
+/// Entries in objects are serialized in the order in which they are stored,
+/// use [`Value::serialize_sorted`] to serialize them sorted by key.
 impl ser::Serialize for Value {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
     {
-        match &*self.borrow_kind_ref().map_err(S::Error::custom)? {
-            ValueKind::EmptyTuple => serializer.serialize_unit(),
-            ValueKind::Bool(b) => serializer.serialize_bool(*b),
-            ValueKind::Char(c) => serializer.serialize_char(*c),
-            ValueKind::Byte(c) => serializer.serialize_u8(*c),
-            ValueKind::Integer(integer) => serializer.serialize_i64(*integer),
-            ValueKind::Float(float) => serializer.serialize_f64(*float),
-            ValueKind::Type(..) => Err(ser::Error::custom("cannot serialize types")),
-            ValueKind::Ordering(..) => Err(ser::Error::custom("cannot serialize orderings")),
-            ValueKind::String(string) => serializer.serialize_str(string),
-            ValueKind::Bytes(bytes) => serializer.serialize_bytes(bytes),
-            ValueKind::Vec(vec) => {
-                let mut serializer = serializer.serialize_seq(Some(vec.len()))?;
-
-                for value in vec {
-                    serializer.serialize_element(value)?;
-                }
+        serialize(self, false, serializer)
+    }
+}
+
+impl Value {
+    /// Wrap the value so that entries in objects are serialized sorted by
+    /// key, which makes output such as JSON deterministic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::String;
+    /// use rune::runtime::{Object, Value};
+    ///
+    /// let mut object = Object::new();
+    /// object.insert(String::try_from("b")?, Value::try_from(2i64)?)?;
+    /// object.insert(String::try_from("a")?, Value::try_from(1i64)?)?;
+    ///
+    /// let value = Value::try_from(object)?;
+    /// let json = serde_json::to_string(&value.serialize_sorted())?;
+    /// assert_eq!(json, r#"{"a":1,"b":2}"#);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn serialize_sorted(&self) -> SortedValue<'_> {
+        SortedValue { value: self }
+    }
+}
+
+/// A value which serializes entries in objects sorted by key.
+///
+/// See [`Value::serialize_sorted`].
+pub struct SortedValue<'a> {
+    value: &'a Value,
+}
+
+impl ser::Serialize for SortedValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serialize(self.value, true, serializer)
+    }
+}
+
+/// A nested value, serialized with the same ordering as its parent.
+struct Nested<'a> {
+    value: &'a Value,
+    sorted: bool,
+}
+
+impl ser::Serialize for Nested<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serialize(self.value, self.sorted, serializer)
+    }
+}
 
-                serializer.end()
+fn serialize<S>(value: &Value, sorted: bool, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    match &*value.borrow_kind_ref().map_err(S::Error::custom)? {
+        ValueKind::EmptyTuple => serializer.serialize_unit(),
+        ValueKind::Bool(b) => serializer.serialize_bool(*b),
+        ValueKind::Char(c) => serializer.serialize_char(*c),
+        ValueKind::Byte(c) => serializer.serialize_u8(*c),
+        ValueKind::Integer(integer) => serializer.serialize_i64(*integer),
+        ValueKind::Float(float) => serializer.serialize_f64(*float),
+        ValueKind::Type(..) => Err(ser::Error::custom("cannot serialize types")),
+        ValueKind::Ordering(..) => Err(ser::Error::custom("cannot serialize orderings")),
+        ValueKind::String(string) => serializer.serialize_str(string),
+        ValueKind::Bytes(bytes) => serializer.serialize_bytes(bytes),
+        ValueKind::Vec(vec) => {
+            let mut serializer = serializer.serialize_seq(Some(vec.len()))?;
+
+            for value in vec {
+                serializer.serialize_element(&Nested { value, sorted })?;
             }
-            ValueKind::Tuple(tuple) => {
-                let mut serializer = serializer.serialize_seq(Some(tuple.len()))?;
 
-                for value in tuple.iter() {
-                    serializer.serialize_element(value)?;
-                }
+            serializer.end()
+        }
+        ValueKind::Tuple(tuple) => {
+            let mut serializer = serializer.serialize_seq(Some(tuple.len()))?;
 
-                serializer.end()
+            for value in tuple.iter() {
+                serializer.serialize_element(&Nested { value, sorted })?;
             }
-            ValueKind::Object(object) => {
-                let mut serializer = serializer.serialize_map(Some(object.len()))?;
 
+            serializer.end()
+        }
+        ValueKind::Object(object) => {
+            let mut serializer = serializer.serialize_map(Some(object.len()))?;
+
+            if sorted {
+                for (key, value) in object.try_iter_sorted().map_err(S::Error::custom)? {
+                    serializer.serialize_entry(key, &Nested { value, sorted })?;
+                }
+            } else {
                 for (key, value) in object {
-                    serializer.serialize_entry(key, value)?;
+                    serializer.serialize_entry(key, &Nested { value, sorted })?;
                 }
-
-                serializer.end()
-            }
-            ValueKind::Option(option) => <Option<Value>>::serialize(option, serializer),
-            ValueKind::EmptyStruct(..) => Err(ser::Error::custom("cannot serialize empty structs")),
-            ValueKind::TupleStruct(..) => Err(ser::Error::custom("cannot serialize tuple structs")),
-            ValueKind::Struct(..) => Err(ser::Error::custom("cannot serialize objects structs")),
-            ValueKind::Variant(..) => Err(ser::Error::custom("cannot serialize variants")),
-            ValueKind::Result(..) => Err(ser::Error::custom("cannot serialize results")),
-            ValueKind::Future(..) => Err(ser::Error::custom("cannot serialize futures")),
-            ValueKind::Stream(..) => Err(ser::Error::custom("cannot serialize streams")),
-            ValueKind::Generator(..) => Err(ser::Error::custom("cannot serialize generators")),
-            ValueKind::GeneratorState(..) => {
-                Err(ser::Error::custom("cannot serialize generator states"))
-            }
-            ValueKind::Function(..) => {
-                Err(ser::Error::custom("cannot serialize function pointers"))
             }
-            ValueKind::Format(..) => {
-                Err(ser::Error::custom("cannot serialize format specifications"))
-            }
-            ValueKind::Iterator(..) => Err(ser::Error::custom("cannot serialize iterators")),
-            ValueKind::RangeFrom(..) => {
-                Err(ser::Error::custom("cannot serialize `start..` ranges"))
-            }
-            ValueKind::RangeFull(..) => Err(ser::Error::custom("cannot serialize `..` ranges")),
-            ValueKind::RangeInclusive(..) => {
-                Err(ser::Error::custom("cannot serialize `start..=end` ranges"))
-            }
-            ValueKind::RangeToInclusive(..) => {
-                Err(ser::Error::custom("cannot serialize `..=end` ranges"))
-            }
-            ValueKind::RangeTo(..) => Err(ser::Error::custom("cannot serialize `..end` ranges")),
-            ValueKind::Range(..) => Err(ser::Error::custom("cannot serialize `start..end` ranges")),
-            ValueKind::ControlFlow(..) => {
-                Err(ser::Error::custom("cannot serialize `start..end` ranges"))
-            }
-            ValueKind::Any(..) => Err(ser::Error::custom("cannot serialize external objects")),
+
+            serializer.end()
+        }
+        ValueKind::Option(option) => match option {
+            Some(value) => serializer.serialize_some(&Nested { value, sorted }),
+            None => serializer.serialize_none(),
+        },
+        ValueKind::EmptyStruct(..) => Err(ser::Error::custom("cannot serialize empty structs")),
+        ValueKind::TupleStruct(..) => Err(ser::Error::custom("cannot serialize tuple structs")),
+        ValueKind::Struct(..) => Err(ser::Error::custom("cannot serialize objects structs")),
+        ValueKind::Variant(..) => Err(ser::Error::custom("cannot serialize variants")),
+        ValueKind::Result(..) => Err(ser::Error::custom("cannot serialize results")),
+        ValueKind::Future(..) => Err(ser::Error::custom("cannot serialize futures")),
+        ValueKind::Stream(..) => Err(ser::Error::custom("cannot serialize streams")),
+        ValueKind::Generator(..) => Err(ser::Error::custom("cannot serialize generators")),
+        ValueKind::GeneratorState(..) => {
+            Err(ser::Error::custom("cannot serialize generator states"))
+        }
+        ValueKind::Function(..) => Err(ser::Error::custom("cannot serialize function pointers")),
+        ValueKind::Format(..) => Err(ser::Error::custom("cannot serialize format specifications")),
+        ValueKind::Iterator(..) => Err(ser::Error::custom("cannot serialize iterators")),
+        ValueKind::RangeFrom(..) => Err(ser::Error::custom("cannot serialize `start..` ranges")),
+        ValueKind::RangeFull(..) => Err(ser::Error::custom("cannot serialize `..` ranges")),
+        ValueKind::RangeInclusive(..) => {
+            Err(ser::Error::custom("cannot serialize `start..=end` ranges"))
+        }
+        ValueKind::RangeToInclusive(..) => {
+            Err(ser::Error::custom("cannot serialize `..=end` ranges"))
+        }
+        ValueKind::RangeTo(..) => Err(ser::Error::custom("cannot serialize `..end` ranges")),
+        ValueKind::Range(..) => Err(ser::Error::custom("cannot serialize `start..end` ranges")),
+        ValueKind::ControlFlow(..) => {
+            Err(ser::Error::custom("cannot serialize `start..end` ranges"))
         }
+        ValueKind::Any(..) => Err(ser::Error::custom("cannot serialize external objects")),
     }
 }
 
@@ -56,6 +56,16 @@ impl Variant {
         &mut self.data
     }
 
+    /// Get the discriminant of the variant.
+    ///
+    /// Only unit variants of enums declared in scripts have a discriminant.
+    pub fn discriminant(&self) -> Option<i64> {
+        match self.data {
+            VariantData::Empty => self.rtti.discriminant,
+            _ => None,
+        }
+    }
+
     /// Get type info for the variant.
     pub fn type_info(&self) -> TypeInfo {
         TypeInfo::Variant(self.rtti.clone())
@@ -0,0 +1,141 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::alloc::String;
+use crate::runtime::{
+    Formatter, FromValue, FullTypeOf, Future, Iterator, MaybeTypeOf, Protocol, Value, VmResult,
+};
+
+/// A type which can be constructed from any value which implements a given
+/// protocol.
+///
+/// This is used through [`Via`] to declare that a native function accepts any
+/// value implementing the protocol.
+pub trait FromProtocol: 'static + Sized {
+    /// The protocol which is used to perform the conversion.
+    const PROTOCOL: Protocol;
+
+    /// Convert the value by calling [`FromProtocol::PROTOCOL`] on it.
+    fn from_protocol(value: Value) -> VmResult<Self>;
+}
+
+impl FromProtocol for Iterator {
+    const PROTOCOL: Protocol = Protocol::INTO_ITER;
+
+    #[inline]
+    fn from_protocol(value: Value) -> VmResult<Self> {
+        value.into_iter()
+    }
+}
+
+impl FromProtocol for Future {
+    const PROTOCOL: Protocol = Protocol::INTO_FUTURE;
+
+    #[inline]
+    fn from_protocol(value: Value) -> VmResult<Self> {
+        value.into_future()
+    }
+}
+
+impl FromProtocol for String {
+    const PROTOCOL: Protocol = Protocol::STRING_DISPLAY;
+
+    #[inline]
+    fn from_protocol(value: Value) -> VmResult<Self> {
+        let mut f = Formatter::new();
+        vm_try!(value.string_display(&mut f));
+        VmResult::Ok(f.string)
+    }
+}
+
+/// A native function argument which accepts any value implementing the
+/// protocol associated with `T`, like [`Protocol::INTO_ITER`] for
+/// [`Iterator`].
+///
+/// The protocol is called on the argument before the native function is
+/// invoked, so a single function can accept a vector, an object's iterator or
+/// a generator alike. If the argument doesn't implement the protocol, the call
+/// fails with a runtime error.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Module, Vm};
+/// use rune::runtime::{Iterator, Via, VmResult};
+/// use std::sync::Arc;
+///
+/// fn count(mut iter: Via<Iterator>) -> VmResult<usize> {
+///     let mut n = 0;
+///
+///     while rune::vm_try!(iter.next()).is_some() {
+///         n += 1;
+///     }
+///
+///     VmResult::Ok(n)
+/// }
+///
+/// let mut m = Module::new();
+/// m.function("count", count).build()?;
+///
+/// let mut context = Context::with_default_modules()?;
+/// context.install(m)?;
+/// let runtime = Arc::new(context.runtime()?);
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             count([1, 2, 3]) + count(0..2)
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+/// let output: usize = rune::from_value(vm.call(["main"], ())?)?;
+/// assert_eq!(output, 5);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Via<T>(pub T);
+
+impl<T> Via<T> {
+    /// Coerce into the converted value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Via<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Via<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> FromValue for Via<T>
+where
+    T: FromProtocol,
+{
+    #[inline]
+    fn from_value(value: Value) -> VmResult<Self> {
+        VmResult::Ok(Self(vm_try!(T::from_protocol(value))))
+    }
+}
+
+impl<T> MaybeTypeOf for Via<T> {
+    /// Any value implementing the protocol is accepted, so the argument has
+    /// no static type.
+    #[inline]
+    fn maybe_type_of() -> Option<FullTypeOf> {
+        None
+    }
+}
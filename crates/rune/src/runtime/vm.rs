@@ -14,16 +14,20 @@ use crate::runtime::budget;
 use crate::runtime::future::SelectFuture;
 use crate::runtime::unit::{UnitFn, UnitStorage};
 use crate::runtime::{
-    self, Args, Awaited, BorrowMut, Bytes, Call, ControlFlow, EmptyStruct, Format, FormatSpec,
-    Formatter, FromValue, Function, Future, Generator, GuardedArgs, Inst, InstAddress,
-    InstAssignOp, InstOp, InstRange, InstTarget, InstValue, InstVariant, Object, OwnedTuple, Panic,
-    Protocol, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
-    RuntimeContext, Select, Stack, Stream, Struct, Type, TypeCheck, TypeOf, Unit, Value, ValueKind,
-    Variant, VariantData, Vec, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmResult,
-    VmSendExecution,
+    self, Args, Awaited, BorrowMut, Bytes, Call, CancellationToken, ControlFlow, EmptyStruct,
+    Format, FormatSpec, Formatter, FromValue, Function, FunctionSignature, Future, Generator,
+    GuardedArgs, Inst, InstAddress, InstAssignOp, InstOp, InstRange, InstTarget, InstValue,
+    InstVariant, Object, OwnedTuple, Panic, Protocol, Range, RangeFrom, RangeFull, RangeInclusive,
+    RangeTo, RangeToInclusive, RuntimeContext, Select, Stack, Stream, Struct, Type, TypeCheck,
+    TypeOf, Unit, Value, ValueKind, Variant, VariantData, Vec, VmError, VmErrorKind, VmExecution,
+    VmHalt, VmIntegerRepr, VmResult, VmSendExecution,
 };
 
-use super::{VmDiagnostics, VmDiagnosticsObj};
+use super::{VmDiagnostics, VmDiagnosticsObj, VmTracer, VmTracerObj};
+
+/// The number of backward jumps between each time the deadline of a
+/// cancellation token is checked.
+const DEADLINE_INTERVAL: u32 = 1024;
 
 /// Small helper function to build errors.
 fn err<T, E>(error: E) -> VmResult<T>
@@ -105,6 +109,13 @@ pub struct Vm {
     stack: Stack,
     /// Frames relative to the stack.
     call_frames: alloc::Vec<CallFrame>,
+    /// The installed tracer, if any.
+    tracer: Option<VmTracerObj>,
+    /// The token used to cancel execution, if any.
+    cancellation: Option<CancellationToken>,
+    /// The number of backward jumps performed, used to decide when to check
+    /// the deadline of the cancellation token.
+    backward_jumps: u32,
 }
 
 impl Vm {
@@ -130,9 +141,85 @@ impl Vm {
             last_ip_len: 0,
             stack,
             call_frames: alloc::Vec::new(),
+            tracer: None,
+            cancellation: None,
+            backward_jumps: 0,
+        }
+    }
+
+    /// Install a [`VmTracer`] which is called as the virtual machine executes,
+    /// replacing any previously installed tracer.
+    ///
+    /// Tracers are not cloned along with the virtual machine.
+    ///
+    /// See [`CountingTracer`] for an example.
+    ///
+    /// [`CountingTracer`]: crate::runtime::CountingTracer
+    pub fn with_tracer<T>(mut self, tracer: T) -> Self
+    where
+        T: VmTracer,
+    {
+        self.tracer = Some(VmTracerObj::new(tracer));
+        self
+    }
+
+    /// Access the installed tracer if it is of type `T`.
+    pub fn tracer<T>(&self) -> Option<&T>
+    where
+        T: VmTracer,
+    {
+        self.tracer.as_ref()?.downcast_ref()
+    }
+
+    /// Access the installed tracer mutably if it is of type `T`.
+    pub fn tracer_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: VmTracer,
+    {
+        self.tracer.as_mut()?.downcast_mut()
+    }
+
+    /// Remove and return the installed tracer if it is of type `T`.
+    ///
+    /// If the installed tracer is of a different type, it is left in place.
+    pub fn take_tracer<T>(&mut self) -> Option<T>
+    where
+        T: VmTracer,
+    {
+        match self.tracer.take()?.downcast() {
+            Ok(tracer) => Some(tracer),
+            Err(tracer) => {
+                self.tracer = Some(tracer);
+                None
+            }
         }
     }
 
+    /// Get the token used to cancel execution, if one is installed.
+    pub(crate) fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Install the token used to cancel execution.
+    pub(crate) fn set_cancellation(&mut self, cancellation: Option<CancellationToken>) {
+        self.cancellation = cancellation;
+    }
+
+    /// Test if execution has been cancelled at a backward jump.
+    ///
+    /// The deadline of the token is only checked every
+    /// [`DEADLINE_INTERVAL`] jumps, since reading the clock in a tight loop is
+    /// comparatively expensive.
+    #[inline]
+    fn is_cancelled_at_jump(&mut self) -> bool {
+        let Some(cancellation) = &self.cancellation else {
+            return false;
+        };
+
+        self.backward_jumps = self.backward_jumps.wrapping_add(1);
+        cancellation.check(self.backward_jumps % DEADLINE_INTERVAL == 0)
+    }
+
     /// Construct a vm with a default empty [RuntimeContext]. This is useful
     /// when the [Unit] was constructed with an empty
     /// [Context][crate::compile::Context].
@@ -173,6 +260,25 @@ impl Vm {
         &self.stack
     }
 
+    /// Inspect the stack frame at the given `depth` without executing any
+    /// instructions.
+    ///
+    /// A depth of `0` corresponds to the currently executing function, `1` to
+    /// its caller and so forth. Returns `None` if there is no frame at the
+    /// given depth.
+    pub fn stack_frame_at(&self, depth: usize) -> Option<StackFrame<'_>> {
+        let (ip, start, end) = if depth == 0 {
+            (self.ip, self.stack.stack_bottom(), self.stack.len())
+        } else {
+            let index = self.call_frames.len().checked_sub(depth)?;
+            let frame = self.call_frames.get(index)?;
+            (frame.ip, frame.stack_bottom, frame.stack_base)
+        };
+
+        let stack = self.stack.get(start..end)?;
+        Some(StackFrame { ip, stack })
+    }
+
     /// Get the stack mutably.
     #[inline]
     pub fn stack_mut(&mut self) -> &mut Stack {
@@ -707,11 +813,13 @@ impl Vm {
         tracing::trace!("pushing call frame");
 
         let stack_bottom = self.stack.swap_stack_bottom(args)?;
+        let stack_base = self.stack.stack_bottom();
         let ip = replace(&mut self.ip, ip);
 
         let frame = CallFrame {
             ip,
             stack_bottom,
+            stack_base,
             isolated,
         };
 
@@ -1141,6 +1249,16 @@ impl Vm {
             ValueKind::Integer(a) => convert!(i64, *a, ty),
             ValueKind::Float(a) => convert!(f64, *a, ty),
             ValueKind::Byte(a) => convert!(u8, *a, ty),
+            ValueKind::Variant(variant) => {
+                let Some(discriminant) = variant.discriminant() else {
+                    return err(VmErrorKind::UnsupportedAs {
+                        value: variant.type_info(),
+                        type_hash: ty.into_hash(),
+                    });
+                };
+
+                convert!(i64, discriminant, ty)
+            }
             kind => {
                 return err(VmErrorKind::UnsupportedAs {
                     value: kind.type_info(),
@@ -1200,6 +1318,10 @@ impl Vm {
         let stack = self.stack.drain(args)?.try_collect::<Stack>()?;
         let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
         vm.ip = offset;
+        vm.cancellation = self
+            .cancellation
+            .as_ref()
+            .map(CancellationToken::detached_child);
         self.stack.push(Generator::new(vm))?;
         Ok(())
     }
@@ -1209,6 +1331,10 @@ impl Vm {
         let stack = self.stack.drain(args)?.try_collect::<Stack>()?;
         let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
         vm.ip = offset;
+        vm.cancellation = self
+            .cancellation
+            .as_ref()
+            .map(CancellationToken::detached_child);
         self.stack.push(Stream::new(vm))?;
         Ok(())
     }
@@ -1218,6 +1344,10 @@ impl Vm {
         let stack = self.stack.drain(args)?.try_collect::<Stack>()?;
         let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
         vm.ip = offset;
+        vm.cancellation = self
+            .cancellation
+            .as_ref()
+            .map(CancellationToken::detached_child);
         let mut execution = vm.into_execution();
         let future = Future::new(async move { execution.async_complete().await })?;
         self.stack.push(future)?;
@@ -1599,7 +1729,7 @@ impl Vm {
         }
 
         vm_try!(self.stack.popn(count));
-        self.ip = vm_try!(self.unit.translate(jump));
+        vm_try!(self.jump_to(jump));
         VmResult::Ok(())
     }
 
@@ -1622,6 +1752,14 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    /// Prepare the value on top of the stack to be yielded.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_yield(&mut self) -> VmResult<()> {
+        let value = vm_try!(vm_try!(self.stack.pop()).detach());
+        vm_try!(self.stack.push(value));
+        VmResult::Ok(())
+    }
+
     /// Move a value from a position relative to the top of the stack, to the
     /// top of the stack.
     #[cfg_attr(feature = "bench", inline(never))]
@@ -1655,10 +1793,26 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    /// Jump to the given offset.
+    ///
+    /// Since loops are built out of backward jumps, this is where a
+    /// cancellation token is checked.
+    #[inline]
+    fn jump_to(&mut self, jump: usize) -> VmResult<()> {
+        let ip = vm_try!(self.unit.translate(jump));
+
+        if ip < self.ip && self.is_cancelled_at_jump() {
+            return err(VmErrorKind::Cancelled);
+        }
+
+        self.ip = ip;
+        VmResult::Ok(())
+    }
+
     /// Perform a jump operation.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_jump(&mut self, jump: usize) -> VmResult<()> {
-        self.ip = vm_try!(self.unit.translate(jump));
+        vm_try!(self.jump_to(jump));
         VmResult::Ok(())
     }
 
@@ -1666,7 +1820,17 @@ impl Vm {
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_jump_if(&mut self, jump: usize) -> VmResult<()> {
         if vm_try!(vm_try!(self.stack.pop()).as_bool()) {
-            self.ip = vm_try!(self.unit.translate(jump));
+            vm_try!(self.jump_to(jump));
+        }
+
+        VmResult::Ok(())
+    }
+
+    /// Perform a negated conditional jump operation.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_jump_if_not(&mut self, jump: usize) -> VmResult<()> {
+        if !vm_try!(vm_try!(self.stack.pop()).as_bool()) {
+            vm_try!(self.jump_to(jump));
         }
 
         VmResult::Ok(())
@@ -1677,7 +1841,7 @@ impl Vm {
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_jump_if_or_pop(&mut self, jump: usize) -> VmResult<()> {
         if vm_try!(vm_try!(self.stack.last()).as_bool()) {
-            self.ip = vm_try!(self.unit.translate(jump));
+            vm_try!(self.jump_to(jump));
         } else {
             vm_try!(self.stack.pop());
         }
@@ -1690,7 +1854,7 @@ impl Vm {
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_jump_if_not_or_pop(&mut self, jump: usize) -> VmResult<()> {
         if !vm_try!(vm_try!(self.stack.last()).as_bool()) {
-            self.ip = vm_try!(self.unit.translate(jump));
+            vm_try!(self.jump_to(jump));
         } else {
             vm_try!(self.stack.pop());
         }
@@ -1704,7 +1868,7 @@ impl Vm {
         if let Some(current) = self.stack.peek() {
             if matches!(*vm_try!(current.borrow_kind_ref()), ValueKind::Integer(current) if current == branch)
             {
-                self.ip = vm_try!(self.unit.translate(jump));
+                vm_try!(self.jump_to(jump));
                 vm_try!(self.stack.pop());
             }
         }
@@ -2190,17 +2354,39 @@ impl Vm {
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
-    fn op_return(&mut self, address: InstAddress) -> Result<bool, VmErrorKind> {
-        let return_value = self.stack.address(address)?;
-        self.op_return_internal(return_value)
+    fn op_return(&mut self, address: InstAddress) -> VmResult<bool> {
+        vm_try!(self.trace_return());
+        let return_value = vm_try!(self.stack.address(address));
+        VmResult::Ok(vm_try!(self.op_return_internal(return_value)))
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
     #[tracing::instrument(skip(self))]
-    fn op_return_unit(&mut self) -> Result<bool, VmErrorKind> {
-        let exit = self.pop_call_frame()?;
-        self.stack.push(())?;
-        Ok(exit)
+    fn op_return_unit(&mut self) -> VmResult<bool> {
+        vm_try!(self.trace_return());
+        let exit = vm_try!(self.pop_call_frame());
+        vm_try!(self.stack.push(()));
+        VmResult::Ok(exit)
+    }
+
+    /// Notify the installed tracer, if any, that a function is being called.
+    #[inline]
+    fn trace_call(&mut self, hash: Hash) -> VmResult<()> {
+        if let Some(tracer) = &mut self.tracer {
+            vm_try!(tracer.on_call(hash));
+        }
+
+        VmResult::Ok(())
+    }
+
+    /// Notify the installed tracer, if any, that a function is returning.
+    #[inline]
+    fn trace_return(&mut self) -> VmResult<()> {
+        if let Some(tracer) = &mut self.tracer {
+            vm_try!(tracer.on_return());
+        }
+
+        VmResult::Ok(())
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
@@ -2531,6 +2717,13 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_constant(&mut self, slot: usize) -> VmResult<()> {
+        let value = vm_try!(vm_try!(self.unit.lookup_constant(slot)).as_value());
+        vm_try!(self.stack.push(value));
+        VmResult::Ok(())
+    }
+
     /// Optimize operation to perform string concatenation.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_string_concat(&mut self, len: usize, size_hint: usize) -> VmResult<()> {
@@ -2651,6 +2844,27 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    /// Jump to the offset in the given string table which matches the top of
+    /// the stack, if any.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_jump_string_table(&mut self, slot: usize) -> VmResult<()> {
+        let value = vm_try!(self.stack.pop());
+
+        let jump = match *vm_try!(value.borrow_kind_ref()) {
+            ValueKind::String(ref actual) => {
+                let table = vm_try!(self.unit.lookup_string_table(slot));
+                table.lookup(actual.as_str())
+            }
+            _ => None,
+        };
+
+        if let Some(jump) = jump {
+            vm_try!(self.jump_to(jump));
+        }
+
+        VmResult::Ok(())
+    }
+
     /// Test if the top of stack is equal to the string at the given static
     /// bytes slot.
     #[cfg_attr(feature = "bench", inline(never))]
@@ -2869,16 +3083,45 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    /// Check that the number of arguments matches what the unit function
+    /// identified by `hash` expects, naming the function in the error if it's
+    /// available in debug info.
+    fn check_unit_args(&self, hash: Hash, args: usize, expected: usize) -> VmResult<()> {
+        if args == expected {
+            return VmResult::Ok(());
+        }
+
+        let Some(signature) = self
+            .unit
+            .debug_info()
+            .and_then(|debug| debug.functions.get(&hash))
+        else {
+            return err(VmErrorKind::BadArgumentCount {
+                actual: args,
+                expected,
+            });
+        };
+
+        err(VmErrorKind::BadFunctionArgumentCount {
+            function: vm_try!(signature.path.try_clone()),
+            actual: args,
+            expected,
+        })
+    }
+
     /// Implementation of a function call.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_call(&mut self, hash: Hash, args: usize) -> VmResult<()> {
+        vm_try!(self.trace_call(hash));
+
         let Some(info) = self.unit.function(hash) else {
             let handler = vm_try!(self
                 .context
                 .function(hash)
                 .ok_or(VmErrorKind::MissingFunction { hash }));
 
-            vm_try!(handler(&mut self.stack, args));
+            let result = handler(&mut self.stack, args);
+            vm_try!(check_native_call(&self.context, hash, args, result));
             return VmResult::Ok(());
         };
 
@@ -2889,7 +3132,7 @@ impl Vm {
                 args: expected,
                 ..
             } => {
-                vm_try!(check_args(args, expected));
+                vm_try!(self.check_unit_args(hash, args, expected));
                 vm_try!(self.call_offset_fn(offset, call, args));
             }
             UnitFn::EmptyStruct { hash } => {
@@ -2952,6 +3195,16 @@ impl Vm {
     /// Call a function at the given offset with the given number of arguments.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_call_offset(&mut self, offset: usize, call: Call, args: usize) -> VmResult<()> {
+        if self.tracer.is_some() {
+            let hash = self
+                .unit
+                .debug_info()
+                .and_then(|debug| debug.functions_rev.get(&offset).copied())
+                .unwrap_or(Hash::EMPTY);
+
+            vm_try!(self.trace_call(hash));
+        }
+
         vm_try!(self.call_offset_fn(offset, call, args));
         VmResult::Ok(())
     }
@@ -2960,9 +3213,9 @@ impl Vm {
     fn op_call_associated(&mut self, hash: Hash, args: usize) -> VmResult<()> {
         // NB: +1 to include the instance itself.
         let args = args + 1;
-        let instance = vm_try!(self.stack.at_offset_from_top(args));
-        let type_hash = vm_try!(instance.type_hash());
+        let type_hash = vm_try!(vm_try!(self.stack.at_offset_from_top(args)).type_hash());
         let hash = Hash::associated_function(type_hash, hash);
+        vm_try!(self.trace_call(hash));
 
         if let Some(UnitFn::Offset {
             offset,
@@ -2971,17 +3224,20 @@ impl Vm {
             ..
         }) = self.unit.function(hash)
         {
-            vm_try!(check_args(args, expected));
+            vm_try!(self.check_unit_args(hash, args, expected));
             vm_try!(self.call_offset_fn(offset, call, args));
             return VmResult::Ok(());
         }
 
         if let Some(handler) = self.context.function(hash) {
             vm_try!(self.called_function_hook(hash));
-            vm_try!(handler(&mut self.stack, args));
+            let result = handler(&mut self.stack, args);
+            vm_try!(check_native_call(&self.context, hash, args, result));
             return VmResult::Ok(());
         }
 
+        let instance = vm_try!(self.stack.at_offset_from_top(args));
+
         err(VmErrorKind::MissingInstanceFunction {
             instance: vm_try!(instance.type_info()),
             hash,
@@ -2996,6 +3252,7 @@ impl Vm {
         let ty = match *vm_try!(function.borrow_kind_ref()) {
             ValueKind::Type(ty) => ty,
             ValueKind::Function(ref function) => {
+                vm_try!(self.trace_call(function.type_hash()));
                 return function.call_with_vm(self, args);
             }
             ref actual => {
@@ -3108,6 +3365,10 @@ impl Vm {
 
             tracing::trace!(ip = ?self.ip, ?inst);
 
+            if let Some(tracer) = &mut self.tracer {
+                vm_try!(tracer.on_instruction(&self.unit, self.ip, &inst));
+            }
+
             self.ip = self.ip.wrapping_add(inst_len);
             self.last_ip_len = inst_len as u8;
 
@@ -3220,6 +3481,9 @@ impl Vm {
                 Inst::JumpIf { jump } => {
                     vm_try!(self.op_jump_if(jump));
                 }
+                Inst::JumpIfNot { jump } => {
+                    vm_try!(self.op_jump_if_not(jump));
+                }
                 Inst::JumpIfOrPop { jump } => {
                     vm_try!(self.op_jump_if_or_pop(jump));
                 }
@@ -3274,6 +3538,9 @@ impl Vm {
                 Inst::Bytes { slot } => {
                     vm_try!(self.op_bytes(slot));
                 }
+                Inst::Constant { slot } => {
+                    vm_try!(self.op_constant(slot));
+                }
                 Inst::StringConcat { len, size_hint } => {
                     vm_try!(self.op_string_concat(len, size_hint));
                 }
@@ -3306,6 +3573,9 @@ impl Vm {
                 Inst::EqBytes { slot } => {
                     vm_try!(self.op_eq_bytes(slot));
                 }
+                Inst::JumpStringTable { slot } => {
+                    vm_try!(self.op_jump_string_table(slot));
+                }
                 Inst::MatchSequence {
                     type_check,
                     len,
@@ -3330,6 +3600,7 @@ impl Vm {
                     vm_try!(self.op_match_object(slot, exact));
                 }
                 Inst::Yield => {
+                    vm_try!(self.op_yield());
                     return VmResult::Ok(VmHalt::Yielded);
                 }
                 Inst::YieldUnit => {
@@ -3367,6 +3638,12 @@ impl TryClone for Vm {
             last_ip_len: self.last_ip_len,
             stack: self.stack.try_clone()?,
             call_frames: self.call_frames.try_clone()?,
+            tracer: None,
+            cancellation: self
+                .cancellation
+                .as_ref()
+                .map(CancellationToken::detached_child),
+            backward_jumps: 0,
         })
     }
 }
@@ -3399,11 +3676,36 @@ pub struct CallFrame {
     /// I.e. a function should not be able to manipulate the size of any other
     /// stack than its own.
     pub stack_bottom: usize,
+    /// The offset in the stack where the frame of the called function begins.
+    stack_base: usize,
     /// Indicates that the call frame is isolated and should force an exit into
     /// the vm execution context.
     pub isolated: bool,
 }
 
+impl CallFrame {
+    /// The offset in the stack where the frame of the called function begins.
+    #[inline]
+    pub fn stack_base(&self) -> usize {
+        self.stack_base
+    }
+}
+
+/// A view into a single frame of the stack, as returned by
+/// [`Vm::stack_frame_at`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct StackFrame<'a> {
+    /// The instruction pointer of the frame.
+    ///
+    /// For the currently executing frame this is the current instruction
+    /// pointer, for every other frame it's the point at which execution will
+    /// resume once the call it's waiting on returns.
+    pub ip: usize,
+    /// The values on the stack which belong to the frame.
+    pub stack: &'a [Value],
+}
+
 impl TryClone for CallFrame {
     #[inline]
     fn try_clone(&self) -> alloc::Result<Self> {
@@ -3420,6 +3722,41 @@ impl Drop for ClearStack<'_> {
     }
 }
 
+/// Name the native function identified by `hash` in the error raised when
+/// it's called with the wrong number of arguments.
+///
+/// The error is only replaced if it was raised by the function's own argument
+/// check, and not by something it in turn called.
+fn check_native_call(
+    context: &RuntimeContext,
+    hash: Hash,
+    args: usize,
+    result: VmResult<()>,
+) -> VmResult<()> {
+    let VmResult::Err(error) = result else {
+        return result;
+    };
+
+    let Some(FunctionSignature {
+        item: Some(item),
+        args: Some(expected),
+        ..
+    }) = context.signature(hash)
+    else {
+        return VmResult::Err(error);
+    };
+
+    if !error.is_bad_argument_count(args, *expected) {
+        return VmResult::Err(error);
+    }
+
+    err(VmErrorKind::BadFunctionArgumentCount {
+        function: vm_try!(item.try_clone()),
+        actual: args,
+        expected: *expected,
+    })
+}
+
 /// Check that arguments matches expected or raise the appropriate error.
 fn check_args(args: usize, expected: usize) -> Result<(), VmErrorKind> {
     if args != expected {
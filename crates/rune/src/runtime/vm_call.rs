@@ -3,8 +3,8 @@ use ::rust_alloc::sync::Arc;
 use crate::alloc::prelude::*;
 use crate::runtime::vm_execution::VmExecutionState;
 use crate::runtime::{
-    Call, Future, Generator, RuntimeContext, Stack, Stream, Unit, Value, Vm, VmErrorKind,
-    VmExecution, VmResult,
+    Call, CancellationToken, Future, Generator, RuntimeContext, Stack, Stream, Unit, Value, Vm,
+    VmErrorKind, VmExecution, VmResult,
 };
 
 /// An instruction to push a virtual machine to the execution.
@@ -88,6 +88,12 @@ impl VmCall {
 
         let mut vm = Vm::with_stack(context, unit, new_stack);
         vm.set_ip(ip);
+        vm.set_cancellation(
+            execution
+                .vm()
+                .cancellation()
+                .map(CancellationToken::detached_child),
+        );
         VmResult::Ok(vm)
     }
 }
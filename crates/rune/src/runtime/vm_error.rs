@@ -7,6 +7,7 @@ use ::rust_alloc::sync::Arc;
 use crate::alloc::error::CustomError;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, String};
+use crate::ast::Span;
 use crate::compile::ItemBuf;
 use crate::hash::Hash;
 use crate::runtime::unit::{BadInstruction, BadJump};
@@ -14,6 +15,7 @@ use crate::runtime::{
     AccessError, AccessErrorKind, BoxedPanic, CallFrame, ExecutionState, FullTypeOf, MaybeTypeOf,
     Panic, Protocol, StackError, TypeInfo, TypeOf, Unit, Vm, VmHaltInfo,
 };
+use crate::SourceId;
 
 /// Trait used to convert result types to [`VmResult`].
 #[doc(hidden)]
@@ -85,6 +87,23 @@ pub struct VmErrorLocation {
     pub frames: ::rust_alloc::vec::Vec<CallFrame>,
 }
 
+/// A call-site annotation added through [`VmError::with_context`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct VmErrorFrame {
+    /// The span of the call site.
+    pub span: Span,
+    /// The source the span belongs to.
+    pub source_id: SourceId,
+}
+
+impl fmt::Display for VmErrorFrame {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at {}:{}", self.source_id, self.span)
+    }
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct VmErrorAt {
@@ -120,6 +139,7 @@ pub(crate) struct VmErrorInner {
     pub(crate) error: VmErrorAt,
     pub(crate) chain: ::rust_alloc::vec::Vec<VmErrorAt>,
     pub(crate) stacktrace: ::rust_alloc::vec::Vec<VmErrorLocation>,
+    pub(crate) frames: ::rust_alloc::vec::Vec<VmErrorFrame>,
 }
 
 /// A virtual machine error which includes tracing information.
@@ -141,6 +161,7 @@ impl VmError {
                 },
                 chain: ::rust_alloc::vec::Vec::new(),
                 stacktrace: ::rust_alloc::vec::Vec::new(),
+                frames: ::rust_alloc::vec::Vec::new(),
             }),
         }
     }
@@ -177,11 +198,46 @@ impl VmError {
         Self::from(VmErrorKind::Overflow)
     }
 
+    /// Construct an underflow error.
+    pub fn underflow() -> Self {
+        Self::from(VmErrorKind::Underflow)
+    }
+
     /// Get the first error location.
     pub fn first_location(&self) -> Option<&VmErrorLocation> {
         self.inner.stacktrace.first()
     }
 
+    /// Annotate the error with the call site it passed through.
+    ///
+    /// Frames are recorded in the order they are added, so the innermost call
+    /// site should be added first. They are printed as a stack trace when the
+    /// error is displayed.
+    pub fn with_context(mut self, span: Span, source_id: SourceId) -> Self {
+        self.inner.frames.push(VmErrorFrame { span, source_id });
+        self
+    }
+
+    /// Get the call-site frames added through [`VmError::with_context`].
+    pub fn frames(&self) -> &[VmErrorFrame] {
+        &self.inner.frames
+    }
+
+    /// Test if the error was caused by the execution being cancelled through
+    /// a [`CancellationToken`].
+    ///
+    /// [`CancellationToken`]: crate::runtime::CancellationToken
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.inner.error.kind, VmErrorKind::Cancelled)
+    }
+
+    /// Test if this is a bad argument count error with the given number of
+    /// arguments, which hasn't been annotated with any further errors.
+    pub(crate) fn is_bad_argument_count(&self, actual: usize, expected: usize) -> bool {
+        self.inner.chain.is_empty()
+            && self.inner.error.kind == VmErrorKind::BadArgumentCount { actual, expected }
+    }
+
     #[cfg(test)]
     pub(crate) fn into_kind(self) -> VmErrorKind {
         self.inner.error.kind
@@ -191,7 +247,13 @@ impl VmError {
 impl fmt::Display for VmError {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner.error.fmt(f)
+        self.inner.error.fmt(f)?;
+
+        for frame in &self.inner.frames {
+            write!(f, "\n    {frame}")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -201,6 +263,7 @@ impl fmt::Debug for VmError {
             .field("error", &self.inner.error)
             .field("chain", &self.inner.chain)
             .field("stacktrace", &self.inner.stacktrace)
+            .field("frames", &self.inner.frames)
             .finish()
     }
 }
@@ -444,6 +507,7 @@ impl<const N: usize> From<[VmErrorKind; N]> for VmError {
                 },
                 chain,
                 stacktrace: ::rust_alloc::vec::Vec::new(),
+                frames: ::rust_alloc::vec::Vec::new(),
             }),
         }
     }
@@ -569,6 +633,7 @@ pub(crate) enum VmErrorKind {
         reason: Panic,
     },
     NoRunningVm,
+    Cancelled,
     Halted {
         halt: VmHaltInfo,
     },
@@ -615,6 +680,12 @@ pub(crate) enum VmErrorKind {
     MissingStaticObjectKeys {
         slot: usize,
     },
+    MissingConstant {
+        slot: usize,
+    },
+    MissingStringTable {
+        slot: usize,
+    },
     MissingVariantRtti {
         hash: Hash,
     },
@@ -625,6 +696,11 @@ pub(crate) enum VmErrorKind {
         actual: usize,
         expected: usize,
     },
+    BadFunctionArgumentCount {
+        function: ItemBuf,
+        actual: usize,
+        expected: usize,
+    },
     BadEnvironmentCount {
         actual: usize,
         expected: usize,
@@ -683,6 +759,10 @@ pub(crate) enum VmErrorKind {
         index: VmIntegerRepr,
         length: VmIntegerRepr,
     },
+    SliceStartAfterEnd {
+        start: VmIntegerRepr,
+        end: VmIntegerRepr,
+    },
     UnsupportedTryOperand {
         actual: TypeInfo,
     },
@@ -762,6 +842,9 @@ pub(crate) enum VmErrorKind {
     },
     MissingCallFrame,
     IllegalFormat,
+    RecursionLimitExceeded {
+        limit: usize,
+    },
 }
 
 impl fmt::Display for VmErrorKind {
@@ -780,6 +863,7 @@ impl fmt::Display for VmErrorKind {
             }
             VmErrorKind::Panic { reason } => write!(f, "Panicked: {reason}"),
             VmErrorKind::NoRunningVm {} => write!(f, "No running virtual machines"),
+            VmErrorKind::Cancelled => write!(f, "Execution was cancelled"),
             VmErrorKind::Halted { halt } => write!(f, "Halted for unexpected reason `{halt}`"),
             VmErrorKind::Overflow {} => write!(f, "Numerical overflow"),
             VmErrorKind::Underflow {} => write!(f, "Numerical underflow"),
@@ -818,6 +902,12 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::MissingStaticString { slot } => {
                 write!(f, "Static string slot `{slot}` does not exist",)
             }
+            VmErrorKind::MissingConstant { slot } => {
+                write!(f, "Constant slot `{slot}` does not exist",)
+            }
+            VmErrorKind::MissingStringTable { slot } => {
+                write!(f, "String table slot `{slot}` does not exist",)
+            }
             VmErrorKind::MissingStaticObjectKeys { slot } => {
                 write!(f, "Static object keys slot `{slot}` does not exist",)
             }
@@ -832,6 +922,14 @@ impl fmt::Display for VmErrorKind {
                 f,
                 "Wrong number of arguments `{actual}`, expected `{expected}`",
             ),
+            VmErrorKind::BadFunctionArgumentCount {
+                function,
+                actual,
+                expected,
+            } => write!(
+                f,
+                "Wrong number of arguments `{actual}` to `{function}`, expected `{expected}`",
+            ),
             VmErrorKind::BadEnvironmentCount { actual, expected } => write!(
                 f,
                 "Wrong environment size `{actual}`, expected `{expected}`",
@@ -890,6 +988,9 @@ impl fmt::Display for VmErrorKind {
                 f,
                 "Index out of bounds, the length is `{length}` but the index is `{index}`",
             ),
+            VmErrorKind::SliceStartAfterEnd { start, end } => {
+                write!(f, "Slice index starts at `{start}` but ends at `{end}`",)
+            }
             VmErrorKind::UnsupportedTryOperand { actual } => {
                 write!(f, "Type `{actual}` is not supported as try operand",)
             }
@@ -972,6 +1073,9 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::IllegalFormat => {
                 write!(f, "Value cannot be formatted")
             }
+            VmErrorKind::RecursionLimitExceeded { limit } => {
+                write!(f, "Recursion limit of {limit} exceeded")
+            }
         }
     }
 }
@@ -8,8 +8,8 @@ use crate::alloc::clone::TryClone;
 use crate::alloc::Vec;
 use crate::runtime::budget;
 use crate::runtime::{
-    Generator, GeneratorState, RuntimeContext, Stream, Unit, Value, Vm, VmErrorKind, VmHalt,
-    VmHaltInfo, VmResult,
+    CancellationToken, Generator, GeneratorState, RuntimeContext, Stream, Unit, Value, Vm,
+    VmErrorKind, VmHalt, VmHaltInfo, VmResult,
 };
 use crate::shared::AssertSend;
 
@@ -148,6 +148,23 @@ where
         Stream::from_execution(self)
     }
 
+    /// Install a token which can be used to cancel the execution.
+    ///
+    /// The token is checked at every backward jump, and while waiting for a
+    /// future. Once it's been cancelled, the execution errors with an error
+    /// for which [`VmError::is_cancelled`] returns `true`, and every value
+    /// which is live in the execution is dropped before the error is
+    /// returned.
+    ///
+    /// Any async functions, generators and streams called by the execution
+    /// are cancelled along with it, see [`CancellationToken`] for details.
+    ///
+    /// [`VmError::is_cancelled`]: crate::runtime::VmError::is_cancelled
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.head.as_mut().set_cancellation(Some(token));
+        self
+    }
+
     /// Get a reference to the current virtual machine.
     pub fn vm(&self) -> &Vm {
         self.head.as_ref()
@@ -170,6 +187,42 @@ where
         }
     }
 
+    /// Complete the current execution with support for async instructions,
+    /// cancelling it if it hasn't completed within the given `timeout`.
+    ///
+    /// A cancelled execution errors with an error for which
+    /// [`VmError::is_cancelled`] returns `true`. This also respects any token
+    /// installed with [`VmExecution::with_cancellation`].
+    ///
+    /// [`VmError::is_cancelled`]: crate::runtime::VmError::is_cancelled
+    #[cfg(feature = "tokio")]
+    pub async fn async_complete_with_timeout(
+        &mut self,
+        timeout: core::time::Duration,
+    ) -> VmResult<Value> {
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        let parent = self.head.as_ref().cancellation().cloned();
+        let token = parent
+            .as_ref()
+            .map(|parent| parent.child_with_deadline(deadline.into_std()))
+            .unwrap_or_else(|| CancellationToken::with_deadline(deadline.into_std()));
+
+        self.head.as_mut().set_cancellation(Some(token));
+
+        let result = match tokio::time::timeout_at(deadline, self.async_complete()).await {
+            Ok(result) => result,
+            // The timer fired while the execution was waiting for a future.
+            Err(..) => {
+                self.clear();
+                VmResult::err(VmErrorKind::Cancelled)
+            }
+        };
+
+        self.head.as_mut().set_cancellation(parent);
+        result
+    }
+
     /// Complete the current execution without support for async instructions.
     ///
     /// If any async instructions are encountered, this will error. This will
@@ -204,6 +257,7 @@ where
             });
         }
 
+        let value = vm_try!(value.detach());
         vm_try!(self.head.as_mut().stack_mut().push(value));
         self.inner_async_resume(None).await
     }
@@ -234,6 +288,14 @@ where
     }
 
     async fn inner_async_resume(
+        &mut self,
+        diagnostics: Option<&mut dyn VmDiagnostics>,
+    ) -> VmResult<GeneratorState> {
+        let result = self.async_resume_loop(diagnostics).await;
+        self.check_cancelled(result)
+    }
+
+    async fn async_resume_loop(
         &mut self,
         mut diagnostics: Option<&mut dyn VmDiagnostics>,
     ) -> VmResult<GeneratorState> {
@@ -249,7 +311,11 @@ where
             {
                 VmHalt::Exited => (),
                 VmHalt::Awaited(awaited) => {
-                    vm_try!(awaited.into_vm(vm).await);
+                    match vm.cancellation().cloned() {
+                        Some(token) => vm_try!(token.or_cancelled(awaited.into_vm(vm)).await),
+                        None => vm_try!(awaited.into_vm(vm).await),
+                    }
+
                     continue;
                 }
                 VmHalt::VmCall(vm_call) => {
@@ -287,6 +353,7 @@ where
             });
         }
 
+        let value = vm_try!(value.detach());
         vm_try!(self.head.as_mut().stack_mut().push(value));
         self.inner_resume(None)
     }
@@ -322,6 +389,14 @@ where
     }
 
     fn inner_resume(
+        &mut self,
+        diagnostics: Option<&mut dyn VmDiagnostics>,
+    ) -> VmResult<GeneratorState> {
+        let result = self.resume_loop(diagnostics);
+        self.check_cancelled(result)
+    }
+
+    fn resume_loop(
         &mut self,
         mut diagnostics: Option<&mut dyn VmDiagnostics>,
     ) -> VmResult<GeneratorState> {
@@ -424,6 +499,36 @@ where
         VmResult::Ok(None)
     }
 
+    /// If the execution was cancelled, drop every value which is still live in
+    /// it so that cleanup happens deterministically.
+    fn check_cancelled<O>(&mut self, result: VmResult<O>) -> VmResult<O> {
+        if let VmResult::Err(error) = &result {
+            if error.is_cancelled() {
+                self.clear();
+            }
+        }
+
+        result
+    }
+
+    /// Clear the execution, restoring the original context and unit of the
+    /// head virtual machine.
+    fn clear(&mut self) {
+        while let Some(state) = self.states.pop() {
+            let vm = self.head.as_mut();
+
+            if let Some(context) = state.context {
+                *vm.context_mut() = context;
+            }
+
+            if let Some(unit) = state.unit {
+                *vm.unit_mut() = unit;
+            }
+        }
+
+        self.head.as_mut().clear();
+    }
+
     /// End execution and perform debug checks.
     pub(crate) fn end(&mut self) -> VmResult<Value> {
         let vm = self.head.as_mut();
@@ -493,6 +598,29 @@ pub struct VmSendExecution(pub(crate) VmExecution<Vm>);
 unsafe impl Send for VmSendExecution {}
 
 impl VmSendExecution {
+    /// Install a token which can be used to cancel the execution.
+    ///
+    /// See [`VmExecution::with_cancellation`].
+    pub fn with_cancellation(self, token: CancellationToken) -> Self {
+        Self(self.0.with_cancellation(token))
+    }
+
+    /// Complete the current execution with support for async instructions,
+    /// cancelling it if it hasn't completed within the given `timeout`.
+    ///
+    /// See [`VmExecution::async_complete_with_timeout`].
+    #[cfg(feature = "tokio")]
+    pub fn async_complete_with_timeout(
+        mut self,
+        timeout: core::time::Duration,
+    ) -> impl Future<Output = VmResult<Value>> + Send + 'static {
+        let future = async move { self.0.async_complete_with_timeout(timeout).await };
+
+        // Safety: we wrap all APIs around the [VmExecution], preventing values
+        // from escaping from contained virtual machine.
+        unsafe { AssertSend::new(future) }
+    }
+
     /// Complete the current execution with support for async instructions.
     ///
     /// This requires that the result of the Vm is converted into a
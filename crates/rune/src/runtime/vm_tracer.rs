@@ -0,0 +1,234 @@
+use core::any::Any;
+use core::fmt;
+
+use ::rust_alloc::boxed::Box;
+
+use crate::alloc::HashMap;
+use crate::hash::Hash;
+use crate::runtime::{Inst, Unit, VmResult};
+
+/// A hook which is called by the virtual machine as it executes.
+///
+/// Tracers are installed with [`Vm::with_tracer`] and are only consulted if
+/// one is present, so a virtual machine without a tracer pays no additional
+/// cost.
+///
+/// Note that generators, streams and async functions run on virtual machines
+/// of their own, which do not inherit the tracer.
+///
+/// [`Vm::with_tracer`]: crate::Vm::with_tracer
+pub trait VmTracer: 'static {
+    /// Called before the instruction `inst` at `ip` in `unit` is executed.
+    ///
+    /// The instruction pointer can be combined with the [`DebugInfo`] of the
+    /// unit to resolve which source location is being executed.
+    ///
+    /// [`DebugInfo`]: crate::runtime::DebugInfo
+    fn on_instruction(&mut self, unit: &Unit, ip: usize, inst: &Inst) -> VmResult<()> {
+        _ = (unit, ip, inst);
+        VmResult::Ok(())
+    }
+
+    /// Called when a function identified by `hash` is called.
+    ///
+    /// Calls to functions in the unit which have been resolved to offsets
+    /// during compilation are only identified if the unit has debug
+    /// information, otherwise they are reported as [`Hash::EMPTY`].
+    fn on_call(&mut self, hash: Hash) -> VmResult<()> {
+        _ = hash;
+        VmResult::Ok(())
+    }
+
+    /// Called when a function in the unit returns.
+    fn on_return(&mut self) -> VmResult<()> {
+        VmResult::Ok(())
+    }
+}
+
+/// A [`VmTracer`] which counts how many times each instruction was executed
+/// and how many times each function was called.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use rune::{Context, Vm};
+/// use rune::runtime::CountingTracer;
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime()?);
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn add(a, b) { a + b }
+///         pub fn main() { add(1, 2) + add(3, 4) }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit)).with_tracer(CountingTracer::new());
+///
+/// let output = vm.call(["main"], ())?;
+/// let output: i64 = rune::from_value(output)?;
+/// assert_eq!(output, 10);
+///
+/// let tracer = vm.tracer::<CountingTracer>().expect("tracer is installed");
+/// assert_eq!(tracer.calls(rune::Hash::type_hash(["add"])), 2);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Default, Debug)]
+pub struct CountingTracer {
+    instructions: HashMap<usize, usize>,
+    calls: HashMap<Hash, usize>,
+    returns: usize,
+}
+
+impl CountingTracer {
+    /// Construct a new empty counting tracer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of times the instruction at `ip` was executed.
+    pub fn instruction(&self, ip: usize) -> usize {
+        self.instructions.get(&ip).copied().unwrap_or_default()
+    }
+
+    /// Iterate over the instruction pointers and the number of times they were
+    /// executed, in no particular order.
+    pub fn instructions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.instructions.iter().map(|(ip, count)| (*ip, *count))
+    }
+
+    /// Get the total number of instructions executed.
+    pub fn total_instructions(&self) -> usize {
+        self.instructions.values().sum()
+    }
+
+    /// Get the number of times the function identified by `hash` was called.
+    pub fn calls(&self, hash: Hash) -> usize {
+        self.calls.get(&hash).copied().unwrap_or_default()
+    }
+
+    /// Get the number of times a function in the unit returned.
+    pub fn returns(&self) -> usize {
+        self.returns
+    }
+}
+
+impl VmTracer for CountingTracer {
+    fn on_instruction(&mut self, _: &Unit, ip: usize, _: &Inst) -> VmResult<()> {
+        *vm_try!(self.instructions.entry(ip).or_try_insert(0)) += 1;
+        VmResult::Ok(())
+    }
+
+    fn on_call(&mut self, hash: Hash) -> VmResult<()> {
+        *vm_try!(self.calls.entry(hash).or_try_insert(0)) += 1;
+        VmResult::Ok(())
+    }
+
+    fn on_return(&mut self) -> VmResult<()> {
+        self.returns += 1;
+        VmResult::Ok(())
+    }
+}
+
+/// Object-safe extension of [`VmTracer`] which allows the installed tracer to
+/// be accessed by its concrete type.
+pub(crate) trait DynVmTracer: VmTracer {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T> DynVmTracer for T
+where
+    T: VmTracer,
+{
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// A tracer installed in a virtual machine.
+pub(crate) struct VmTracerObj {
+    inner: Box<dyn DynVmTracer>,
+}
+
+impl VmTracerObj {
+    #[inline]
+    pub(crate) fn new<T>(tracer: T) -> Self
+    where
+        T: VmTracer,
+    {
+        Self {
+            inner: Box::new(tracer),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: VmTracer,
+    {
+        self.inner.as_any().downcast_ref()
+    }
+
+    #[inline]
+    pub(crate) fn downcast_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: VmTracer,
+    {
+        self.inner.as_any_mut().downcast_mut()
+    }
+
+    #[inline]
+    pub(crate) fn downcast<T>(self) -> Result<T, Self>
+    where
+        T: VmTracer,
+    {
+        if !self.inner.as_any().is::<T>() {
+            return Err(self);
+        }
+
+        match self.inner.into_any().downcast() {
+            Ok(tracer) => Ok(*tracer),
+            Err(..) => unreachable!(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn on_instruction(&mut self, unit: &Unit, ip: usize, inst: &Inst) -> VmResult<()> {
+        self.inner.on_instruction(unit, ip, inst)
+    }
+
+    #[inline]
+    pub(crate) fn on_call(&mut self, hash: Hash) -> VmResult<()> {
+        self.inner.on_call(hash)
+    }
+
+    #[inline]
+    pub(crate) fn on_return(&mut self) -> VmResult<()> {
+        self.inner.on_return()
+    }
+}
+
+impl fmt::Debug for VmTracerObj {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VmTracerObj").finish_non_exhaustive()
+    }
+}
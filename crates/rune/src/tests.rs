@@ -414,8 +414,10 @@ mod bug_454;
 mod bug_700;
 mod bugfixes;
 mod builtin_macros;
+mod bytes;
 mod capture;
 mod char;
+mod clone_deep;
 mod collections;
 mod comments;
 mod compiler_docs;
@@ -433,6 +435,8 @@ mod core_macros;
 mod custom_macros;
 mod deprecation;
 mod derive_from_to_value;
+mod derive_heap_size;
+mod derive_try_clone;
 mod destructuring;
 mod esoteric_impls;
 mod external_constructor;
@@ -442,10 +446,12 @@ mod external_ops;
 mod float;
 mod for_loop;
 mod format_source;
+mod function_arity;
 mod generics;
 mod getter_setter;
 mod instance;
 mod int;
+mod item_cycles;
 mod iter;
 mod iterator;
 mod macros;
@@ -454,27 +460,40 @@ mod option;
 mod patterns;
 mod quote;
 mod range;
+mod recursion_limit;
 mod reference_error;
 mod rename_type;
 mod result;
+mod runtime_context;
+mod slot_reuse;
+mod source_loader;
 mod stmt_reordering;
 mod string_debug;
+mod struct_defaults;
+mod target_arch;
 mod tuple;
 mod type_name_native;
 mod type_name_rune;
 mod unit_constants;
+mod unit_disassemble;
+mod used_items;
+mod value_display;
 mod variants;
+mod via;
 mod vm_arithmetic;
 mod vm_assign_exprs;
 mod vm_async_block;
 mod vm_blocks;
+mod vm_cancellation;
 mod vm_closures;
 mod vm_const_exprs;
 mod vm_early_termination;
+mod vm_error_context;
 mod vm_function;
 mod vm_function_pointers;
 mod vm_general;
 mod vm_generators;
+mod vm_if;
 mod vm_is;
 mod vm_lazy_and_or;
 mod vm_literals;
@@ -483,12 +502,14 @@ mod vm_not_used;
 mod vm_option;
 mod vm_pat;
 mod vm_result;
+mod vm_stack_frames;
 mod vm_streams;
 mod vm_test_from_value_derive;
 mod vm_test_imports;
 mod vm_test_instance_fns;
 mod vm_test_linked_list;
 mod vm_test_mod;
+mod vm_tracer;
 mod vm_try;
 mod vm_tuples;
 mod vm_typed_tuple;
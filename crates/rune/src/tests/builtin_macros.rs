@@ -71,3 +71,39 @@ fn format_macros() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn dbg_macro() -> Result<()> {
+    let string = capture!(dbg!(1 + 2););
+    assert_eq!(string, "[<memory>:1] 1 + 2 = 3\n");
+
+    let string = capture!(dbg!("hello"););
+    assert_eq!(string, "[<memory>:1] \"hello\" = \"hello\"\n");
+
+    // The value is passed through, and only evaluated once.
+    let string = capture!(
+        let n = 0;
+        let x = dbg!({ n += 1; n * 10 });
+        println!("{} {}", x, n);
+    );
+    assert_eq!(string, "[<memory>:1] { n += 1; n * 10 } = 10\n10 1\n");
+
+    // Used in expression position.
+    let string = capture!(println!("{}", dbg!(2) + dbg!(3)););
+    assert_eq!(string, "[<memory>:1] 2 = 2\n[<memory>:1] 3 = 3\n5\n");
+
+    // Multiple arguments evaluate to a tuple.
+    let string = capture!(
+        let (a, b) = dbg!(1, "two",);
+        println!("{} {}", a, b);
+    );
+    assert_eq!(
+        string,
+        "[<memory>:1] 1 = 1\n[<memory>:1] \"two\" = \"two\"\n1 two\n"
+    );
+
+    // No arguments prints the location.
+    let string = capture!(dbg!(););
+    assert_eq!(string, "[<memory>:1]\n");
+    Ok(())
+}
@@ -0,0 +1,108 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn test_byte_string_escapes() {
+    let bytes: runtime::Bytes = rune! {
+        pub fn main() {
+            b"\x00\x7f\xff\n\t\\\"a"
+        }
+    };
+
+    assert_eq!(bytes, b"\x00\x7f\xff\n\t\\\"a");
+
+    rune_assert! {
+        pub fn main() {
+            b"\xf0\x9f\x92\x96".to_hex() == "f09f9296" && Bytes::from_hex("f09f9296") == Some(b"\xf0\x9f\x92\x96")
+        }
+    };
+}
+
+#[test]
+fn test_byte_string_non_ascii() {
+    assert_errors! {
+        r#"pub fn main() { b"aé" }"#,
+        span!(19, 21), BadByteStringChar
+    };
+
+    assert_errors! {
+        r#"pub fn main() { b"\x00é" }"#,
+        span!(22, 24), BadByteStringChar
+    };
+}
+
+#[test]
+fn test_bytes_index() {
+    rune_assert! {
+        pub fn main() {
+            let bytes = b"abcd";
+            bytes[0] == b'a' && bytes[1..3] == b"bc" && bytes.get(4).is_none()
+        }
+    };
+
+    assert_vm_error!(
+        r#"pub fn main() { b"abcd"[1..5] }"#,
+        VmErrorKind::MissingIndex { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { b"abcd"[4] }"#,
+        VmErrorKind::MissingIndex { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { b"abcd".slice(1, 5) }"#,
+        VmErrorKind::OutOfRange { index, length } => {
+            assert_eq!(index.to_string(), "5");
+            assert_eq!(length.to_string(), "4");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { b"abcd".slice(3, 1) }"#,
+        VmErrorKind::SliceStartAfterEnd { start, end } => {
+            assert_eq!(start.to_string(), "3");
+            assert_eq!(end.to_string(), "1");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { b"abcd".split_at(5) }"#,
+        VmErrorKind::OutOfRange { .. } => {}
+    );
+}
+
+#[test]
+fn test_bytes_search() {
+    let result: (Option<usize>, bool, bool, bool) = rune! {
+        pub fn main() {
+            let bytes = b"hi \xf0\x9f\x92\x96 \xf0\x9f\x92\x96";
+            (
+                bytes.find(b"\x92\x96 \xf0"),
+                bytes.starts_with(b"hi \xf0\x9f"),
+                bytes.starts_with(b"hi \xf0\x9e"),
+                bytes.ends_with(b"\x9f\x92\x96"),
+            )
+        }
+    };
+
+    assert_eq!(result, (Some(5), true, false, true));
+}
+
+#[test]
+fn test_bytes_for_loop() {
+    let values: Vec<u8> = rune! {
+        pub fn main() {
+            let out = [];
+
+            for b in b"a\xffz" {
+                out.push(b);
+            }
+
+            out
+        }
+    };
+
+    assert_eq!(values, [b'a', 0xff, b'z']);
+}
@@ -0,0 +1,69 @@
+prelude!();
+
+#[test]
+fn test_clone_deep() -> Result<()> {
+    let original: Value = rune! {
+        pub fn main() {
+            let inner = [1, 2];
+            [inner, (inner, #{ inner })]
+        }
+    };
+
+    let copy = original.try_clone_deep()?;
+
+    copy.borrow_vec_ref()?[0]
+        .borrow_vec_mut()?
+        .push(rune::to_value(3i64)?)?;
+
+    let tuple = copy.borrow_vec_ref()?[1].clone();
+    let tuple = tuple.borrow_tuple_ref()?;
+    let object = tuple[1].borrow_object_ref()?;
+    object
+        .get("inner")
+        .context("missing inner")?
+        .borrow_vec_mut()?
+        .push(rune::to_value(4i64)?)?;
+
+    let lengths = |value: &Value| -> Result<[usize; 3]> {
+        let vec = value.borrow_vec_ref()?;
+        let tuple = vec[1].borrow_tuple_ref()?;
+        let object = tuple[1].borrow_object_ref()?;
+
+        let first = vec[0].borrow_vec_ref()?.len();
+        let second = tuple[0].borrow_vec_ref()?.len();
+        let third = object
+            .get("inner")
+            .context("missing inner")?
+            .borrow_vec_ref()?
+            .len();
+
+        Ok([first, second, third])
+    };
+
+    assert_eq!(lengths(&original)?, [2, 2, 2]);
+    assert_eq!(lengths(&copy)?, [3, 2, 3]);
+    Ok(())
+}
+
+#[test]
+fn test_clone_deep_cycle() -> Result<()> {
+    let value: Value = rune! {
+        pub fn main() {
+            let a = [[]];
+            let b = a[0];
+            b.push(a);
+            a
+        }
+    };
+
+    let error = value.try_clone_deep().unwrap_err();
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::RecursionLimitExceeded { .. }
+    ));
+
+    // Break the cycle so that the values are freed.
+    value.borrow_vec_mut()?.clear();
+    Ok(())
+}
@@ -19,3 +19,11 @@ fn mut_let() {
         span!(20, 23), UnsupportedMut
     };
 }
+
+#[test]
+fn destructure_assign_non_place() {
+    assert_errors! {
+        r#"pub fn main() { let a = 0; (a, 1) = (1, 2); }"#,
+        span!(31, 32), UnsupportedAssignExpr
+    };
+}
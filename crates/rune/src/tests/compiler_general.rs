@@ -59,8 +59,9 @@ fn test_wrong_arguments() {
 fn test_bad_struct_declaration() {
     assert_errors! {
         r#"struct Foo { a, b } pub fn main() { Foo { a: 12 } }"#,
-        span!(36, 49), LitObjectMissingField { field, .. } => {
-            assert_eq!(field.as_ref(), "b");
+        span!(36, 49), LitObjectMissingField { fields, .. } => {
+            assert_eq!(fields.len(), 1);
+            assert_eq!(fields[0].as_ref(), "b");
         }
     };
 
@@ -18,7 +18,7 @@ fn test_import_cycle() {
             Foo
         }             
         "#,
-        span!(244, 247), ImportCycle { .. }
+        span!(244, 247), Cycle { .. }
     };
 
     assert_errors! {
@@ -35,7 +35,7 @@ fn test_import_cycle() {
             a::Foo
         }           
         "#,
-        span!(161, 167), ImportCycle { path, .. } => {
+        span!(161, 167), Cycle { path, .. } => {
             assert_eq!(3, path.len());
             assert_eq!(span!(99, 112), path[0].location.span);
             assert_eq!(span!(37, 50), path[1].location.span);
@@ -152,3 +152,100 @@ fn test_access_super() {
 
     assert_eq!(value, 1);
 }
+
+#[test]
+fn test_private_sibling() {
+    assert_errors! {
+        r#"
+        mod a { fn hidden() { 1 } }
+        mod b { pub fn test() { crate::a::hidden() } }
+        pub fn main() { b::test() }
+        "#,
+        span, NotVisible { .. } => {
+            assert_eq!(span, span!(69, 85));
+        }
+    };
+}
+
+#[test]
+fn test_private_from_child() {
+    let value: i64 = rune! {
+        mod a {
+            fn hidden() { 1 }
+
+            mod c {
+                pub(super) fn test() { super::hidden() }
+            }
+
+            pub fn test() { c::test() }
+        }
+
+        pub fn main() {
+            a::test()
+        }
+    };
+
+    assert_eq!(value, 1);
+}
+
+#[test]
+fn test_pub_super_one_level() {
+    let value: i64 = rune! {
+        mod a {
+            pub mod b {
+                pub(super) fn test() { 1 }
+            }
+
+            pub fn test() { b::test() }
+        }
+
+        pub fn main() {
+            a::test()
+        }
+    };
+
+    assert_eq!(value, 1);
+
+    assert_errors! {
+        r#"
+        mod a { pub mod b { pub(super) fn test() { 1 } } }
+        pub fn main() { a::b::test() }
+        "#,
+        span, NotVisible { .. } => {
+            assert_eq!(span, span!(84, 94));
+        }
+    };
+}
+
+#[test]
+fn test_reexport_deep_item() {
+    let value: i64 = rune! {
+        mod a {
+            mod b {
+                pub fn deep() { 1 }
+            }
+
+            pub use self::b::deep;
+        }
+
+        pub fn main() {
+            a::deep()
+        }
+    };
+
+    assert_eq!(value, 1);
+
+    assert_errors! {
+        r#"
+        mod a {
+            mod b { pub fn deep() { 1 } }
+            pub use self::b::deep;
+        }
+
+        pub fn main() { a::b::deep() }
+        "#,
+        span, NotVisibleMod { .. } => {
+            assert_eq!(span, span!(129, 139));
+        }
+    };
+}
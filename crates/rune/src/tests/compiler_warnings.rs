@@ -25,3 +25,76 @@ fn test_remove_variant_parens() {
         span!(20, 22), RemoveTupleCallParams { variant: span!(16, 20), .. }
     };
 }
+
+#[test]
+fn test_excessive_shadowing() {
+    assert_warnings! {
+        r#"pub fn main() { let a = 1; let a = a + 1; let a = a + 1; let a = a + 1; a }"#,
+        span!(61, 62), ExcessiveShadowing { context: Some(span!(14, 75)), count: 3, .. }
+    };
+}
+
+/// Compile the given source with the given shadowing limit, returning the name
+/// and count of every excessive shadowing warning.
+fn shadowing(source: &str, limit: usize) -> Result<Vec<(String, usize)>> {
+    let context = Context::with_default_modules()?;
+
+    let mut options = compile::Options::default();
+    options.shadowing_limit(limit);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source)?)?;
+
+    let mut diagnostics = Diagnostics::new();
+
+    prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .with_diagnostics(&mut diagnostics)
+        .build()?;
+
+    let mut counts = Vec::new();
+
+    for diagnostic in diagnostics.into_diagnostics() {
+        if let diagnostics::Diagnostic::Warning(warning) = diagnostic {
+            if let ExcessiveShadowing { name, count, .. } = warning.into_kind() {
+                counts.push((name.into_std(), count));
+            }
+        }
+    }
+
+    Ok(counts)
+}
+
+#[test]
+fn test_shadow_counts() -> Result<()> {
+    let none = "pub fn main() { let a = 1; let b = 2; a + b }";
+    assert_eq!(shadowing(none, 1)?, []);
+
+    let once = "pub fn main() { let a = 1; let a = a + 1; a }";
+    assert_eq!(shadowing(once, 1)?, [("a".to_string(), 1)]);
+
+    // Variables in enclosing scopes count, but not those in sibling scopes
+    // which are no longer live.
+    let nested = r#"
+    pub fn main() {
+        let a = 1;
+        { let a = 2; }
+        { let a = 3; { let a = 4; { let a = 5; a } } }
+    }
+    "#;
+
+    assert_eq!(
+        shadowing(nested, 1)?,
+        [
+            ("a".to_string(), 1),
+            ("a".to_string(), 1),
+            ("a".to_string(), 2),
+            ("a".to_string(), 3)
+        ]
+    );
+
+    assert_eq!(shadowing(nested, 3)?, [("a".to_string(), 3)]);
+    assert_eq!(shadowing(nested, 0)?, []);
+    Ok(())
+}
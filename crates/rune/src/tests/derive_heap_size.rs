@@ -0,0 +1,73 @@
+prelude!();
+
+use core::marker::PhantomData;
+
+use crate::alloc::heap_size::HeapSize;
+use crate::alloc::{String, Vec};
+
+#[derive(HeapSize)]
+#[heap_size(crate)]
+enum Shape {
+    Empty,
+    Point(i64, i64),
+    Named { name: String, points: Vec<u32> },
+}
+
+#[derive(HeapSize)]
+#[heap_size(crate)]
+struct Mixed<T> {
+    values: Vec<T>,
+    shape: Shape,
+    #[heap_size(skip)]
+    cache: Vec<u8>,
+}
+
+/// Doesn't implement `HeapSize`.
+struct Opaque;
+
+#[derive(HeapSize)]
+#[heap_size(crate, bound = {})]
+struct Phantom<T> {
+    name: String,
+    #[heap_size(skip)]
+    _marker: PhantomData<T>,
+}
+
+#[test]
+fn test_derive_heap_size() -> Result<()> {
+    assert_eq!(Shape::Empty.heap_size(), 0);
+    assert_eq!(Shape::Point(1, 2).heap_size(), 0);
+
+    let named = Shape::Named {
+        name: String::try_with_capacity(7)?,
+        points: Vec::try_with_capacity(3)?,
+    };
+
+    assert_eq!(named.heap_size(), 7 + 3 * 4);
+
+    let mut values = Vec::try_with_capacity(2)?;
+    values.try_push(String::try_with_capacity(5)?)?;
+
+    let mixed = Mixed {
+        values,
+        shape: named,
+        cache: Vec::try_with_capacity(100)?,
+    };
+
+    // The skipped cache isn't included.
+    let expected = 2 * core::mem::size_of::<String>() + 5 + 7 + 3 * 4;
+    assert_eq!(mixed.heap_size(), expected);
+    assert_eq!(mixed.cache.capacity(), 100);
+    Ok(())
+}
+
+#[test]
+fn test_derive_heap_size_bound() -> Result<()> {
+    let value = Phantom::<Opaque> {
+        name: String::try_with_capacity(3)?,
+        _marker: PhantomData,
+    };
+
+    assert_eq!(value.heap_size(), 3);
+    Ok(())
+}
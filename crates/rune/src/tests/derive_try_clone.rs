@@ -0,0 +1,198 @@
+prelude!();
+
+use core::marker::PhantomData;
+
+use crate::alloc::clone::TryClone;
+use crate::alloc::{HashMap, String, Vec};
+
+fn scale(value: &u32) -> u32 {
+    *value * 2
+}
+
+/// Records whether it was assigned through `try_clone_from`.
+#[derive(Debug)]
+struct Tracked {
+    value: u32,
+    reused: bool,
+}
+
+impl PartialEq for Tracked {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl TryClone for Tracked {
+    fn try_clone(&self) -> alloc::Result<Self> {
+        Ok(Self {
+            value: self.value,
+            reused: false,
+        })
+    }
+
+    fn try_clone_from(&mut self, source: &Self) -> alloc::Result<()> {
+        self.value = source.value;
+        self.reused = true;
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, TryClone)]
+#[try_clone(crate)]
+enum Shape {
+    Empty,
+    Point(#[try_clone(copy)] i64, #[try_clone(copy)] i64),
+    Named { name: String, tracked: Tracked },
+}
+
+#[derive(Debug, PartialEq, TryClone)]
+#[try_clone(crate)]
+struct Mixed {
+    values: Vec<u32>,
+    tracked: Tracked,
+    lookup: HashMap<String, u32>,
+    #[try_clone(copy)]
+    count: usize,
+    #[try_clone(with = scale)]
+    scaled: u32,
+    shape: Shape,
+}
+
+#[derive(Debug, PartialEq, TryClone)]
+#[try_clone(crate)]
+struct Generic<T> {
+    inner: Vec<T>,
+}
+
+/// A type which doesn't implement `TryClone`.
+struct Opaque;
+
+#[derive(TryClone)]
+#[try_clone(crate, bound = {})]
+struct Phantom<T> {
+    id: u32,
+    #[try_clone(copy)]
+    marker: PhantomData<T>,
+}
+
+fn mixed() -> alloc::Result<Mixed> {
+    let mut lookup = HashMap::new();
+    lookup.try_insert(String::try_from("a")?, 1)?;
+    lookup.try_insert(String::try_from("b")?, 2)?;
+
+    Ok(Mixed {
+        values: alloc::try_vec![1, 2, 3],
+        tracked: Tracked {
+            value: 1,
+            reused: false,
+        },
+        lookup,
+        count: 3,
+        scaled: 21,
+        shape: Shape::Named {
+            name: String::try_from("line")?,
+            tracked: Tracked {
+                value: 2,
+                reused: false,
+            },
+        },
+    })
+}
+
+#[test]
+fn derive_try_clone() -> alloc::Result<()> {
+    let a = mixed()?;
+    let b = a.try_clone()?;
+
+    assert_eq!(b.values, a.values);
+    assert_eq!(b.lookup, a.lookup);
+    assert_eq!(b.count, 3);
+    assert_eq!(b.scaled, 42);
+    assert_eq!(b.shape, a.shape);
+
+    let point = Shape::Point(1, 2);
+    assert_eq!(point.try_clone()?, Shape::Point(1, 2));
+    assert_eq!(Shape::Empty.try_clone()?, Shape::Empty);
+
+    let generic = Generic {
+        inner: alloc::try_vec![String::try_from("x")?],
+    };
+
+    assert_eq!(generic.try_clone()?, generic);
+    Ok(())
+}
+
+#[test]
+fn derive_try_clone_from() -> alloc::Result<()> {
+    let source = mixed()?;
+
+    let mut target = Mixed {
+        values: Vec::new(),
+        tracked: Tracked {
+            value: 0,
+            reused: false,
+        },
+        lookup: HashMap::new(),
+        count: 0,
+        scaled: 0,
+        shape: Shape::Named {
+            name: String::new(),
+            tracked: Tracked {
+                value: 0,
+                reused: false,
+            },
+        },
+    };
+
+    target.try_clone_from(&source)?;
+
+    assert_eq!(target.values, source.values);
+    assert_eq!(target.lookup, source.lookup);
+    assert_eq!(target.count, 3);
+    assert_eq!(target.scaled, 42);
+    assert_eq!(target.shape, source.shape);
+
+    // Fields and matching variants are assigned field-wise.
+    assert!(target.tracked.reused);
+
+    let Shape::Named { tracked, .. } = &target.shape else {
+        panic!("expected named shape");
+    };
+
+    assert!(tracked.reused);
+
+    // Mismatched variants are replaced wholesale.
+    let mut shape = Shape::Empty;
+    shape.try_clone_from(&Shape::Point(3, 4))?;
+    assert_eq!(shape, Shape::Point(3, 4));
+
+    shape.try_clone_from(&source.shape)?;
+    assert_eq!(shape, source.shape);
+
+    let Shape::Named { tracked, .. } = &shape else {
+        panic!("expected named shape");
+    };
+
+    assert!(!tracked.reused);
+    Ok(())
+}
+
+#[test]
+fn derive_try_clone_bound() -> alloc::Result<()> {
+    let source = Phantom::<Opaque> {
+        id: 7,
+        marker: PhantomData,
+    };
+
+    // An empty `bound` means `Opaque` doesn't have to implement `TryClone`.
+    assert_eq!(source.try_clone()?.id, 7);
+
+    let mut target = Phantom {
+        id: 0,
+        marker: PhantomData,
+    };
+
+    target.try_clone_from(&source)?;
+    assert_eq!(target.id, 7);
+    Ok(())
+}
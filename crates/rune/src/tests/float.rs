@@ -58,3 +58,36 @@ fn test_float_fns() {
     };
     assert_eq!(n, 1728.0);
 }
+
+#[test]
+fn test_float_classify() -> Result<()> {
+    let nan = rune::to_value(f64::NAN)?;
+    let inf = rune::to_value(f64::INFINITY)?;
+    let one = rune::to_value(1.0f64)?;
+    let int = rune::to_value(1i64)?;
+
+    assert!(nan.is_nan());
+    assert!(!nan.is_infinite());
+    assert!(!nan.is_finite());
+
+    assert!(!inf.is_nan());
+    assert!(inf.is_infinite());
+    assert!(!inf.is_finite());
+
+    assert!(!one.is_nan());
+    assert!(!one.is_infinite());
+    assert!(one.is_finite());
+
+    assert!(!int.is_nan());
+    assert!(!int.is_infinite());
+    assert!(!int.is_finite());
+
+    let out: (bool, bool, bool) = rune! {
+        pub fn main() {
+            let inf = 1.0 / 0.0;
+            ((0.0 / 0.0).is_nan(), inf.is_infinite(), !inf.is_finite())
+        }
+    };
+    assert_eq!(out, (true, true, true));
+    Ok(())
+}
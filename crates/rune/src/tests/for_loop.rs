@@ -53,3 +53,200 @@ fn test_ignore_binding() {
 
     assert_eq!(out, 4);
 }
+
+/// Collect the values produced by a for loop over `range`, once with the range
+/// written directly in the loop and once through a variable, which uses the
+/// iterator protocol.
+fn collect_range(range: &str) -> (Vec<i64>, Vec<i64>) {
+    let direct =
+        format!("pub fn main() {{ let out = []; for i in {range} {{ out.push(i); }} out }}");
+
+    let iter = format!(
+        "pub fn main() {{ let out = []; let r = {range}; for i in r {{ out.push(i); }} out }}"
+    );
+
+    (crate::tests::eval(direct), crate::tests::eval(iter))
+}
+
+#[test]
+fn test_integer_range() {
+    let ranges = [
+        "0..5",
+        "0..=5",
+        "5..5",
+        "5..=5",
+        "5..0",
+        "5..=0",
+        "-3..3",
+        "-3..=-1",
+        "9223372036854775805..9223372036854775807",
+        "9223372036854775805..=9223372036854775807",
+        "-9223372036854775807..=-9223372036854775806",
+    ];
+
+    for range in ranges {
+        let (direct, iter) = collect_range(range);
+        assert_eq!(direct, iter, "{range}");
+    }
+
+    let (direct, _) = collect_range("9223372036854775805..=9223372036854775807");
+    assert_eq!(direct, [i64::MAX - 2, i64::MAX - 1, i64::MAX]);
+}
+
+#[test]
+fn test_integer_range_bounds_once() {
+    let out: (i64, Vec<i64>) = rune! {
+        pub fn main() {
+            let calls = [];
+            let n = 3;
+
+            for i in 0..({ calls.push(n); n }) {
+                n = 10;
+            }
+
+            (n, calls)
+        }
+    };
+
+    assert_eq!(out, (10, vec![3]));
+}
+
+#[test]
+fn test_integer_range_binding() {
+    let out: Vec<i64> = rune! {
+        pub fn main() {
+            let out = [];
+
+            for i in 0..6 {
+                if i == 1 {
+                    continue;
+                }
+
+                if i == 4 {
+                    break;
+                }
+
+                // Assigning the binding doesn't affect iteration.
+                i = i * 10;
+                out.push(i);
+            }
+
+            out
+        }
+    };
+
+    assert_eq!(out, [0, 20, 30]);
+
+    let out: i64 = rune! {
+        pub fn main() {
+            let n = 0;
+
+            'outer: for i in 0..=10 {
+                for j in i..5 {
+                    if j == 3 {
+                        continue 'outer;
+                    }
+
+                    n += 1;
+                }
+            }
+
+            n
+        }
+    };
+
+    assert_eq!(out, 3 + 2 + 1 + 1);
+}
+
+#[test]
+fn test_integer_range_modify() {
+    // Modifying the loop variable or the variable a bound was read from
+    // doesn't affect iteration.
+    let out: Vec<i64> = rune! {
+        pub fn main() {
+            let n = 3;
+            let out = [];
+
+            for i in n..6 {
+                i += 1;
+                n += 1;
+                out.push(i);
+            }
+
+            out.push(n);
+            out
+        }
+    };
+
+    assert_eq!(out, [4, 5, 6, 6]);
+
+    let out: Vec<i64> = rune! {
+        pub fn main() {
+            let n = 3;
+            let out = [];
+
+            for i in 0..=n {
+                i += 1;
+                n += 1;
+                out.push(i);
+            }
+
+            out.push(n);
+            out
+        }
+    };
+
+    assert_eq!(out, [1, 2, 3, 4, 7]);
+}
+
+#[test]
+fn test_integer_range_unsupported() {
+    let context = Context::with_default_modules().unwrap();
+
+    let run =
+        |source: &str| crate::tests::run::<_, _, ()>(&context, source, ["main"], ()).unwrap_err();
+
+    let message = "Cannot build an iterator out of i64..f64";
+    assert!(run("pub fn main() { for i in 0..1.5 {} }")
+        .to_string()
+        .contains(message));
+    assert!(run("pub fn main() { let r = 0..1.5; for i in r {} }")
+        .to_string()
+        .contains(message));
+}
+
+#[test]
+fn test_integer_range_instructions() {
+    let mut diagnostics = Default::default();
+
+    let unit = crate::tests::compile_helper(
+        "pub fn main(n) { let out = 0; for i in 0..n { out += i; } out }",
+        &mut diagnostics,
+    )
+    .unwrap();
+
+    // No iterator is advanced on the fast path. The only protocol call is
+    // `into_iter` which raises an error if `n` isn't an integer.
+    assert!(!unit
+        .iter_instructions()
+        .any(|(_, inst)| matches!(inst, runtime::Inst::IterNext { .. })));
+
+    let calls = unit
+        .iter_instructions()
+        .filter(|(_, inst)| matches!(inst, runtime::Inst::CallAssociated { .. }))
+        .count();
+    assert_eq!(calls, 1);
+
+    let unit = crate::tests::compile_helper(
+        "pub fn main() { let out = 0; for i in 0..10 { out += i; } out }",
+        &mut diagnostics,
+    )
+    .unwrap();
+
+    assert!(!unit.iter_instructions().any(|(_, inst)| matches!(
+        inst,
+        runtime::Inst::IterNext { .. }
+            | runtime::Inst::CallAssociated { .. }
+            | runtime::Inst::Range { .. }
+    )));
+}
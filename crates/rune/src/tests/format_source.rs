@@ -193,3 +193,27 @@ pub async fn main() {
 
     assert_format_source(source, None)
 }
+
+#[test]
+fn fmt_enum_discriminants() -> Result<()> {
+    let source = r#"enum Flag {
+    A = 1,
+    B,
+    D = 1 << 3,
+}
+"#;
+
+    assert_format_source(source, None)
+}
+
+#[test]
+fn fmt_struct_field_defaults() -> Result<()> {
+    let source = r#"struct Config {
+    retries = 3,
+    verbose = false,
+    name,
+}
+"#;
+
+    assert_format_source(source, None)
+}
@@ -0,0 +1,120 @@
+prelude!();
+
+use ErrorKind::*;
+
+fn vm(context: &Context, mut sources: Sources) -> Result<Vm> {
+    let unit = prepare(&mut sources).with_context(context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()?), Arc::new(unit)))
+}
+
+#[test]
+fn test_native_arity_compile_error() {
+    assert_errors! {
+        "pub fn main() { let v = []; Vec::push(v, 1, 2) }",
+        span!(37, 46), UnsupportedArgumentCount { expected: 2, actual: 3, signature: Some(signature) } => {
+            #[cfg(feature = "doc")]
+            assert_eq!(signature.to_string(), "::std::vec::Vec::push(self, value)");
+            #[cfg(not(feature = "doc"))]
+            assert_eq!(signature.to_string(), "::std::vec::Vec::push(*, *)");
+        }
+    };
+}
+
+#[test]
+fn test_instance_arity_runtime_error() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = vm(
+        &context,
+        sources! {
+            entry => {
+                struct Foo;
+
+                impl Foo {
+                    fn bar(self, a) {
+                        a
+                    }
+                }
+
+                pub fn native(v) {
+                    v.push(1, 2)
+                }
+
+                pub fn script() {
+                    Foo.bar()
+                }
+            }
+        },
+    )?;
+
+    let error = vm.call(["native"], (Vec::<i64>::new(),)).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Wrong number of arguments `3` to `::std::vec::Vec::push`, expected `2`"
+    );
+
+    let error = vm.call(["script"], ()).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Wrong number of arguments `1` to `Foo::bar`, expected `2`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_variadic_arity() -> Result<()> {
+    let mut m = Module::new();
+
+    m.raw_function("sum", |stack, args| {
+        let mut sum = 0;
+
+        for value in vm_try!(stack.drain(args)) {
+            sum += vm_try!(value.as_integer());
+        }
+
+        vm_try!(stack.push(sum));
+        VmResult::Ok(())
+    })
+    .build()?;
+
+    m.function("call", |f: Function| f.call::<i64>((1i64,)))
+        .build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let mut vm = vm(
+        &context,
+        sources! {
+            entry => {
+                pub fn main() {
+                    sum(1) + sum(1, 2, 3)
+                }
+
+                pub fn closure() {
+                    call(|a, b| a + b)
+                }
+            }
+        },
+    )?;
+
+    let value: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(value, 7);
+
+    // The error is raised when calling the closure, so it isn't attributed to
+    // the native function which did so.
+    let error = vm.call(["closure"], ()).unwrap_err();
+
+    assert_eq!(
+        error.into_kind(),
+        VmErrorKind::BadArgumentCount {
+            actual: 1,
+            expected: 2
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn test_const_self_reference() {
+    assert_errors! {
+        "const A = A; pub fn main() { A }",
+        span!(10, 11), kind @ Cycle { .. } => {
+            assert_eq!(kind.to_string(), "Cycle detected: `A` -> `A`");
+        }
+    };
+}
+
+#[test]
+fn test_const_cycle_across_modules() {
+    assert_errors! {
+        r#"mod a { pub const A = crate::b::B; } mod b { pub const B = crate::c::C; } mod c { pub const C = crate::a::A; } pub fn main() { a::A }"#,
+        span!(96, 107), kind @ Cycle { .. } => {
+            assert_eq!(
+                kind.to_string(),
+                "Cycle detected: `a::A` -> `b::B` -> `c::C` -> `a::A`"
+            );
+
+            let Cycle { path } = kind else {
+                unreachable!();
+            };
+
+            // Each step points to the item participating in the cycle.
+            let spans = path.iter().map(|step| step.location.span).collect::<Vec<_>>();
+            assert_eq!(spans, [span!(8, 33), span!(45, 70), span!(82, 107), span!(8, 33)]);
+        }
+    };
+}
+
+#[test]
+fn test_const_fn_cycle() {
+    assert_errors! {
+        "const fn f() { g() } const fn g() { f() } const X = f(); pub fn main() { X }",
+        span!(36, 37), kind @ Cycle { .. } => {
+            assert_eq!(kind.to_string(), "Cycle detected: `f` -> `g` -> `f`");
+        }
+    };
+}
+
+#[test]
+fn test_diamond_is_not_a_cycle() {
+    let result: i64 = rune! {
+        mod d { pub const D = 1; }
+        mod b { pub const B = crate::d::D + 1; }
+        mod c { pub const C = crate::d::D + 2; }
+        const A = b::B + c::C;
+
+        pub fn main() {
+            A + d::D
+        }
+    };
+
+    assert_eq!(result, 6);
+}
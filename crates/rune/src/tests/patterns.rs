@@ -113,12 +113,18 @@ fn test_vec_patterns() {
     );
     assert_eq!(out, true);
 
-    let _: () = rune!(
+    // A match without a matching branch panics.
+    assert_vm_error!(
+        r#"
         pub fn main() {
             match [] {
                 [a, b] => a + 1 == b,
             }
         }
+        "#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "pattern did not match");
+        }
     );
 
     let out: bool = rune!(
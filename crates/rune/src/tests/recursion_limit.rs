@@ -0,0 +1,77 @@
+prelude!();
+
+use crate::ast::{Span, Spanned};
+use crate::compile::Options;
+use crate::diagnostics::{Diagnostic, FatalDiagnosticKind};
+
+/// Build an expression consisting of arrays nested `depth` levels deep.
+fn nested(depth: usize) -> String {
+    let mut source = String::from("pub fn main() { ");
+
+    for _ in 0..depth {
+        source.push('[');
+    }
+
+    source.push('1');
+
+    for _ in 0..depth {
+        source.push(']');
+    }
+
+    source.push_str(" }");
+    source
+}
+
+fn build(source: &str, limit: usize) -> Result<(), Diagnostics> {
+    let context = Context::with_default_modules().unwrap();
+
+    let mut options = Options::default();
+    options.recursion_limit(limit);
+
+    let mut sources = Sources::new();
+    sources
+        .insert(Source::new("main", source).unwrap())
+        .unwrap();
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    match result {
+        Ok(..) => Ok(()),
+        Err(..) => Err(diagnostics),
+    }
+}
+
+#[test]
+fn test_recursion_limit() {
+    assert!(build(&nested(6), 8).is_ok());
+
+    let diagnostics = build(&nested(10), 8).unwrap_err();
+    let mut it = diagnostics.into_diagnostics().into_iter();
+
+    let Some(Diagnostic::Fatal(e)) = it.next() else {
+        panic!("expected a fatal diagnostic");
+    };
+
+    let FatalDiagnosticKind::CompileError(e) = e.into_kind() else {
+        panic!("expected a compile error");
+    };
+
+    // The error points at the first array which is nested too deeply.
+    assert_eq!(e.span(), Span::new(24, 29));
+    assert!(matches!(
+        e.into_kind(),
+        ErrorKind::RecursionLimit { limit: 8 }
+    ));
+}
+
+#[test]
+fn test_recursion_limit_disabled() {
+    assert!(build(&nested(32), 0).is_ok());
+    assert!(build(&nested(32), 16).is_err());
+}
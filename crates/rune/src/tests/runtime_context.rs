@@ -0,0 +1,47 @@
+prelude!();
+
+use crate::runtime::FunctionMeta;
+
+#[derive(Any)]
+struct Counter {
+    value: i64,
+}
+
+#[test]
+fn test_iter_functions() -> Result<()> {
+    let mut m = Module::new();
+    m.ty::<Counter>()?;
+    m.function("add", |a: i64, b: i64| a + b).build()?;
+    m.associated_function("bump", |this: &mut Counter, n: i64| this.value += n)?;
+
+    let mut context = Context::new();
+    context.install(m)?;
+
+    let runtime = context.runtime()?;
+
+    let find = |name: &str| -> Option<FunctionMeta<'_>> {
+        runtime
+            .iter_functions()
+            .find(|f| f.item.is_some_and(|item| item.to_string() == name))
+    };
+
+    let add = find("add").expect("add is registered");
+    assert_eq!(add.hash, Hash::type_hash(["add"]));
+    assert_eq!(add.args, Some(2));
+    assert!(!add.is_method);
+    assert!(runtime.function(add.hash).is_some());
+
+    let bump = find("Counter::bump").expect("bump is registered");
+    assert_eq!(
+        bump.hash,
+        Hash::associated_function(<Counter as Any>::type_hash(), "bump")
+    );
+    assert_eq!(bump.args, Some(2));
+    assert!(bump.is_method);
+
+    // Every function in the iterator has a handler.
+    assert!(runtime
+        .iter_functions()
+        .all(|f| runtime.function(f.hash).is_some()));
+    Ok(())
+}
@@ -0,0 +1,59 @@
+prelude!();
+
+use crate::runtime::Inst;
+
+fn replaced(source: &str) -> usize {
+    let mut diagnostics = Default::default();
+    let unit = crate::tests::compile_helper(source, &mut diagnostics).unwrap();
+
+    unit.iter_instructions()
+        .filter(|(_, inst)| matches!(inst, Inst::Replace { .. }))
+        .count()
+}
+
+#[test]
+fn test_reuse_shadowed_slot() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let a = 1;
+            let a = a + 1;
+            let a = a * 10;
+            a
+        }
+    };
+    assert_eq!(out, 20);
+
+    let source = "pub fn main() { let a = 1; let a = a + 1; let a = a * 10; a }";
+    assert_eq!(replaced(source), 2);
+}
+
+#[test]
+fn test_reuse_moved_slot() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let a = 1;
+            let f = move || a;
+            let b = 2;
+            f() + b
+        }
+    };
+    assert_eq!(out, 3);
+
+    let source = "pub fn main() { let a = 1; let f = move || a; let b = 2; f() + b }";
+    assert_eq!(replaced(source), 1);
+}
+
+#[test]
+fn test_no_reuse_across_scopes() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let a = 1;
+            let b = { let a = 2; let c = 3; a + c };
+            a + b
+        }
+    };
+    assert_eq!(out, 6);
+
+    let source = "pub fn main() { let a = 1; let b = { let a = 2; let c = 3; a + c }; a + b }";
+    assert_eq!(replaced(source), 0);
+}
@@ -0,0 +1,227 @@
+prelude!();
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ErrorKind::*;
+
+use crate::ast::Spanned;
+use crate::compile::{FileSourceLoader, SourceLoader};
+use crate::diagnostics::{Diagnostic, FatalDiagnosticKind};
+
+/// A temporary directory which is removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str, files: &[(&str, &str)]) -> Result<Self> {
+        let dir =
+            std::env::temp_dir().join(format!("rune-source-loader-{name}-{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+
+        for (path, contents) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().context("missing parent")?)?;
+            fs::write(path, contents)?;
+        }
+
+        Ok(Self(dir))
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Compile the given sources with a source loader, returning the first
+/// compile error if compilation failed.
+fn build(
+    sources: &mut Sources,
+    loader: &mut dyn SourceLoader,
+) -> Result<core::result::Result<crate::Unit, compile::Error>> {
+    let mut diagnostics = Diagnostics::new();
+
+    let result = prepare(sources)
+        .with_diagnostics(&mut diagnostics)
+        .with_source_loader(loader)
+        .build();
+
+    let Ok(unit) = result else {
+        for diagnostic in diagnostics.into_diagnostics() {
+            if let Diagnostic::Fatal(e) = diagnostic {
+                if let FatalDiagnosticKind::CompileError(e) = e.into_kind() {
+                    return Ok(Err(e));
+                }
+            }
+        }
+
+        panic!("expected compile error");
+    };
+
+    Ok(Ok(unit))
+}
+
+fn run_main(unit: crate::Unit) -> Result<i64> {
+    let context = Context::with_default_modules()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    Ok(from_value(vm.call(["main"], ())?)?)
+}
+
+#[test]
+fn test_file_source_loader() -> Result<()> {
+    let dir = TempDir::new(
+        "project",
+        &[
+            (
+                "main.rn",
+                "mod a; pub fn main() { a::value() + a::b::value() }",
+            ),
+            ("a.rn", "pub mod b; pub fn value() { 1 }"),
+            ("a/b.rn", "pub fn value() { super::value() + 10 }"),
+        ],
+    )?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::from_path(dir.0.join("main.rn"))?)?;
+
+    let unit =
+        build(&mut sources, &mut FileSourceLoader::new())?.map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    assert_eq!(run_main(unit)?, 12);
+    assert_eq!(sources.source_ids().count(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_file_source_loader_ambiguous() -> Result<()> {
+    let dir = TempDir::new(
+        "ambiguous",
+        &[
+            ("main.rn", "mod a; pub fn main() { 0 }"),
+            ("a.rn", ""),
+            ("a/mod.rn", ""),
+        ],
+    )?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::from_path(dir.0.join("main.rn"))?)?;
+
+    let Err(e) = build(&mut sources, &mut FileSourceLoader::new())? else {
+        panic!("expected ambiguous module error");
+    };
+
+    assert_eq!(e.span(), span!(0, 6));
+
+    let AmbiguousModFile { first, second } = e.into_kind() else {
+        panic!("expected ambiguous module error");
+    };
+
+    assert_eq!(first, dir.0.join("a").join("mod.rn"));
+    assert_eq!(second, dir.0.join("a.rn"));
+    Ok(())
+}
+
+/// An in-memory loader, which maps module items to sources.
+struct MemoryLoader {
+    modules: HashMap<&'static str, Source>,
+}
+
+impl SourceLoader for MemoryLoader {
+    fn load(&mut self, _: &Path, item: &Item, span: &dyn Spanned) -> compile::Result<Source> {
+        let name = item.try_to_string()?;
+
+        match self.modules.get(name.as_str()) {
+            Some(source) => Ok(source.try_clone()?),
+            None => Err(compile::Error::msg(span, "missing module")),
+        }
+    }
+}
+
+#[test]
+fn test_memory_source_loader() -> Result<()> {
+    let mut loader = MemoryLoader {
+        modules: HashMap::from([
+            ("a", Source::new("a", "pub mod b; pub fn value() { 2 }")?),
+            ("a::b", Source::new("b", "pub fn value() { 40 }")?),
+        ]),
+    };
+
+    let mut sources = Sources::new();
+    sources.insert(Source::with_path(
+        "main",
+        "mod a; pub fn main() { a::value() + a::b::value() }",
+        "main.rn",
+    )?)?;
+
+    let unit = build(&mut sources, &mut loader)?.map_err(|e| anyhow::anyhow!("{e}"))?;
+    assert_eq!(run_main(unit)?, 42);
+    Ok(())
+}
+
+#[test]
+fn test_cyclic_source() -> Result<()> {
+    // Both modules resolve to the same source, which declares itself again.
+    let mut loader = MemoryLoader {
+        modules: HashMap::from([
+            ("a", Source::with_path("a", "pub mod a;", "a.rn")?),
+            ("a::a", Source::with_path("a", "pub mod a;", "a.rn")?),
+        ]),
+    };
+
+    let mut sources = Sources::new();
+    sources.insert(Source::with_path(
+        "main",
+        "mod a; pub fn main() { 0 }",
+        "main.rn",
+    )?)?;
+
+    let Err(e) = build(&mut sources, &mut loader)? else {
+        panic!("expected cycle error");
+    };
+
+    assert_eq!(e.span(), span!(0, 10));
+
+    let ModSourceAlreadyLoaded { item } = e.into_kind() else {
+        panic!("expected cycle error");
+    };
+
+    assert_eq!(item.try_to_string()?, "a::a");
+    Ok(())
+}
+
+/// A loader which always returns a source without a path which declares
+/// another module.
+struct RecursiveLoader;
+
+impl SourceLoader for RecursiveLoader {
+    fn load(&mut self, _: &Path, _: &Item, _: &dyn Spanned) -> compile::Result<Source> {
+        Ok(Source::new("a", "pub mod a;")?)
+    }
+}
+
+#[test]
+fn test_cyclic_source_without_path() -> Result<()> {
+    let mut sources = Sources::new();
+    sources.insert(Source::with_path(
+        "main",
+        "mod a; pub fn main() { 0 }",
+        "main.rn",
+    )?)?;
+
+    let Err(e) = build(&mut sources, &mut RecursiveLoader)? else {
+        panic!("expected depth error");
+    };
+
+    assert_eq!(e.span(), span!(0, 10));
+
+    let MaxModSourceDepth { item, max } = e.into_kind() else {
+        panic!("expected depth error");
+    };
+
+    assert_eq!(max, 64);
+    assert_eq!(item.try_to_string()?.split("::").count(), max + 1);
+    Ok(())
+}
@@ -0,0 +1,139 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn test_struct_defaults() {
+    let out: (i64, bool, String) = rune! {
+        struct Config { retries = 3, verbose = false, name }
+
+        pub fn main() {
+            let c = Config { name: "x" };
+            (c.retries, c.verbose, c.name)
+        }
+    };
+    assert_eq!(out, (3, false, String::from("x")));
+
+    let out: (i64, bool, String) = rune! {
+        struct Config { retries = 3, verbose = false, name }
+
+        pub fn main() {
+            let c = Config { verbose: true, name: "y" };
+            (c.retries, c.verbose, c.name)
+        }
+    };
+    assert_eq!(out, (3, true, String::from("y")));
+
+    let out: (i64, bool, String) = rune! {
+        struct Config { retries = 3, verbose = false, name }
+
+        pub fn main() {
+            let c = Config { name: "z", verbose: true, retries: 10 };
+            (c.retries, c.verbose, c.name)
+        }
+    };
+    assert_eq!(out, (10, true, String::from("z")));
+
+    let out: i64 = rune! {
+        struct Point { x = 1, y = 2 }
+
+        pub fn main() {
+            let p = Point {};
+            p.x + p.y
+        }
+    };
+    assert_eq!(out, 3);
+}
+
+#[test]
+fn test_struct_defaults_const() {
+    let out: (i64, String) = rune! {
+        const RETRIES = 2;
+        const PREFIX = "app";
+
+        struct Config { retries = RETRIES * 2, name = PREFIX }
+
+        pub fn main() {
+            let c = Config {};
+            (c.retries, c.name)
+        }
+    };
+    assert_eq!(out, (4, String::from("app")));
+}
+
+#[test]
+fn test_struct_defaults_associated_fn() {
+    let out: (i64, i64) = rune! {
+        struct Config { retries = 3 }
+
+        impl Config {
+            fn retries(self) {
+                self.retries * 10
+            }
+        }
+
+        pub fn main() {
+            let c = Config {};
+            (c.retries, c.retries())
+        }
+    };
+    assert_eq!(out, (3, 30));
+
+    let type_hash = Hash::type_hash(["Config"]);
+
+    assert_ne!(
+        Hash::field_default(type_hash, "retries"),
+        Hash::associated_function(type_hash, "retries")
+    );
+}
+
+#[test]
+fn test_variant_defaults() {
+    let out: (i64, i64) = rune! {
+        enum Shape { Rect { w = 1, h }, Empty }
+
+        pub fn main() {
+            let shape = Shape::Rect { h: 5 };
+
+            match shape {
+                Shape::Rect { w, h } => (w, h),
+                _ => (0, 0),
+            }
+        }
+    };
+    assert_eq!(out, (1, 5));
+}
+
+#[test]
+fn test_struct_defaults_patterns() {
+    let out: i64 = rune! {
+        struct Config { retries = 3, name }
+
+        pub fn main() {
+            let c = Config { name: "x" };
+
+            match c {
+                Config { retries: 3, name } => 1,
+                _ => 2,
+            }
+        }
+    };
+    assert_eq!(out, 1);
+}
+
+#[test]
+fn test_struct_defaults_missing() {
+    assert_errors! {
+        r#"struct Foo { a, b = 1, c } pub fn main() { Foo { } }"#,
+        span!(43, 50), LitObjectMissingField { fields, .. } => {
+            assert_eq!(fields.len(), 2);
+            assert_eq!(fields[0].as_ref(), "a");
+            assert_eq!(fields[1].as_ref(), "c");
+        }
+    };
+
+    assert_errors! {
+        r#"struct Foo(a = 1);"#,
+        span!(15, 16), Custom { .. }
+    };
+}
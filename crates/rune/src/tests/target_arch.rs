@@ -0,0 +1,82 @@
+prelude!();
+
+use crate::compile::{Options, TargetArch};
+use crate::modules::capture_io::{self, CaptureIo};
+
+fn run_with_arch(arch: Option<TargetArch>, source: &str) -> Result<String> {
+    let capture = CaptureIo::new();
+
+    let mut context = Context::with_config(false)?;
+    context.install(capture_io::module(&capture)?)?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let mut options = Options::default();
+    options.target_arch(arch);
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    vm.call(["main"], ())?;
+    Ok(capture.drain_utf8()?.into_std())
+}
+
+#[test]
+fn test_size_of_usize() -> Result<()> {
+    let source = r#"
+        pub fn main() {
+            println!("{}", size_of::<usize>());
+        }
+    "#;
+
+    assert_eq!(run_with_arch(Some(TargetArch::Wasm32), source)?, "4\n");
+    assert_eq!(run_with_arch(Some(TargetArch::X86_64), source)?, "8\n");
+    assert_eq!(run_with_arch(Some(TargetArch::AArch64), source)?, "8\n");
+
+    let host = ::rust_alloc::format!("{}\n", core::mem::size_of::<usize>());
+    assert_eq!(run_with_arch(None, source)?, host);
+    Ok(())
+}
+
+#[test]
+fn test_size_of_fixed() -> Result<()> {
+    let source = r#"
+        pub fn main() {
+            println!("{} {} {}", size_of::<u8>(), size_of::<i32>(), size_of::<f64>());
+        }
+    "#;
+
+    assert_eq!(run_with_arch(Some(TargetArch::Wasm32), source)?, "1 4 8\n");
+    Ok(())
+}
+
+#[test]
+fn test_size_of_path() -> Result<()> {
+    let source = r#"
+        fn size_of() {
+            "local"
+        }
+
+        pub fn main() {
+            println!("{} {}", std::mem::size_of::<usize>(), size_of());
+        }
+    "#;
+
+    assert_eq!(
+        run_with_arch(Some(TargetArch::Wasm32), source)?,
+        "4 local\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_target_arch() {
+    let mut options = Options::default();
+    assert!(options.parse_option("target-arch=wasm32").is_ok());
+    assert_eq!(options.target_arch, Some(TargetArch::Wasm32));
+    assert!(options.parse_option("target-arch=mips").is_err());
+}
@@ -1,5 +1,7 @@
 prelude!();
 
+use crate::runtime::{Inst, InstOp};
+
 #[test]
 fn test_get_const() -> Result<()> {
     let context = Context::with_default_modules()?;
@@ -73,3 +75,234 @@ fn test_get_const_nested() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_meta_try_into_const() -> Result<()> {
+    use crate::compile::meta::Meta;
+    use crate::compile::{ItemBuf, ItemMeta, Pool};
+    use crate::shared::Gen;
+
+    let mut m = Module::new();
+    m.constant("LEET", 1337).build()?;
+    m.function("leet", || 1337i64).build()?;
+
+    let mut context = Context::new();
+    context.install(m)?;
+
+    let gen = Gen::new();
+    let mut pool = Pool::new()?;
+
+    let mut lookup = |name: &str| -> Result<Meta> {
+        let item = ItemBuf::with_item([name])?;
+        let meta = context.lookup_meta(&item).context("missing meta")?;
+        let meta = meta.into_iter().next().context("missing meta")?;
+
+        Ok(Meta {
+            context: true,
+            hash: meta.hash,
+            item_meta: ItemMeta {
+                id: gen.next(),
+                location: Default::default(),
+                item: pool.alloc_item(&item)?,
+                visibility: Default::default(),
+                module: Default::default(),
+            },
+            kind: meta.kind.try_clone()?,
+            source: None,
+            parameters: Hash::EMPTY,
+        })
+    };
+
+    let meta = lookup("LEET")?;
+    let value = meta.as_const().context("missing constant")?;
+    assert_eq!(value.as_value()?.as_integer()?, 1337);
+    let value = meta.try_into_const().context("missing constant")?;
+    assert_eq!(value.as_value()?.as_integer()?, 1337);
+
+    let meta = lookup("leet")?;
+    assert!(meta.as_const().is_none());
+    assert!(meta.try_into_const().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_const_dedup() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = sources! {
+        entry => {
+            pub const A = "";
+            pub const B = "";
+            pub const C = [1, 2.0, ("a", true)];
+            pub const D = [1, 2.0, ("a", true)];
+            pub const E = [1, 2.0, ("a", false)];
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    let get = |name: &str| {
+        unit.constant(Hash::type_hash([name]))
+            .context("missing constant")
+    };
+
+    // Identical constants share the same slot in the constant pool.
+    assert!(core::ptr::eq(get("A")?, get("B")?));
+    assert!(core::ptr::eq(get("C")?, get("D")?));
+    assert!(!core::ptr::eq(get("C")?, get("E")?));
+    Ok(())
+}
+
+#[test]
+fn test_const_pool_load() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = sources! {
+        entry => {
+            const SMALL = [1, 2, 3];
+            const BIG = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19];
+
+            pub fn main() {
+                let out = [];
+
+                for n in 0..3 {
+                    let big = BIG;
+                    big.push(n);
+                    out.push(big.len());
+                }
+
+                (out, SMALL.len())
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    // Only the large constant is loaded from the constant pool.
+    let count = unit
+        .iter_instructions()
+        .filter(|(_, inst)| matches!(inst, Inst::Constant { .. }))
+        .count();
+
+    assert_eq!(count, 1);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    let value: (Vec<i64>, i64) = from_value(vm.call(["main"], ())?)?;
+
+    // Every load produces a fresh value, so mutations don't leak between
+    // iterations.
+    assert_eq!(value, (vec![21, 21, 21], 3));
+    Ok(())
+}
+
+#[test]
+fn test_const_pool_fresh_values() -> Result<()> {
+    let value: (Vec<i64>, Vec<i64>) = rune! {
+        const BIG = #{ a: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15] };
+
+        pub fn main() {
+            let vec = [];
+            let object = [];
+
+            for n in 0..3 {
+                let big = BIG;
+                big.a.push(n);
+                vec.push(big.a.len());
+                big.b = n;
+                object.push(big.len());
+            }
+
+            (vec, object)
+        }
+    };
+
+    // Every load of a pooled constant is a new value, including the values
+    // nested inside of it.
+    assert_eq!(value, (vec![17, 17, 17], vec![2, 2, 2]));
+    Ok(())
+}
+
+#[test]
+fn test_const_string_concat() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = sources! {
+        entry => {
+            const NAME = "world";
+            const PATH = "pre_" + NAME;
+
+            pub fn main() {
+                let dynamic = "dyn";
+                ("pre_" + NAME + "_post", PATH, ("a" + "b") + NAME, NAME + dynamic)
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    // Only the concatenation involving a local variable is performed at
+    // runtime.
+    let count = unit
+        .iter_instructions()
+        .filter(|(_, inst)| {
+            matches!(
+                inst,
+                Inst::Op {
+                    op: InstOp::Add,
+                    ..
+                }
+            )
+        })
+        .count();
+
+    assert_eq!(count, 1);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    let value: (String, String, String, String) = from_value(vm.call(["main"], ())?)?;
+
+    assert_eq!(
+        value,
+        (
+            "pre_world_post".to_owned(),
+            "pre_world".to_owned(),
+            "abworld".to_owned(),
+            "worlddyn".to_owned(),
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_const_index_and_len_folding() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = sources! {
+        entry => {
+            const TABLE = [10, 20, 30];
+            const PAIR = (40, 50);
+            const NAME = "hello";
+
+            pub fn main() {
+                let n = 1;
+                [TABLE[0], PAIR[1], TABLE.len(), NAME.len(), TABLE[n]]
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    // Only indexing with a dynamic index is performed at runtime.
+    let count = unit
+        .iter_instructions()
+        .filter(|(_, inst)| matches!(inst, Inst::IndexGet { .. } | Inst::CallAssociated { .. }))
+        .count();
+
+    assert_eq!(count, 1);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    let value: Vec<i64> = from_value(vm.call(["main"], ())?)?;
+
+    assert_eq!(value, [10, 50, 3, 5, 20]);
+    Ok(())
+}
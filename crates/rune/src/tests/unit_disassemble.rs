@@ -0,0 +1,43 @@
+prelude!();
+
+#[test]
+fn test_disassemble() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = sources! {
+        entry => {
+            fn add(a, b) {
+                a + b
+            }
+
+            pub fn main() {
+                let value = add(1, 2);
+
+                if value > 2 {
+                    "big"
+                } else {
+                    "small"
+                }
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    let mut out = String::new();
+    unit.try_disassemble(&mut out)?;
+
+    assert!(out.contains("fn main()"));
+    assert!(out.contains("fn add(a, b)"));
+    assert!(out.contains("  0x0000: "));
+    assert!(out.contains("op op=+"));
+    assert!(out.contains("call hash="));
+    assert!(out.contains("jump-if jump="));
+    assert!(out.contains("return address=top"));
+    assert!(out.contains("; source=0 span="));
+
+    // Every instruction is written on its own line.
+    let count = out.lines().filter(|line| line.starts_with("  0x")).count();
+    assert_eq!(count, unit.iter_instructions().count());
+    Ok(())
+}
@@ -0,0 +1,70 @@
+prelude!();
+
+use crate::compile::{
+    compile_query, NoopCompileVisitor, NoopSourceLoader, Options, Pool, Prelude, UnitBuilder,
+};
+use crate::hir;
+use crate::macros::Storage;
+use crate::query::{Query, Used};
+use crate::runtime::unit::DefaultStorage;
+use crate::shared::{Consts, Gen};
+
+#[test]
+fn test_used_items() -> Result<()> {
+    let mut sources = crate::tests::sources(
+        r#"
+        fn helper() { 1 }
+        fn dead() { 2 }
+        pub fn main() { helper() }
+        "#,
+    );
+
+    let context = Context::with_default_modules()?;
+    let mut unit = UnitBuilder::default();
+    let prelude = Prelude::with_default_prelude()?;
+    let gen = Gen::default();
+    let const_arena = hir::Arena::new();
+    let mut consts = Consts::default();
+    let mut storage = Storage::default();
+    let mut pool = Pool::new()?;
+    let mut visitor = NoopCompileVisitor::new();
+    let mut diagnostics = Diagnostics::default();
+    let mut source_loader = NoopSourceLoader::default();
+    let options = Options::default();
+    let mut unit_storage = DefaultStorage::default();
+    let mut inner = Default::default();
+
+    let mut q = Query::new(
+        &mut unit,
+        &prelude,
+        &const_arena,
+        &mut consts,
+        &mut storage,
+        &mut sources,
+        &mut pool,
+        &mut visitor,
+        &mut diagnostics,
+        &mut source_loader,
+        &options,
+        &gen,
+        &context,
+        &mut inner,
+    );
+
+    compile_query(q.borrow(), &options, &mut unit_storage)?;
+    assert!(!q.diagnostics.has_error());
+
+    let used = |name: &str| -> Result<Used> {
+        let item = ItemBuf::with_item([name])?;
+
+        q.used_items()
+            .find(|(i, _)| **i == *item)
+            .map(|(_, used)| used)
+            .context("missing item")
+    };
+
+    assert_eq!(used("main")?, Used::Used);
+    assert_eq!(used("helper")?, Used::Used);
+    assert_eq!(used("dead")?, Used::Unused);
+    Ok(())
+}
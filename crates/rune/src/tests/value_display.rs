@@ -0,0 +1,68 @@
+prelude!();
+
+use core::fmt;
+
+/// Helper to format a value through its `Display` implementation.
+struct Display(Value);
+
+impl fmt::Display for Display {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.try_display_fmt(f).map_err(|_| fmt::Error)
+    }
+}
+
+#[derive(Any)]
+#[rune(item = ::native_crate)]
+struct Custom {
+    value: i64,
+}
+
+impl Custom {
+    #[rune::function(protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "Custom({})", self.value);
+        VmResult::Ok(())
+    }
+}
+
+#[test]
+fn test_display_primitives() -> Result<()> {
+    let display = |value: Value| Display(value).to_string();
+
+    assert_eq!(display(rune::to_value(42i64)?), "42");
+    assert_eq!(display(rune::to_value(-7i64)?), "-7");
+    assert_eq!(display(rune::to_value(1.5f64)?), "1.5");
+    assert_eq!(display(rune::to_value(true)?), "true");
+    assert_eq!(display(rune::to_value('a')?), "a");
+    assert_eq!(display(rune::to_value(b'a')?), "97");
+    assert_eq!(display(rune::to_value(String::from("hello"))?), "hello");
+
+    // Formatter flags are respected for primitives.
+    let value = rune::to_value(42i64)?;
+    assert_eq!(::rust_alloc::format!("{:>5}", Display(value)), "   42");
+    Ok(())
+}
+
+#[test]
+fn test_display_custom() -> Result<()> {
+    let mut module = Module::with_crate("native_crate")?;
+    module.ty::<Custom>()?;
+    module.function_meta(Custom::string_display)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {}
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+
+    let value = rune::to_value(Custom { value: 42 })?;
+    let string = vm.with(|| Display(value).to_string());
+    assert_eq!(string, "Custom(42)");
+    Ok(())
+}
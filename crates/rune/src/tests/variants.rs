@@ -1,5 +1,7 @@
 prelude!();
 
+use ErrorKind::*;
+
 /// Tests that different variants of the same enum can be compared to each other
 /// See: https://github.com/rune-rs/rune/pull/215
 #[test]
@@ -31,3 +33,62 @@ fn assert_variant_comparisons() {
         }
     };
 }
+
+#[test]
+fn test_discriminants() {
+    let out: (i64, i64, i64, i64) = rune! {
+        const SHIFT = 3;
+
+        enum Flag { A = 1, B, C = 1 << SHIFT, D }
+
+        pub fn main() {
+            (Flag::A as i64, Flag::B as i64, Flag::C as i64, Flag::D as i64)
+        }
+    };
+    assert_eq!(out, (1, 2, 8, 9));
+
+    let out: (i64, i64, i64) = rune! {
+        enum Mixed { X, Y(a), Z = -5, W }
+
+        pub fn main() {
+            assert!(Mixed::Z != Mixed::W);
+            (Mixed::X as i64, Mixed::Z as i64, Mixed::W as i64)
+        }
+    };
+    assert_eq!(out, (0, -5, -4));
+}
+
+#[test]
+fn test_discriminant_from_match() {
+    let out: i64 = rune! {
+        enum Flag { A = 1, B = 2, D = 8 }
+
+        fn flag(n) {
+            match n {
+                1 => Flag::A,
+                2 => Flag::B,
+                _ => Flag::D,
+            }
+        }
+
+        pub fn main() {
+            let flag = flag(3);
+
+            match flag {
+                Flag::D => flag as i64,
+                _ => 0,
+            }
+        }
+    };
+    assert_eq!(out, 8);
+}
+
+#[test]
+fn test_duplicate_discriminant() {
+    assert_errors! {
+        r#"enum Flag { A = 1, B = 0, C } pub fn main() { Flag::C }"#,
+        span!(26, 27), DuplicateDiscriminant { discriminant, .. } => {
+            assert_eq!(discriminant, 1);
+        }
+    };
+}
@@ -0,0 +1,114 @@
+prelude!();
+
+use crate::runtime::{Iterator, Via};
+
+fn sum(mut iter: Via<Iterator>) -> VmResult<i64> {
+    let mut sum = 0;
+
+    while let Some(value) = vm_try!(iter.next()) {
+        sum += vm_try!(i64::from_value(value));
+    }
+
+    VmResult::Ok(sum)
+}
+
+fn display(value: Via<alloc::String>) -> alloc::String {
+    value.into_inner()
+}
+
+async fn resolve(future: Via<runtime::Future>) -> VmResult<Value> {
+    future.into_inner().await
+}
+
+fn context() -> Result<Context> {
+    let mut m = Module::new();
+    m.function("sum", sum).build()?;
+    m.function("display", display).build()?;
+    m.function("resolve", resolve).build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+    Ok(context)
+}
+
+fn vm(source: &str) -> Result<Vm> {
+    let context = context()?;
+    let mut sources = crate::tests::sources(source);
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()?), Arc::new(unit)))
+}
+
+#[test]
+fn test_via_into_iter() -> Result<()> {
+    let mut vm = vm(r#"
+    fn generate() {
+        yield 1;
+        yield 2;
+        yield 3;
+    }
+
+    pub fn main() {
+        [sum([1, 2, 3]), sum(#{a: 10, b: 20}.values()), sum(generate())]
+    }
+    "#)?;
+
+    let output: Vec<i64> = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, [6, 30, 6]);
+    Ok(())
+}
+
+#[test]
+fn test_via_string_display_and_into_future() -> Result<()> {
+    let mut vm = vm(r#"
+    async fn forty_two() {
+        42
+    }
+
+    pub async fn main() {
+        let value = resolve(forty_two()).await;
+        [display(value), display("hello"), display('c')]
+    }
+    "#)?;
+
+    let output = block_on(vm.async_call(["main"], ()))?;
+    let output: Vec<String> = from_value(output)?;
+    assert_eq!(output, ["42", "hello", "c"]);
+    Ok(())
+}
+
+#[test]
+fn test_via_missing_protocol() -> Result<()> {
+    let mut vm = vm(r#"
+    struct Foo;
+
+    pub fn main() {
+        sum(Foo)
+    }
+    "#)?;
+
+    let error = vm.call(["main"], ()).unwrap_err();
+    let error = error.to_string();
+    assert!(
+        error.contains("Missing protocol function `into_iter` for `Foo`"),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_via_arity() -> Result<()> {
+    let context = context()?;
+    let mut diagnostics = Diagnostics::new();
+    let mut sources = crate::tests::sources("pub fn main() { sum([1], [2]) }");
+
+    let Err(error) = crate::tests::vm(&context, &mut sources, &mut diagnostics) else {
+        panic!("expected compile error");
+    };
+
+    let error = error.to_string();
+    assert!(
+        error.contains("Wrong number of arguments to `sum(*)`, expected `1` but got `2`"),
+        "{error}"
+    );
+    Ok(())
+}
@@ -93,3 +93,71 @@ fn test_assign_assign_exprs() {
     );
     assert_eq!(out, (4, (), ()));
 }
+
+#[test]
+fn test_destructure_assign_swap() {
+    let out: (i64, i64) = rune! {
+        pub fn main() { let a = 1; let b = 2; (a, b) = (b, a); (a, b) }
+    };
+
+    assert_eq!(out, (2, 1));
+}
+
+#[test]
+fn test_destructure_assign_nested() {
+    let out: (i64, i64, i64, i64) = rune! {
+        fn f() { (1, [2, (3, 4)]) }
+
+        pub fn main() {
+            let a = 0;
+            let obj = #{ field: 0 };
+            let t = (0, 0);
+            (a, [obj.field, (t.0, t.1)]) = f();
+            (a, obj.field, t.0, t.1)
+        }
+    };
+
+    assert_eq!(out, (1, 2, 3, 4));
+}
+
+#[test]
+fn test_destructure_assign_index() {
+    let out: (i64, i64) = rune! {
+        pub fn main() {
+            let v = [0, 0];
+            let i = 1;
+            [v[0], v[i]] = [10, 20];
+            (v[0], v[1])
+        }
+    };
+
+    assert_eq!(out, (10, 20));
+}
+
+#[test]
+fn test_destructure_assign_object() {
+    let out: (i64, i64) = rune! {
+        struct Point { x, y }
+
+        pub fn main() {
+            let x = 0;
+            let y = 0;
+            Point { x, y } = Point { x: 1, y: 2 };
+            let a = 0;
+            #{ x: a, y } = #{ x: 3, y: 4 };
+            (x + a, y)
+        }
+    };
+
+    assert_eq!(out, (4, 4));
+}
+
+#[test]
+fn test_destructure_assign_mismatch() {
+    assert_vm_error!(
+        r#"pub fn main() { let a = 0; let b = 0; (a, b) = (1, 2, 3); }"#,
+        VmErrorKind::Panic { reason } => {
+            assert_eq!(reason.to_string(), "pattern did not match");
+        }
+    );
+}
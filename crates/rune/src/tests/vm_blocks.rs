@@ -11,3 +11,28 @@ fn test_anonymous_type_precedence() {
     };
     assert_eq!(out, 3);
 }
+
+#[test]
+fn test_empty_blocks_elided() {
+    fn instructions(source: &str) -> Vec<String> {
+        let mut diagnostics = Default::default();
+        let unit = crate::tests::compile_helper(source, &mut diagnostics).unwrap();
+        unit.iter_instructions()
+            .map(|(_, inst)| format!("{inst:?}"))
+            .collect()
+    }
+
+    let expected = instructions("pub fn main() { 1 }");
+
+    assert_eq!(instructions("pub fn main() { {}; 1 }"), expected);
+    assert_eq!(instructions("pub fn main() { { () }; 1 }"), expected);
+    assert_eq!(
+        instructions("pub fn main() { let a = 1; a }"),
+        instructions("pub fn main() { let a = { 1 }; a }")
+    );
+
+    let out: () = rune! {
+        pub fn main() { let a = { () }; a }
+    };
+    assert_eq!(out, ());
+}
@@ -0,0 +1,274 @@
+prelude!();
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+
+use std::time::Instant;
+
+use crate::runtime::CancellationToken;
+
+/// A value which counts how many times it's been dropped.
+#[derive(Any)]
+struct Guard {
+    drops: Arc<AtomicUsize>,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn vm(drops: &Arc<AtomicUsize>, mut sources: Sources) -> Result<Vm> {
+    let mut m = Module::new();
+    m.ty::<Guard>()?;
+
+    let drops = drops.clone();
+
+    m.function("guard", move || Guard {
+        drops: drops.clone(),
+    })
+    .build()?;
+
+    m.function("pending", || async { core::future::pending::<()>().await })
+        .build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()?), Arc::new(unit)))
+}
+
+#[test]
+fn test_cancel_backward_jump() -> Result<()> {
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let mut vm = vm(
+        &drops,
+        sources! {
+            entry => {
+                pub fn main() {
+                    let guard = guard();
+                    let n = 0;
+
+                    loop {
+                        n += 1;
+                    }
+                }
+            }
+        },
+    )?;
+
+    let token = CancellationToken::new();
+    let handle = token.clone();
+
+    let thread = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(10));
+        handle.cancel();
+    });
+
+    let error = vm
+        .execute(["main"], ())?
+        .with_cancellation(token)
+        .complete()
+        .into_result()
+        .unwrap_err();
+
+    thread.join().unwrap();
+
+    assert!(error.is_cancelled());
+    // The guard is dropped as part of cancellation, while the virtual machine
+    // is still alive.
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+    assert!(vm.stack().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_cancel_deadline() -> Result<()> {
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let mut vm = vm(
+        &drops,
+        sources! {
+            entry => {
+                pub fn main() {
+                    let guard = guard();
+                    loop {}
+                }
+            }
+        },
+    )?;
+
+    let token = CancellationToken::with_deadline(Instant::now() + Duration::from_millis(10));
+
+    let error = vm
+        .execute(["main"], ())?
+        .with_cancellation(token)
+        .complete()
+        .into_result()
+        .unwrap_err();
+
+    assert!(error.is_cancelled());
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+    assert!(vm.stack().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cancel_await() -> Result<()> {
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let mut vm = vm(
+        &drops,
+        sources! {
+            entry => {
+                pub async fn main() {
+                    let guard = guard();
+                    pending().await;
+                }
+            }
+        },
+    )?;
+
+    let token = CancellationToken::new();
+    let handle = token.clone();
+
+    let task = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        handle.cancel();
+    });
+
+    let error = vm
+        .execute(["main"], ())?
+        .with_cancellation(token)
+        .async_complete()
+        .await
+        .into_result()
+        .unwrap_err();
+
+    task.await?;
+
+    assert!(error.is_cancelled());
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+    assert!(vm.stack().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cancel_called_async_fn() -> Result<()> {
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let mut vm = vm(
+        &drops,
+        sources! {
+            entry => {
+                async fn spin() {
+                    let guard = guard();
+                    loop {}
+                }
+
+                async fn wait() {
+                    let guard = guard();
+                    pending().await;
+                }
+
+                pub async fn spinning() {
+                    let guard = guard();
+                    spin().await;
+                }
+
+                pub async fn waiting() {
+                    let guard = guard();
+                    wait().await;
+                }
+            }
+        },
+    )?;
+
+    // The called function observes the cancellation at a backward jump, and
+    // the waiting caller is woken up when it's cancelled.
+    for (name, expected) in [("spinning", 2), ("waiting", 4)] {
+        let token = CancellationToken::new();
+        let handle = token.clone();
+
+        let thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(10));
+            handle.cancel();
+        });
+
+        let error = vm
+            .execute([name], ())?
+            .with_cancellation(token)
+            .async_complete()
+            .await
+            .into_result()
+            .unwrap_err();
+
+        thread.join().unwrap();
+
+        assert!(error.is_cancelled());
+        assert_eq!(drops.load(Ordering::SeqCst), expected);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_async_complete_with_timeout() -> Result<()> {
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let mut vm = vm(
+        &drops,
+        sources! {
+            entry => {
+                pub async fn spin() {
+                    let guard = guard();
+                    loop {}
+                }
+
+                pub async fn wait() {
+                    let guard = guard();
+                    pending().await;
+                }
+
+                pub async fn quick() {
+                    let guard = guard();
+                    42
+                }
+            }
+        },
+    )?;
+
+    let error = vm
+        .execute(["spin"], ())?
+        .async_complete_with_timeout(Duration::from_millis(10))
+        .await
+        .into_result()
+        .unwrap_err();
+
+    assert!(error.is_cancelled());
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+    let error = vm
+        .execute(["wait"], ())?
+        .async_complete_with_timeout(Duration::from_millis(10))
+        .await
+        .into_result()
+        .unwrap_err();
+
+    assert!(error.is_cancelled());
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+    let value = vm
+        .execute(["quick"], ())?
+        .async_complete_with_timeout(Duration::from_secs(60))
+        .await
+        .into_result()?;
+
+    assert_eq!(from_value::<i64>(value)?, 42);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+    Ok(())
+}
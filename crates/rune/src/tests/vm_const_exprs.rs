@@ -270,3 +270,84 @@ fn test_const_block() {
 
     assert_eq!(result, "Hello World");
 }
+
+#[test]
+fn test_const_block_bindings() {
+    let result: i64 = rune! {
+        pub fn main() {
+            let value = const { let x = 1; let y = x + 2; y };
+            value
+        }
+    };
+
+    assert_eq!(result, 3);
+
+    let result: i64 = rune! {
+        const VALUE = { let a = 2; let b = a * 3; let a = b + a; a };
+        pub fn main() { VALUE }
+    };
+
+    assert_eq!(result, 8);
+}
+
+#[test]
+fn test_const_block_binding_error() {
+    assert_errors! {
+        r#"pub fn main() { let value = const { let x = 1; let y = z + 2; y }; value }"#,
+        span, ErrorKind::MissingLocal { .. } => {
+            assert_eq!(span, span!(55, 56));
+        }
+    };
+}
+
+#[test]
+fn test_const_index_and_len() {
+    let out: (i64, i64, String, i64) = eval(
+        r#"
+        const TABLE = [10, 20, 30];
+        const PAIR = (1, "two");
+        const N = TABLE.len();
+        const LAST = TABLE[N - 1];
+        const NAME = PAIR[1];
+        const LENGTHS = "hello".len() + PAIR.len() + #{a: 1}.len();
+        pub fn main() { (N, LAST, NAME, LENGTHS) }
+    "#,
+    );
+    assert_eq!(out, (3, 30, String::from("two"), 8));
+
+    let out: i64 = rune! {
+        const TABLE = [1, 2, 3, 4];
+
+        const fn sum() {
+            let total = 0;
+            let n = 0;
+
+            while n < TABLE.len() {
+                total += TABLE[n];
+                n += 1;
+            }
+
+            total
+        }
+
+        pub fn main() { sum() }
+    };
+    assert_eq!(out, 10);
+}
+
+#[test]
+fn test_const_index_out_of_bounds() {
+    assert_errors! {
+        r#"const TABLE = [1, 2, 3]; const VALUE = TABLE[3]; pub fn main() { VALUE }"#,
+        span, ErrorKind::IrError(compile::IrErrorKind::IndexOutOfBounds { index: 3, length: 3 }) => {
+            assert_eq!(span, span!(39, 47));
+        }
+    };
+
+    assert_errors! {
+        r#"const VALUE = (1, 2)[-1]; pub fn main() { VALUE }"#,
+        span, ErrorKind::IrError(compile::IrErrorKind::IndexOutOfBounds { index: -1, length: 2 }) => {
+            assert_eq!(span, span!(14, 24));
+        }
+    };
+}
@@ -0,0 +1,59 @@
+prelude!();
+
+#[test]
+fn test_with_context_trace() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime()?);
+
+    let source = r#"
+        fn inner() { panic("boom") }
+        pub fn main() { inner() }
+    "#;
+
+    let mut sources = crate::tests::sources(source);
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(runtime, Arc::new(unit));
+
+    let mut error = vm.call(["main"], ()).unwrap_err();
+
+    // Annotate the error with every call site the VM unwound through,
+    // innermost first.
+    let mut sites = Vec::new();
+
+    let location = error.first_location().context("missing location")?;
+    let debug_info = location.unit.debug_info().context("missing debug info")?;
+
+    for ip in [location.ip]
+        .into_iter()
+        .chain(location.frames.iter().rev().map(|frame| frame.ip))
+    {
+        let inst = debug_info
+            .instruction_at(ip)
+            .context("missing debug inst")?;
+        sites.push((inst.span, inst.source_id));
+    }
+
+    for (span, source_id) in sites {
+        error = error.with_context(span, source_id);
+    }
+
+    let snippets = error
+        .frames()
+        .iter()
+        .map(|frame| &source[frame.span.range()])
+        .collect::<Vec<_>>();
+
+    assert_eq!(snippets, ["panic(\"boom\")", "pub fn main() { inner() }"]);
+
+    let string = error.to_string();
+    let mut lines = string.lines();
+    assert_eq!(lines.next(), Some("Panicked: boom"));
+
+    for frame in error.frames() {
+        let expected = format!("    at 0:{}", frame.span);
+        assert_eq!(lines.next(), Some(expected.as_str()));
+    }
+
+    assert_eq!(lines.next(), None);
+    Ok(())
+}
@@ -71,3 +71,77 @@ fn test_function() {
     let value: Value = function.call(()).unwrap();
     assert!(matches!(value.take_kind().unwrap(), ValueKind::Integer(3)));
 }
+
+#[test]
+fn test_function_call_with_stack() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let stack = |values: &[i64]| -> Result<Stack> {
+        let mut stack = Stack::new();
+
+        for &value in values {
+            stack.push(rune::to_value(value)?)?;
+        }
+
+        Ok(stack)
+    };
+
+    let function: Function = rune! {
+        fn foo(a, b) { a - b }
+
+        pub fn main() { foo }
+    };
+
+    // Values below the arguments are not visible to the function.
+    let value = function.try_call_with_stack(stack(&[100, 10, 3])?, 2)?;
+    assert_eq!(rune::from_value::<i64>(value)?, 7);
+    assert!(function.try_call_with_stack(stack(&[1])?, 2).is_err());
+    assert!(function.try_call_with_stack(stack(&[1, 2, 3])?, 3).is_err());
+
+    // Chain calls where the output of one call is the argument to the next.
+    let mut value = rune::to_value(0i64)?;
+
+    for n in 0..4 {
+        let mut stack = Stack::new();
+        stack.push(value)?;
+        stack.push(rune::to_value(n)?)?;
+        value = function.try_call_with_stack(stack, 2)?;
+    }
+
+    assert_eq!(rune::from_value::<i64>(value)?, -6);
+
+    let function: Function = rune! {
+        pub fn main() { i64::max }
+    };
+
+    let value = function.try_call_with_stack(stack(&[0, 4, 9])?, 2)?;
+    assert_eq!(rune::from_value::<i64>(value)?, 9);
+
+    let function: Function = rune! {
+        struct Custom(a, b);
+        pub fn main() { Custom }
+    };
+
+    let value = function.try_call_with_stack(stack(&[0, 1, 2])?, 2)?;
+    let (a, b) = {
+        let ValueKind::TupleStruct(tuple) = &*value.borrow_kind_ref()? else {
+            panic!("expected tuple struct");
+        };
+
+        (
+            rune::from_value::<i64>(tuple.get(0).unwrap().clone())?,
+            rune::from_value::<i64>(tuple.get(1).unwrap().clone())?,
+        )
+    };
+    assert_eq!((a, b), (1, 2));
+
+    let function: Function = run(
+        &context,
+        r#"pub fn main(a) { |b, c| a * b + c }"#,
+        ["main"],
+        (10i64,),
+    )?;
+    let value = function.try_call_with_stack(stack(&[99, 2, 3])?, 2)?;
+    assert_eq!(rune::from_value::<i64>(value)?, 23);
+    Ok(())
+}
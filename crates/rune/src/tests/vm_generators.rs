@@ -53,3 +53,100 @@ fn test_resume() {
     };
     assert_eq!(out, 6);
 }
+
+#[test]
+fn test_ping_pong() {
+    let out: Vec<i64> = rune! {
+        use std::ops::GeneratorState;
+
+        fn pong() {
+            let total = 0;
+            let received = yield total;
+
+            while received != 0 {
+                total += received;
+                received = yield total;
+            }
+
+            total * 10
+        }
+
+        pub fn main() {
+            let gen = pong();
+            let out = [];
+
+            let ball = match gen.resume(()) {
+                GeneratorState::Yielded(value) => value,
+                GeneratorState::Complete(..) => panic("unexpected"),
+            };
+
+            out.push(ball);
+
+            for ball in [1, 2, 3, 0] {
+                match gen.resume(ball) {
+                    GeneratorState::Yielded(value) => out.push(value),
+                    GeneratorState::Complete(value) => out.push(value),
+                }
+            }
+
+            out
+        }
+    };
+    assert_eq!(out, [0, 1, 3, 6, 60]);
+}
+
+#[test]
+fn test_generator_for_loop() {
+    let out: Vec<i64> = rune! {
+        fn countdown(n) {
+            while n > 0 {
+                yield n;
+                n -= 1;
+            }
+        }
+
+        pub fn main() {
+            let out = [];
+
+            for n in countdown(3) {
+                out.push(n);
+            }
+
+            for n in countdown(2).iter() {
+                out.push(n * 10);
+            }
+
+            out
+        }
+    };
+    assert_eq!(out, [3, 2, 1, 20, 10]);
+}
+
+#[test]
+fn test_resume_complete() {
+    assert_vm_error!(
+        r#"
+        fn foo() { yield 1; }
+
+        pub fn main() {
+            let gen = foo();
+            gen.resume(());
+            gen.resume(());
+            gen.resume(())
+        }
+        "#,
+        VmErrorKind::GeneratorComplete => {}
+    );
+
+    let out: Option<i64> = rune! {
+        fn foo() { yield 1; }
+
+        pub fn main() {
+            let gen = foo();
+            gen.next();
+            gen.next();
+            gen.next()
+        }
+    };
+    assert_eq!(out, None);
+}
@@ -0,0 +1,149 @@
+prelude!();
+
+use crate::runtime::Inst;
+
+use ErrorKind::*;
+
+#[test]
+fn test_if_without_else() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let n = 0;
+            if n == 0 { n += 1; }
+            if n == 0 { n += 10; }
+            n
+        }
+    };
+    assert_eq!(out, 1);
+
+    let out: i64 = rune! {
+        pub fn main() {
+            let n = 0;
+            if n > 0 { n += 1; } else if n == 0 { n += 2; }
+            n
+        }
+    };
+    assert_eq!(out, 2);
+}
+
+#[test]
+fn test_if_guard_single_jump() {
+    let mut diagnostics = Default::default();
+    let unit =
+        crate::tests::compile_helper("pub fn main(n) { if n { n = false; } n }", &mut diagnostics)
+            .unwrap();
+
+    let jumps = unit
+        .iter_instructions()
+        .filter(|(_, inst)| {
+            matches!(
+                inst,
+                Inst::Jump { .. } | Inst::JumpIf { .. } | Inst::JumpIfNot { .. }
+            )
+        })
+        .count();
+
+    assert_eq!(jumps, 1);
+    assert!(unit
+        .iter_instructions()
+        .any(|(_, inst)| matches!(inst, Inst::JumpIfNot { .. })));
+}
+
+#[test]
+fn test_if_const_condition() {
+    let out: i64 = rune! {
+        const DEBUG = false;
+        const LEVEL = 3;
+
+        pub fn main() {
+            if DEBUG { 1 } else if LEVEL > 2 && !DEBUG { 2 } else { 3 }
+        }
+    };
+    assert_eq!(out, 2);
+
+    let out: i64 = rune! {
+        const ENABLED = true;
+
+        pub fn main() {
+            let a = 1;
+            if ENABLED { let b = 2; a + b } else { let c = 10; a + c }
+        }
+    };
+    assert_eq!(out, 3);
+
+    let out: i64 = rune! {
+        const ENABLED = true;
+
+        pub fn main() {
+            let n = 1;
+            if !ENABLED { n += 10; }
+            if ENABLED || n > 0 { n += 100; }
+            n
+        }
+    };
+    assert_eq!(out, 101);
+}
+
+#[test]
+fn test_if_const_condition_pruned() {
+    let mut diagnostics = Default::default();
+    let unit = crate::tests::compile_helper(
+        r#"
+        const DEBUG = false;
+        pub fn main() { if DEBUG { "debug" } else if (1 < 2) { "release" } else { "other" } }
+        "#,
+        &mut diagnostics,
+    )
+    .unwrap();
+
+    // Only the taken branch is emitted, without any branching.
+    assert!(unit.iter_instructions().all(|(_, inst)| !matches!(
+        inst,
+        Inst::Jump { .. } | Inst::JumpIf { .. } | Inst::JumpIfNot { .. }
+    )));
+
+    let strings = unit
+        .iter_instructions()
+        .filter(|(_, inst)| matches!(inst, Inst::String { .. }))
+        .count();
+    assert_eq!(strings, 1);
+}
+
+#[test]
+fn test_if_const_condition_mixed() {
+    let mut diagnostics = Default::default();
+    let unit = crate::tests::compile_helper(
+        "pub fn main(n) { if false { 1 } else if n { 2 } else { 3 } }",
+        &mut diagnostics,
+    )
+    .unwrap();
+
+    // The constant branch is dropped, while `n` is still tested at runtime.
+    let jumps = unit
+        .iter_instructions()
+        .filter(|(_, inst)| matches!(inst, Inst::JumpIf { .. }))
+        .count();
+    assert_eq!(jumps, 1);
+
+    let out: i64 = rune! {
+        pub fn main() {
+            let n = true;
+            if false { 1 } else if n { 2 } else { 3 }
+        }
+    };
+    assert_eq!(out, 2);
+}
+
+#[test]
+fn test_if_const_condition_pruned_errors() {
+    // Errors in branches which are never taken are still reported.
+    assert_errors! {
+        "pub fn main() { if false { 1 = 42; } }",
+        span!(27, 33), UnsupportedAssignExpr
+    };
+
+    assert_errors! {
+        "pub fn main() { if true { 1 } else { 1 = 42; } }",
+        span!(37, 43), UnsupportedAssignExpr
+    };
+}
@@ -1,5 +1,15 @@
 prelude!();
 
+use core::fmt::Write;
+
+use crate::alloc::try_vec;
+use crate::compile::Options;
+use crate::runtime::budget;
+use crate::runtime::unit::StringTable;
+use crate::runtime::Inst;
+use crate::Unit;
+use VmErrorKind::*;
+
 #[test]
 fn test_match_primitives() {
     let out: bool = rune! {
@@ -121,3 +131,200 @@ fn test_struct_matching() {
     };
     assert_eq!(out, 3);
 }
+
+#[test]
+fn test_match_conditional_branches() {
+    let mut diagnostics = Default::default();
+
+    let unit = crate::tests::compile_helper(
+        "pub fn main(n) { match n { 1 => 10, 2 => 20, _ => 30 } }",
+        &mut diagnostics,
+    )
+    .unwrap();
+
+    let conditional = unit
+        .iter_instructions()
+        .filter(|(_, inst)| {
+            matches!(
+                inst,
+                Inst::JumpIf { .. }
+                    | Inst::JumpIfNot { .. }
+                    | Inst::JumpIfOrPop { .. }
+                    | Inst::JumpIfNotOrPop { .. }
+                    | Inst::JumpIfBranch { .. }
+                    | Inst::PopAndJumpIfNot { .. }
+            )
+        })
+        .count();
+
+    assert!(conditional <= 3, "{conditional} conditional branches");
+
+    // The wildcard branch matches unconditionally, so no fallthrough is
+    // needed.
+    assert!(!unit
+        .iter_instructions()
+        .any(|(_, inst)| matches!(inst, Inst::Panic { .. })));
+}
+
+#[test]
+fn test_match_non_exhaustive() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let n = 2;
+            match n { 1 => 10, 2 => 20, 3 => 30 }
+        }
+    };
+    assert_eq!(out, 20);
+
+    assert_vm_error!(
+        r#"pub fn main() { match 4 { 1 => 10, 2 if false => 20, 3 => 30 } }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "pattern did not match");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { match 2 { 1 => 10, n if n > 2 => 20 } }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "pattern did not match");
+        }
+    );
+}
+
+/// Build a function which matches its argument against `count` string
+/// literals, where `"cmd{n}"` evaluates to `n`.
+fn string_match_source(count: usize) -> String {
+    let mut source = String::new();
+    source.push_str("pub fn main(cmd) { match cmd { ");
+
+    for n in 0..count {
+        write!(source, "\"cmd{n}\" => {n}, ").unwrap();
+    }
+
+    // A duplicate arm, which is never reached.
+    source.push_str("\"cmd1\" => 1000, ");
+    source.push_str("\"late\" if true => 2000, ");
+    source.push_str("_ => -1 } }");
+    source
+}
+
+fn compile_string_match(count: usize, threshold: usize) -> Result<Arc<Unit>> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(string_match_source(count))?)?;
+
+    let mut options = Options::default();
+    options.match_string_table(threshold);
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()?;
+
+    Ok(Arc::new(unit))
+}
+
+/// Call `main` and return its output together with the number of
+/// instructions executed.
+fn call_counted(unit: &Arc<Unit>, arg: impl ToValue) -> Result<(i64, usize)> {
+    const BUDGET: usize = 100_000;
+
+    let context = Arc::new(Context::with_default_modules()?.runtime()?);
+    let mut vm = Vm::new(context, unit.clone());
+
+    budget::with(BUDGET, || {
+        let output: i64 = from_value(vm.call(["main"], (arg,))?)?;
+
+        // Drain what's left of the budget to figure out how much was used.
+        let mut remaining = 0;
+
+        while budget::take() {
+            remaining += 1;
+        }
+
+        Ok((output, BUDGET - remaining))
+    })
+    .call()
+}
+
+#[test]
+fn test_match_string_table() -> Result<()> {
+    let table = compile_string_match(50, 8)?;
+    let linear = compile_string_match(50, 0)?;
+
+    assert!(table
+        .iter_instructions()
+        .any(|(_, inst)| matches!(inst, Inst::JumpStringTable { .. })));
+
+    assert!(!linear
+        .iter_instructions()
+        .any(|(_, inst)| matches!(inst, Inst::JumpStringTable { .. })));
+
+    for unit in [&table, &linear] {
+        for n in 0..50 {
+            let (output, _) = call_counted(unit, format!("cmd{n}"))?;
+            assert_eq!(output, n);
+        }
+
+        assert_eq!(call_counted(unit, "late")?.0, 2000);
+        assert_eq!(call_counted(unit, "missing")?.0, -1);
+        assert_eq!(call_counted(unit, "")?.0, -1);
+        assert_eq!(call_counted(unit, 42i64)?.0, -1);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_match_string_table_threshold() -> Result<()> {
+    // Too few string literal arms for the table to be used.
+    let unit = compile_string_match(4, 8)?;
+
+    assert!(!unit
+        .iter_instructions()
+        .any(|(_, inst)| matches!(inst, Inst::JumpStringTable { .. })));
+
+    assert_eq!(call_counted(&unit, "cmd3")?.0, 3);
+    Ok(())
+}
+
+#[test]
+fn test_match_string_table_instructions() -> Result<()> {
+    let table = compile_string_match(50, 8)?;
+    let linear = compile_string_match(50, 0)?;
+
+    let (output, table_count) = call_counted(&table, "cmd49")?;
+    assert_eq!(output, 49);
+
+    let (output, linear_count) = call_counted(&linear, "cmd49")?;
+    assert_eq!(output, 49);
+
+    // The linear version has to test every preceding arm.
+    assert!(
+        table_count * 10 < linear_count,
+        "table: {table_count}, linear: {linear_count}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_string_table_collisions() -> Result<()> {
+    let table = StringTable::new(try_vec![
+        (Hash::new(2), "c".try_to_owned()?, 30),
+        (Hash::new(1), "a".try_to_owned()?, 10),
+        (Hash::new(1), "b".try_to_owned()?, 20),
+        (Hash::new(1), "a".try_to_owned()?, 40),
+    ]);
+
+    assert_eq!(table.lookup_hashed(Hash::new(1), "a"), Some(10));
+    assert_eq!(table.lookup_hashed(Hash::new(1), "b"), Some(20));
+    assert_eq!(table.lookup_hashed(Hash::new(2), "c"), Some(30));
+
+    // Strings which share a hash with an entry are rejected.
+    assert_eq!(table.lookup_hashed(Hash::new(1), "c"), None);
+    assert_eq!(table.lookup_hashed(Hash::new(2), "a"), None);
+    assert_eq!(table.lookup_hashed(Hash::new(3), "a"), None);
+    Ok(())
+}
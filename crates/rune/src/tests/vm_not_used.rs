@@ -1,5 +1,7 @@
 prelude!();
 
+use crate::runtime::Inst;
+
 #[test]
 fn test_not_used() {
     let _: () = rune! {
@@ -16,3 +18,56 @@ fn test_not_used() {
         }
     };
 }
+
+#[test]
+fn test_pure_not_used_elided() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                let x = 1;
+                let values = [];
+                x;
+                (x);
+                1;
+                1 == 2;
+                ("a" != "b") && (true || false);
+                values.push(x);
+                values.len() + 1;
+                values
+            }
+        }
+    };
+
+    let mut diagnostics = Diagnostics::new();
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .build()?;
+
+    // Elided expressions warn exactly as if they had been assembled, which is
+    // only the case for the bare literal.
+    let not_used = diagnostics
+        .into_diagnostics()
+        .into_iter()
+        .filter(|d| {
+            matches!(d, diagnostics::Diagnostic::Warning(w)
+                if matches!(w.kind(), diagnostics::WarningDiagnosticKind::NotUsed { .. }))
+        })
+        .count();
+
+    assert_eq!(not_used, 1);
+
+    // Only the operation on the result of a call is kept, and variables are
+    // only copied when passed to calls or returned.
+    let count = |f: fn(&Inst) -> bool| unit.iter_instructions().filter(|(_, inst)| f(inst)).count();
+    assert_eq!(count(|inst| matches!(inst, Inst::Op { .. })), 1);
+    assert_eq!(count(|inst| matches!(inst, Inst::Copy { .. })), 4);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+    let values: Vec<i64> = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(values, [1]);
+    Ok(())
+}
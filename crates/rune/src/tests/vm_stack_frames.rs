@@ -0,0 +1,51 @@
+prelude!();
+
+#[test]
+fn test_stack_frame_at() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime()?);
+
+    let mut sources = crate::tests::sources(
+        r#"
+        fn inner(a, b) { a + b }
+        pub fn main() { let x = 10; inner(x, 20) }
+        "#,
+    );
+
+    let unit = Arc::new(prepare(&mut sources).with_context(&context).build()?);
+    let mut vm = Vm::new(runtime, unit);
+
+    assert!(vm.stack_frame_at(0).is_some());
+    assert!(vm.stack_frame_at(1).is_none());
+
+    let mut execution = vm.execute(["main"], ())?;
+
+    while execution.vm().call_frames().is_empty() {
+        assert!(execution.step().into_result()?.is_none());
+    }
+
+    let vm = execution.vm();
+    assert_eq!(vm.call_frames().len(), 1);
+
+    let inner = vm.stack_frame_at(0).context("missing inner frame")?;
+    assert_eq!(inner.ip, vm.ip());
+    let args = inner
+        .stack
+        .iter()
+        .map(|value| from_value::<i64>(value.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(args, [10, 20]);
+
+    let main = vm.stack_frame_at(1).context("missing main frame")?;
+    assert_eq!(main.ip, vm.call_frames()[0].ip);
+    assert!(main
+        .stack
+        .iter()
+        .any(|value| matches!(from_value::<i64>(value.clone()), Ok(10))));
+
+    assert!(vm.stack_frame_at(2).is_none());
+
+    let output: i64 = from_value(execution.complete().into_result()?)?;
+    assert_eq!(output, 30);
+    Ok(())
+}
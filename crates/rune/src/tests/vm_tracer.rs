@@ -0,0 +1,70 @@
+prelude!();
+
+use crate::runtime::CountingTracer;
+
+#[test]
+fn test_counting_tracer() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime()?);
+
+    let mut sources = crate::tests::sources(
+        r#"
+        fn add(a, b) { a + b }
+        pub fn main() { add(1, 2) + add(3, 4) }
+        "#,
+    );
+
+    let unit = Arc::new(prepare(&mut sources).with_context(&context).build()?);
+
+    let add = Hash::type_hash(["add"]);
+    let main = Hash::type_hash(["main"]);
+
+    let debug = unit.debug_info().context("missing debug info")?;
+    let add_offset = *debug
+        .functions_rev
+        .iter()
+        .find(|(_, hash)| **hash == add)
+        .context("missing add")?
+        .0;
+    let main_offset = *debug
+        .functions_rev
+        .iter()
+        .find(|(_, hash)| **hash == main)
+        .context("missing main")?
+        .0;
+
+    let mut vm = Vm::new(runtime, unit.clone()).with_tracer(CountingTracer::new());
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 10);
+
+    let tracer = vm
+        .take_tracer::<CountingTracer>()
+        .context("missing tracer")?;
+    assert!(vm.tracer::<CountingTracer>().is_none());
+
+    assert_eq!(tracer.calls(add), 2);
+    assert_eq!(tracer.calls(main), 0);
+    assert_eq!(tracer.returns(), 3);
+
+    assert_eq!(tracer.instruction(add_offset), 2);
+    assert_eq!(tracer.instruction(main_offset), 1);
+
+    // Both functions are straight-line code and `main` is assembled before
+    // `add`, so every instruction in `add` is executed twice and every
+    // instruction in `main` once.
+    assert!(main_offset < add_offset);
+
+    let expected = unit
+        .iter_instructions()
+        .map(|(ip, _)| if ip >= add_offset { 2 } else { 1 })
+        .sum::<usize>();
+
+    assert_eq!(tracer.total_instructions(), expected);
+
+    for (ip, count) in tracer.instructions() {
+        let expected = if ip >= add_offset { 2 } else { 1 };
+        assert_eq!(count, expected, "instruction at {ip}");
+    }
+
+    Ok(())
+}
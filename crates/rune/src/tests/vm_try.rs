@@ -1,6 +1,9 @@
 prelude!();
 
 use core::ops::ControlFlow;
+use std::sync::Mutex;
+
+use crate::runtime::Inst;
 
 #[test]
 fn test_unwrap() {
@@ -82,3 +85,141 @@ fn custom_try() -> Result<()> {
 
     Ok(())
 }
+
+/// A value which records when it's dropped.
+#[derive(Any)]
+struct Guard {
+    id: i64,
+    log: Arc<Mutex<Vec<i64>>>,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        self.log.lock().unwrap().push(self.id);
+    }
+}
+
+fn guarded(log: &Arc<Mutex<Vec<i64>>>, mut sources: Sources) -> Result<Vm> {
+    let mut m = Module::new();
+    m.ty::<Guard>()?;
+
+    let log = log.clone();
+
+    m.function("guard", move |id: i64| Guard {
+        id,
+        log: log.clone(),
+    })
+    .build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    // The operator is lowered into a single instruction, which unwinds the
+    // whole call frame on the early-return path.
+    let tries = unit
+        .iter_instructions()
+        .filter(|(_, inst)| matches!(inst, Inst::Try { .. }))
+        .count();
+
+    assert_eq!(tries, 1);
+    Ok(Vm::new(Arc::new(context.runtime()?), Arc::new(unit)))
+}
+
+#[test]
+fn test_try_nested_blocks() -> Result<()> {
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let mut vm = guarded(
+        &log,
+        sources! {
+            entry => {
+                pub fn main(value) {
+                    let a = guard(1);
+
+                    let b = {
+                        let b = guard(2);
+
+                        {
+                            let c = guard(3);
+                            value?
+                        }
+                    };
+
+                    let d = guard(4);
+                    Ok(b + 10)
+                }
+            }
+        },
+    )?;
+
+    let output = vm.call(["main"], (Err::<i64, i64>(42),))?;
+    assert_eq!(from_value::<Result<i64, i64>>(output)?, Err(42));
+    assert!(vm.stack().is_empty());
+
+    let mut drops = log.lock().unwrap();
+    drops.sort();
+    assert_eq!(*drops, [1, 2, 3]);
+    drops.clear();
+    drop(drops);
+
+    let output = vm.call(["main"], (Ok::<i64, i64>(1),))?;
+    assert_eq!(from_value::<Result<i64, i64>>(output)?, Ok(11));
+    assert!(vm.stack().is_empty());
+
+    let mut drops = log.lock().unwrap();
+    drops.sort();
+    assert_eq!(*drops, [1, 2, 3, 4]);
+    Ok(())
+}
+
+#[test]
+fn test_try_in_loop() -> Result<()> {
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let mut vm = guarded(
+        &log,
+        sources! {
+            entry => {
+                pub fn main(values) {
+                    let outer = guard(0);
+                    let sum = 0;
+
+                    for value in values {
+                        let inner = guard(sum + 1);
+
+                        while true {
+                            let nested = guard(100);
+                            sum += value?;
+                            break;
+                        }
+                    }
+
+                    Some(sum)
+                }
+            }
+        },
+    )?;
+
+    let values = [Some(1i64), Some(2), None, Some(4)];
+    let output = vm.call(["main"], (values.into_iter().collect::<Vec<_>>(),))?;
+    assert_eq!(from_value::<Option<i64>>(output)?, None);
+    assert!(vm.stack().is_empty());
+
+    let mut drops = log.lock().unwrap();
+    // Guards from completed iterations are dropped innermost first as the loop
+    // progresses, while everything else is dropped when the frame is unwound.
+    assert_eq!(drops[..4], [100, 1, 100, 2]);
+    drops[4..].sort();
+    assert_eq!(drops[4..], [0, 4, 100]);
+    drops.clear();
+    drop(drops);
+
+    let values = [Some(1i64), Some(2)];
+    let output = vm.call(["main"], (values.into_iter().collect::<Vec<_>>(),))?;
+    assert_eq!(from_value::<Option<i64>>(output)?, Some(3));
+    assert!(vm.stack().is_empty());
+    assert_eq!(log.lock().unwrap().len(), 5);
+    Ok(())
+}